@@ -0,0 +1,278 @@
+//! A blocking client for STAC APIs.
+use reqwest::blocking::{self, RequestBuilder};
+use reqwest::header::ACCEPT;
+use rustac_core::{Collection, Item, Link};
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
+use std::collections::HashSet;
+use std::vec;
+use url::Url;
+
+use crate::error::{STACError, STACResult};
+use crate::search::Search;
+use crate::types::{Collections, Conformance, ItemCollection, LandingPage};
+
+/// A client for a STAC API. Endpoints are resolved relative to the root URL of the API, e.g.
+/// `{root}/search`.
+#[derive(Debug, Clone)]
+pub struct Client {
+    root: Url,
+    http: blocking::Client,
+}
+
+/// A request for a page of results.
+#[derive(Debug, Clone)]
+enum Request {
+    Get(Url),
+    Post(Url, Map<String, Value>),
+}
+
+impl Request {
+    /// Identifies the request by its URL and, for `POST` requests, its body.
+    fn key(&self) -> (Url, Option<String>) {
+        match self {
+            Request::Get(url) => (url.clone(), None),
+            Request::Post(url, body) => {
+                (url.clone(), Some(Value::Object(body.clone()).to_string()))
+            }
+        }
+    }
+}
+
+impl Client {
+    /// Creates a client for the API with the given root URL.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::URL`] if `url` is not a valid URL, or [`STACError::Other`] if it cannot
+    /// have a path, e.g. a `data:` URL.
+    pub fn new(url: &str) -> STACResult<Client> {
+        let mut root = Url::parse(url)?;
+        if root.cannot_be_a_base() {
+            return Err(STACError::Other(format!(
+                "{url} cannot be the root of an API"
+            )));
+        }
+        if !root.path().ends_with('/') {
+            root.set_path(&format!("{}/", root.path()));
+        }
+        Ok(Client {
+            root,
+            http: blocking::Client::new(),
+        })
+    }
+
+    /// Uses the given HTTP client for all requests, e.g. to set default headers or timeouts.
+    #[must_use]
+    pub fn with_http_client(mut self, http: blocking::Client) -> Client {
+        self.http = http;
+        self
+    }
+
+    /// The root URL of the API, always ending in `/`.
+    #[must_use]
+    pub fn root(&self) -> &Url {
+        &self.root
+    }
+
+    /// Fetches the landing page.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::HTTP`] if the request fails, [`STACError::API`] if the API responds
+    /// with an error, or [`STACError::JSONParse`] if the response is not a landing page.
+    pub fn landing_page(&self) -> STACResult<LandingPage> {
+        send(self.http.get(self.root.clone()))
+    }
+
+    /// Fetches the conformance classes from `/conformance`.
+    ///
+    /// # Errors
+    ///
+    /// See [`Client::landing_page`].
+    pub fn conformance(&self) -> STACResult<Conformance> {
+        send(self.http.get(self.endpoint("conformance")?))
+    }
+
+    /// Fetches all Collections from `/collections`, following `next` links until a page is empty,
+    /// has no `next` link, or links to a page that was already fetched.
+    ///
+    /// # Errors
+    ///
+    /// See [`Client::landing_page`].
+    pub fn collections(&self) -> STACResult<Vec<Collection>> {
+        let mut collections = Vec::new();
+        let mut fetched = HashSet::new();
+        let mut url = Some(self.endpoint("collections")?);
+        while let Some(current) = url.take() {
+            if !fetched.insert(current.clone()) {
+                break;
+            }
+            let page: Collections = send(self.http.get(current.clone()))?;
+            if page.collections.is_empty() {
+                break;
+            }
+            collections.extend(page.collections);
+            if let Some(link) = next_link(&page.links) {
+                url = Some(current.join(&link.href)?);
+            }
+        }
+        Ok(collections)
+    }
+
+    /// Fetches a Collection from `/collections/{id}`.
+    ///
+    /// # Errors
+    ///
+    /// See [`Client::landing_page`].
+    pub fn collection(&self, id: &str) -> STACResult<Collection> {
+        let mut url = self.endpoint("collections")?;
+        url.path_segments_mut()
+            .map_err(|()| STACError::Other(format!("{} cannot have a path", self.root)))?
+            .push(id);
+        send(self.http.get(url))
+    }
+
+    /// Searches for Items with a `GET` request to `/search`. No request is made until the first
+    /// Item is read from the returned iterator, and further pages are fetched as needed by
+    /// following `next` links, see [`Items`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::URL`] if the search endpoint cannot be resolved. Errors fetching pages
+    /// are returned by the iterator.
+    pub fn search_get(&self, search: &Search) -> STACResult<Items<'_>> {
+        let mut url = self.endpoint("search")?;
+        if !search.to_query().is_empty() {
+            url.query_pairs_mut().extend_pairs(search.to_query());
+        }
+        Ok(Items::new(self, Request::Get(url)))
+    }
+
+    /// Searches for Items with a `POST` request to `/search`, see [`Client::search_get`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::URL`] if the search endpoint cannot be resolved, or
+    /// [`STACError::JSONParse`] if the search cannot be serialized.
+    pub fn search_post(&self, search: &Search) -> STACResult<Items<'_>> {
+        let Value::Object(body) = serde_json::to_value(search)? else {
+            return Err(STACError::Other(String::from(
+                "Search must serialize to a JSON object",
+            )));
+        };
+        Ok(Items::new(
+            self,
+            Request::Post(self.endpoint("search")?, body),
+        ))
+    }
+
+    fn endpoint(&self, path: &str) -> STACResult<Url> {
+        Ok(self.root.join(path)?)
+    }
+
+    fn fetch(&self, request: &Request) -> STACResult<ItemCollection> {
+        match request {
+            Request::Get(url) => send(self.http.get(url.clone())),
+            Request::Post(url, body) => send(self.http.post(url.clone()).json(body)),
+        }
+    }
+}
+
+/// Sends a request and parses the JSON response, returning the body as an error if the request
+/// was not successful.
+fn send<T: DeserializeOwned>(request: RequestBuilder) -> STACResult<T> {
+    let response = request.header(ACCEPT, "application/json").send()?;
+    let status = response.status();
+    let body = response.text()?;
+    if !status.is_success() {
+        return Err(STACError::API(status, body));
+    }
+    Ok(serde_json::from_str(&body)?)
+}
+
+fn next_link(links: &[Link]) -> Option<&Link> {
+    links.iter().find(|link| link.rel == "next")
+}
+
+/// Builds the request for the page a `next` link points to. Links may use `POST` with a `body`,
+/// which is merged into the body of the previous request if `merge` is `true`.
+fn next_request(link: &Link, previous: &Request) -> STACResult<Request> {
+    let (previous_url, previous_body) = match previous {
+        Request::Get(url) => (url, None),
+        Request::Post(url, body) => (url, Some(body)),
+    };
+    let url = previous_url.join(&link.href)?;
+    let field = |name: &str| link.extra_fields.get(name);
+    let method = field("method").and_then(Value::as_str).unwrap_or("GET");
+    if !method.eq_ignore_ascii_case("POST") {
+        return Ok(Request::Get(url));
+    }
+
+    let mut body = match (field("merge").and_then(Value::as_bool), previous_body) {
+        (Some(true), Some(previous)) => previous.clone(),
+        _ => Map::new(),
+    };
+    match field("body") {
+        Some(Value::Object(fields)) => body.extend(fields.clone()),
+        Some(_) => {
+            return Err(STACError::Other(format!(
+                "next link to {} has a body that is not a JSON object",
+                link.href
+            )))
+        }
+        None => {}
+    }
+    Ok(Request::Post(url, body))
+}
+
+/// An iterator over the Items matched by a search, fetching pages of results as needed. Iteration
+/// stops after an error, after a page without Items or without a `next` link, or when a `next`
+/// link repeats a request that was already made.
+pub struct Items<'a> {
+    client: &'a Client,
+    page: vec::IntoIter<Item>,
+    next: Option<Request>,
+    fetched: HashSet<(Url, Option<String>)>,
+}
+
+impl<'a> Items<'a> {
+    fn new(client: &'a Client, request: Request) -> Items<'a> {
+        Items {
+            client,
+            page: Vec::new().into_iter(),
+            next: Some(request),
+            fetched: HashSet::new(),
+        }
+    }
+}
+
+impl Iterator for Items<'_> {
+    type Item = STACResult<Item>;
+
+    fn next(&mut self) -> Option<STACResult<Item>> {
+        loop {
+            if let Some(item) = self.page.next() {
+                return Some(Ok(item));
+            }
+            let request = self.next.take()?;
+            if !self.fetched.insert(request.key()) {
+                return None;
+            }
+            let page = match self.client.fetch(&request) {
+                Ok(page) => page,
+                Err(err) => return Some(Err(err)),
+            };
+            if page.features.is_empty() {
+                return None;
+            }
+            if let Some(link) = next_link(&page.links) {
+                match next_request(link, &request) {
+                    Ok(next) => self.next = Some(next),
+                    Err(err) => return Some(Err(err)),
+                }
+            }
+            self.page = page.features.into_iter();
+        }
+    }
+}
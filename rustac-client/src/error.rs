@@ -0,0 +1,60 @@
+//! Possible errors when working with a STAC API.
+use reqwest::StatusCode;
+use std::error;
+use std::fmt;
+use std::result;
+
+/// Alias for [`result::Result`] that uses a [`STACError`]
+pub type STACResult<T> = result::Result<T, STACError>;
+
+/// All errors that may be encountered when working with a STAC API in this package
+#[derive(Debug)]
+pub enum STACError {
+    /// Errors resulting from failed serialization/deserialization of types using the [`serde_json`]
+    /// package
+    JSONParse(serde_json::Error),
+
+    /// Errors resulting from failed HTTP requests in the [`reqwest`] package
+    HTTP(reqwest::Error),
+
+    /// Errors resulting from parsing or joining URLs with the [`url`] package
+    URL(url::ParseError),
+
+    /// Responses from the API with a status code other than success, with the body of the response
+    API(StatusCode, String),
+
+    /// Other errors not covered by the variants above.
+    Other(String),
+}
+
+impl error::Error for STACError {}
+
+impl fmt::Display for STACError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self {
+            STACError::JSONParse(source) => source.fmt(f),
+            STACError::HTTP(source) => source.fmt(f),
+            STACError::URL(source) => source.fmt(f),
+            STACError::API(status, body) => write!(f, "{status}: {body}"),
+            STACError::Other(message) => write!(f, "{}", message.as_str()),
+        }
+    }
+}
+
+impl From<serde_json::Error> for STACError {
+    fn from(err: serde_json::Error) -> STACError {
+        STACError::JSONParse(err)
+    }
+}
+
+impl From<reqwest::Error> for STACError {
+    fn from(err: reqwest::Error) -> STACError {
+        STACError::HTTP(err)
+    }
+}
+
+impl From<url::ParseError> for STACError {
+    fn from(err: url::ParseError) -> STACError {
+        STACError::URL(err)
+    }
+}
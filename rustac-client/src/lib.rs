@@ -0,0 +1,33 @@
+#![warn(missing_docs)]
+#![deny(clippy::all, clippy::pedantic)]
+#![allow(
+    clippy::module_name_repetitions,
+    clippy::doc_markdown,
+    clippy::upper_case_acronyms
+)]
+//! A client for [STAC APIs](https://github.com/radiantearth/stac-api-spec).
+//!
+//! [`Client`] fetches the landing page, conformance classes and Collections of an API, and runs
+//! Item Searches described by a [`Search`]. Search results are returned as an iterator of
+//! [`Item`](rustac_core::Item)s that follows `next` links to fetch further pages as needed.
+//!
+//! ```no_run
+//! use rustac_client::{Client, Search};
+//!
+//! let client = Client::new("https://planetarycomputer.microsoft.com/api/stac/v1").unwrap();
+//! let search = Search::new()
+//!     .collections(vec!["sentinel-2-l2a"])
+//!     .bbox(vec![-105.1, 39.9, -104.9, 40.1])
+//!     .limit(100);
+//! for item in client.search_post(&search).unwrap().take(250) {
+//!     println!("{}", item.unwrap().id);
+//! }
+//! ```
+
+pub use client::{Client, Items};
+pub use search::Search;
+
+pub mod client;
+pub mod error;
+pub mod search;
+pub mod types;
@@ -0,0 +1,5 @@
+//! Parameters of an [Item Search](https://github.com/radiantearth/stac-api-spec/tree/v1.0.0/item-search).
+//!
+//! These are defined in [`rustac_core::search`], so the same searches can be sent to an API or run
+//! against Items in memory.
+pub use rustac_core::search::{Direction, Fields, Search, SortBy};
@@ -0,0 +1,80 @@
+//! Documents returned by a STAC API, other than the core STAC objects.
+use rustac_core::{Collection, Item, Link};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// The [landing page](https://github.com/radiantearth/stac-api-spec/tree/v1.0.0/core#landing-page)
+/// of a STAC API, which is a Catalog that also lists the conformance classes of the API.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct LandingPage {
+    /// Identifier for the API.
+    pub id: String,
+
+    /// A short descriptive one-line title for the API.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+
+    /// Detailed multi-line description to fully explain the API.
+    pub description: String,
+
+    /// The conformance classes implemented by the API.
+    #[serde(rename = "conformsTo", default)]
+    pub conforms_to: Vec<String>,
+
+    /// Links to the endpoints of the API and to other resources.
+    pub links: Vec<Link>,
+
+    /// Additional fields, such as `stac_version` and `type`.
+    #[serde(flatten)]
+    pub extra_fields: Map<String, Value>,
+}
+
+/// The conformance classes implemented by an API, as returned by `/conformance`.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct Conformance {
+    /// The conformance class URIs.
+    #[serde(rename = "conformsTo")]
+    pub conforms_to: Vec<String>,
+}
+
+impl Conformance {
+    /// Checks whether the API implements the conformance class. A trailing `/` is ignored when
+    /// comparing URIs.
+    #[must_use]
+    pub fn conforms_to(&self, class: &str) -> bool {
+        let class = class.trim_end_matches('/');
+        self.conforms_to
+            .iter()
+            .any(|uri| uri.trim_end_matches('/') == class)
+    }
+}
+
+/// A page of Collections, as returned by `/collections`.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Collections {
+    /// The Collections on this page.
+    pub collections: Vec<Collection>,
+
+    /// Links to other pages and related resources.
+    #[serde(default)]
+    pub links: Vec<Link>,
+
+    /// Additional fields, such as `numberMatched` and `numberReturned`.
+    #[serde(flatten)]
+    pub extra_fields: Map<String, Value>,
+}
+
+/// A page of Items, as returned by `/search`. This is a GeoJSON FeatureCollection.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ItemCollection {
+    /// The Items on this page.
+    pub features: Vec<Item>,
+
+    /// Links to other pages and related resources.
+    #[serde(default)]
+    pub links: Vec<Link>,
+
+    /// Additional fields, such as `type`, `numberMatched` and `context`.
+    #[serde(flatten)]
+    pub extra_fields: Map<String, Value>,
+}
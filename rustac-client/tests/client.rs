@@ -0,0 +1,287 @@
+mod helpers;
+
+use geojson::{Geometry, Value as GeometryValue};
+use helpers::{collection, item, serve};
+use rustac_client::error::STACError;
+use rustac_client::{Client, Search};
+use serde_json::{json, Value};
+
+/// An Item in a page of search results.
+fn feature(id: &str) -> Value {
+    item(id, json!({"datetime": "2021-01-01T00:00:00Z"}))
+}
+
+fn ids<I: Iterator<Item = rustac_client::error::STACResult<rustac_core::Item>>>(
+    items: I,
+) -> Vec<String> {
+    items.map(|item| item.unwrap().id).collect()
+}
+
+#[test]
+fn test_landing_page_and_conformance() {
+    let (root, requests) = serve(|request| match request.path.as_str() {
+        "/api/" => Some(json!({
+            "id": "api",
+            "description": "A STAC API",
+            "stac_version": "1.0.0",
+            "conformsTo": ["https://api.stacspec.org/v1.0.0/core"],
+            "links": [{"rel": "search", "href": "/api/search"}]
+        })),
+        "/api/conformance" => Some(json!({
+            "conformsTo": [
+                "https://api.stacspec.org/v1.0.0/core",
+                "https://api.stacspec.org/v1.0.0/item-search/"
+            ]
+        })),
+        _ => None,
+    });
+    let client = Client::new(&format!("{root}/api")).unwrap();
+    assert_eq!(client.root().path(), "/api/");
+
+    let landing_page = client.landing_page().unwrap();
+    assert_eq!(landing_page.id, "api");
+    assert_eq!(landing_page.links[0].rel, "search");
+    assert_eq!(landing_page.extra_fields["stac_version"], json!("1.0.0"));
+
+    let conformance = client.conformance().unwrap();
+    assert!(conformance.conforms_to("https://api.stacspec.org/v1.0.0/item-search"));
+    assert!(!conformance.conforms_to("https://api.stacspec.org/v1.0.0/collections"));
+    assert_eq!(requests.lock().unwrap().len(), 2);
+}
+
+#[test]
+fn test_collections() {
+    let (root, _) = serve(
+        |request| match (request.path.as_str(), request.param("page")) {
+            ("/collections", None) => Some(json!({
+                "collections": [collection("a"), collection("b")],
+                "links": [{"rel": "next", "href": "collections?page=2"}]
+            })),
+            ("/collections", Some("2")) => Some(json!({
+                "collections": [collection("c")],
+                "links": []
+            })),
+            ("/collections/a%20b", None) => Some(collection("a b")),
+            _ => None,
+        },
+    );
+    let client = Client::new(&root).unwrap();
+    let ids: Vec<String> = client
+        .collections()
+        .unwrap()
+        .into_iter()
+        .map(|collection| collection.id)
+        .collect();
+    assert_eq!(ids, vec!["a", "b", "c"]);
+
+    assert_eq!(client.collection("a b").unwrap().id, "a b");
+    match client.collection("missing") {
+        Err(STACError::API(status, body)) => {
+            assert_eq!(status.as_u16(), 404);
+            assert!(body.contains("NotFound"));
+        }
+        other => panic!("expected an API error, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_collections_with_repeated_next_link() {
+    let (root, requests) = serve(|request| match request.param("page") {
+        None => Some(json!({
+            "collections": [collection("a")],
+            "links": [{"rel": "next", "href": "collections?page=2"}]
+        })),
+        Some(_) => Some(json!({
+            "collections": [collection("b")],
+            "links": [{"rel": "next", "href": "collections?page=2"}]
+        })),
+    });
+    let ids: Vec<String> = Client::new(&root)
+        .unwrap()
+        .collections()
+        .unwrap()
+        .into_iter()
+        .map(|collection| collection.id)
+        .collect();
+    assert_eq!(ids, vec!["a", "b"]);
+    assert_eq!(requests.lock().unwrap().len(), 2);
+}
+
+#[test]
+fn test_search_get() {
+    let (root, requests) = serve(|request| {
+        if request.path != "/search" || request.method != "GET" {
+            return None;
+        }
+        let page = match request.param("token") {
+            None => json!({
+                "type": "FeatureCollection",
+                "features": [feature("a"), feature("b")],
+                "links": [{"rel": "next", "href": "/search?limit=2&token=2"}]
+            }),
+            Some("2") => json!({
+                "type": "FeatureCollection",
+                "features": [feature("c"), feature("d")],
+                "links": [{"rel": "next", "href": "/search?limit=2&token=3"}]
+            }),
+            _ => json!({"type": "FeatureCollection", "features": [feature("e")], "links": []}),
+        };
+        Some(page)
+    });
+    let client = Client::new(&root).unwrap();
+    let search = Search::new()
+        .bbox(vec![-10.0, -5.5, 10.0, 5.5])
+        .datetime("2021-01-01T00:00:00Z/..")
+        .ids(vec!["a", "b"])
+        .collections(vec!["one", "two"])
+        .limit(2);
+
+    let items = client.search_get(&search).unwrap();
+    assert!(requests.lock().unwrap().is_empty());
+    assert_eq!(ids(items), vec!["a", "b", "c", "d", "e"]);
+
+    let requests = requests.lock().unwrap();
+    assert_eq!(requests.len(), 3);
+    let first = &requests[0];
+    assert_eq!(first.param("bbox"), Some("-10,-5.5,10,5.5"));
+    assert_eq!(first.param("datetime"), Some("2021-01-01T00:00:00Z/.."));
+    assert_eq!(first.param("ids"), Some("a,b"));
+    assert_eq!(first.param("collections"), Some("one,two"));
+    assert_eq!(first.param("limit"), Some("2"));
+    assert_eq!(requests[2].param("token"), Some("3"));
+}
+
+#[test]
+fn test_search_with_repeated_next_link() {
+    let (root, requests) = serve(|request| {
+        let page = match request.param("token") {
+            None => json!({
+                "type": "FeatureCollection",
+                "features": [feature("a")],
+                "links": [{"rel": "next", "href": "/search?token=2"}]
+            }),
+            Some(_) => json!({
+                "type": "FeatureCollection",
+                "features": [feature("b")],
+                "links": [{"rel": "next", "href": "/search?token=2"}]
+            }),
+        };
+        Some(page)
+    });
+    let client = Client::new(&root).unwrap();
+    assert_eq!(
+        ids(client.search_get(&Search::new()).unwrap()),
+        vec!["a", "b"]
+    );
+    assert_eq!(requests.lock().unwrap().len(), 2);
+
+    let (root, requests) = serve(|_| {
+        Some(json!({
+            "type": "FeatureCollection",
+            "features": [feature("a")],
+            "links": [{"rel": "next", "href": "/search", "method": "POST", "body": {"token": "2"}}]
+        }))
+    });
+    let client = Client::new(&root).unwrap();
+    assert_eq!(
+        ids(client.search_post(&Search::new()).unwrap()),
+        vec!["a", "a"]
+    );
+    assert_eq!(requests.lock().unwrap().len(), 2);
+}
+
+#[test]
+fn test_search_post() {
+    let (root, requests) = serve(|request| {
+        if request.path != "/search" || request.method != "POST" {
+            return None;
+        }
+        let body = request.body.clone().unwrap();
+        let page = match body.get("token").and_then(|token| token.as_str()) {
+            None => json!({
+                "type": "FeatureCollection",
+                "features": [feature("a")],
+                "links": [{
+                    "rel": "next",
+                    "href": "/search",
+                    "method": "POST",
+                    "body": {"token": "2"},
+                    "merge": true
+                }]
+            }),
+            Some(_) => json!({
+                "type": "FeatureCollection",
+                "features": [feature("b")],
+                "links": [{
+                    "rel": "next",
+                    "href": "/search",
+                    "method": "POST",
+                    "body": {"token": "3"}
+                }]
+            }),
+        };
+        Some(page)
+    });
+    let client = Client::new(&root).unwrap();
+    let point = Geometry::new(GeometryValue::Point(vec![0.5, 0.5]));
+    let search = Search::new()
+        .intersects(point)
+        .collections(vec!["one"])
+        .limit(1);
+
+    // The server links every page after the first to another page, so only take three Items
+    assert_eq!(
+        ids(client.search_post(&search).unwrap().take(3)),
+        vec!["a", "b", "b"]
+    );
+
+    let requests = requests.lock().unwrap();
+    assert_eq!(
+        requests[0].body,
+        Some(json!({
+            "intersects": {"type": "Point", "coordinates": [0.5, 0.5]},
+            "collections": ["one"],
+            "limit": 1
+        }))
+    );
+    assert_eq!(
+        requests[1].body,
+        Some(json!({
+            "intersects": {"type": "Point", "coordinates": [0.5, 0.5]},
+            "collections": ["one"],
+            "limit": 1,
+            "token": "2"
+        }))
+    );
+    assert_eq!(requests[2].body, Some(json!({"token": "3"})));
+}
+
+#[test]
+fn test_search_errors() {
+    let (root, _) = serve(|request| match request.param("token") {
+        None => Some(json!({
+            "type": "FeatureCollection",
+            "features": [feature("a")],
+            "links": [{"rel": "next", "href": "/search?token=2"}]
+        })),
+        Some(_) => None,
+    });
+    let client = Client::new(&root).unwrap();
+    let mut items = client.search_get(&Search::new()).unwrap();
+    assert_eq!(items.next().unwrap().unwrap().id, "a");
+    assert!(matches!(items.next(), Some(Err(STACError::API(_, _)))));
+    assert!(items.next().is_none());
+
+    let (root, _) = serve(|_| {
+        Some(json!({
+            "type": "FeatureCollection",
+            "features": [],
+            "links": [{"rel": "next", "href": "/search?token=2"}]
+        }))
+    });
+    let client = Client::new(&root).unwrap();
+    assert!(client.search_get(&Search::new()).unwrap().next().is_none());
+
+    assert!(matches!(Client::new("not a url"), Err(STACError::URL(_))));
+    assert!(Client::new("data:text/plain,stac").is_err());
+}
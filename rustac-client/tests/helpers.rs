@@ -0,0 +1,120 @@
+use serde_json::{json, Value};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+/// A request received by the mock server.
+#[allow(dead_code)]
+#[derive(Debug, Clone)]
+pub(crate) struct Request {
+    pub(crate) method: String,
+    pub(crate) path: String,
+    pub(crate) query: Vec<(String, String)>,
+    pub(crate) body: Option<Value>,
+}
+
+impl Request {
+    #[allow(dead_code)]
+    pub(crate) fn param(&self, name: &str) -> Option<&str> {
+        self.query
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Serves the documents returned by `handler` on a local HTTP server, responding with 404 if the
+/// handler returns `None`. Returns the root URL of the server and the requests it has received.
+/// The server runs until the test process exits.
+#[allow(dead_code)]
+pub(crate) fn serve<F>(handler: F) -> (String, Arc<Mutex<Vec<Request>>>)
+where
+    F: Fn(&Request) -> Option<Value> + Send + 'static,
+{
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let root = format!("http://{}", listener.local_addr().unwrap());
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let received = Arc::clone(&requests);
+    let base = url::Url::parse(&root).unwrap();
+
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+            let mut content_length = 0;
+            loop {
+                let mut header = String::new();
+                reader.read_line(&mut header).unwrap();
+                if header.trim().is_empty() {
+                    break;
+                }
+                if let Some((name, value)) = header.split_once(':') {
+                    if name.eq_ignore_ascii_case("content-length") {
+                        content_length = value.trim().parse().unwrap();
+                    }
+                }
+            }
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).unwrap();
+
+            let mut parts = request_line.split_whitespace();
+            let method = parts.next().unwrap_or_default().to_string();
+            let url = base.join(parts.next().unwrap_or("/")).unwrap();
+            let request = Request {
+                method,
+                path: url.path().to_string(),
+                query: url.query_pairs().into_owned().collect(),
+                body: serde_json::from_slice(&body).ok(),
+            };
+            let response = handler(&request);
+            received.lock().unwrap().push(request);
+
+            let (status, body) = match response {
+                Some(body) => ("200 OK", body.to_string()),
+                None => ("404 Not Found", json!({"code": "NotFound"}).to_string()),
+            };
+            let response = format!(
+                "HTTP/1.1 {status}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            stream.write_all(response.as_bytes()).unwrap();
+        }
+    });
+
+    (root, requests)
+}
+
+/// A minimal Item with the given id and properties.
+#[allow(dead_code)]
+pub(crate) fn item(id: &str, properties: Value) -> Value {
+    json!({
+        "stac_version": "1.0.0",
+        "type": "Feature",
+        "id": id,
+        "bbox": [0.0, 0.0, 1.0, 1.0],
+        "geometry": {"type": "Point", "coordinates": [0.0, 0.0]},
+        "properties": properties,
+        "links": [],
+        "assets": {}
+    })
+}
+
+/// A minimal Collection with the given id.
+#[allow(dead_code)]
+pub(crate) fn collection(id: &str) -> Value {
+    json!({
+        "stac_version": "1.0.0",
+        "type": "Collection",
+        "id": id,
+        "description": "A collection",
+        "license": "proprietary",
+        "extent": {
+            "spatial": {"bbox": [[0.0, 0.0, 1.0, 1.0]]},
+            "temporal": {"interval": [["2021-01-01T00:00:00Z", null]]}
+        },
+        "links": []
+    })
+}
@@ -0,0 +1,479 @@
+//! Parses, converts and evaluates [CQL2](https://docs.ogc.org/is/21-065r2/21-065r2.html) filter
+//! expressions.
+//!
+//! CQL2-Text and CQL2-JSON are both parsed into the same [`Expr`], which can be written back out in
+//! either encoding and evaluated against an [`Item`]:
+//!
+//! ```
+//! use rustac_core::cql2::Expr;
+//! use rustac_core::Item;
+//! use serde_json::json;
+//!
+//! let item: Item = serde_json::from_value(json!({
+//!     "stac_version": "1.0.0",
+//!     "type": "Feature",
+//!     "id": "an-item",
+//!     "bbox": [0.0, 0.0, 1.0, 1.0],
+//!     "geometry": {"type": "Point", "coordinates": [0.5, 0.5]},
+//!     "properties": {"datetime": "2021-06-01T00:00:00Z", "eo:cloud_cover": 12.5},
+//!     "links": [],
+//!     "assets": {}
+//! })).unwrap();
+//!
+//! let expr: Expr = "\"eo:cloud_cover\" < 20 AND S_INTERSECTS(geometry, BBOX(0, 0, 1, 1)) \
+//!                   AND T_INTERSECTS(datetime, INTERVAL('2021-01-01', '2021-12-31'))"
+//!     .parse()
+//!     .unwrap();
+//! assert!(expr.matches(&item).unwrap());
+//!
+//! let json = expr.to_json();
+//! assert_eq!(json["op"], "and");
+//! assert_eq!(Expr::from_json(&json).unwrap(), expr);
+//! ```
+//!
+//! ## Evaluation
+//!
+//! Property names refer to the Item's `properties`, except for `id`, `collection`, `geometry` and
+//! `bbox`, which refer to the top-level fields of the Item. `datetime` is the Item's datetime, or its
+//! `start_datetime`/`end_datetime` interval if `datetime` is `null`. Comparisons with a missing or
+//! `null` property are false.
+//!
+//! Temporal operators compare intervals; instants are intervals that start and end at the same time,
+//! and dates are intervals covering the whole day. Spatial operators treat polygons as closed, so
+//! geometries that only touch a polygon intersect it, and geometries on its boundary are within it.
+//! `S_INTERSECTS`, `S_DISJOINT`, `S_WITHIN`, `S_CONTAINS` and `S_EQUALS` are supported. `CASEI` is
+//! the only supported function.
+use geojson::Geometry;
+use serde::de::{self, Deserializer};
+use serde::ser::Serializer;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::error;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::STACResult;
+use crate::Item;
+
+mod eval;
+mod geometry;
+mod text;
+
+/// A CQL2 expression.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    /// An operator applied to its arguments, e.g. `a = 1` or `S_INTERSECTS(geometry, ...)`.
+    Operation {
+        /// The operator.
+        op: Op,
+
+        /// The arguments, in order.
+        args: Vec<Expr>,
+    },
+
+    /// A call of a function that is not one of the standard operators, e.g. `CASEI(name)`.
+    Function {
+        /// The name of the function, as written.
+        name: String,
+
+        /// The arguments, in order.
+        args: Vec<Expr>,
+    },
+
+    /// The value of a property of the Item.
+    Property(String),
+
+    /// A string literal.
+    String(String),
+
+    /// A numeric literal.
+    Number(f64),
+
+    /// A boolean literal.
+    Bool(bool),
+
+    /// The `NULL` literal.
+    Null,
+
+    /// A date, e.g. `DATE('2021-01-01')`.
+    Date(String),
+
+    /// A timestamp, e.g. `TIMESTAMP('2021-01-01T00:00:00Z')`.
+    Timestamp(String),
+
+    /// An interval between two instants, either of which may be open (`'..'`), e.g.
+    /// `INTERVAL('2021-01-01', '..')`. The bounds are dates, timestamps, `String("..")`,
+    /// properties or functions.
+    Interval(Box<Expr>, Box<Expr>),
+
+    /// A geometry literal, written as WKT in CQL2-Text and as GeoJSON in CQL2-JSON.
+    Geometry(Geometry),
+
+    /// A bounding box literal, with 4 or 6 numbers.
+    Bbox(Vec<f64>),
+
+    /// An array literal.
+    Array(Vec<Expr>),
+}
+
+macro_rules! ops {
+    ($($variant:ident => $name:literal, $min:literal, $max:expr, $doc:literal;)*) => {
+        /// An operator of a CQL2 [`Expr::Operation`].
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum Op {
+            $(
+                #[doc = $doc]
+                $variant,
+            )*
+        }
+
+        impl Op {
+            /// All operators.
+            pub const ALL: &'static [Op] = &[$(Op::$variant),*];
+
+            /// The name of the operator in CQL2-JSON, e.g. `"s_intersects"`.
+            #[must_use]
+            pub fn name(self) -> &'static str {
+                match self {
+                    $(Op::$variant => $name,)*
+                }
+            }
+
+            /// The minimum and maximum number of arguments of the operator.
+            fn arity(self) -> (usize, Option<usize>) {
+                match self {
+                    $(Op::$variant => ($min, $max),)*
+                }
+            }
+        }
+    };
+}
+
+ops! {
+    And => "and", 2, None, "Logical conjunction";
+    Or => "or", 2, None, "Logical disjunction";
+    Not => "not", 1, Some(1), "Logical negation";
+    Eq => "=", 2, Some(2), "Equal to";
+    Ne => "<>", 2, Some(2), "Not equal to";
+    Lt => "<", 2, Some(2), "Less than";
+    Le => "<=", 2, Some(2), "Less than or equal to";
+    Gt => ">", 2, Some(2), "Greater than";
+    Ge => ">=", 2, Some(2), "Greater than or equal to";
+    Like => "like", 2, Some(2), "String matches a pattern, where `%` matches any characters and `_` a single character";
+    Between => "between", 3, Some(3), "Value is between two values, inclusive";
+    In => "in", 2, Some(2), "Value is one of a list of values";
+    IsNull => "isNull", 1, Some(1), "Value is null or missing";
+    SIntersects => "s_intersects", 2, Some(2), "Geometries intersect";
+    SEquals => "s_equals", 2, Some(2), "Geometries are equal";
+    SDisjoint => "s_disjoint", 2, Some(2), "Geometries do not intersect";
+    STouches => "s_touches", 2, Some(2), "Geometries touch";
+    SWithin => "s_within", 2, Some(2), "The first geometry is within the second";
+    SOverlaps => "s_overlaps", 2, Some(2), "Geometries overlap";
+    SCrosses => "s_crosses", 2, Some(2), "Geometries cross";
+    SContains => "s_contains", 2, Some(2), "The first geometry contains the second";
+    TAfter => "t_after", 2, Some(2), "The first interval starts after the second ends";
+    TBefore => "t_before", 2, Some(2), "The first interval ends before the second starts";
+    TContains => "t_contains", 2, Some(2), "The first interval contains the second";
+    TDisjoint => "t_disjoint", 2, Some(2), "Intervals do not intersect";
+    TDuring => "t_during", 2, Some(2), "The first interval is during the second";
+    TEquals => "t_equals", 2, Some(2), "Intervals are equal";
+    TFinishedBy => "t_finishedBy", 2, Some(2), "The second interval finishes the first";
+    TFinishes => "t_finishes", 2, Some(2), "The first interval finishes the second";
+    TIntersects => "t_intersects", 2, Some(2), "Intervals intersect";
+    TMeets => "t_meets", 2, Some(2), "The first interval ends when the second starts";
+    TMetBy => "t_metBy", 2, Some(2), "The first interval starts when the second ends";
+    TOverlappedBy => "t_overlappedBy", 2, Some(2), "The second interval overlaps the start of the first";
+    TOverlaps => "t_overlaps", 2, Some(2), "The first interval overlaps the start of the second";
+    TStartedBy => "t_startedBy", 2, Some(2), "The second interval starts the first";
+    TStarts => "t_starts", 2, Some(2), "The first interval starts the second";
+    AEquals => "a_equals", 2, Some(2), "Arrays have the same elements";
+    AContains => "a_contains", 2, Some(2), "The first array contains all elements of the second";
+    AContainedBy => "a_containedBy", 2, Some(2), "All elements of the first array are in the second";
+    AOverlaps => "a_overlaps", 2, Some(2), "Arrays have an element in common";
+}
+
+impl Op {
+    /// Finds the operator with the given CQL2-JSON name, or CQL2-Text function name, ignoring case.
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Op> {
+        Op::ALL
+            .iter()
+            .copied()
+            .find(|op| op.name().eq_ignore_ascii_case(name))
+    }
+
+    /// Returns `true` for the spatial, temporal and array operators, which are written as functions
+    /// in CQL2-Text, e.g. `S_INTERSECTS(a, b)`.
+    #[must_use]
+    pub fn is_function(self) -> bool {
+        matches!(self.name().get(..2), Some("s_" | "t_" | "a_"))
+    }
+
+    /// Checks that the operation can take `count` arguments, returning a message describing the
+    /// expected number otherwise.
+    pub(crate) fn check_arity(self, count: usize) -> Result<(), String> {
+        let (min, max) = self.arity();
+        if count >= min && max.is_none_or(|max| count <= max) {
+            return Ok(());
+        }
+        let expected = match max {
+            Some(max) if max == min => format!("{min}"),
+            Some(max) => format!("{min} to {max}"),
+            None => format!("at least {min}"),
+        };
+        Err(format!(
+            "{} takes {expected} argument{}, found {count}",
+            self.name(),
+            if max == Some(1) { "" } else { "s" },
+        ))
+    }
+}
+
+/// An error parsing a CQL2 expression.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// What went wrong.
+    pub message: String,
+
+    /// The character offset in CQL2-Text at which the error occurred. `None` for CQL2-JSON.
+    pub position: Option<usize>,
+}
+
+impl ParseError {
+    pub(crate) fn new<M: Into<String>>(message: M, position: Option<usize>) -> ParseError {
+        ParseError {
+            message: message.into(),
+            position,
+        }
+    }
+}
+
+impl error::Error for ParseError {}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.position {
+            Some(position) => write!(f, "{} at position {position}", self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl Expr {
+    /// Parses CQL2-Text.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ParseError`] with the position of the first unexpected token.
+    pub fn from_text(text: &str) -> Result<Expr, ParseError> {
+        text::parse(text)
+    }
+
+    /// Writes the expression as CQL2-Text.
+    #[must_use]
+    pub fn to_text(&self) -> String {
+        self.to_string()
+    }
+
+    /// Parses CQL2-JSON.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ParseError`] describing the first invalid part of the expression.
+    pub fn from_json(value: &Value) -> Result<Expr, ParseError> {
+        let error = |message: String| Err(ParseError::new(message, None));
+        match value {
+            Value::Null => Ok(Expr::Null),
+            Value::Bool(value) => Ok(Expr::Bool(*value)),
+            Value::Number(number) => Ok(Expr::Number(number.as_f64().unwrap_or(f64::NAN))),
+            Value::String(value) => Ok(Expr::String(value.clone())),
+            Value::Array(values) => Ok(Expr::Array(
+                values
+                    .iter()
+                    .map(Expr::from_json)
+                    .collect::<Result<_, _>>()?,
+            )),
+            Value::Object(object) => {
+                if let Some(op) = object.get("op") {
+                    let Some(name) = op.as_str() else {
+                        return error(format!("\"op\" must be a string, found {op}"));
+                    };
+                    let args = match object.get("args") {
+                        Some(Value::Array(args)) => args
+                            .iter()
+                            .map(Expr::from_json)
+                            .collect::<Result<Vec<_>, _>>()?,
+                        Some(args) => {
+                            return error(format!(
+                                "\"args\" of {name} must be an array, found {args}"
+                            ))
+                        }
+                        None => Vec::new(),
+                    };
+                    return match Op::from_name(name) {
+                        Some(op) => Expr::operation(op, args, None),
+                        None => Ok(Expr::Function {
+                            name: name.to_string(),
+                            args,
+                        }),
+                    };
+                }
+                let single = |key: &str| object.get(key).filter(|_| object.len() == 1);
+                if let Some(name) = single("property") {
+                    return match name.as_str() {
+                        Some(name) => Ok(Expr::Property(name.to_string())),
+                        None => error(format!("\"property\" must be a string, found {name}")),
+                    };
+                }
+                if let Some(date) = single("date") {
+                    return match date.as_str() {
+                        Some(date) => Ok(Expr::Date(date.to_string())),
+                        None => error(format!("\"date\" must be a string, found {date}")),
+                    };
+                }
+                if let Some(timestamp) = single("timestamp") {
+                    return match timestamp.as_str() {
+                        Some(timestamp) => Ok(Expr::Timestamp(timestamp.to_string())),
+                        None => error(format!("\"timestamp\" must be a string, found {timestamp}")),
+                    };
+                }
+                if let Some(interval) = single("interval") {
+                    return match interval.as_array().map(Vec::as_slice) {
+                        Some([start, end]) => Ok(Expr::Interval(
+                            Box::new(interval_bound(Expr::from_json(start)?)),
+                            Box::new(interval_bound(Expr::from_json(end)?)),
+                        )),
+                        _ => error(format!(
+                            "\"interval\" must be an array of two instants, found {interval}"
+                        )),
+                    };
+                }
+                if let Some(bbox) = single("bbox") {
+                    return match serde_json::from_value::<Vec<f64>>(bbox.clone()) {
+                        Ok(bbox) if bbox.len() == 4 || bbox.len() == 6 => Ok(Expr::Bbox(bbox)),
+                        _ => error(format!(
+                            "\"bbox\" must be an array of 4 or 6 numbers, found {bbox}"
+                        )),
+                    };
+                }
+                if object.contains_key("type") {
+                    return Geometry::from_json_object(object.clone())
+                        .map(Expr::Geometry)
+                        .map_err(|err| ParseError::new(format!("Invalid geometry: {err}"), None));
+                }
+                error(format!("Unrecognized expression {value}"))
+            }
+        }
+    }
+
+    /// Writes the expression as CQL2-JSON.
+    #[must_use]
+    pub fn to_json(&self) -> Value {
+        let object = |key: &str, value: Value| {
+            let mut object = Map::new();
+            object.insert(String::from(key), value);
+            Value::Object(object)
+        };
+        let operation = |name: &str, args: &[Expr]| {
+            let mut object = Map::new();
+            object.insert(String::from("op"), Value::from(name));
+            object.insert(
+                String::from("args"),
+                Value::Array(args.iter().map(Expr::to_json).collect()),
+            );
+            Value::Object(object)
+        };
+        match self {
+            Expr::Operation { op, args } => operation(op.name(), args),
+            Expr::Function { name, args } => operation(name, args),
+            Expr::Property(name) => object("property", Value::from(name.as_str())),
+            Expr::String(value) => Value::from(value.as_str()),
+            Expr::Number(value) => Value::from(*value),
+            Expr::Bool(value) => Value::from(*value),
+            Expr::Null => Value::Null,
+            Expr::Date(date) => object("date", Value::from(date.as_str())),
+            Expr::Timestamp(timestamp) => object("timestamp", Value::from(timestamp.as_str())),
+            Expr::Interval(start, end) => {
+                let bound = |bound: &Expr| match bound {
+                    Expr::Date(instant) | Expr::Timestamp(instant) | Expr::String(instant) => {
+                        Value::from(instant.as_str())
+                    }
+                    bound => bound.to_json(),
+                };
+                object("interval", Value::Array(vec![bound(start), bound(end)]))
+            }
+            Expr::Geometry(geometry) => Value::Object(geometry.into()),
+            Expr::Bbox(bbox) => object("bbox", Value::from(bbox.clone())),
+            Expr::Array(values) => Value::Array(values.iter().map(Expr::to_json).collect()),
+        }
+    }
+
+    /// Evaluates the expression against an Item, see the [module documentation](self) for the
+    /// semantics.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::Other`](crate::error::STACError::Other) if the expression is not
+    /// boolean, uses an unsupported operator or function, or compares values of incompatible types,
+    /// such as a string with a number.
+    pub fn matches(&self, item: &Item) -> STACResult<bool> {
+        self.matches_json(&serde_json::to_value(item)?)
+    }
+
+    /// Evaluates the expression against the JSON of an Item, see [`Expr::matches`]. This avoids
+    /// serializing the Item again when it is evaluated against several expressions.
+    ///
+    /// # Errors
+    ///
+    /// See [`Expr::matches`].
+    pub fn matches_json(&self, item: &Value) -> STACResult<bool> {
+        eval::matches(self, item)
+    }
+
+    /// Creates an operation, checking the number of arguments.
+    pub(crate) fn operation(
+        op: Op,
+        args: Vec<Expr>,
+        position: Option<usize>,
+    ) -> Result<Expr, ParseError> {
+        op.check_arity(args.len())
+            .map_err(|message| ParseError::new(message, position))?;
+        Ok(Expr::Operation { op, args })
+    }
+}
+
+/// Classifies a string interval bound as a date, timestamp or open bound (`..`).
+pub(crate) fn interval_bound(bound: Expr) -> Expr {
+    match bound {
+        Expr::String(instant) if instant == ".." => Expr::String(instant),
+        Expr::String(instant) if instant.len() == 10 => Expr::Date(instant),
+        Expr::String(instant) => Expr::Timestamp(instant),
+        bound => bound,
+    }
+}
+
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        text::write(self, f)
+    }
+}
+
+impl FromStr for Expr {
+    type Err = ParseError;
+
+    fn from_str(text: &str) -> Result<Expr, ParseError> {
+        Expr::from_text(text)
+    }
+}
+
+impl Serialize for Expr {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.to_json().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Expr {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Expr, D::Error> {
+        Expr::from_json(&Value::deserialize(deserializer)?).map_err(de::Error::custom)
+    }
+}
@@ -0,0 +1,504 @@
+//! Evaluates expressions against the JSON of an Item.
+use chrono::{DateTime, NaiveDate};
+use geojson::Geometry;
+use serde_json::{Map, Value};
+use std::cmp::Ordering;
+
+use super::geometry::Shape;
+use super::{Expr, Op};
+use crate::error::{STACError, STACResult};
+
+/// The value of an expression.
+#[derive(Debug, Clone, PartialEq)]
+enum Val {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Val>),
+    /// An interval of microseconds since the epoch, inclusive, with open ends at the extremes.
+    Time(i64, i64),
+    Geometry(Shape),
+    Object(Map<String, Value>),
+}
+
+impl Val {
+    fn kind(&self) -> &'static str {
+        match self {
+            Val::Null => "null",
+            Val::Bool(_) => "boolean",
+            Val::Number(_) => "number",
+            Val::String(_) => "string",
+            Val::Array(_) => "array",
+            Val::Time(..) => "temporal value",
+            Val::Geometry(_) => "geometry",
+            Val::Object(_) => "object",
+        }
+    }
+}
+
+fn error<T>(message: String) -> STACResult<T> {
+    Err(STACError::Other(message))
+}
+
+pub(crate) fn matches(expr: &Expr, item: &Value) -> STACResult<bool> {
+    match eval(expr, item)? {
+        Val::Bool(value) => Ok(value),
+        Val::Null => Ok(false),
+        value => error(format!(
+            "Expression {expr} is a {}, not a boolean",
+            value.kind()
+        )),
+    }
+}
+
+fn eval(expr: &Expr, item: &Value) -> STACResult<Val> {
+    Ok(match expr {
+        Expr::Operation { op, args } => operation(*op, args, item)?,
+        Expr::Function { name, args } => function(name, args, item)?,
+        Expr::Property(name) => property(name, item)?,
+        Expr::String(value) => Val::String(value.clone()),
+        Expr::Number(value) => Val::Number(*value),
+        Expr::Bool(value) => Val::Bool(*value),
+        Expr::Null => Val::Null,
+        Expr::Date(date) => {
+            let (start, end) = parse_date(date)?;
+            Val::Time(start, end)
+        }
+        Expr::Timestamp(timestamp) => {
+            let instant = parse_timestamp(timestamp)?;
+            Val::Time(instant, instant)
+        }
+        Expr::Interval(start, end) => {
+            let bound = |bound: &Expr, item: &Value| -> STACResult<Option<(i64, i64)>> {
+                match bound {
+                    Expr::String(open) if open == ".." => Ok(Some((i64::MIN, i64::MAX))),
+                    bound => Ok(temporal(eval(bound, item)?)?),
+                }
+            };
+            match (bound(start, item)?, bound(end, item)?) {
+                (Some((start, _)), Some((_, end))) => Val::Time(start, end),
+                _ => Val::Null,
+            }
+        }
+        Expr::Geometry(geometry) => Val::Geometry(Shape::from_geojson(&geometry.value)),
+        Expr::Bbox(bbox) => match Shape::from_bbox(bbox) {
+            Some(shape) => Val::Geometry(shape),
+            None => return error(format!("Invalid bbox {bbox:?}")),
+        },
+        Expr::Array(values) => Val::Array(
+            values
+                .iter()
+                .map(|value| eval(value, item))
+                .collect::<STACResult<_>>()?,
+        ),
+    })
+}
+
+/// Resolves a property of the Item, see the module documentation of [`cql2`](super).
+fn property(name: &str, item: &Value) -> STACResult<Val> {
+    let properties = item.get("properties");
+    let value = match name {
+        "id" | "collection" | "geometry" | "bbox" => item.get(name),
+        "datetime" => {
+            let get = |key: &str| {
+                properties
+                    .and_then(|properties| properties.get(key))
+                    .and_then(Value::as_str)
+            };
+            return Ok(
+                match (get("datetime"), get("start_datetime"), get("end_datetime")) {
+                    (Some(datetime), ..) => {
+                        let instant = parse_timestamp(datetime)?;
+                        Val::Time(instant, instant)
+                    }
+                    (None, Some(start), Some(end)) => {
+                        Val::Time(parse_timestamp(start)?, parse_timestamp(end)?)
+                    }
+                    _ => Val::Null,
+                },
+            );
+        }
+        name => properties.and_then(|properties| {
+            properties.get(name).or_else(|| {
+                name.strip_prefix("properties.")
+                    .and_then(|name| properties.get(name))
+            })
+        }),
+    };
+    Ok(value.map_or(Val::Null, from_json))
+}
+
+fn from_json(value: &Value) -> Val {
+    match value {
+        Value::Null => Val::Null,
+        Value::Bool(value) => Val::Bool(*value),
+        Value::Number(number) => Val::Number(number.as_f64().unwrap_or(f64::NAN)),
+        Value::String(value) => Val::String(value.clone()),
+        Value::Array(values) => Val::Array(values.iter().map(from_json).collect()),
+        Value::Object(object) => match Geometry::from_json_object(object.clone()) {
+            Ok(geometry) => Val::Geometry(Shape::from_geojson(&geometry.value)),
+            Err(_) => Val::Object(object.clone()),
+        },
+    }
+}
+
+fn parse_timestamp(timestamp: &str) -> STACResult<i64> {
+    match DateTime::parse_from_rfc3339(timestamp) {
+        Ok(datetime) => Ok(datetime.timestamp_micros()),
+        Err(err) => error(format!("Invalid timestamp {timestamp}: {err}")),
+    }
+}
+
+/// Parses a date into the interval covering the whole day.
+fn parse_date(date: &str) -> STACResult<(i64, i64)> {
+    match NaiveDate::parse_from_str(date, "%Y-%m-%d") {
+        Ok(date) => {
+            let start = date
+                .and_hms_opt(0, 0, 0)
+                .map(|start| start.and_utc().timestamp_micros());
+            match start {
+                Some(start) => Ok((start, start + 86_400_000_000 - 1)),
+                None => error(format!("Invalid date {date}")),
+            }
+        }
+        Err(err) => error(format!("Invalid date {date}: {err}")),
+    }
+}
+
+/// Converts a value to an interval, parsing strings as dates or timestamps. Returns `None` for null.
+fn temporal(value: Val) -> STACResult<Option<(i64, i64)>> {
+    match value {
+        Val::Null => Ok(None),
+        Val::Time(start, end) => Ok(Some((start, end))),
+        Val::String(instant) if instant.len() == 10 => parse_date(&instant).map(Some),
+        Val::String(instant) => {
+            let instant = parse_timestamp(&instant)?;
+            Ok(Some((instant, instant)))
+        }
+        value => error(format!(
+            "Expected a temporal value, found a {}",
+            value.kind()
+        )),
+    }
+}
+
+/// Converts a value to a shape, treating arrays of 4 or 6 numbers as bboxes. Returns `None` for
+/// null.
+fn spatial(value: Val) -> STACResult<Option<Shape>> {
+    match value {
+        Val::Null => Ok(None),
+        Val::Geometry(shape) => Ok(Some(shape)),
+        Val::Array(values) => {
+            let bbox: Option<Vec<f64>> = values
+                .iter()
+                .map(|value| match value {
+                    Val::Number(number) => Some(*number),
+                    _ => None,
+                })
+                .collect();
+            match bbox.as_deref().and_then(Shape::from_bbox) {
+                Some(shape) => Ok(Some(shape)),
+                None => error(String::from("Expected a geometry, found an array")),
+            }
+        }
+        value => error(format!("Expected a geometry, found a {}", value.kind())),
+    }
+}
+
+fn boolean(expr: &Expr, item: &Value) -> STACResult<bool> {
+    matches(expr, item)
+}
+
+/// Orders two values, returning `None` if either is null.
+fn compare(left: &Val, right: &Val) -> STACResult<Option<Ordering>> {
+    let ordering = match (left, right) {
+        (Val::Null, _) | (_, Val::Null) => return Ok(None),
+        (Val::Number(a), Val::Number(b)) => a.partial_cmp(b),
+        (Val::String(a), Val::String(b)) => Some(a.cmp(b)),
+        (Val::Bool(a), Val::Bool(b)) => Some(a.cmp(b)),
+        (Val::Time(..), _) | (_, Val::Time(..)) => {
+            match (temporal(left.clone())?, temporal(right.clone())?) {
+                (Some(a), Some(b)) => Some(a.cmp(&b)),
+                _ => None,
+            }
+        }
+        (a, b) if a.kind() == b.kind() && !is_ordered(a) => {
+            return error(format!("Values of type {} cannot be ordered", a.kind()))
+        }
+        (a, b) => return error(format!("Cannot compare a {} with a {}", a.kind(), b.kind())),
+    };
+    Ok(ordering)
+}
+
+/// Arrays, geometries and objects can be equal, but have no order.
+fn is_ordered(value: &Val) -> bool {
+    !matches!(value, Val::Array(_) | Val::Geometry(_) | Val::Object(_))
+}
+
+/// Checks whether two values are equal, returning `None` if either is null.
+fn equality(left: &Val, right: &Val) -> STACResult<Option<bool>> {
+    match (left, right) {
+        (a, b) if a.kind() == b.kind() && !is_ordered(a) => Ok(Some(a == b)),
+        _ => Ok(compare(left, right)?.map(|ordering| ordering == Ordering::Equal)),
+    }
+}
+
+fn equals(left: &Val, right: &Val) -> STACResult<bool> {
+    Ok(equality(left, right)? == Some(true))
+}
+
+/// Returns `true` if any of the values equals `value`.
+fn contains(values: &[Val], value: &Val) -> STACResult<bool> {
+    for candidate in values {
+        if equals(candidate, value)? {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+fn operation(op: Op, args: &[Expr], item: &Value) -> STACResult<Val> {
+    // Operations built by hand may have any number of arguments
+    op.check_arity(args.len()).or_else(error)?;
+    let arg = |i: usize| eval(&args[i], item);
+    let result = match op {
+        Op::And => {
+            for arg in args {
+                if !boolean(arg, item)? {
+                    return Ok(Val::Bool(false));
+                }
+            }
+            true
+        }
+        Op::Or => {
+            for arg in args {
+                if boolean(arg, item)? {
+                    return Ok(Val::Bool(true));
+                }
+            }
+            false
+        }
+        Op::Not => !boolean(&args[0], item)?,
+        Op::Eq | Op::Ne => match equality(&arg(0)?, &arg(1)?)? {
+            Some(equal) => equal == (op == Op::Eq),
+            None => false,
+        },
+        Op::Lt | Op::Le | Op::Gt | Op::Ge => {
+            let Some(ordering) = compare(&arg(0)?, &arg(1)?)? else {
+                return Ok(Val::Bool(false));
+            };
+            match op {
+                Op::Lt => ordering == Ordering::Less,
+                Op::Le => ordering != Ordering::Greater,
+                Op::Gt => ordering == Ordering::Greater,
+                _ => ordering != Ordering::Less,
+            }
+        }
+        Op::Like => match (arg(0)?, arg(1)?) {
+            (Val::Null, _) | (_, Val::Null) => false,
+            (Val::String(value), Val::String(pattern)) => like(&value, &pattern),
+            (value, pattern) => {
+                return error(format!(
+                    "LIKE requires strings, found a {} and a {}",
+                    value.kind(),
+                    pattern.kind()
+                ))
+            }
+        },
+        Op::Between => {
+            let value = arg(0)?;
+            let low = compare(&value, &arg(1)?)?;
+            let high = compare(&value, &arg(2)?)?;
+            matches!(
+                (low, high),
+                (
+                    Some(Ordering::Greater | Ordering::Equal),
+                    Some(Ordering::Less | Ordering::Equal)
+                )
+            )
+        }
+        Op::In => match arg(1)? {
+            Val::Array(list) => contains(&list, &arg(0)?)?,
+            value => equals(&arg(0)?, &value)?,
+        },
+        Op::IsNull => arg(0)? == Val::Null,
+        Op::SIntersects
+        | Op::SEquals
+        | Op::SDisjoint
+        | Op::STouches
+        | Op::SWithin
+        | Op::SOverlaps
+        | Op::SCrosses
+        | Op::SContains => spatial_operation(op, arg(0)?, arg(1)?)?,
+        Op::TAfter
+        | Op::TBefore
+        | Op::TContains
+        | Op::TDisjoint
+        | Op::TDuring
+        | Op::TEquals
+        | Op::TFinishedBy
+        | Op::TFinishes
+        | Op::TIntersects
+        | Op::TMeets
+        | Op::TMetBy
+        | Op::TOverlappedBy
+        | Op::TOverlaps
+        | Op::TStartedBy
+        | Op::TStarts => match (temporal(arg(0)?)?, temporal(arg(1)?)?) {
+            (Some(a), Some(b)) => allen(op, a, b),
+            _ => false,
+        },
+        Op::AEquals | Op::AContains | Op::AContainedBy | Op::AOverlaps => {
+            array_operation(op, arg(0)?, arg(1)?)?
+        }
+    };
+    Ok(Val::Bool(result))
+}
+
+fn spatial_operation(op: Op, a: Val, b: Val) -> STACResult<bool> {
+    let (Some(a), Some(b)) = (spatial(a)?, spatial(b)?) else {
+        return Ok(false);
+    };
+    Ok(match op {
+        Op::SIntersects => a.intersects(&b),
+        Op::SDisjoint => !a.intersects(&b),
+        Op::SWithin => a.within(&b),
+        Op::SContains => b.within(&a),
+        Op::SEquals => a.within(&b) && b.within(&a),
+        op => {
+            return error(format!(
+                "{} is not supported",
+                op.name().to_ascii_uppercase()
+            ))
+        }
+    })
+}
+
+/// Evaluates an array operator, treating a value that is not an array as an array of one element.
+fn array_operation(op: Op, a: Val, b: Val) -> STACResult<bool> {
+    let array = |value: Val| match value {
+        Val::Null => None,
+        Val::Array(values) => Some(values),
+        value => Some(vec![value]),
+    };
+    let (Some(a), Some(b)) = (array(a), array(b)) else {
+        return Ok(false);
+    };
+    let all_in = |values: &[Val], others: &[Val]| -> STACResult<bool> {
+        for value in values {
+            if !contains(others, value)? {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    };
+    Ok(match op {
+        Op::AEquals => all_in(&a, &b)? && all_in(&b, &a)?,
+        Op::AContains => all_in(&b, &a)?,
+        Op::AContainedBy => all_in(&a, &b)?,
+        _ => {
+            for value in &a {
+                if contains(&b, value)? {
+                    return Ok(true);
+                }
+            }
+            false
+        }
+    })
+}
+
+/// Evaluates a temporal operator on two inclusive intervals.
+fn allen(op: Op, (a0, a1): (i64, i64), (b0, b1): (i64, i64)) -> bool {
+    match op {
+        Op::TAfter => a0 > b1,
+        Op::TBefore => a1 < b0,
+        Op::TContains => a0 < b0 && a1 > b1,
+        Op::TDisjoint => a0 > b1 || a1 < b0,
+        Op::TDuring => a0 > b0 && a1 < b1,
+        Op::TEquals => a0 == b0 && a1 == b1,
+        Op::TFinishedBy => a0 < b0 && a1 == b1,
+        Op::TFinishes => a0 > b0 && a1 == b1,
+        Op::TIntersects => a0 <= b1 && a1 >= b0,
+        Op::TMeets => a1 == b0,
+        Op::TMetBy => a0 == b1,
+        Op::TOverlappedBy => a0 > b0 && a0 < b1 && a1 > b1,
+        Op::TOverlaps => a0 < b0 && a1 > b0 && a1 < b1,
+        Op::TStartedBy => a0 == b0 && a1 > b1,
+        Op::TStarts => a0 == b0 && a1 < b1,
+        _ => false,
+    }
+}
+
+fn function(name: &str, args: &[Expr], item: &Value) -> STACResult<Val> {
+    if !name.eq_ignore_ascii_case("casei") {
+        return error(format!("Function {name} is not supported"));
+    }
+    let [arg] = args else {
+        return error(format!("CASEI takes 1 argument, found {}", args.len()));
+    };
+    Ok(lower(eval(arg, item)?))
+}
+
+fn lower(value: Val) -> Val {
+    match value {
+        Val::String(value) => Val::String(value.to_lowercase()),
+        Val::Array(values) => Val::Array(values.into_iter().map(lower).collect()),
+        value => value,
+    }
+}
+
+/// A part of a LIKE pattern.
+#[derive(Clone, Copy, PartialEq)]
+enum Wildcard {
+    Any,
+    One,
+    Char(char),
+}
+
+/// Matches a string against a LIKE pattern, where `%` matches any characters, `_` matches a single
+/// character and `\` escapes the next character.
+///
+/// Only the most recent `%` is ever retried, since any match through an earlier one can be moved
+/// to the later one, so this takes at most time proportional to the product of the lengths.
+fn like(value: &str, pattern: &str) -> bool {
+    let value: Vec<char> = value.chars().collect();
+    let mut parts = Vec::new();
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        parts.push(match c {
+            '%' => Wildcard::Any,
+            '_' => Wildcard::One,
+            '\\' => Wildcard::Char(chars.next().unwrap_or('\\')),
+            c => Wildcard::Char(c),
+        });
+    }
+    let (mut v, mut p) = (0, 0);
+    // The part after the last `%` and the position in the value that it currently matches from.
+    let mut retry: Option<(usize, usize)> = None;
+    while v < value.len() {
+        match parts.get(p) {
+            Some(Wildcard::Any) => {
+                p += 1;
+                retry = Some((p, v));
+            }
+            Some(Wildcard::One) => {
+                p += 1;
+                v += 1;
+            }
+            Some(Wildcard::Char(c)) if *c == value[v] => {
+                p += 1;
+                v += 1;
+            }
+            _ => match retry {
+                Some((after, from)) => {
+                    p = after;
+                    v = from + 1;
+                    retry = Some((after, v));
+                }
+                None => return false,
+            },
+        }
+    }
+    parts[p..].iter().all(|part| *part == Wildcard::Any)
+}
@@ -0,0 +1,249 @@
+//! Spatial predicates on planar geometries, used to evaluate the `S_*` operators.
+//!
+//! Geometries are decomposed into points, lines and polygons, and only the first two coordinates of
+//! each position are used. Polygons are closed sets, so their boundaries belong to them.
+use geojson::{Position, Value};
+
+type Point = [f64; 2];
+
+/// A geometry decomposed into its parts.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub(crate) struct Shape {
+    points: Vec<Point>,
+    lines: Vec<Vec<Point>>,
+    /// Polygons as lists of rings, the first of which is the exterior.
+    polygons: Vec<Vec<Vec<Point>>>,
+}
+
+fn point(position: &Position) -> Point {
+    [
+        position.first().copied().unwrap_or(f64::NAN),
+        position.get(1).copied().unwrap_or(f64::NAN),
+    ]
+}
+
+fn points(positions: &[Position]) -> Vec<Point> {
+    positions.iter().map(point).collect()
+}
+
+fn polygon(rings: &[Vec<Position>]) -> Vec<Vec<Point>> {
+    rings.iter().map(|ring| points(ring)).collect()
+}
+
+impl Shape {
+    /// Decomposes a GeoJSON geometry.
+    pub(crate) fn from_geojson(value: &Value) -> Shape {
+        let mut shape = Shape::default();
+        shape.add(value);
+        shape
+    }
+
+    /// Creates the polygon covered by a bounding box with 4 or 6 numbers.
+    pub(crate) fn from_bbox(bbox: &[f64]) -> Option<Shape> {
+        let ([x0, y0, x1, y1] | [x0, y0, _, x1, y1, _]) = *bbox else {
+            return None;
+        };
+        Some(Shape {
+            polygons: vec![vec![vec![[x0, y0], [x1, y0], [x1, y1], [x0, y1], [x0, y0]]]],
+            ..Shape::default()
+        })
+    }
+
+    fn add(&mut self, value: &Value) {
+        match value {
+            Value::Point(position) => self.points.push(point(position)),
+            Value::MultiPoint(positions) => self.points.extend(points(positions)),
+            Value::LineString(line) => self.lines.push(points(line)),
+            Value::MultiLineString(lines) => {
+                self.lines.extend(lines.iter().map(|line| points(line)));
+            }
+            Value::Polygon(rings) => self.polygons.push(polygon(rings)),
+            Value::MultiPolygon(polygons) => {
+                self.polygons
+                    .extend(polygons.iter().map(|rings| polygon(rings)));
+            }
+            Value::GeometryCollection(geometries) => {
+                for geometry in geometries {
+                    self.add(&geometry.value);
+                }
+            }
+        }
+    }
+
+    fn parts(&self) -> impl Iterator<Item = Part<'_>> {
+        self.points
+            .iter()
+            .map(Part::Point)
+            .chain(self.lines.iter().map(|line| Part::Line(line)))
+            .chain(self.polygons.iter().map(|rings| Part::Polygon(rings)))
+    }
+
+    fn is_empty(&self) -> bool {
+        self.points.is_empty() && self.lines.is_empty() && self.polygons.is_empty()
+    }
+
+    /// Returns `true` if the shapes have at least one point in common.
+    pub(crate) fn intersects(&self, other: &Shape) -> bool {
+        self.parts()
+            .any(|part| other.parts().any(|other| part.intersects(other)))
+    }
+
+    /// Returns `true` if every part of this shape lies within some part of the other.
+    pub(crate) fn within(&self, other: &Shape) -> bool {
+        !self.is_empty()
+            && self
+                .parts()
+                .all(|part| other.parts().any(|other| part.within(other)))
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Part<'a> {
+    Point(&'a Point),
+    Line(&'a [Point]),
+    Polygon(&'a [Vec<Point>]),
+}
+
+impl Part<'_> {
+    fn intersects(self, other: Part) -> bool {
+        match (self, other) {
+            (Part::Point(a), Part::Point(b)) => same(*a, *b),
+            (Part::Point(p), Part::Line(line)) | (Part::Line(line), Part::Point(p)) => {
+                on_line(*p, line)
+            }
+            (Part::Point(p), Part::Polygon(rings)) | (Part::Polygon(rings), Part::Point(p)) => {
+                in_polygon(*p, rings)
+            }
+            (Part::Line(a), Part::Line(b)) => {
+                segments(a).any(|(p, q)| segments(b).any(|(r, s)| segments_intersect(p, q, r, s)))
+            }
+            (Part::Line(line), Part::Polygon(rings)) | (Part::Polygon(rings), Part::Line(line)) => {
+                line.iter().any(|p| in_polygon(*p, rings))
+                    || rings
+                        .iter()
+                        .any(|ring| Part::Line(line).intersects(Part::Line(ring)))
+            }
+            (Part::Polygon(a), Part::Polygon(b)) => {
+                a.iter()
+                    .any(|ring| Part::Line(ring).intersects(Part::Polygon(b)))
+                    || b.first()
+                        .and_then(|ring| ring.first())
+                        .is_some_and(|p| in_polygon(*p, a))
+            }
+        }
+    }
+
+    fn within(self, other: Part) -> bool {
+        match (self, other) {
+            (Part::Point(p), other) => Part::Point(p).intersects(other),
+            (Part::Line(line), Part::Line(other)) => {
+                line.iter().all(|p| on_line(*p, other))
+                    && segments(line).all(|(p, q)| on_line(midpoint(p, q), other))
+            }
+            (Part::Line(line), Part::Polygon(rings)) => line_in_polygon(line, rings),
+            (Part::Polygon(rings), Part::Polygon(other)) => {
+                rings
+                    .first()
+                    .is_some_and(|exterior| line_in_polygon(exterior, other))
+                    && other.iter().skip(1).all(|hole| {
+                        !hole
+                            .iter()
+                            .any(|p| in_polygon(*p, rings) && !on_rings(*p, rings))
+                    })
+            }
+            _ => false,
+        }
+    }
+}
+
+fn segments(line: &[Point]) -> impl Iterator<Item = (Point, Point)> + '_ {
+    line.windows(2).map(|pair| (pair[0], pair[1]))
+}
+
+fn midpoint(p: Point, q: Point) -> Point {
+    [f64::midpoint(p[0], q[0]), f64::midpoint(p[1], q[1])]
+}
+
+/// Compares points exactly, since geometries in filters usually share coordinates with the Items
+/// they are compared with.
+#[allow(clippy::float_cmp)]
+fn same(p: Point, q: Point) -> bool {
+    p == q
+}
+
+/// The sign of the cross product of `q - p` and `r - p`.
+fn orientation(p: Point, q: Point, r: Point) -> i8 {
+    let cross = (q[0] - p[0]) * (r[1] - p[1]) - (q[1] - p[1]) * (r[0] - p[0]);
+    if cross > 0.0 {
+        1
+    } else if cross < 0.0 {
+        -1
+    } else {
+        0
+    }
+}
+
+/// Returns `true` if `r` lies on the segment from `p` to `q`.
+fn on_segment(r: Point, p: Point, q: Point) -> bool {
+    orientation(p, q, r) == 0
+        && r[0] >= p[0].min(q[0])
+        && r[0] <= p[0].max(q[0])
+        && r[1] >= p[1].min(q[1])
+        && r[1] <= p[1].max(q[1])
+}
+
+fn segments_intersect(p: Point, q: Point, r: Point, s: Point) -> bool {
+    let (o1, o2) = (orientation(p, q, r), orientation(p, q, s));
+    let (o3, o4) = (orientation(r, s, p), orientation(r, s, q));
+    (o1 != o2 && o3 != o4)
+        || on_segment(r, p, q)
+        || on_segment(s, p, q)
+        || on_segment(p, r, s)
+        || on_segment(q, r, s)
+}
+
+/// Returns `true` if the segments cross at a single point in the interior of both.
+fn segments_cross(p: Point, q: Point, r: Point, s: Point) -> bool {
+    let (o1, o2) = (orientation(p, q, r), orientation(p, q, s));
+    let (o3, o4) = (orientation(r, s, p), orientation(r, s, q));
+    o1 * o2 < 0 && o3 * o4 < 0
+}
+
+fn on_line(p: Point, line: &[Point]) -> bool {
+    match line {
+        [only] => same(*only, p),
+        line => segments(line).any(|(q, r)| on_segment(p, q, r)),
+    }
+}
+
+fn on_rings(p: Point, rings: &[Vec<Point>]) -> bool {
+    rings.iter().any(|ring| on_line(p, ring))
+}
+
+/// Returns `true` if the point is inside the polygon or on its boundary.
+fn in_polygon(p: Point, rings: &[Vec<Point>]) -> bool {
+    if on_rings(p, rings) {
+        return true;
+    }
+    let mut inside = false;
+    for ring in rings {
+        for (a, b) in segments(ring) {
+            if (a[1] > p[1]) != (b[1] > p[1])
+                && p[0] < (b[0] - a[0]) * (p[1] - a[1]) / (b[1] - a[1]) + a[0]
+            {
+                inside = !inside;
+            }
+        }
+    }
+    inside
+}
+
+fn line_in_polygon(line: &[Point], rings: &[Vec<Point>]) -> bool {
+    line.iter().all(|p| in_polygon(*p, rings))
+        && segments(line).all(|(p, q)| {
+            in_polygon(midpoint(p, q), rings)
+                && rings
+                    .iter()
+                    .all(|ring| segments(ring).all(|(r, s)| !segments_cross(p, q, r, s)))
+        })
+}
@@ -0,0 +1,855 @@
+//! Reads and writes CQL2-Text.
+use geojson::{Geometry, PolygonType, Position, Value as Shape};
+use std::convert::TryFrom;
+use std::fmt::{self, Write};
+
+use super::{interval_bound, Expr, Op, ParseError};
+
+const KEYWORDS: &[&str] = &[
+    "AND", "OR", "NOT", "LIKE", "BETWEEN", "IN", "IS", "NULL", "TRUE", "FALSE",
+];
+
+const GEOMETRY_TYPES: &[&str] = &[
+    "POINT",
+    "LINESTRING",
+    "POLYGON",
+    "MULTIPOINT",
+    "MULTILINESTRING",
+    "MULTIPOLYGON",
+    "GEOMETRYCOLLECTION",
+];
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    /// An unquoted identifier or keyword.
+    Word(String),
+
+    /// A double-quoted identifier, which is always a property.
+    Quoted(String),
+
+    /// A single-quoted string.
+    String(String),
+
+    Number(f64),
+
+    Punct(&'static str),
+
+    End,
+}
+
+impl Token {
+    fn describe(&self) -> String {
+        match self {
+            Token::Word(word) => format!("'{word}'"),
+            Token::Quoted(name) => format!("\"{name}\""),
+            Token::String(value) => format!("string '{value}'"),
+            Token::Number(number) => format!("number {number}"),
+            Token::Punct(punct) => format!("'{punct}'"),
+            Token::End => String::from("end of input"),
+        }
+    }
+
+    fn is_keyword(&self, keyword: &str) -> bool {
+        matches!(self, Token::Word(word) if word.eq_ignore_ascii_case(keyword))
+    }
+}
+
+fn is_identifier_start(c: char) -> bool {
+    c.is_alphabetic() || c == '_' || c == ':'
+}
+
+fn is_identifier_part(c: char) -> bool {
+    is_identifier_start(c) || c.is_ascii_digit() || c == '.'
+}
+
+/// Splits CQL2-Text into tokens, each with its character offset.
+fn tokenize(text: &str) -> Result<Vec<(Token, usize)>, ParseError> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let start = i;
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+        let token = if c == '\'' || c == '"' {
+            let mut value = String::new();
+            i += 1;
+            loop {
+                match chars.get(i) {
+                    None => {
+                        return Err(ParseError::new(
+                            format!("Unterminated {c} quote"),
+                            Some(start),
+                        ))
+                    }
+                    Some(&next) if next == c => {
+                        if chars.get(i + 1) == Some(&c) {
+                            value.push(c);
+                            i += 2;
+                        } else {
+                            i += 1;
+                            break;
+                        }
+                    }
+                    Some(&next) => {
+                        value.push(next);
+                        i += 1;
+                    }
+                }
+            }
+            if c == '\'' {
+                Token::String(value)
+            } else {
+                Token::Quoted(value)
+            }
+        } else if c.is_ascii_digit()
+            || (c == '.' && chars.get(i + 1).is_some_and(char::is_ascii_digit))
+        {
+            while i < chars.len()
+                && (chars[i].is_ascii_digit()
+                    || chars[i] == '.'
+                    || ((chars[i] == 'e' || chars[i] == 'E') && i > start)
+                    || ((chars[i] == '+' || chars[i] == '-')
+                        && (chars[i - 1] == 'e' || chars[i - 1] == 'E')))
+            {
+                i += 1;
+            }
+            let number: String = chars[start..i].iter().collect();
+            match number.parse() {
+                Ok(number) => Token::Number(number),
+                Err(_) => {
+                    return Err(ParseError::new(
+                        format!("Invalid number {number}"),
+                        Some(start),
+                    ))
+                }
+            }
+        } else if is_identifier_start(c) {
+            while i < chars.len() && is_identifier_part(chars[i]) {
+                i += 1;
+            }
+            Token::Word(chars[start..i].iter().collect())
+        } else {
+            let next = chars.get(i + 1).copied();
+            let punct = match (c, next) {
+                ('<', Some('>')) => "<>",
+                ('<', Some('=')) => "<=",
+                ('>', Some('=')) => ">=",
+                ('<', _) => "<",
+                ('>', _) => ">",
+                ('=', _) => "=",
+                ('(', _) => "(",
+                (')', _) => ")",
+                (',', _) => ",",
+                ('-', _) => "-",
+                _ => {
+                    return Err(ParseError::new(
+                        format!("Unexpected character '{c}'"),
+                        Some(start),
+                    ))
+                }
+            };
+            i += punct.len();
+            Token::Punct(punct)
+        };
+        tokens.push((token, start));
+    }
+    tokens.push((Token::End, chars.len()));
+    Ok(tokens)
+}
+
+/// The deepest nesting of parentheses, arrays, function calls, geometry collections and `NOT`s
+/// that the parser accepts, so that deeply nested input cannot overflow the stack. Each level can
+/// take more than 16 KiB of stack in a debug build, so this stays well within the 2 MiB stack of a
+/// spawned thread.
+const MAX_DEPTH: usize = 64;
+
+/// Parses CQL2-Text into an expression.
+pub(crate) fn parse(text: &str) -> Result<Expr, ParseError> {
+    let mut parser = Parser {
+        tokens: tokenize(text)?,
+        index: 0,
+        depth: 0,
+    };
+    let expr = parser.parse_or()?;
+    if parser.peek() != &Token::End {
+        return Err(parser.unexpected("end of input"));
+    }
+    Ok(expr)
+}
+
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    index: usize,
+    depth: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> &Token {
+        &self.tokens[self.index].0
+    }
+
+    fn peek_at(&self, offset: usize) -> &Token {
+        let index = (self.index + offset).min(self.tokens.len() - 1);
+        &self.tokens[index].0
+    }
+
+    fn position(&self) -> usize {
+        self.tokens[self.index].1
+    }
+
+    fn advance(&mut self) -> Token {
+        let token = self.tokens[self.index].0.clone();
+        if token != Token::End {
+            self.index += 1;
+        }
+        token
+    }
+
+    fn unexpected(&self, expected: &str) -> ParseError {
+        ParseError::new(
+            format!("Expected {expected}, found {}", self.peek().describe()),
+            Some(self.position()),
+        )
+    }
+
+    /// Runs a parser one level deeper, failing if the input is nested too deeply.
+    fn nested<T, F>(&mut self, parse: F) -> Result<T, ParseError>
+    where
+        F: FnOnce(&mut Parser) -> Result<T, ParseError>,
+    {
+        if self.depth >= MAX_DEPTH {
+            return Err(ParseError::new(
+                format!("Expressions cannot be nested more than {MAX_DEPTH} levels deep"),
+                Some(self.position()),
+            ));
+        }
+        self.depth += 1;
+        let result = parse(self);
+        self.depth -= 1;
+        result
+    }
+
+    fn eat_punct(&mut self, punct: &str) -> bool {
+        if matches!(self.peek(), Token::Punct(found) if *found == punct) {
+            self.advance();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect_punct(&mut self, punct: &str) -> Result<(), ParseError> {
+        if self.eat_punct(punct) {
+            Ok(())
+        } else {
+            Err(self.unexpected(&format!("'{punct}'")))
+        }
+    }
+
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        if self.peek().is_keyword(keyword) {
+            self.advance();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect_keyword(&mut self, keyword: &str) -> Result<(), ParseError> {
+        if self.eat_keyword(keyword) {
+            Ok(())
+        } else {
+            Err(self.unexpected(keyword))
+        }
+    }
+
+    fn parse_or(&mut self) -> Result<Expr, ParseError> {
+        let mut args = vec![self.parse_and()?];
+        while self.eat_keyword("OR") {
+            args.push(self.parse_and()?);
+        }
+        Ok(if args.len() == 1 {
+            args.remove(0)
+        } else {
+            Expr::Operation { op: Op::Or, args }
+        })
+    }
+
+    fn parse_and(&mut self) -> Result<Expr, ParseError> {
+        let mut args = vec![self.parse_not()?];
+        while self.eat_keyword("AND") {
+            args.push(self.parse_not()?);
+        }
+        Ok(if args.len() == 1 {
+            args.remove(0)
+        } else {
+            Expr::Operation { op: Op::And, args }
+        })
+    }
+
+    fn parse_not(&mut self) -> Result<Expr, ParseError> {
+        if self.eat_keyword("NOT") {
+            Ok(not(self.nested(Parser::parse_not)?))
+        } else {
+            self.parse_predicate()
+        }
+    }
+
+    fn parse_predicate(&mut self) -> Result<Expr, ParseError> {
+        let left = self.parse_primary()?;
+        if let Token::Punct(punct) = self.peek() {
+            let op = match *punct {
+                "=" => Some(Op::Eq),
+                "<>" => Some(Op::Ne),
+                "<" => Some(Op::Lt),
+                "<=" => Some(Op::Le),
+                ">" => Some(Op::Gt),
+                ">=" => Some(Op::Ge),
+                _ => None,
+            };
+            if let Some(op) = op {
+                self.advance();
+                let right = self.parse_primary()?;
+                return Ok(Expr::Operation {
+                    op,
+                    args: vec![left, right],
+                });
+            }
+        }
+        if self.eat_keyword("IS") {
+            let negated = self.eat_keyword("NOT");
+            self.expect_keyword("NULL")?;
+            let expr = Expr::Operation {
+                op: Op::IsNull,
+                args: vec![left],
+            };
+            return Ok(if negated { not(expr) } else { expr });
+        }
+        let negated = self.peek().is_keyword("NOT")
+            && ["LIKE", "BETWEEN", "IN"]
+                .iter()
+                .any(|keyword| self.peek_at(1).is_keyword(keyword));
+        if negated {
+            self.advance();
+        }
+        let expr = if self.eat_keyword("LIKE") {
+            let pattern = self.parse_primary()?;
+            Expr::Operation {
+                op: Op::Like,
+                args: vec![left, pattern],
+            }
+        } else if self.eat_keyword("BETWEEN") {
+            let low = self.parse_primary()?;
+            self.expect_keyword("AND")?;
+            let high = self.parse_primary()?;
+            Expr::Operation {
+                op: Op::Between,
+                args: vec![left, low, high],
+            }
+        } else if self.peek().is_keyword("IN") {
+            self.advance();
+            if self.peek() != &Token::Punct("(") {
+                return Err(self.unexpected("'(' starting the list of IN"));
+            }
+            let list = self.parse_array()?;
+            Expr::Operation {
+                op: Op::In,
+                args: vec![left, list],
+            }
+        } else {
+            return Ok(left);
+        };
+        Ok(if negated { not(expr) } else { expr })
+    }
+
+    /// Parses a parenthesized, comma-separated list as an array.
+    fn parse_array(&mut self) -> Result<Expr, ParseError> {
+        self.expect_punct("(")?;
+        let mut values = Vec::new();
+        if !self.eat_punct(")") {
+            loop {
+                values.push(self.parse_argument()?);
+                if self.eat_punct(")") {
+                    break;
+                }
+                if !self.eat_punct(",") {
+                    return Err(self.unexpected("',' or ')'"));
+                }
+            }
+        }
+        Ok(Expr::Array(values))
+    }
+
+    /// Parses an argument of a function or an element of an array, where parentheses always start
+    /// a nested array.
+    fn parse_argument(&mut self) -> Result<Expr, ParseError> {
+        if self.peek() == &Token::Punct("(") {
+            self.nested(Parser::parse_array)
+        } else {
+            self.nested(Parser::parse_or)
+        }
+    }
+
+    fn parse_arguments(&mut self) -> Result<Vec<Expr>, ParseError> {
+        match self.parse_array()? {
+            Expr::Array(args) => Ok(args),
+            _ => unreachable!("parse_array always returns an array"),
+        }
+    }
+
+    fn parse_number(&mut self) -> Result<f64, ParseError> {
+        let negative = self.eat_punct("-");
+        match self.peek() {
+            Token::Number(number) => {
+                let number = *number;
+                self.advance();
+                Ok(if negative { -number } else { number })
+            }
+            _ => Err(self.unexpected("a number")),
+        }
+    }
+
+    fn parse_string(&mut self) -> Result<String, ParseError> {
+        match self.peek().clone() {
+            Token::String(value) => {
+                self.advance();
+                Ok(value)
+            }
+            _ => Err(self.unexpected("a string")),
+        }
+    }
+
+    fn parse_primary(&mut self) -> Result<Expr, ParseError> {
+        let position = self.position();
+        match self.peek().clone() {
+            Token::Number(_) | Token::Punct("-") => Ok(Expr::Number(self.parse_number()?)),
+            Token::String(value) => {
+                self.advance();
+                Ok(Expr::String(value))
+            }
+            Token::Quoted(name) => {
+                self.advance();
+                Ok(Expr::Property(name))
+            }
+            Token::Punct("(") => {
+                self.advance();
+                let expr = self.nested(Parser::parse_or)?;
+                if self.peek() == &Token::Punct(",") {
+                    let mut values = vec![expr];
+                    while self.eat_punct(",") {
+                        values.push(self.parse_argument()?);
+                    }
+                    self.expect_punct(")")?;
+                    return Ok(Expr::Array(values));
+                }
+                self.expect_punct(")")?;
+                Ok(expr)
+            }
+            Token::Word(word) => {
+                let upper = word.to_ascii_uppercase();
+                match upper.as_str() {
+                    "TRUE" | "FALSE" => {
+                        self.advance();
+                        return Ok(Expr::Bool(upper == "TRUE"));
+                    }
+                    "NULL" => {
+                        self.advance();
+                        return Ok(Expr::Null);
+                    }
+                    _ if KEYWORDS.contains(&upper.as_str()) => {
+                        return Err(self.unexpected("a value"));
+                    }
+                    _ => {}
+                }
+                let is_call = self.peek_at(1) == &Token::Punct("(");
+                let is_empty = self.peek_at(1).is_keyword("EMPTY");
+                if GEOMETRY_TYPES.contains(&upper.as_str()) && (is_call || is_empty) {
+                    return Ok(Expr::Geometry(Geometry::new(self.parse_geometry()?)));
+                }
+                self.advance();
+                if !is_call {
+                    return Ok(Expr::Property(word));
+                }
+                match upper.as_str() {
+                    "DATE" | "TIMESTAMP" => {
+                        self.expect_punct("(")?;
+                        let instant = self.parse_string()?;
+                        self.expect_punct(")")?;
+                        Ok(if upper == "DATE" {
+                            Expr::Date(instant)
+                        } else {
+                            Expr::Timestamp(instant)
+                        })
+                    }
+                    "INTERVAL" => match <[Expr; 2]>::try_from(self.parse_arguments()?) {
+                        Ok([start, end]) => Ok(Expr::Interval(
+                            Box::new(interval_bound(start)),
+                            Box::new(interval_bound(end)),
+                        )),
+                        Err(args) => Err(ParseError::new(
+                            format!("INTERVAL takes 2 arguments, found {}", args.len()),
+                            Some(position),
+                        )),
+                    },
+                    "BBOX" => {
+                        self.expect_punct("(")?;
+                        let mut bbox = vec![self.parse_number()?];
+                        while self.eat_punct(",") {
+                            bbox.push(self.parse_number()?);
+                        }
+                        self.expect_punct(")")?;
+                        if bbox.len() == 4 || bbox.len() == 6 {
+                            Ok(Expr::Bbox(bbox))
+                        } else {
+                            Err(ParseError::new(
+                                format!("BBOX takes 4 or 6 numbers, found {}", bbox.len()),
+                                Some(position),
+                            ))
+                        }
+                    }
+                    _ => {
+                        let args = self.parse_arguments()?;
+                        match Op::from_name(&word).filter(|op| op.is_function()) {
+                            Some(op) => Expr::operation(op, args, Some(position)),
+                            None => Ok(Expr::Function { name: word, args }),
+                        }
+                    }
+                }
+            }
+            _ => Err(self.unexpected("a value")),
+        }
+    }
+
+    fn parse_geometry(&mut self) -> Result<Shape, ParseError> {
+        let position = self.position();
+        let Token::Word(word) = self.advance() else {
+            unreachable!("geometries start with their type");
+        };
+        let upper = word.to_ascii_uppercase();
+        if self.peek().is_keyword("EMPTY") {
+            return Err(ParseError::new(
+                format!("{upper} EMPTY is not supported"),
+                Some(position),
+            ));
+        }
+        Ok(match upper.as_str() {
+            "POINT" => {
+                self.expect_punct("(")?;
+                let position = self.parse_position()?;
+                self.expect_punct(")")?;
+                Shape::Point(position)
+            }
+            "LINESTRING" => Shape::LineString(self.parse_positions()?),
+            "POLYGON" => Shape::Polygon(self.parse_polygon()?),
+            "MULTIPOINT" => Shape::MultiPoint(self.parse_list(|parser| {
+                if parser.eat_punct("(") {
+                    let position = parser.parse_position()?;
+                    parser.expect_punct(")")?;
+                    Ok(position)
+                } else {
+                    parser.parse_position()
+                }
+            })?),
+            "MULTILINESTRING" => Shape::MultiLineString(self.parse_list(Parser::parse_positions)?),
+            "MULTIPOLYGON" => Shape::MultiPolygon(self.parse_list(Parser::parse_polygon)?),
+            _ => Shape::GeometryCollection(self.parse_list(|parser| match parser.peek() {
+                Token::Word(word)
+                    if GEOMETRY_TYPES.contains(&word.to_ascii_uppercase().as_str()) =>
+                {
+                    Ok(Geometry::new(parser.nested(Parser::parse_geometry)?))
+                }
+                _ => Err(parser.unexpected("a geometry")),
+            })?),
+        })
+    }
+
+    fn parse_list<T, F>(&mut self, mut parse: F) -> Result<Vec<T>, ParseError>
+    where
+        F: FnMut(&mut Parser) -> Result<T, ParseError>,
+    {
+        self.expect_punct("(")?;
+        let mut values = vec![parse(self)?];
+        while self.eat_punct(",") {
+            values.push(parse(self)?);
+        }
+        self.expect_punct(")")?;
+        Ok(values)
+    }
+
+    fn parse_position(&mut self) -> Result<Position, ParseError> {
+        let mut position = vec![self.parse_number()?, self.parse_number()?];
+        if matches!(self.peek(), Token::Number(_) | Token::Punct("-")) {
+            position.push(self.parse_number()?);
+        }
+        Ok(position)
+    }
+
+    fn parse_positions(&mut self) -> Result<Vec<Position>, ParseError> {
+        self.parse_list(Parser::parse_position)
+    }
+
+    fn parse_polygon(&mut self) -> Result<PolygonType, ParseError> {
+        self.parse_list(Parser::parse_positions)
+    }
+}
+
+fn not(expr: Expr) -> Expr {
+    Expr::Operation {
+        op: Op::Not,
+        args: vec![expr],
+    }
+}
+
+/// Binding strength of an expression, used to decide where parentheses are needed.
+fn precedence(expr: &Expr) -> u8 {
+    match expr {
+        Expr::Operation { op: Op::Or, .. } => 1,
+        Expr::Operation { op: Op::And, .. } => 2,
+        Expr::Operation { op: Op::Not, args } => match args.as_slice() {
+            [Expr::Operation { op, args }] if is_negatable(*op) && args.len() == op.arity().0 => 4,
+            _ => 3,
+        },
+        Expr::Operation { op, .. } if !op.is_function() => 4,
+        _ => 5,
+    }
+}
+
+/// Writes an expression as CQL2-Text.
+pub(crate) fn write(expr: &Expr, f: &mut fmt::Formatter) -> fmt::Result {
+    match expr {
+        Expr::Operation { op, args } => write_operation(*op, args, f),
+        Expr::Function { name, args } => {
+            f.write_str(name)?;
+            write_list(args, f)
+        }
+        Expr::Property(name) => write_property(name, f),
+        Expr::String(value) => write_string(value, f),
+        Expr::Number(number) => write!(f, "{number}"),
+        Expr::Bool(value) => f.write_str(if *value { "TRUE" } else { "FALSE" }),
+        Expr::Null => f.write_str("NULL"),
+        Expr::Date(date) => {
+            f.write_str("DATE(")?;
+            write_string(date, f)?;
+            f.write_char(')')
+        }
+        Expr::Timestamp(timestamp) => {
+            f.write_str("TIMESTAMP(")?;
+            write_string(timestamp, f)?;
+            f.write_char(')')
+        }
+        Expr::Interval(start, end) => {
+            f.write_str("INTERVAL(")?;
+            for (i, bound) in [start, end].iter().enumerate() {
+                if i > 0 {
+                    f.write_str(", ")?;
+                }
+                match bound.as_ref() {
+                    Expr::Date(instant) | Expr::Timestamp(instant) | Expr::String(instant) => {
+                        write_string(instant, f)?;
+                    }
+                    bound => write(bound, f)?,
+                }
+            }
+            f.write_char(')')
+        }
+        Expr::Geometry(geometry) => write_geometry(&geometry.value, f),
+        Expr::Bbox(bbox) => {
+            f.write_str("BBOX(")?;
+            write_joined(bbox, ", ", f)?;
+            f.write_char(')')
+        }
+        Expr::Array(values) => write_list(values, f),
+    }
+}
+
+fn write_operation(op: Op, args: &[Expr], f: &mut fmt::Formatter) -> fmt::Result {
+    match (op, args) {
+        (Op::And | Op::Or, args) => {
+            let separator = if op == Op::And { " AND " } else { " OR " };
+            for (i, arg) in args.iter().enumerate() {
+                if i > 0 {
+                    f.write_str(separator)?;
+                }
+                write_operand(arg, 3, f)?;
+            }
+            Ok(())
+        }
+        (Op::Not, [Expr::Operation { op: inner, args }])
+            if is_negatable(*inner) && args.len() == inner.arity().0 =>
+        {
+            write_infix(*inner, args, true, f)
+        }
+        (Op::Not, [arg]) => {
+            f.write_str("NOT ")?;
+            write_operand(arg, 3, f)
+        }
+        (op, args) if !op.is_function() && args.len() == op.arity().0 => {
+            write_infix(op, args, false, f)
+        }
+        (op, args) => {
+            f.write_str(&op.name().to_ascii_uppercase())?;
+            write_list(args, f)
+        }
+    }
+}
+
+/// Returns `true` for the predicates that are negated with an infix `NOT`, e.g. `a NOT LIKE b`.
+fn is_negatable(op: Op) -> bool {
+    matches!(op, Op::Like | Op::Between | Op::In | Op::IsNull)
+}
+
+/// Writes a comparison, `LIKE`, `BETWEEN`, `IN` or `IS NULL` predicate.
+fn write_infix(op: Op, args: &[Expr], negated: bool, f: &mut fmt::Formatter) -> fmt::Result {
+    let not = if negated { "NOT " } else { "" };
+    write_operand(&args[0], 5, f)?;
+    match op {
+        Op::IsNull => return write!(f, " IS {not}NULL"),
+        Op::Like => write!(f, " {not}LIKE ")?,
+        Op::Between => {
+            write!(f, " {not}BETWEEN ")?;
+            write_operand(&args[1], 5, f)?;
+            f.write_str(" AND ")?;
+            return write_operand(&args[2], 5, f);
+        }
+        Op::In => {
+            write!(f, " {not}IN ")?;
+            return match &args[1] {
+                Expr::Array(values) => write_list(values, f),
+                value => write_list(std::slice::from_ref(value), f),
+            };
+        }
+        op => write!(f, " {} ", op.name())?,
+    }
+    write_operand(&args[1], 5, f)
+}
+
+/// Writes an expression, in parentheses if it binds less tightly than `precedence`.
+fn write_operand(expr: &Expr, min: u8, f: &mut fmt::Formatter) -> fmt::Result {
+    if precedence(expr) < min {
+        f.write_char('(')?;
+        write(expr, f)?;
+        f.write_char(')')
+    } else {
+        write(expr, f)
+    }
+}
+
+fn write_list(values: &[Expr], f: &mut fmt::Formatter) -> fmt::Result {
+    f.write_char('(')?;
+    for (i, value) in values.iter().enumerate() {
+        if i > 0 {
+            f.write_str(", ")?;
+        }
+        write(value, f)?;
+    }
+    f.write_char(')')
+}
+
+fn write_joined(numbers: &[f64], separator: &str, f: &mut fmt::Formatter) -> fmt::Result {
+    for (i, number) in numbers.iter().enumerate() {
+        if i > 0 {
+            f.write_str(separator)?;
+        }
+        write!(f, "{number}")?;
+    }
+    Ok(())
+}
+
+fn write_string(value: &str, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "'{}'", value.replace('\'', "''"))
+}
+
+fn write_property(name: &str, f: &mut fmt::Formatter) -> fmt::Result {
+    let upper = name.to_ascii_uppercase();
+    let plain = name.chars().next().is_some_and(is_identifier_start)
+        && name.chars().all(is_identifier_part)
+        && !KEYWORDS.contains(&upper.as_str())
+        && !GEOMETRY_TYPES.contains(&upper.as_str());
+    if plain {
+        f.write_str(name)
+    } else {
+        write!(f, "\"{}\"", name.replace('"', "\"\""))
+    }
+}
+
+fn write_geometry(shape: &Shape, f: &mut fmt::Formatter) -> fmt::Result {
+    let positions = |positions: &[Position], f: &mut fmt::Formatter| {
+        f.write_char('(')?;
+        for (i, position) in positions.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write_joined(position, " ", f)?;
+        }
+        f.write_char(')')
+    };
+    let polygon = |rings: &PolygonType, f: &mut fmt::Formatter| {
+        f.write_char('(')?;
+        for (i, ring) in rings.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            positions(ring, f)?;
+        }
+        f.write_char(')')
+    };
+    match shape {
+        Shape::Point(position) => {
+            f.write_str("POINT(")?;
+            write_joined(position, " ", f)?;
+            f.write_char(')')
+        }
+        Shape::LineString(line) => {
+            f.write_str("LINESTRING")?;
+            positions(line, f)
+        }
+        Shape::Polygon(rings) => {
+            f.write_str("POLYGON")?;
+            polygon(rings, f)
+        }
+        Shape::MultiPoint(points) => {
+            f.write_str("MULTIPOINT(")?;
+            for (i, point) in points.iter().enumerate() {
+                if i > 0 {
+                    f.write_str(", ")?;
+                }
+                positions(std::slice::from_ref(point), f)?;
+            }
+            f.write_char(')')
+        }
+        Shape::MultiLineString(lines) => {
+            f.write_str("MULTILINESTRING(")?;
+            for (i, line) in lines.iter().enumerate() {
+                if i > 0 {
+                    f.write_str(", ")?;
+                }
+                positions(line, f)?;
+            }
+            f.write_char(')')
+        }
+        Shape::MultiPolygon(polygons) => {
+            f.write_str("MULTIPOLYGON(")?;
+            for (i, rings) in polygons.iter().enumerate() {
+                if i > 0 {
+                    f.write_str(", ")?;
+                }
+                polygon(rings, f)?;
+            }
+            f.write_char(')')
+        }
+        Shape::GeometryCollection(geometries) => {
+            f.write_str("GEOMETRYCOLLECTION(")?;
+            for (i, geometry) in geometries.iter().enumerate() {
+                if i > 0 {
+                    f.write_str(", ")?;
+                }
+                write_geometry(&geometry.value, f)?;
+            }
+            f.write_char(')')
+        }
+    }
+}
@@ -19,6 +19,12 @@ pub enum STACError {
     /// Errors resulting from trying to parse a semantic version string with [`semver`]
     SemVer(semver::SemVerError),
 
+    /// Errors resulting from reading or writing files
+    IO(std::io::Error),
+
+    /// Errors resulting from parsing a CQL2 expression
+    CQL2(crate::cql2::ParseError),
+
     /// Other errors not covered by the variants above.
     Other(String),
 }
@@ -30,6 +36,8 @@ impl fmt::Display for STACError {
         match &self {
             STACError::JSONParse(source) => source.fmt(f),
             STACError::SemVer(source) => source.fmt(f),
+            STACError::IO(source) => source.fmt(f),
+            STACError::CQL2(source) => source.fmt(f),
             STACError::Other(message) => write!(f, "{}", message.as_str()),
         }
     }
@@ -46,3 +54,15 @@ impl From<semver::SemVerError> for STACError {
         STACError::SemVer(err)
     }
 }
+
+impl From<std::io::Error> for STACError {
+    fn from(err: std::io::Error) -> STACError {
+        STACError::IO(err)
+    }
+}
+
+impl From<crate::cql2::ParseError> for STACError {
+    fn from(err: crate::cql2::ParseError) -> STACError {
+        STACError::CQL2(err)
+    }
+}
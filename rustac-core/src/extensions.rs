@@ -0,0 +1,481 @@
+//! Typed access to fields defined by [STAC Extensions](https://stac-extensions.github.io/).
+//!
+//! Each extension has its own module with a `struct` holding the extension's fields. The extension
+//! modules add methods to [`Item`], [`Asset`] and [`Collection`] that read those fields out of
+//! `extra_fields` and write them back. Setters on [`Item`] and [`Collection`] also add the extension's
+//! schema URI to `stac_extensions`, and remove it again once no fields from the extension are left.
+//! Setters on [`Asset`] cannot do this because an Asset does not know which object it belongs to, so
+//! each extension that applies to Assets also has a setter on [`Item`] that takes the asset key.
+//!
+//! Extensions that are not supported by this crate can be used in the same way by implementing
+//! [`Extension`] for a `struct` holding their fields. The [`Extensions`] trait then gives typed access
+//! to those fields on any object that can hold them:
+//!
+//! ```
+//! use rustac_core::extensions::{Extension, Extensions};
+//! use rustac_core::Item;
+//! use serde::{Deserialize, Serialize};
+//! use serde_json::json;
+//!
+//! #[derive(Serialize, Deserialize, Debug, Default, PartialEq)]
+//! struct Acquisition {
+//!     #[serde(rename = "acq:operator")]
+//!     operator: Option<String>,
+//! }
+//!
+//! impl Extension for Acquisition {
+//!     const SCHEMA_URI: &'static str = "https://example.com/acquisition/v1.0.0/schema.json";
+//!     const PREFIX: &'static str = "acq:";
+//! }
+//!
+//! let mut item: Item = serde_json::from_value(json!({
+//!     "stac_version": "1.0.0",
+//!     "type": "Feature",
+//!     "id": "an-item",
+//!     "bbox": [0.0, 0.0, 1.0, 1.0],
+//!     "geometry": {"type": "Point", "coordinates": [0.0, 0.0]},
+//!     "properties": {"datetime": "2021-01-01T00:00:00Z"},
+//!     "links": [],
+//!     "assets": {}
+//! })).unwrap();
+//!
+//! let fields = Acquisition { operator: Some(String::from("Jane")) };
+//! item.set_extension(&fields).unwrap();
+//! assert!(item.has_extension::<Acquisition>());
+//! assert_eq!(item.get_extension::<Acquisition>().unwrap(), fields);
+//!
+//! item.remove_extension::<Acquisition>();
+//! assert!(!item.has_extension::<Acquisition>());
+//! assert_eq!(item.stac_extensions, Some(vec![]));
+//! ```
+use serde::de::{DeserializeOwned, Deserializer};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+use crate::error::{STACError, STACResult};
+use crate::{Asset, Catalog, Collection, Item, ItemProperties};
+
+pub mod datacube;
+pub mod eo;
+pub mod file;
+pub mod item_assets;
+pub mod label;
+pub mod pointcloud;
+pub mod processing;
+pub mod proj;
+pub mod raster;
+pub mod sar;
+pub mod sat;
+pub mod sci;
+pub mod storage;
+pub mod timestamps;
+pub mod version;
+pub mod view;
+
+/// A STAC Extension whose fields are held by the implementing `struct`.
+///
+/// The `struct` must serialize to a JSON object whose keys are all fields of the extension (see
+/// [`Extension::is_field`]), and must serialize unset fields as `null` rather than skipping them so
+/// that [`Extensions::set_extension`] can remove them.
+pub trait Extension: Serialize + DeserializeOwned {
+    /// Schema URI of the extension, as listed in `stac_extensions`.
+    const SCHEMA_URI: &'static str;
+
+    /// Prefix of all fields defined by the extension, including the trailing `:`.
+    const PREFIX: &'static str;
+
+    /// Checks rules that cannot be enforced by the types of the fields. Called by
+    /// [`Extensions::set_extension`] before any fields are written. Does nothing by default.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::Other`] describing the first invalid field.
+    fn validate(&self) -> STACResult<()> {
+        Ok(())
+    }
+
+    /// Deserializes the extension's fields out of a JSON object, ignoring all other fields.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::JSONParse`] if a field does not match the extension's schema.
+    fn from_fields(fields: &Value) -> STACResult<Self> {
+        get_fields(fields, Self::is_field)
+    }
+
+    /// Names of the fields that are explicitly set to `null`, which [`Extensions::set_extension`]
+    /// writes as `null` instead of removing them. Empty by default; extensions that give `null` a
+    /// meaning of its own, such as `"proj:epsg": null`, must override it.
+    #[must_use]
+    fn null_fields(&self) -> Vec<&'static str> {
+        Vec::new()
+    }
+
+    /// Checks whether `key` is the name of one of the extension's fields. By default, this checks
+    /// for [`Extension::PREFIX`]; extensions whose fields are not prefixed must override it.
+    #[must_use]
+    fn is_field(key: &str) -> bool {
+        key.starts_with(Self::PREFIX)
+    }
+}
+
+/// Typed access to the fields of any [`Extension`] on a STAC object.
+///
+/// On [`Item`], [`Collection`] and [`Catalog`] the extension's schema URI in `stac_extensions` is
+/// kept in sync with the fields. [`ItemProperties`] and [`Asset`] do not have `stac_extensions`, so
+/// for those [`Extensions::has_extension`] only checks whether any of the extension's fields are set; use
+/// [`Item::set_asset_extension`] to set fields on an Asset and update the Item.
+pub trait Extensions {
+    /// Checks whether the object uses the extension.
+    fn has_extension<E: Extension>(&self) -> bool;
+
+    /// Gets the extension's fields. Fields of an Item are read from its properties.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::JSONParse`] if a field does not match the extension's schema.
+    fn get_extension<E: Extension>(&self) -> STACResult<E>;
+
+    /// Validates and sets the extension's fields, removing fields that are `None`. Fields of an Item
+    /// are written to its properties.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::Other`] if [`Extension::validate`] fails.
+    fn set_extension<E: Extension>(&mut self, fields: &E) -> STACResult<()>;
+
+    /// Removes every field with the extension's prefix, including fields on Assets and in summaries,
+    /// and removes the extension's schema URI.
+    fn remove_extension<E: Extension>(&mut self);
+}
+
+/// A [Range Object](https://github.com/radiantearth/stac-spec/blob/v1.0.0/collection-spec/collection-spec.md#range-object)
+/// used to summarize numeric fields in Collection `summaries`.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct Range<T> {
+    /// Minimum value.
+    pub minimum: T,
+
+    /// Maximum value.
+    pub maximum: T,
+}
+
+/// Deserializes the fields for which `is_field` returns `true` into `T`.
+pub(crate) fn get_fields<T: DeserializeOwned>(
+    fields: &Value,
+    is_field: fn(&str) -> bool,
+) -> STACResult<T> {
+    let mut map = Map::new();
+    if let Value::Object(fields) = fields {
+        for (key, value) in fields {
+            if is_field(key) {
+                map.insert(key.clone(), value.clone());
+            }
+        }
+    }
+    Ok(serde_json::from_value(Value::Object(map))?)
+}
+
+/// Serializes `value` into `fields`. Extension structs serialize unset fields as `null`, which removes
+/// the field, so any fields the struct does not know about are left alone.
+pub(crate) fn set_fields<T: Serialize>(fields: &mut Value, value: &T) -> STACResult<()> {
+    let Value::Object(values) = serde_json::to_value(value)? else {
+        return Err(STACError::Other(String::from(
+            "Extension fields must serialize to a JSON object",
+        )));
+    };
+    if !fields.is_object() {
+        *fields = Value::Object(Map::new());
+    }
+    if let Value::Object(fields) = fields {
+        for (key, value) in values {
+            if value.is_null() {
+                fields.remove(&key);
+            } else {
+                fields.insert(key, value);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Serializes an extension's fields into `fields` with [`set_fields`], then writes the fields listed
+/// by [`Extension::null_fields`] as `null`.
+fn set_extension_fields<E: Extension>(fields: &mut Value, value: &E) -> STACResult<()> {
+    set_fields(fields, value)?;
+    if let Value::Object(fields) = fields {
+        for key in value.null_fields() {
+            fields.insert(String::from(key), Value::Null);
+        }
+    }
+    Ok(())
+}
+
+/// Deserializes a field that may be missing, `null` or set, as `None`, `Some(None)` or
+/// `Some(Some(value))` respectively. Must be used with `#[serde(default)]`.
+#[allow(clippy::option_option)]
+pub(crate) fn deserialize_nullable<'de, D, T>(
+    deserializer: D,
+) -> Result<Option<Option<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
+/// Deserializes the summaries for which `is_field` returns `true` into `T`.
+pub(crate) fn get_summaries<T: DeserializeOwned>(
+    summaries: Option<&HashMap<String, Value>>,
+    is_field: fn(&str) -> bool,
+) -> STACResult<T> {
+    let fields = summaries
+        .map(|summaries| Value::Object(summaries.clone().into_iter().collect()))
+        .unwrap_or_default();
+    get_fields(&fields, is_field)
+}
+
+/// Serializes `value` into the summaries, see [`set_fields`].
+pub(crate) fn set_summaries<T: Serialize>(
+    summaries: &mut Option<HashMap<String, Value>>,
+    value: &T,
+) -> STACResult<()> {
+    let mut fields = Value::Object(summaries.take().unwrap_or_default().into_iter().collect());
+    set_fields(&mut fields, value)?;
+    if let Value::Object(fields) = fields {
+        if !fields.is_empty() {
+            *summaries = Some(fields.into_iter().collect());
+        }
+    }
+    Ok(())
+}
+
+/// Adds `uri` to `stac_extensions` if the extension is in use, otherwise removes it.
+pub(crate) fn sync_schema_uri(stac_extensions: &mut Option<Vec<String>>, uri: &str, in_use: bool) {
+    if stac_extensions.is_none() && !in_use {
+        return;
+    }
+    let extensions = stac_extensions.get_or_insert_with(Vec::new);
+    let position = extensions.iter().position(|extension| extension == uri);
+    match (in_use, position) {
+        (true, None) => extensions.push(uri.into()),
+        (false, Some(position)) => {
+            extensions.remove(position);
+        }
+        _ => {}
+    }
+}
+
+/// Removes all fields for which `is_field` returns `true`.
+fn remove_fields(fields: &mut Value, is_field: fn(&str) -> bool) {
+    if let Value::Object(fields) = fields {
+        fields.retain(|key, _| !is_field(key));
+    }
+}
+
+/// Removes all summaries for which `is_field` returns `true`, leaving `None` if no summaries are
+/// left.
+fn remove_summaries(summaries: &mut Option<HashMap<String, Value>>, is_field: fn(&str) -> bool) {
+    if let Some(map) = summaries {
+        map.retain(|key, _| !is_field(key));
+        if map.is_empty() {
+            *summaries = None;
+        }
+    }
+}
+
+fn has_schema_uri(stac_extensions: Option<&Vec<String>>, uri: &str) -> bool {
+    stac_extensions.is_some_and(|extensions| extensions.iter().any(|extension| extension == uri))
+}
+
+fn summaries_have_field(
+    summaries: Option<&HashMap<String, Value>>,
+    is_field: fn(&str) -> bool,
+) -> bool {
+    summaries.is_some_and(|summaries| summaries.keys().any(|key| is_field(key)))
+}
+
+fn has_field(fields: &Value, is_field: fn(&str) -> bool) -> bool {
+    fields
+        .as_object()
+        .is_some_and(|fields| fields.keys().any(|key| is_field(key)))
+}
+
+fn assets_have_field<'a, I>(assets: I, is_field: fn(&str) -> bool) -> bool
+where
+    I: IntoIterator<Item = &'a Asset>,
+{
+    assets
+        .into_iter()
+        .any(|asset| has_field(&asset.extra_fields, is_field))
+}
+
+impl Item {
+    /// Adds or removes the schema URI of an extension depending on whether any of its fields are used
+    /// by the Item or its Assets.
+    pub(crate) fn sync_extension<E: Extension>(&mut self) {
+        let in_use = has_field(&self.properties.extra_fields, E::is_field)
+            || has_field(&self.extra_fields, E::is_field)
+            || assets_have_field(self.assets.values(), E::is_field);
+        sync_schema_uri(&mut self.stac_extensions, E::SCHEMA_URI, in_use);
+    }
+
+    /// Gets a mutable reference to the asset with the given key.
+    pub(crate) fn asset_mut(&mut self, key: &str) -> STACResult<&mut Asset> {
+        let id = &self.id;
+        self.assets
+            .get_mut(key)
+            .ok_or_else(|| STACError::Other(format!("Item {id} has no asset {key}")))
+    }
+}
+
+impl Collection {
+    /// Adds or removes the schema URI of an extension depending on whether any of its fields are used
+    /// by the Collection, its summaries or its Assets.
+    pub(crate) fn sync_extension<E: Extension>(&mut self) {
+        let in_use = has_field(&self.extra_fields, E::is_field)
+            || summaries_have_field(self.summaries.as_ref(), E::is_field)
+            || self
+                .assets
+                .as_ref()
+                .is_some_and(|assets| assets_have_field(assets.values(), E::is_field));
+        sync_schema_uri(&mut self.stac_extensions, E::SCHEMA_URI, in_use);
+    }
+}
+
+impl Catalog {
+    /// Adds or removes the schema URI of an extension depending on whether any of its fields are used
+    /// by the Catalog or its summaries.
+    pub(crate) fn sync_extension<E: Extension>(&mut self) {
+        let in_use = has_field(&self.extra_fields, E::is_field)
+            || summaries_have_field(self.summaries.as_ref(), E::is_field);
+        sync_schema_uri(&mut self.stac_extensions, E::SCHEMA_URI, in_use);
+    }
+}
+
+impl Item {
+    /// Validates and sets the fields of any [`Extension`] on one of the Item's assets.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::Other`] if the Item has no asset with the given key or if
+    /// [`Extension::validate`] fails.
+    pub fn set_asset_extension<E: Extension>(&mut self, key: &str, fields: &E) -> STACResult<()> {
+        self.asset_mut(key)?.set_extension(fields)?;
+        self.sync_extension::<E>();
+        Ok(())
+    }
+}
+
+impl Extensions for Item {
+    fn has_extension<E: Extension>(&self) -> bool {
+        has_schema_uri(self.stac_extensions.as_ref(), E::SCHEMA_URI)
+    }
+
+    fn get_extension<E: Extension>(&self) -> STACResult<E> {
+        self.properties.get_extension()
+    }
+
+    fn set_extension<E: Extension>(&mut self, fields: &E) -> STACResult<()> {
+        self.properties.set_extension(fields)?;
+        self.sync_extension::<E>();
+        Ok(())
+    }
+
+    fn remove_extension<E: Extension>(&mut self) {
+        remove_fields(&mut self.properties.extra_fields, E::is_field);
+        remove_fields(&mut self.extra_fields, E::is_field);
+        for asset in self.assets.values_mut() {
+            asset.remove_extension::<E>();
+        }
+        sync_schema_uri(&mut self.stac_extensions, E::SCHEMA_URI, false);
+    }
+}
+
+impl Extensions for ItemProperties {
+    fn has_extension<E: Extension>(&self) -> bool {
+        has_field(&self.extra_fields, E::is_field)
+    }
+
+    fn get_extension<E: Extension>(&self) -> STACResult<E> {
+        E::from_fields(&self.extra_fields)
+    }
+
+    fn set_extension<E: Extension>(&mut self, fields: &E) -> STACResult<()> {
+        fields.validate()?;
+        set_extension_fields(&mut self.extra_fields, fields)
+    }
+
+    fn remove_extension<E: Extension>(&mut self) {
+        remove_fields(&mut self.extra_fields, E::is_field);
+    }
+}
+
+impl Extensions for Asset {
+    fn has_extension<E: Extension>(&self) -> bool {
+        has_field(&self.extra_fields, E::is_field)
+    }
+
+    fn get_extension<E: Extension>(&self) -> STACResult<E> {
+        E::from_fields(&self.extra_fields)
+    }
+
+    fn set_extension<E: Extension>(&mut self, fields: &E) -> STACResult<()> {
+        fields.validate()?;
+        set_extension_fields(&mut self.extra_fields, fields)
+    }
+
+    fn remove_extension<E: Extension>(&mut self) {
+        remove_fields(&mut self.extra_fields, E::is_field);
+    }
+}
+
+impl Extensions for Collection {
+    fn has_extension<E: Extension>(&self) -> bool {
+        has_schema_uri(self.stac_extensions.as_ref(), E::SCHEMA_URI)
+    }
+
+    fn get_extension<E: Extension>(&self) -> STACResult<E> {
+        E::from_fields(&self.extra_fields)
+    }
+
+    fn set_extension<E: Extension>(&mut self, fields: &E) -> STACResult<()> {
+        fields.validate()?;
+        set_extension_fields(&mut self.extra_fields, fields)?;
+        self.sync_extension::<E>();
+        Ok(())
+    }
+
+    fn remove_extension<E: Extension>(&mut self) {
+        remove_fields(&mut self.extra_fields, E::is_field);
+        remove_summaries(&mut self.summaries, E::is_field);
+        for asset in self.assets.iter_mut().flat_map(HashMap::values_mut) {
+            asset.remove_extension::<E>();
+        }
+        sync_schema_uri(&mut self.stac_extensions, E::SCHEMA_URI, false);
+    }
+}
+
+impl Extensions for Catalog {
+    fn has_extension<E: Extension>(&self) -> bool {
+        has_schema_uri(self.stac_extensions.as_ref(), E::SCHEMA_URI)
+    }
+
+    fn get_extension<E: Extension>(&self) -> STACResult<E> {
+        E::from_fields(&self.extra_fields)
+    }
+
+    fn set_extension<E: Extension>(&mut self, fields: &E) -> STACResult<()> {
+        fields.validate()?;
+        set_extension_fields(&mut self.extra_fields, fields)?;
+        self.sync_extension::<E>();
+        Ok(())
+    }
+
+    fn remove_extension<E: Extension>(&mut self) {
+        remove_fields(&mut self.extra_fields, E::is_field);
+        remove_summaries(&mut self.summaries, E::is_field);
+        sync_schema_uri(&mut self.stac_extensions, E::SCHEMA_URI, false);
+    }
+}
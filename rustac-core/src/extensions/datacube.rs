@@ -0,0 +1,462 @@
+//! Implementation of the [Datacube Extension](https://github.com/stac-extensions/datacube).
+//!
+//! Dimensions are typed by kind: horizontal spatial (`x` and `y` axes), vertical spatial (`z`
+//! axis), temporal, and additional dimensions of any other type. Variables refer to dimensions by
+//! name; [`Datacube::unknown_dimensions`] lists references to dimensions that do not exist.
+//!
+//! ```
+//! use rustac_core::extensions::datacube::{Datacube, Dimension};
+//! use serde_json::json;
+//!
+//! let cube: Datacube = serde_json::from_value(json!({
+//!     "cube:dimensions": {
+//!         "x": {"type": "spatial", "axis": "x", "extent": [-180.0, 180.0], "step": 0.25},
+//!         "y": {"type": "spatial", "axis": "y", "extent": [-90.0, 90.0], "step": 0.25},
+//!         "time": {"type": "temporal", "extent": ["2020-01-01T00:00:00Z", null], "step": "P1D"}
+//!     },
+//!     "cube:variables": {
+//!         "temperature": {"type": "data", "dimensions": ["time", "y", "x"], "unit": "K"},
+//!         "pressure": {"type": "data", "dimensions": ["time", "level", "y", "x"]}
+//!     }
+//! })).unwrap();
+//!
+//! let dimensions = cube.dimensions.as_ref().unwrap();
+//! assert!(matches!(dimensions["time"], Dimension::Temporal(_)));
+//! assert_eq!(
+//!     cube.unknown_dimensions(),
+//!     vec![(String::from("pressure"), String::from("level"))]
+//! );
+//! assert!(cube.validate().is_err());
+//! ```
+use chrono::DateTime;
+use serde::de::{self, Deserializer};
+use serde::ser::{self, Serializer};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+use super::{Extension, Extensions};
+use crate::error::{STACError, STACResult};
+use crate::{Collection, Item};
+
+/// Schema URI of the version of the extension implemented by this module.
+pub const SCHEMA_URI: &str = "https://stac-extensions.github.io/datacube/v2.2.0/schema.json";
+
+/// Prefix of all fields defined by the extension.
+pub const PREFIX: &str = "cube:";
+
+/// Fields of the extension that may be used in Item properties or in a Collection.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct Datacube {
+    /// The dimensions of the datacube, by name.
+    #[serde(rename = "cube:dimensions")]
+    pub dimensions: Option<HashMap<String, Dimension>>,
+
+    /// The variables of the datacube, by name.
+    #[serde(rename = "cube:variables")]
+    pub variables: Option<HashMap<String, Variable>>,
+}
+
+/// A dimension of a datacube. The kind of dimension is determined by its `type` and, for spatial
+/// dimensions, its `axis`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Dimension {
+    /// A spatial dimension along the `x` or `y` axis.
+    Horizontal(HorizontalDimension),
+
+    /// A spatial dimension along the `z` axis.
+    Vertical(VerticalDimension),
+
+    /// A temporal dimension.
+    Temporal(TemporalDimension),
+
+    /// A dimension of any other type, such as bands.
+    Additional(AdditionalDimension),
+}
+
+/// The axis of a horizontal spatial dimension.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum HorizontalAxis {
+    /// The `x` axis, usually longitude or easting.
+    X,
+
+    /// The `y` axis, usually latitude or northing.
+    Y,
+}
+
+/// A spatial dimension along the `x` or `y` axis.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct HorizontalDimension {
+    /// The axis of the dimension.
+    pub axis: HorizontalAxis,
+
+    /// Detailed description of the dimension.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// The minimum and maximum coordinates of the dimension.
+    pub extent: [f64; 2],
+
+    /// The coordinates of the dimension, if they are irregularly spaced.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub values: Option<Vec<f64>>,
+
+    /// The space between the coordinates, if they are regularly spaced.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub step: Option<f64>,
+
+    /// The reference system of the coordinates, as an EPSG code, WKT2 string or PROJJSON object.
+    /// Defaults to EPSG code 4326.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reference_system: Option<Value>,
+
+    /// Additional fields not covered by the extension.
+    #[serde(flatten)]
+    pub extra_fields: Map<String, Value>,
+}
+
+/// A spatial dimension along the `z` axis.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct VerticalDimension {
+    /// Detailed description of the dimension.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// The minimum and maximum coordinates of the dimension. Either may be open.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extent: Option<[Option<f64>; 2]>,
+
+    /// The coordinates of the dimension, as numbers or strings.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub values: Option<Vec<Value>>,
+
+    /// The space between the coordinates, if they are regularly spaced.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub step: Option<f64>,
+
+    /// The unit of measurement of the coordinates.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unit: Option<String>,
+
+    /// The reference system of the coordinates, as an EPSG code, WKT2 string or PROJJSON object.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reference_system: Option<Value>,
+
+    /// Additional fields not covered by the extension.
+    #[serde(flatten)]
+    pub extra_fields: Map<String, Value>,
+}
+
+/// A temporal dimension.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct TemporalDimension {
+    /// Detailed description of the dimension.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// The first and last datetimes of the dimension, in RFC 3339 format. Either may be open.
+    pub extent: [Option<String>; 2],
+
+    /// The datetimes of the dimension, if they are irregularly spaced.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub values: Option<Vec<String>>,
+
+    /// The time between the datetimes as an ISO 8601 duration, if they are regularly spaced.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub step: Option<String>,
+
+    /// Additional fields not covered by the extension.
+    #[serde(flatten)]
+    pub extra_fields: Map<String, Value>,
+}
+
+/// A dimension of any type other than `spatial` or `temporal`.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct AdditionalDimension {
+    /// The type of the dimension, such as `bands`.
+    pub r#type: String,
+
+    /// Detailed description of the dimension.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// The minimum and maximum values of the dimension. Either may be open.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extent: Option<[Option<f64>; 2]>,
+
+    /// The values of the dimension, as numbers or strings.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub values: Option<Vec<Value>>,
+
+    /// The space between the values, if they are regularly spaced.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub step: Option<f64>,
+
+    /// The unit of measurement of the values.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unit: Option<String>,
+
+    /// The reference system of the values.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reference_system: Option<Value>,
+
+    /// Additional fields not covered by the extension.
+    #[serde(flatten)]
+    pub extra_fields: Map<String, Value>,
+}
+
+/// A variable of a datacube.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct Variable {
+    /// The names of the dimensions of the variable, in order.
+    pub dimensions: Vec<String>,
+
+    /// Whether the variable holds data or auxiliary values such as coordinates.
+    pub r#type: VariableType,
+
+    /// Detailed description of the variable.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// The minimum and maximum values of the variable. Either may be open.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extent: Option<[Value; 2]>,
+
+    /// The values of the variable, if there are few enough to list.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub values: Option<Vec<Value>>,
+
+    /// The unit of measurement of the values.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unit: Option<String>,
+
+    /// Additional fields not covered by the extension.
+    #[serde(flatten)]
+    pub extra_fields: Map<String, Value>,
+}
+
+/// The type of a variable.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum VariableType {
+    /// A variable holding measured or derived data.
+    #[default]
+    Data,
+
+    /// A variable holding auxiliary values, such as coordinates.
+    Auxiliary,
+}
+
+impl Dimension {
+    /// The value of the dimension's `type` field.
+    #[must_use]
+    pub fn r#type(&self) -> &str {
+        match self {
+            Dimension::Horizontal(_) | Dimension::Vertical(_) => "spatial",
+            Dimension::Temporal(_) => "temporal",
+            Dimension::Additional(dimension) => &dimension.r#type,
+        }
+    }
+
+    /// Checks that the minimum of a numeric extent is not greater than its maximum, and that both
+    /// ends of a temporal extent are RFC 3339 datetimes in order.
+    fn validate(&self, name: &str) -> STACResult<()> {
+        let (minimum, maximum) = match self {
+            Dimension::Horizontal(dimension) => {
+                (Some(dimension.extent[0]), Some(dimension.extent[1]))
+            }
+            Dimension::Vertical(VerticalDimension { extent, .. })
+            | Dimension::Additional(AdditionalDimension { extent, .. }) => {
+                let [minimum, maximum] = extent.unwrap_or_default();
+                (minimum, maximum)
+            }
+            Dimension::Temporal(dimension) => {
+                let [start, end] = &dimension.extent;
+                let parse = |datetime: &Option<String>| {
+                    datetime
+                        .as_deref()
+                        .map(DateTime::parse_from_rfc3339)
+                        .transpose()
+                        .map_err(|err| {
+                            STACError::Other(format!(
+                                "cube:dimensions \"{name}\" has an invalid extent: {err}"
+                            ))
+                        })
+                };
+                if let (Some(start), Some(end)) = (parse(start)?, parse(end)?) {
+                    if start > end {
+                        return Err(STACError::Other(format!(
+                            "cube:dimensions \"{name}\" extent starts after it ends"
+                        )));
+                    }
+                }
+                return Ok(());
+            }
+        };
+        if let (Some(minimum), Some(maximum)) = (minimum, maximum) {
+            if minimum > maximum {
+                return Err(STACError::Other(format!(
+                    "cube:dimensions \"{name}\" extent minimum {minimum} is greater than maximum {maximum}"
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Serialize for Dimension {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let value = match self {
+            Dimension::Horizontal(dimension) => serde_json::to_value(dimension),
+            Dimension::Vertical(dimension) => serde_json::to_value(dimension),
+            Dimension::Temporal(dimension) => serde_json::to_value(dimension),
+            Dimension::Additional(dimension) => serde_json::to_value(dimension),
+        };
+        let Value::Object(mut fields) = value.map_err(ser::Error::custom)? else {
+            return Err(ser::Error::custom(
+                "dimension must serialize to a JSON object",
+            ));
+        };
+        if let Dimension::Vertical(_) = self {
+            fields.insert(String::from("axis"), Value::from("z"));
+        }
+        fields.insert(String::from("type"), Value::from(self.r#type()));
+        fields.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Dimension {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Dimension, D::Error> {
+        let mut fields = Map::deserialize(deserializer)?;
+        let r#type = fields
+            .get("type")
+            .and_then(Value::as_str)
+            .ok_or_else(|| de::Error::missing_field("type"))?
+            .to_string();
+        let dimension = match r#type.as_str() {
+            "spatial" => {
+                fields.remove("type");
+                if fields.get("axis").and_then(Value::as_str) == Some("z") {
+                    fields.remove("axis");
+                    serde_json::from_value(Value::Object(fields)).map(Dimension::Vertical)
+                } else {
+                    serde_json::from_value(Value::Object(fields)).map(Dimension::Horizontal)
+                }
+            }
+            "temporal" => {
+                fields.remove("type");
+                serde_json::from_value(Value::Object(fields)).map(Dimension::Temporal)
+            }
+            _ => serde_json::from_value(Value::Object(fields)).map(Dimension::Additional),
+        };
+        dimension.map_err(de::Error::custom)
+    }
+}
+
+impl Datacube {
+    /// Lists the dimensions that are referenced by a variable but not defined in
+    /// `cube:dimensions`, as pairs of variable and dimension names sorted by variable.
+    #[must_use]
+    pub fn unknown_dimensions(&self) -> Vec<(String, String)> {
+        let mut unknown: Vec<(String, String)> = self
+            .variables
+            .iter()
+            .flatten()
+            .flat_map(|(variable, fields)| {
+                fields
+                    .dimensions
+                    .iter()
+                    .filter(|dimension| {
+                        !self
+                            .dimensions
+                            .as_ref()
+                            .is_some_and(|dimensions| dimensions.contains_key(*dimension))
+                    })
+                    .map(move |dimension| (variable.clone(), dimension.clone()))
+            })
+            .collect();
+        unknown.sort();
+        unknown
+    }
+
+    /// Checks that every dimension referenced by a variable exists and is referenced only once by
+    /// that variable, and that dimension extents are in order.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::Other`] describing the first invalid field.
+    pub fn validate(&self) -> STACResult<()> {
+        if let Some((variable, dimension)) = self.unknown_dimensions().into_iter().next() {
+            return Err(STACError::Other(format!(
+                "cube:variables \"{variable}\" references unknown dimension \"{dimension}\""
+            )));
+        }
+        let mut variables: Vec<_> = self.variables.iter().flatten().collect();
+        variables.sort_by_key(|(name, _)| *name);
+        for (name, variable) in variables {
+            for (index, dimension) in variable.dimensions.iter().enumerate() {
+                if variable.dimensions[..index].contains(dimension) {
+                    return Err(STACError::Other(format!(
+                        "cube:variables \"{name}\" references dimension \"{dimension}\" more than once"
+                    )));
+                }
+            }
+        }
+        let mut dimensions: Vec<_> = self.dimensions.iter().flatten().collect();
+        dimensions.sort_by_key(|(name, _)| *name);
+        for (name, dimension) in dimensions {
+            dimension.validate(name)?;
+        }
+        Ok(())
+    }
+}
+
+impl Extension for Datacube {
+    const SCHEMA_URI: &'static str = SCHEMA_URI;
+    const PREFIX: &'static str = PREFIX;
+
+    fn validate(&self) -> STACResult<()> {
+        Datacube::validate(self)
+    }
+}
+
+impl Item {
+    /// Gets the extension's fields from the Item properties.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::JSONParse`] if a field does not match the extension's schema.
+    pub fn datacube(&self) -> STACResult<Datacube> {
+        self.get_extension()
+    }
+
+    /// Sets the extension's fields in the Item properties, removing fields that are `None`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::Other`] if the fields are not valid, see [`Datacube::validate`].
+    pub fn set_datacube(&mut self, fields: &Datacube) -> STACResult<()> {
+        self.set_extension(fields)
+    }
+}
+
+impl Collection {
+    /// Gets the extension's fields from the Collection.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::JSONParse`] if a field does not match the extension's schema.
+    pub fn datacube(&self) -> STACResult<Datacube> {
+        self.get_extension()
+    }
+
+    /// Sets the extension's fields in the Collection, removing fields that are `None`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::Other`] if the fields are not valid, see [`Datacube::validate`].
+    pub fn set_datacube(&mut self, fields: &Datacube) -> STACResult<()> {
+        self.set_extension(fields)
+    }
+}
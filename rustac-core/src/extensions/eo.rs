@@ -0,0 +1,270 @@
+//! Implementation of the [Electro-Optical Extension](https://github.com/stac-extensions/eo).
+//!
+//! ```
+//! use rustac_core::extensions::eo::{self, Band, CommonBandName, Eo};
+//! use rustac_core::Item;
+//! use serde_json::json;
+//!
+//! let mut item: Item = serde_json::from_value(json!({
+//!     "stac_version": "1.0.0",
+//!     "type": "Feature",
+//!     "id": "an-item",
+//!     "bbox": [0.0, 0.0, 1.0, 1.0],
+//!     "geometry": {"type": "Point", "coordinates": [0.0, 0.0]},
+//!     "properties": {"datetime": "2021-01-01T00:00:00Z"},
+//!     "links": [],
+//!     "assets": {"B02": {"href": "B02.tif"}}
+//! })).unwrap();
+//!
+//! let mut fields = item.eo().unwrap();
+//! fields.cloud_cover = Some(12.5);
+//! item.set_eo(&fields).unwrap();
+//!
+//! let blue = Band {
+//!     name: Some(String::from("B02")),
+//!     common_name: Some(CommonBandName::Blue),
+//!     center_wavelength: Some(0.49),
+//!     ..Default::default()
+//! };
+//! item.set_asset_eo("B02", &Eo { bands: Some(vec![blue]), ..Default::default() }).unwrap();
+//!
+//! assert_eq!(item.properties.extra_fields["eo:cloud_cover"], json!(12.5));
+//! assert_eq!(item.stac_extensions, Some(vec![String::from(eo::SCHEMA_URI)]));
+//! ```
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use super::{get_summaries, set_summaries, Extension, Extensions, Range};
+use crate::error::{STACError, STACResult};
+use crate::{Asset, Collection, Item};
+
+/// Schema URI of the version of the extension implemented by this module.
+pub const SCHEMA_URI: &str = "https://stac-extensions.github.io/eo/v1.0.0/schema.json";
+
+/// Prefix of all fields defined by the extension.
+pub const PREFIX: &str = "eo:";
+
+/// Fields of the extension that may be used in Item properties or on an Asset.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct Eo {
+    /// An array of available bands.
+    #[serde(rename = "eo:bands")]
+    pub bands: Option<Vec<Band>>,
+
+    /// Estimate of cloud cover, as a percentage (0-100) of the entire scene.
+    #[serde(rename = "eo:cloud_cover")]
+    pub cloud_cover: Option<f32>,
+
+    /// Estimate of snow and ice cover, as a percentage (0-100) of the entire scene.
+    #[serde(rename = "eo:snow_cover")]
+    pub snow_cover: Option<f32>,
+}
+
+impl Eo {
+    /// Checks that the cover percentages are between 0 and 100.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::Other`] if a percentage is out of range.
+    pub fn validate(&self) -> STACResult<()> {
+        for (name, value) in [
+            ("eo:cloud_cover", self.cloud_cover),
+            ("eo:snow_cover", self.snow_cover),
+        ] {
+            if let Some(value) = value {
+                if !(0.0..=100.0).contains(&value) {
+                    return Err(STACError::Other(format!(
+                        "{name} must be between 0 and 100, got {value}"
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Summaries of the extension's fields in a Collection.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct EoSummaries {
+    /// All bands available in the Collection.
+    #[serde(rename = "eo:bands")]
+    pub bands: Option<Vec<Band>>,
+
+    /// Range of cloud cover percentages.
+    #[serde(rename = "eo:cloud_cover")]
+    pub cloud_cover: Option<Range<f32>>,
+
+    /// Range of snow and ice cover percentages.
+    #[serde(rename = "eo:snow_cover")]
+    pub snow_cover: Option<Range<f32>>,
+}
+
+/// A spectral band, as described by the [Band Object](https://github.com/stac-extensions/eo#band-object).
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct Band {
+    /// The name of the band (e.g., "B01", "B8", "band2", "red").
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    /// The name commonly used to refer to the band.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub common_name: Option<CommonBandName>,
+
+    /// Description to fully explain the band.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// The center wavelength of the band, in micrometers (μm).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub center_wavelength: Option<f32>,
+
+    /// Full width at half maximum (FWHM). The width of the band, as measured at half the maximum
+    /// transmission, in micrometers (μm).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub full_width_half_max: Option<f32>,
+
+    /// The solar illumination of the band, as measured at half the maximum transmission, in W/m2/micrometers.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub solar_illumination: Option<f32>,
+
+    /// Additional fields not covered by the extension.
+    #[serde(flatten)]
+    pub extra_fields: Map<String, Value>,
+}
+
+/// The [common band names](https://github.com/stac-extensions/eo#common-band-names) defined by the
+/// extension.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum CommonBandName {
+    /// Coastal, 0.40 - 0.45 μm
+    Coastal,
+
+    /// Blue, 0.45 - 0.50 μm
+    Blue,
+
+    /// Green, 0.50 - 0.60 μm
+    Green,
+
+    /// Red, 0.60 - 0.70 μm
+    Red,
+
+    /// Yellow, 0.58 - 0.62 μm
+    Yellow,
+
+    /// Panchromatic, 0.50 - 0.70 μm
+    Pan,
+
+    /// Red edge, 0.70 - 0.79 μm
+    Rededge,
+
+    /// Near infrared, 0.75 - 1.00 μm
+    Nir,
+
+    /// Near infrared, 0.85 - 0.90 μm
+    Nir08,
+
+    /// Near infrared, 0.93 - 0.97 μm
+    Nir09,
+
+    /// Cirrus, 1.35 - 1.40 μm
+    Cirrus,
+
+    /// Short-wave infrared, 1.55 - 1.75 μm
+    Swir16,
+
+    /// Short-wave infrared, 2.08 - 2.35 μm
+    Swir22,
+
+    /// Long-wave infrared, 10.5 - 12.5 μm
+    Lwir,
+
+    /// Long-wave infrared, 10.5 - 11.5 μm
+    Lwir11,
+
+    /// Long-wave infrared, 11.5 - 12.5 μm
+    Lwir12,
+}
+
+impl Extension for Eo {
+    const SCHEMA_URI: &'static str = SCHEMA_URI;
+    const PREFIX: &'static str = PREFIX;
+
+    fn validate(&self) -> STACResult<()> {
+        Eo::validate(self)
+    }
+}
+
+impl Item {
+    /// Gets the extension's fields from the Item properties.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::JSONParse`] if a field does not match the extension's schema.
+    pub fn eo(&self) -> STACResult<Eo> {
+        self.get_extension()
+    }
+
+    /// Sets the extension's fields in the Item properties, removing fields that are `None`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::Other`] if the fields are not valid, see [`Eo::validate`].
+    pub fn set_eo(&mut self, fields: &Eo) -> STACResult<()> {
+        self.set_extension(fields)
+    }
+
+    /// Sets the extension's fields on one of the Item's assets.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::Other`] if the Item has no asset with the given key or if the fields are
+    /// not valid.
+    pub fn set_asset_eo(&mut self, key: &str, fields: &Eo) -> STACResult<()> {
+        self.set_asset_extension(key, fields)
+    }
+}
+
+impl Asset {
+    /// Gets the extension's fields from the Asset.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::JSONParse`] if a field does not match the extension's schema.
+    pub fn eo(&self) -> STACResult<Eo> {
+        self.get_extension()
+    }
+
+    /// Sets the extension's fields on the Asset, removing fields that are `None`. This does not
+    /// update the `stac_extensions` of the Item the Asset belongs to; use [`Item::set_asset_eo`] for
+    /// that.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::Other`] if the fields are not valid, see [`Eo::validate`].
+    pub fn set_eo(&mut self, fields: &Eo) -> STACResult<()> {
+        self.set_extension(fields)
+    }
+}
+
+impl Collection {
+    /// Gets the summaries of the extension's fields.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::JSONParse`] if a summary does not match the extension's schema.
+    pub fn eo_summaries(&self) -> STACResult<EoSummaries> {
+        get_summaries(self.summaries.as_ref(), Eo::is_field)
+    }
+
+    /// Sets the summaries of the extension's fields, removing summaries that are `None`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::JSONParse`] if the summaries cannot be serialized.
+    pub fn set_eo_summaries(&mut self, summaries: &EoSummaries) -> STACResult<()> {
+        set_summaries(&mut self.summaries, summaries)?;
+        self.sync_extension::<Eo>();
+        Ok(())
+    }
+}
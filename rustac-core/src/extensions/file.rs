@@ -0,0 +1,491 @@
+//! Implementation of the [File Info Extension](https://github.com/stac-extensions/file).
+//!
+//! Besides typed access to the `file:*` fields, this module can compute the size and checksum of
+//! local files referenced by Assets, and verify them against the values recorded in the Asset.
+//! Checksums are [multihashes](https://github.com/multiformats/multihash) encoded as hex strings.
+//!
+//! ```
+//! use rustac_core::extensions::file::{HashFunction, Multihash};
+//!
+//! let checksum = Multihash::digest(HashFunction::Sha2_256, b"hello world");
+//! assert_eq!(
+//!     checksum.to_string(),
+//!     "1220b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9"
+//! );
+//! assert_eq!(Multihash::parse(&checksum.to_string()).unwrap(), checksum);
+//! ```
+use blake2::digest::consts::U32;
+use blake2::digest::DynDigest;
+use blake2::{Blake2b, Blake2b512};
+use md5::Md5;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use sha2::{Sha256, Sha512};
+use std::fmt;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use super::{Extension, Extensions};
+use crate::error::{STACError, STACResult};
+use crate::{Asset, Item};
+
+/// Schema URI of the version of the extension implemented by this module.
+pub const SCHEMA_URI: &str = "https://stac-extensions.github.io/file/v2.1.0/schema.json";
+
+/// Prefix of all fields defined by the extension.
+pub const PREFIX: &str = "file:";
+
+/// Fields of the extension that may be used on an Asset or a Link.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct File {
+    /// The file size, specified in bytes.
+    #[serde(rename = "file:size")]
+    pub size: Option<u64>,
+
+    /// Multihash of the file contents, encoded as a hex string.
+    #[serde(rename = "file:checksum")]
+    pub checksum: Option<String>,
+
+    /// The header size of the file, specified in bytes.
+    #[serde(rename = "file:header_size")]
+    pub header_size: Option<u64>,
+
+    /// The byte order of integer values in the file.
+    #[serde(rename = "file:byte_order")]
+    pub byte_order: Option<ByteOrder>,
+
+    /// Lists the values that are in the file and describes their meaning.
+    #[serde(rename = "file:values")]
+    pub values: Option<Vec<MappingObject>>,
+
+    /// A relative local path for the Asset, used when the Asset is stored locally.
+    #[serde(rename = "file:local_path")]
+    pub local_path: Option<String>,
+}
+
+impl File {
+    /// Checks that `file:checksum` is a well-formed multihash.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::Other`] if the checksum cannot be parsed, see [`Multihash::parse`].
+    pub fn validate(&self) -> STACResult<()> {
+        if let Some(checksum) = &self.checksum {
+            Multihash::parse(checksum)?;
+        }
+        Ok(())
+    }
+}
+
+/// The byte order of integer values in a file.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "kebab-case")]
+pub enum ByteOrder {
+    /// Most significant byte first
+    BigEndian,
+
+    /// Least significant byte first
+    LittleEndian,
+}
+
+/// Describes the meaning of one or more values in a file.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct MappingObject {
+    /// The values that are described by the summary.
+    pub values: Vec<Value>,
+
+    /// A short description of the meaning of the values.
+    pub summary: String,
+
+    /// Additional fields on the object.
+    #[serde(flatten)]
+    pub extra_fields: Map<String, Value>,
+}
+
+/// Hash functions supported for computing checksums.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[allow(non_camel_case_types)]
+pub enum HashFunction {
+    /// SHA-256, multihash code `0x12`
+    Sha2_256,
+
+    /// SHA-512, multihash code `0x13`
+    Sha2_512,
+
+    /// BLAKE2b with a 256 bit digest, multihash code `0xb220`
+    Blake2b_256,
+
+    /// BLAKE2b with a 512 bit digest, multihash code `0xb240`
+    Blake2b_512,
+
+    /// MD5, multihash code `0xd5`
+    Md5,
+}
+
+impl HashFunction {
+    /// All supported hash functions.
+    pub const ALL: [HashFunction; 5] = [
+        HashFunction::Sha2_256,
+        HashFunction::Sha2_512,
+        HashFunction::Blake2b_256,
+        HashFunction::Blake2b_512,
+        HashFunction::Md5,
+    ];
+
+    /// The code of the hash function in the multihash table.
+    #[must_use]
+    pub fn code(self) -> u64 {
+        match self {
+            HashFunction::Sha2_256 => 0x12,
+            HashFunction::Sha2_512 => 0x13,
+            HashFunction::Blake2b_256 => 0xb220,
+            HashFunction::Blake2b_512 => 0xb240,
+            HashFunction::Md5 => 0xd5,
+        }
+    }
+
+    /// The name of the hash function in the multihash table.
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            HashFunction::Sha2_256 => "sha2-256",
+            HashFunction::Sha2_512 => "sha2-512",
+            HashFunction::Blake2b_256 => "blake2b-256",
+            HashFunction::Blake2b_512 => "blake2b-512",
+            HashFunction::Md5 => "md5",
+        }
+    }
+
+    /// Gets the hash function with the given multihash code, if it is supported.
+    #[must_use]
+    pub fn from_code(code: u64) -> Option<HashFunction> {
+        HashFunction::ALL
+            .iter()
+            .copied()
+            .find(|function| function.code() == code)
+    }
+
+    fn hasher(self) -> Box<dyn DynDigest> {
+        match self {
+            HashFunction::Sha2_256 => Box::new(Sha256::default()),
+            HashFunction::Sha2_512 => Box::new(Sha512::default()),
+            HashFunction::Blake2b_256 => Box::new(Blake2b::<U32>::default()),
+            HashFunction::Blake2b_512 => Box::new(Blake2b512::default()),
+            HashFunction::Md5 => Box::new(Md5::default()),
+        }
+    }
+}
+
+impl fmt::Display for HashFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// A digest along with the hash function that produced it.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Multihash {
+    /// The hash function.
+    pub function: HashFunction,
+
+    /// The digest produced by the hash function.
+    pub digest: Vec<u8>,
+}
+
+impl Multihash {
+    /// Hashes `data` with the given function.
+    #[must_use]
+    pub fn digest(function: HashFunction, data: &[u8]) -> Multihash {
+        let mut hasher = function.hasher();
+        hasher.update(data);
+        Multihash {
+            function,
+            digest: hasher.finalize().into_vec(),
+        }
+    }
+
+    /// Hashes everything that can be read from `reader` with the given function, returning the
+    /// multihash and the number of bytes that were read.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::IO`] if reading fails.
+    pub fn read<R: Read>(function: HashFunction, mut reader: R) -> STACResult<(Multihash, u64)> {
+        let mut hasher = function.hasher();
+        let mut buffer = vec![0; 64 * 1024];
+        let mut size = 0;
+        loop {
+            let count = reader.read(&mut buffer)?;
+            if count == 0 {
+                break;
+            }
+            hasher.update(&buffer[..count]);
+            size += count as u64;
+        }
+        let multihash = Multihash {
+            function,
+            digest: hasher.finalize().into_vec(),
+        };
+        Ok((multihash, size))
+    }
+
+    /// Parses a multihash from a hex string, as used in `file:checksum`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::Other`] if the string is not valid hex, the hash function is not
+    /// supported, or the length of the digest does not match the length in the multihash.
+    pub fn parse(checksum: &str) -> STACResult<Multihash> {
+        let invalid =
+            |reason: &str| STACError::Other(format!("Invalid checksum {checksum}: {reason}"));
+        let bytes = decode_hex(checksum).ok_or_else(|| invalid("not a hex string"))?;
+        let mut bytes = bytes.as_slice();
+        let code = read_varint(&mut bytes).ok_or_else(|| invalid("missing hash function code"))?;
+        let function = HashFunction::from_code(code)
+            .ok_or_else(|| invalid(&format!("unsupported hash function code {code:#x}")))?;
+        let length = read_varint(&mut bytes).ok_or_else(|| invalid("missing digest length"))?;
+        if bytes.len() as u64 != length {
+            return Err(invalid(&format!(
+                "expected a digest of {length} bytes, got {}",
+                bytes.len()
+            )));
+        }
+        Ok(Multihash {
+            function,
+            digest: bytes.to_vec(),
+        })
+    }
+}
+
+impl fmt::Display for Multihash {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut bytes = Vec::with_capacity(self.digest.len() + 4);
+        write_varint(&mut bytes, self.function.code());
+        write_varint(&mut bytes, self.digest.len() as u64);
+        bytes.extend_from_slice(&self.digest);
+        for byte in bytes {
+            write!(f, "{byte:02x}")?;
+        }
+        Ok(())
+    }
+}
+
+fn decode_hex(value: &str) -> Option<Vec<u8>> {
+    if !value.len().is_multiple_of(2) || !value.is_ascii() {
+        return None;
+    }
+    (0..value.len())
+        .step_by(2)
+        .map(|index| u8::from_str_radix(&value[index..index + 2], 16).ok())
+        .collect()
+}
+
+/// Reads an unsigned varint, as used for the code and length of a multihash.
+fn read_varint(bytes: &mut &[u8]) -> Option<u64> {
+    let mut value = 0;
+    for (index, byte) in bytes.iter().enumerate().take(9) {
+        value |= u64::from(byte & 0x7f) << (7 * index);
+        if byte & 0x80 == 0 {
+            *bytes = &bytes[index + 1..];
+            return Some(value);
+        }
+    }
+    None
+}
+
+/// Writes an unsigned varint. Only the lowest 7 bits are kept by each cast.
+#[allow(clippy::cast_possible_truncation)]
+fn write_varint(bytes: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        bytes.push((value as u8 & 0x7f) | 0x80);
+        value >>= 7;
+    }
+    bytes.push(value as u8);
+}
+
+/// A difference between the `file:*` fields of an Asset and the file it references.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mismatch {
+    /// The key of the Asset.
+    pub asset: String,
+
+    /// The field that does not match, e.g. `file:checksum`.
+    pub field: String,
+
+    /// The value recorded in the Asset.
+    pub expected: String,
+
+    /// The value computed from the file.
+    pub actual: String,
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} of asset {} is {}, but the file has {}",
+            self.field, self.asset, self.expected, self.actual
+        )
+    }
+}
+
+impl Extension for File {
+    const SCHEMA_URI: &'static str = SCHEMA_URI;
+    const PREFIX: &'static str = PREFIX;
+
+    fn validate(&self) -> STACResult<()> {
+        File::validate(self)
+    }
+}
+
+impl Item {
+    /// Sets the extension's fields on one of the Item's assets.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::Other`] if the Item has no asset with the given key or if the fields are
+    /// not valid.
+    pub fn set_asset_file(&mut self, key: &str, fields: &File) -> STACResult<()> {
+        self.set_asset_extension(key, fields)
+    }
+
+    /// Computes `file:size` and `file:checksum` for every asset that references a local file, see
+    /// [`Asset::update_file_info`]. Assets with remote hrefs are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::IO`] if a local file cannot be read.
+    pub fn update_file_info(&mut self, base: &Path, function: HashFunction) -> STACResult<()> {
+        for asset in self.assets.values_mut() {
+            if asset.local_path(base).is_some() {
+                asset.update_file_info(base, function)?;
+            }
+        }
+        self.sync_extension::<File>();
+        Ok(())
+    }
+
+    /// Verifies the `file:size` and `file:checksum` of every asset that references a local file, see
+    /// [`Asset::verify_file_info`]. Assets with remote hrefs are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::IO`] if a local file cannot be read, or [`STACError::Other`] if a
+    /// checksum cannot be parsed.
+    pub fn verify_file_info(&self, base: &Path) -> STACResult<Vec<Mismatch>> {
+        let mut keys: Vec<&String> = self.assets.keys().collect();
+        keys.sort();
+        let mut mismatches = Vec::new();
+        for key in keys {
+            let asset = &self.assets[key];
+            if asset.local_path(base).is_some() {
+                for mut mismatch in asset.verify_file_info(base)? {
+                    mismatch.asset.clone_from(key);
+                    mismatches.push(mismatch);
+                }
+            }
+        }
+        Ok(mismatches)
+    }
+}
+
+impl Asset {
+    /// Gets the extension's fields from the Asset.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::JSONParse`] if a field does not match the extension's schema.
+    pub fn file(&self) -> STACResult<File> {
+        self.get_extension()
+    }
+
+    /// Sets the extension's fields on the Asset, removing fields that are `None`. This does not
+    /// update the `stac_extensions` of the Item the Asset belongs to; use [`Item::set_asset_file`]
+    /// for that.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::Other`] if the checksum is not valid, see [`File::validate`].
+    pub fn set_file(&mut self, fields: &File) -> STACResult<()> {
+        self.set_extension(fields)
+    }
+
+    /// Gets the path of the file referenced by `href`, or `None` if `href` is not a local path.
+    /// Relative hrefs are resolved against `base`, which is usually the directory containing the
+    /// Item.
+    #[must_use]
+    pub fn local_path(&self, base: &Path) -> Option<PathBuf> {
+        let href = self.href.as_str();
+        let path = match href.strip_prefix("file://") {
+            Some(path) => path,
+            None if href.contains("://") => return None,
+            None => href,
+        };
+        Some(base.join(path))
+    }
+
+    /// Reads the file referenced by `href` and sets `file:size` and `file:checksum`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::Other`] if `href` is not a local path, or [`STACError::IO`] if the file
+    /// cannot be read.
+    pub fn update_file_info(&mut self, base: &Path, function: HashFunction) -> STACResult<()> {
+        let (checksum, size) = self.read_file(base, function)?;
+        let fields = File {
+            size: Some(size),
+            checksum: Some(checksum.to_string()),
+            ..self.file()?
+        };
+        self.set_file(&fields)
+    }
+
+    /// Reads the file referenced by `href` and compares it to `file:size` and `file:checksum`. The
+    /// checksum is computed with the hash function used by `file:checksum`. Returns the fields that do
+    /// not match, with an empty asset key.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::Other`] if `href` is not a local path or the checksum cannot be parsed,
+    /// or [`STACError::IO`] if the file cannot be read.
+    pub fn verify_file_info(&self, base: &Path) -> STACResult<Vec<Mismatch>> {
+        let fields = self.file()?;
+        let expected = fields
+            .checksum
+            .as_deref()
+            .map(Multihash::parse)
+            .transpose()?;
+        if fields.size.is_none() && expected.is_none() {
+            return Ok(Vec::new());
+        }
+        let function = expected
+            .as_ref()
+            .map_or(HashFunction::Sha2_256, |expected| expected.function);
+        let (checksum, size) = self.read_file(base, function)?;
+
+        let mut mismatches = Vec::new();
+        let mut mismatch = |field: &str, expected: String, actual: String| {
+            mismatches.push(Mismatch {
+                asset: String::new(),
+                field: field.into(),
+                expected,
+                actual,
+            });
+        };
+        if let Some(expected) = fields.size.filter(|expected| *expected != size) {
+            mismatch("file:size", expected.to_string(), size.to_string());
+        }
+        if let Some(expected) = expected.filter(|expected| *expected != checksum) {
+            mismatch("file:checksum", expected.to_string(), checksum.to_string());
+        }
+        Ok(mismatches)
+    }
+
+    fn read_file(&self, base: &Path, function: HashFunction) -> STACResult<(Multihash, u64)> {
+        let path = self.local_path(base).ok_or_else(|| {
+            STACError::Other(format!("Asset href {} is not a local file", self.href))
+        })?;
+        Multihash::read(function, fs::File::open(path)?)
+    }
+}
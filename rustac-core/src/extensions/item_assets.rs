@@ -0,0 +1,364 @@
+//! Implementation of the [Item Assets Definition Extension](https://github.com/stac-extensions/item-assets).
+//!
+//! `item_assets` on a Collection describes the assets that every Item in the Collection is expected
+//! to have. The definitions can be derived from existing Items with [`ItemAssets::from_items`], and
+//! Items can be checked against them with [`ItemAssets::check_item`].
+//!
+//! ```
+//! use rustac_core::extensions::item_assets::{ItemAsset, ItemAssets, Nonconformance};
+//! use rustac_core::Item;
+//! use serde_json::json;
+//!
+//! let item: Item = serde_json::from_value(json!({
+//!     "stac_version": "1.0.0",
+//!     "type": "Feature",
+//!     "id": "an-item",
+//!     "bbox": [0.0, 0.0, 1.0, 1.0],
+//!     "geometry": {"type": "Point", "coordinates": [0.0, 0.0]},
+//!     "properties": {"datetime": "2021-01-01T00:00:00Z"},
+//!     "links": [],
+//!     "assets": {
+//!         "data": {"href": "./data.tif", "type": "image/tiff", "roles": ["data"]}
+//!     }
+//! })).unwrap();
+//!
+//! let definitions = ItemAssets::from_items(&[item]);
+//! let data = &definitions.assets.as_ref().unwrap()["data"];
+//! assert_eq!(data.r#type, Some(String::from("image/tiff")));
+//! assert_eq!(data.roles, Some(vec![String::from("data")]));
+//! ```
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+
+use super::{Extension, Extensions};
+use crate::error::{STACError, STACResult};
+use crate::{Asset, Collection, Item};
+
+/// Schema URI of the version of the extension implemented by this module.
+pub const SCHEMA_URI: &str = "https://stac-extensions.github.io/item-assets/v1.0.0/schema.json";
+
+/// The extension's only field, `item_assets`, is not prefixed, so the field name is used instead.
+pub const PREFIX: &str = "item_assets";
+
+/// Fields of the extension that may be used in a Collection.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct ItemAssets {
+    /// Definitions of the assets of the Items in the Collection, by asset key.
+    #[serde(rename = "item_assets")]
+    pub assets: Option<HashMap<String, ItemAsset>>,
+}
+
+/// Definition of an asset that is present in the Items of a Collection. This is an Asset without
+/// an `href`.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct ItemAsset {
+    /// The displayed title for clients and users.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+
+    /// A description of the Asset providing additional details.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// Media type of the asset.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub r#type: Option<String>,
+
+    /// The semantic roles of the asset.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub roles: Option<Vec<String>>,
+
+    /// Additional fields, such as fields from other extensions.
+    #[serde(flatten)]
+    pub extra_fields: Map<String, Value>,
+}
+
+impl ItemAsset {
+    /// Number of fields that are set on the definition.
+    fn field_count(&self) -> usize {
+        [
+            self.title.is_some(),
+            self.description.is_some(),
+            self.r#type.is_some(),
+            self.roles.is_some(),
+        ]
+        .iter()
+        .filter(|set| **set)
+        .count()
+            + self.extra_fields.len()
+    }
+}
+
+impl From<&Asset> for ItemAsset {
+    fn from(asset: &Asset) -> ItemAsset {
+        ItemAsset {
+            title: asset.title.clone(),
+            description: asset.description.clone(),
+            r#type: asset.r#type.clone(),
+            roles: asset.roles.clone(),
+            extra_fields: asset.extra_fields.as_object().cloned().unwrap_or_default(),
+        }
+    }
+}
+
+/// A way in which an Item does not match the `item_assets` of its Collection.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Nonconformance {
+    /// An asset defined in `item_assets` is missing from the Item.
+    Missing {
+        /// The asset key.
+        key: String,
+    },
+
+    /// The Item has an asset that is not defined in `item_assets`.
+    Undefined {
+        /// The asset key.
+        key: String,
+    },
+
+    /// The media type of an asset differs from its definition.
+    Type {
+        /// The asset key.
+        key: String,
+
+        /// The media type in `item_assets`.
+        expected: String,
+
+        /// The media type of the Item's asset.
+        actual: Option<String>,
+    },
+
+    /// The roles of an asset differ from its definition. Roles are compared without regard to order.
+    Roles {
+        /// The asset key.
+        key: String,
+
+        /// The roles in `item_assets`.
+        expected: Vec<String>,
+
+        /// The roles of the Item's asset.
+        actual: Vec<String>,
+    },
+}
+
+impl Nonconformance {
+    /// The key of the asset that does not conform.
+    #[must_use]
+    pub fn key(&self) -> &str {
+        match self {
+            Nonconformance::Missing { key }
+            | Nonconformance::Undefined { key }
+            | Nonconformance::Type { key, .. }
+            | Nonconformance::Roles { key, .. } => key,
+        }
+    }
+}
+
+impl fmt::Display for Nonconformance {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Nonconformance::Missing { key } => {
+                write!(f, "asset \"{key}\" is defined in item_assets but missing")
+            }
+            Nonconformance::Undefined { key } => {
+                write!(f, "asset \"{key}\" is not defined in item_assets")
+            }
+            Nonconformance::Type {
+                key,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "asset \"{key}\" has type {actual:?}, but item_assets defines \"{expected}\""
+            ),
+            Nonconformance::Roles {
+                key,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "asset \"{key}\" has roles {actual:?}, but item_assets defines {expected:?}"
+            ),
+        }
+    }
+}
+
+impl ItemAssets {
+    /// Checks that every definition has at least two fields and no `href`, as required by the
+    /// extension.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::Other`] describing the first invalid definition.
+    pub fn validate(&self) -> STACResult<()> {
+        for (key, definition) in self.assets.iter().flatten() {
+            if definition.extra_fields.contains_key("href") {
+                return Err(STACError::Other(format!(
+                    "item_assets definition \"{key}\" must not have an href"
+                )));
+            }
+            if definition.field_count() < 2 {
+                return Err(STACError::Other(format!(
+                    "item_assets definition \"{key}\" must have at least two fields"
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Derives asset definitions from Items. Only asset keys used by every Item are defined, since
+    /// [`ItemAssets::check_item`] expects every defined asset to be present. Each definition has
+    /// the fields whose values are the same for that asset in all Items; fields that differ
+    /// between Items, such as checksums, are left out. Keys left with fewer than two common
+    /// fields are not defined, as [`ItemAssets::validate`] would reject them.
+    #[must_use]
+    pub fn from_items<'a, I>(items: I) -> ItemAssets
+    where
+        I: IntoIterator<Item = &'a Item>,
+    {
+        let mut definitions: Option<HashMap<String, ItemAsset>> = None;
+        for item in items {
+            match definitions.as_mut() {
+                Some(definitions) => {
+                    definitions.retain(|key, definition| match item.assets.get(key) {
+                        Some(asset) => {
+                            intersect(definition, &ItemAsset::from(asset));
+                            true
+                        }
+                        None => false,
+                    });
+                }
+                None => {
+                    definitions = Some(
+                        item.assets
+                            .iter()
+                            .map(|(key, asset)| (key.clone(), ItemAsset::from(asset)))
+                            .collect(),
+                    );
+                }
+            }
+        }
+        let mut definitions = definitions.unwrap_or_default();
+        definitions.retain(|_, definition| definition.field_count() >= 2);
+        ItemAssets {
+            assets: Some(definitions).filter(|definitions| !definitions.is_empty()),
+        }
+    }
+
+    /// Checks that the Item's assets match the definitions: every defined asset must be present,
+    /// every asset must be defined, and the `type` and `roles` of each asset must match its
+    /// definition where the definition sets them. Returns the differences sorted by asset key.
+    #[must_use]
+    pub fn check_item(&self, item: &Item) -> Vec<Nonconformance> {
+        let empty = HashMap::new();
+        let definitions = self.assets.as_ref().unwrap_or(&empty);
+        let mut nonconformances = Vec::new();
+
+        for (key, definition) in definitions {
+            let Some(asset) = item.assets.get(key) else {
+                nonconformances.push(Nonconformance::Missing { key: key.clone() });
+                continue;
+            };
+            if let Some(expected) = &definition.r#type {
+                if asset.r#type.as_ref() != Some(expected) {
+                    nonconformances.push(Nonconformance::Type {
+                        key: key.clone(),
+                        expected: expected.clone(),
+                        actual: asset.r#type.clone(),
+                    });
+                }
+            }
+            if let Some(expected) = &definition.roles {
+                let actual = asset.roles.clone().unwrap_or_default();
+                if !same_roles(expected, &actual) {
+                    nonconformances.push(Nonconformance::Roles {
+                        key: key.clone(),
+                        expected: expected.clone(),
+                        actual,
+                    });
+                }
+            }
+        }
+        for key in item.assets.keys() {
+            if !definitions.contains_key(key) {
+                nonconformances.push(Nonconformance::Undefined { key: key.clone() });
+            }
+        }
+
+        nonconformances.sort_by(|a, b| a.key().cmp(b.key()));
+        nonconformances
+    }
+}
+
+/// Checks whether two lists of roles have the same roles, in any order.
+fn same_roles(roles: &[String], other: &[String]) -> bool {
+    roles.iter().collect::<BTreeSet<_>>() == other.iter().collect()
+}
+
+/// Removes the fields of `definition` that do not have the same value in `asset`. Roles are
+/// compared without regard to order, as in [`ItemAssets::check_item`].
+fn intersect(definition: &mut ItemAsset, asset: &ItemAsset) {
+    if definition.title != asset.title {
+        definition.title = None;
+    }
+    if definition.description != asset.description {
+        definition.description = None;
+    }
+    if definition.r#type != asset.r#type {
+        definition.r#type = None;
+    }
+    let roles_match = match (&definition.roles, &asset.roles) {
+        (Some(roles), Some(other)) => same_roles(roles, other),
+        (roles, other) => roles == other,
+    };
+    if !roles_match {
+        definition.roles = None;
+    }
+    definition
+        .extra_fields
+        .retain(|key, value| asset.extra_fields.get(key) == Some(value));
+}
+
+impl Extension for ItemAssets {
+    const SCHEMA_URI: &'static str = SCHEMA_URI;
+    const PREFIX: &'static str = PREFIX;
+
+    fn validate(&self) -> STACResult<()> {
+        ItemAssets::validate(self)
+    }
+
+    fn is_field(key: &str) -> bool {
+        key == PREFIX
+    }
+}
+
+impl Collection {
+    /// Gets the extension's fields from the Collection.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::JSONParse`] if `item_assets` does not match the extension's schema.
+    pub fn item_assets(&self) -> STACResult<ItemAssets> {
+        self.get_extension()
+    }
+
+    /// Sets `item_assets` in the Collection, removing it if it is `None`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::Other`] if a definition is not valid, see [`ItemAssets::validate`].
+    pub fn set_item_assets(&mut self, fields: &ItemAssets) -> STACResult<()> {
+        self.set_extension(fields)
+    }
+
+    /// Checks that an Item matches the Collection's `item_assets`, see [`ItemAssets::check_item`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::JSONParse`] if `item_assets` does not match the extension's schema.
+    pub fn check_item_assets(&self, item: &Item) -> STACResult<Vec<Nonconformance>> {
+        Ok(self.item_assets()?.check_item(item))
+    }
+}
@@ -0,0 +1,327 @@
+//! Implementation of the [Label Extension](https://github.com/stac-extensions/label).
+//!
+//! Label Items describe labeled areas, such as training data for machine learning, and link to the
+//! imagery that was labeled with `source` links (see [`Item::add_label_source`]). For vector labels,
+//! [`Item::update_label_overviews`] counts the features of each class in a GeoJSON label asset.
+//!
+//! ```
+//! use rustac_core::extensions::label::{Label, LabelType};
+//! use serde_json::json;
+//!
+//! let fields: Label = serde_json::from_value(json!({
+//!     "label:properties": ["class"],
+//!     "label:description": "Building footprints",
+//!     "label:type": "vector",
+//!     "label:tasks": ["segmentation"],
+//!     "label:classes": [{"name": "class", "classes": ["building", "other"]}]
+//! })).unwrap();
+//!
+//! assert_eq!(fields.r#type, Some(LabelType::Vector));
+//! assert!(fields.validate().is_ok());
+//! ```
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use super::{Extension, Extensions};
+use crate::error::{STACError, STACResult};
+use crate::{Item, Link};
+
+/// Schema URI of the version of the extension implemented by this module.
+pub const SCHEMA_URI: &str = "https://stac-extensions.github.io/label/v1.0.1/schema.json";
+
+/// Prefix of all fields defined by the extension.
+pub const PREFIX: &str = "label:";
+
+/// Relation type of the links from a label Item to the imagery it labels.
+pub const SOURCE: &str = "source";
+
+/// Fields of the extension that may be used in Item properties.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct Label {
+    /// The names of the properties of each feature that hold the labels. Must not be set for raster
+    /// labels.
+    #[serde(rename = "label:properties")]
+    pub properties: Option<Vec<String>>,
+
+    /// The classes that each label property may take.
+    #[serde(rename = "label:classes")]
+    pub classes: Option<Vec<Class>>,
+
+    /// A description of the label, how it was created and what it is recommended for.
+    #[serde(rename = "label:description")]
+    pub description: Option<String>,
+
+    /// Whether the labels are vector or raster data.
+    #[serde(rename = "label:type")]
+    pub r#type: Option<LabelType>,
+
+    /// The tasks the labels are intended for, e.g. "classification", "detection" or
+    /// "segmentation".
+    #[serde(rename = "label:tasks")]
+    pub tasks: Option<Vec<String>>,
+
+    /// The methods used to create the labels, e.g. "automated" or "manual".
+    #[serde(rename = "label:methods")]
+    pub methods: Option<Vec<String>>,
+
+    /// Summaries of the labels, such as the number of features per class.
+    #[serde(rename = "label:overviews")]
+    pub overviews: Option<Vec<Overview>>,
+}
+
+/// Whether the labels are vector or raster data.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum LabelType {
+    /// Labels are features in a vector file, such as GeoJSON.
+    Vector,
+
+    /// Labels are pixel values in a raster file.
+    Raster,
+}
+
+/// The classes of a label property.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct Class {
+    /// The property that holds the class. Required for vector labels, must not be set for raster
+    /// labels.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    /// The values the property may take, as strings or numbers.
+    pub classes: Vec<Value>,
+}
+
+/// A summary of the labels of one property.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct Overview {
+    /// The property that is summarized.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub property_key: Option<String>,
+
+    /// The number of labels of each class.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub counts: Option<Vec<Count>>,
+
+    /// Statistics of the values of a regression property.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub statistics: Option<Vec<Statistic>>,
+}
+
+/// The number of labels of a class.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct Count {
+    /// The class.
+    pub name: String,
+
+    /// The number of labels of the class.
+    pub count: u64,
+}
+
+/// A named statistic, such as the mean, of the values of a regression property.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct Statistic {
+    /// The name of the statistic.
+    pub name: String,
+
+    /// The value of the statistic.
+    pub value: f64,
+}
+
+impl Label {
+    /// Checks the rules that cannot be enforced by the types of the fields: raster labels must not
+    /// have `label:properties` or class names, vector classes must be named, and class names and
+    /// overview property keys must be listed in `label:properties` if it is set.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::Other`] describing the first invalid field.
+    pub fn validate(&self) -> STACResult<()> {
+        if self.r#type == Some(LabelType::Raster) && self.properties.is_some() {
+            return Err(STACError::Other(String::from(
+                "label:properties must not be set for raster labels",
+            )));
+        }
+        let is_property = |name: &str| {
+            self.properties
+                .as_ref()
+                .is_none_or(|properties| properties.iter().any(|p| p == name))
+        };
+        for class in self.classes.iter().flatten() {
+            match (&class.name, self.r#type) {
+                (Some(_), Some(LabelType::Raster)) => {
+                    return Err(STACError::Other(String::from(
+                        "label:classes must not have a name for raster labels",
+                    )))
+                }
+                (None, Some(LabelType::Vector)) => {
+                    return Err(STACError::Other(String::from(
+                        "label:classes must have a name for vector labels",
+                    )))
+                }
+                (Some(name), _) if !is_property(name) => {
+                    return Err(STACError::Other(format!(
+                        "label:classes name \"{name}\" is not in label:properties"
+                    )))
+                }
+                _ => {}
+            }
+        }
+        for overview in self.overviews.iter().flatten() {
+            if let Some(key) = overview
+                .property_key
+                .as_deref()
+                .filter(|key| !is_property(key))
+            {
+                return Err(STACError::Other(format!(
+                    "label:overviews property_key \"{key}\" is not in label:properties"
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Extension for Label {
+    const SCHEMA_URI: &'static str = SCHEMA_URI;
+    const PREFIX: &'static str = PREFIX;
+
+    fn validate(&self) -> STACResult<()> {
+        Label::validate(self)
+    }
+}
+
+/// The name of a class value in overview counts: strings are used as is and other values are
+/// written as JSON. Missing values are not counted.
+fn class_name(value: &Value) -> Option<String> {
+    match value {
+        Value::Null => None,
+        Value::String(name) => Some(name.clone()),
+        value => Some(value.to_string()),
+    }
+}
+
+/// Counts the features of each class in a GeoJSON FeatureCollection, for each of the given
+/// properties. Each class listed in `classes` is counted even if no feature has it. Counts are
+/// sorted by class name.
+///
+/// # Errors
+///
+/// Returns [`STACError::IO`] if the file cannot be read, or [`STACError::JSONParse`] if it is not a
+/// GeoJSON FeatureCollection.
+pub fn count_classes(
+    path: &Path,
+    properties: &[String],
+    classes: &[Class],
+) -> STACResult<Vec<Overview>> {
+    let collection: geojson::FeatureCollection = serde_json::from_str(&fs::read_to_string(path)?)?;
+    Ok(properties
+        .iter()
+        .map(|property| {
+            let mut counts: BTreeMap<String, u64> = classes
+                .iter()
+                .filter(|class| class.name.as_ref() == Some(property))
+                .flat_map(|class| class.classes.iter().filter_map(class_name))
+                .map(|name| (name, 0))
+                .collect();
+            for feature in &collection.features {
+                if let Some(name) = feature.property(property).and_then(class_name) {
+                    *counts.entry(name).or_default() += 1;
+                }
+            }
+            Overview {
+                property_key: Some(property.clone()),
+                counts: Some(
+                    counts
+                        .into_iter()
+                        .map(|(name, count)| Count { name, count })
+                        .collect(),
+                ),
+                statistics: None,
+            }
+        })
+        .collect())
+}
+
+impl Item {
+    /// Gets the extension's fields from the Item properties.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::JSONParse`] if a field does not match the extension's schema.
+    pub fn label(&self) -> STACResult<Label> {
+        self.get_extension()
+    }
+
+    /// Sets the extension's fields in the Item properties, removing fields that are `None`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::Other`] if the fields are not valid, see [`Label::validate`].
+    pub fn set_label(&mut self, fields: &Label) -> STACResult<()> {
+        self.set_extension(fields)
+    }
+
+    /// Gets the `source` links to the imagery that was labeled.
+    #[must_use]
+    pub fn label_sources(&self) -> Vec<&Link> {
+        self.links
+            .iter()
+            .filter(|link| link.rel == SOURCE)
+            .collect()
+    }
+
+    /// Adds a `source` link to an imagery Item. `assets` are the keys of the assets of the imagery
+    /// Item that were labeled, written to `label:assets`; by default all assets were labeled.
+    pub fn add_label_source<H: Into<String>>(&mut self, href: H, assets: Option<Vec<String>>) {
+        let mut link = Link::new(href, SOURCE);
+        link.r#type = Some(String::from("application/json"));
+        if let (Some(assets), Value::Object(fields)) = (assets, &mut link.extra_fields) {
+            fields.insert(String::from("label:assets"), assets.into());
+        }
+        self.links.push(link);
+    }
+
+    /// Counts the features of each class in the GeoJSON label asset with the given key, for each
+    /// property in `label:properties`, and sets the counts in `label:overviews`. Statistics in
+    /// existing overviews are kept. Relative asset hrefs are resolved against `base`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::Other`] if the asset does not exist or is not a local file or if
+    /// `label:properties` is not set, [`STACError::IO`] if the file cannot be read, or
+    /// [`STACError::JSONParse`] if it is not a GeoJSON FeatureCollection.
+    pub fn update_label_overviews(&mut self, base: &Path, key: &str) -> STACResult<()> {
+        let asset = self
+            .assets
+            .get(key)
+            .ok_or_else(|| STACError::Other(format!("Item {} has no asset {}", self.id, key)))?;
+        let path = asset.local_path(base).ok_or_else(|| {
+            STACError::Other(format!("Asset href {} is not a local file", asset.href))
+        })?;
+        let mut fields = self.label()?;
+        let properties = fields.properties.clone().unwrap_or_default();
+        if properties.is_empty() {
+            return Err(STACError::Other(format!(
+                "Item {} has no label:properties to count",
+                self.id
+            )));
+        }
+
+        let counted = count_classes(&path, &properties, fields.classes.as_deref().unwrap_or(&[]))?;
+        let overviews = fields.overviews.get_or_insert_with(Vec::new);
+        for overview in counted {
+            match overviews
+                .iter_mut()
+                .find(|existing| existing.property_key == overview.property_key)
+            {
+                Some(existing) => existing.counts = overview.counts,
+                None => overviews.push(overview),
+            }
+        }
+        self.set_label(&fields)
+    }
+}
@@ -0,0 +1,230 @@
+//! Implementation of the [Point Cloud Extension](https://github.com/stac-extensions/pointcloud).
+//!
+//! ```
+//! use rustac_core::extensions::pointcloud::{Pointcloud, SchemaType};
+//! use serde_json::json;
+//!
+//! let fields: Pointcloud = serde_json::from_value(json!({
+//!     "pc:count": 10_653_336,
+//!     "pc:type": "lidar",
+//!     "pc:encoding": "binary",
+//!     "pc:schemas": [
+//!         {"name": "X", "size": 8, "type": "floating"},
+//!         {"name": "Y", "size": 8, "type": "floating"},
+//!         {"name": "Z", "size": 8, "type": "floating"},
+//!         {"name": "Intensity", "size": 2, "type": "unsigned"}
+//!     ],
+//!     "pc:density": 1.9
+//! })).unwrap();
+//!
+//! assert_eq!(fields.schemas.as_ref().unwrap()[3].r#type, SchemaType::Unsigned);
+//! assert_eq!(fields.point_size(), Some(26));
+//! assert!(fields.validate().is_ok());
+//! ```
+use serde::{Deserialize, Serialize};
+
+use super::{Extension, Extensions};
+use crate::error::{STACError, STACResult};
+use crate::{Item, ItemProperties};
+
+/// Schema URI of the version of the extension implemented by this module.
+pub const SCHEMA_URI: &str = "https://stac-extensions.github.io/pointcloud/v1.0.0/schema.json";
+
+/// Prefix of all fields defined by the extension.
+pub const PREFIX: &str = "pc:";
+
+/// Fields of the extension that may be used in Item properties.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct Pointcloud {
+    /// The number of points in the Item.
+    #[serde(rename = "pc:count")]
+    pub count: Option<u64>,
+
+    /// Phenomenology type for the point cloud, e.g. "lidar", "eopc", "radar", "sonar" or "other".
+    #[serde(rename = "pc:type")]
+    pub r#type: Option<String>,
+
+    /// Content encoding or format of the data, e.g. "binary" or "LASzip".
+    #[serde(rename = "pc:encoding")]
+    pub encoding: Option<String>,
+
+    /// The dimensions of each point, in the order they are stored.
+    #[serde(rename = "pc:schemas")]
+    pub schemas: Option<Vec<Schema>>,
+
+    /// Number of points per square unit area.
+    #[serde(rename = "pc:density")]
+    pub density: Option<f64>,
+
+    /// Statistics of the values of each dimension.
+    #[serde(rename = "pc:statistics")]
+    pub statistics: Option<Vec<Statistics>>,
+}
+
+/// A dimension of the points, as described by the [Schema Object](https://github.com/stac-extensions/pointcloud#schema-object).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Schema {
+    /// The name of the dimension.
+    pub name: String,
+
+    /// The size of the dimension in bytes.
+    pub size: u32,
+
+    /// The type of the values of the dimension.
+    pub r#type: SchemaType,
+}
+
+/// The type of the values of a point dimension.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum SchemaType {
+    /// Floating point numbers
+    Floating,
+
+    /// Unsigned integers
+    Unsigned,
+
+    /// Signed integers
+    Signed,
+}
+
+/// Statistics of the values of a point dimension, as described by the [Stats Object](https://github.com/stac-extensions/pointcloud#stats-object).
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct Statistics {
+    /// The name of the dimension.
+    pub name: String,
+
+    /// Position of the dimension in the schemas.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub position: Option<u32>,
+
+    /// Average of the values.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub average: Option<f64>,
+
+    /// Number of values.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub count: Option<u64>,
+
+    /// Maximum value.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maximum: Option<f64>,
+
+    /// Minimum value.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minimum: Option<f64>,
+
+    /// Standard deviation of the values.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stddev: Option<f64>,
+
+    /// Variance of the values.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub variance: Option<f64>,
+}
+
+impl Pointcloud {
+    /// The size of each point in bytes, which is the sum of the sizes of all dimensions in
+    /// `pc:schemas`.
+    #[must_use]
+    pub fn point_size(&self) -> Option<u64> {
+        self.schemas
+            .as_ref()
+            .map(|schemas| schemas.iter().map(|schema| u64::from(schema.size)).sum())
+    }
+
+    /// Checks the values that cannot be enforced by the types of the fields: the density must not
+    /// be negative, schema names must be unique and sizes positive, and statistics must refer to
+    /// dimensions in `pc:schemas` if it is set.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::Other`] describing the first invalid field.
+    pub fn validate(&self) -> STACResult<()> {
+        if let Some(density) = self.density.filter(|density| *density < 0.0) {
+            return Err(STACError::Other(format!(
+                "pc:density must not be negative, got {density}"
+            )));
+        }
+        let schemas = self.schemas.as_deref().unwrap_or_default();
+        for (index, schema) in schemas.iter().enumerate() {
+            if schema.size == 0 {
+                return Err(STACError::Other(format!(
+                    "pc:schemas \"{}\" must have a size greater than 0",
+                    schema.name
+                )));
+            }
+            if schemas[..index]
+                .iter()
+                .any(|other| other.name == schema.name)
+            {
+                return Err(STACError::Other(format!(
+                    "pc:schemas contains \"{}\" more than once",
+                    schema.name
+                )));
+            }
+        }
+        if self.schemas.is_some() {
+            for statistics in self.statistics.iter().flatten() {
+                if !schemas.iter().any(|schema| schema.name == statistics.name) {
+                    return Err(STACError::Other(format!(
+                        "pc:statistics \"{}\" is not in pc:schemas",
+                        statistics.name
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Extension for Pointcloud {
+    const SCHEMA_URI: &'static str = SCHEMA_URI;
+    const PREFIX: &'static str = PREFIX;
+
+    fn validate(&self) -> STACResult<()> {
+        Pointcloud::validate(self)
+    }
+}
+
+impl ItemProperties {
+    /// Gets the extension's fields from the properties.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::JSONParse`] if a field does not match the extension's schema.
+    pub fn pointcloud(&self) -> STACResult<Pointcloud> {
+        self.get_extension()
+    }
+
+    /// Sets the extension's fields in the properties, removing fields that are `None`. This does not
+    /// update the `stac_extensions` of the Item the properties belong to; use
+    /// [`Item::set_pointcloud`] for that.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::Other`] if the fields are not valid, see [`Pointcloud::validate`].
+    pub fn set_pointcloud(&mut self, fields: &Pointcloud) -> STACResult<()> {
+        self.set_extension(fields)
+    }
+}
+
+impl Item {
+    /// Gets the extension's fields from the Item properties.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::JSONParse`] if a field does not match the extension's schema.
+    pub fn pointcloud(&self) -> STACResult<Pointcloud> {
+        self.get_extension()
+    }
+
+    /// Sets the extension's fields in the Item properties, removing fields that are `None`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::Other`] if the fields are not valid, see [`Pointcloud::validate`].
+    pub fn set_pointcloud(&mut self, fields: &Pointcloud) -> STACResult<()> {
+        self.set_extension(fields)
+    }
+}
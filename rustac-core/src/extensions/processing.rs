@@ -0,0 +1,98 @@
+//! Implementation of the [Processing Extension](https://github.com/stac-extensions/processing).
+//!
+//! ```
+//! use rustac_core::extensions::processing::Processing;
+//! use serde_json::json;
+//!
+//! let fields: Processing = serde_json::from_value(json!({
+//!     "processing:level": "L2",
+//!     "processing:facility": "Copernicus S2 Processing and Archiving Facility",
+//!     "processing:software": {"Sentinel-2 MSI L2A Processor": "02.14"}
+//! })).unwrap();
+//!
+//! assert_eq!(fields.level, Some(String::from("L2")));
+//! assert_eq!(fields.software.unwrap()["Sentinel-2 MSI L2A Processor"], "02.14");
+//! ```
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use super::{Extension, Extensions};
+use crate::error::STACResult;
+use crate::{Item, ItemProperties};
+
+/// Schema URI of the version of the extension implemented by this module.
+pub const SCHEMA_URI: &str = "https://stac-extensions.github.io/processing/v1.1.0/schema.json";
+
+/// Prefix of all fields defined by the extension.
+pub const PREFIX: &str = "processing:";
+
+/// Fields of the extension that may be used in Item properties.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct Processing {
+    /// The name of the processing level, e.g. "L1" or "L2A".
+    #[serde(rename = "processing:level")]
+    pub level: Option<String>,
+
+    /// Lineage information, describing how the data was produced, in free text.
+    #[serde(rename = "processing:lineage")]
+    pub lineage: Option<String>,
+
+    /// The name of the facility that produced the data.
+    #[serde(rename = "processing:facility")]
+    pub facility: Option<String>,
+
+    /// The software used to produce the data, as versions by software name.
+    #[serde(rename = "processing:software")]
+    pub software: Option<HashMap<String, String>>,
+}
+
+impl Extension for Processing {
+    const SCHEMA_URI: &'static str = SCHEMA_URI;
+    const PREFIX: &'static str = PREFIX;
+}
+
+impl ItemProperties {
+    /// Gets the extension's fields from the properties.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::JSONParse`](crate::error::STACError::JSONParse) if a field does not
+    /// match the extension's schema.
+    pub fn processing(&self) -> STACResult<Processing> {
+        self.get_extension()
+    }
+
+    /// Sets the extension's fields in the properties, removing fields that are `None`. This does not
+    /// update the `stac_extensions` of the Item the properties belong to; use
+    /// [`Item::set_processing`] for that.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::JSONParse`](crate::error::STACError::JSONParse) if the fields cannot be
+    /// serialized.
+    pub fn set_processing(&mut self, fields: &Processing) -> STACResult<()> {
+        self.set_extension(fields)
+    }
+}
+
+impl Item {
+    /// Gets the extension's fields from the Item properties.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::JSONParse`](crate::error::STACError::JSONParse) if a field does not
+    /// match the extension's schema.
+    pub fn processing(&self) -> STACResult<Processing> {
+        self.get_extension()
+    }
+
+    /// Sets the extension's fields in the Item properties, removing fields that are `None`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::JSONParse`](crate::error::STACError::JSONParse) if the fields cannot be
+    /// serialized.
+    pub fn set_processing(&mut self, fields: &Processing) -> STACResult<()> {
+        self.set_extension(fields)
+    }
+}
@@ -0,0 +1,352 @@
+//! Implementation of the [Projection Extension](https://github.com/stac-extensions/projection).
+//!
+//! ```
+//! use rustac_core::extensions::proj::Proj;
+//! use serde_json::json;
+//!
+//! let fields: Proj = serde_json::from_value(json!({
+//!     "proj:epsg": 32659,
+//!     "proj:shape": [5558, 9559],
+//!     "proj:transform": [0.5, 0.0, 712710.0, 0.0, -0.5, 151406.0, 0.0, 0.0, 1.0]
+//! })).unwrap();
+//!
+//! let transform = fields.transform().unwrap().unwrap();
+//! assert_eq!(transform.to_world(10.0, 20.0), (712715.0, 151396.0));
+//! assert_eq!(transform.to_pixel(712715.0, 151396.0).unwrap(), (10.0, 20.0));
+//!
+//! let bbox = fields.native_bbox().unwrap().unwrap();
+//! assert_eq!(bbox, vec![712710.0, 148627.0, 717489.5, 151406.0]);
+//! ```
+use geojson::{Bbox, Geometry};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use super::{deserialize_nullable, Extension, Extensions};
+use crate::error::{STACError, STACResult};
+use crate::{Asset, Item};
+
+/// Schema URI of the version of the extension implemented by this module.
+pub const SCHEMA_URI: &str = "https://stac-extensions.github.io/projection/v1.0.0/schema.json";
+
+/// Prefix of all fields defined by the extension.
+pub const PREFIX: &str = "proj:";
+
+/// Fields of the extension that may be used in Item properties or on an Asset.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct Proj {
+    /// EPSG code of the datasource. `Some(None)` is an explicit `"proj:epsg": null`, which means the
+    /// data has no EPSG code, while `None` means the field is not set.
+    #[serde(
+        rename = "proj:epsg",
+        default,
+        deserialize_with = "deserialize_nullable"
+    )]
+    pub epsg: Option<Option<u32>>,
+
+    /// WKT2 string representing the Coordinate Reference System (CRS) of the data.
+    #[serde(rename = "proj:wkt2")]
+    pub wkt2: Option<String>,
+
+    /// PROJJSON object representing the Coordinate Reference System (CRS) of the data.
+    #[serde(rename = "proj:projjson")]
+    pub projjson: Option<Map<String, Value>>,
+
+    /// Footprint of the data in the asset's native CRS.
+    #[serde(rename = "proj:geometry")]
+    pub geometry: Option<Geometry>,
+
+    /// Bounding box of the data in the asset's native CRS.
+    #[serde(rename = "proj:bbox")]
+    pub bbox: Option<Bbox>,
+
+    /// Coordinates representing the centroid of the Item, in latitude and longitude.
+    #[serde(rename = "proj:centroid")]
+    pub centroid: Option<Centroid>,
+
+    /// Number of pixels in Y and X directions for the default grid.
+    #[serde(rename = "proj:shape")]
+    pub shape: Option<Vec<usize>>,
+
+    /// The affine transformation coefficients for the default grid.
+    #[serde(rename = "proj:transform")]
+    pub transform: Option<Vec<f64>>,
+}
+
+/// Centroid of an Item in latitude and longitude.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct Centroid {
+    /// Latitude of the centroid.
+    pub lat: f64,
+
+    /// Longitude of the centroid.
+    pub lon: f64,
+}
+
+impl Proj {
+    /// Gets `proj:transform` as a [`Transform`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::Other`] if the transform is not valid, see [`Transform::from_slice`].
+    pub fn transform(&self) -> STACResult<Option<Transform>> {
+        self.transform
+            .as_deref()
+            .map(Transform::from_slice)
+            .transpose()
+    }
+
+    /// Sets `proj:transform` from a [`Transform`].
+    pub fn set_transform(&mut self, transform: &Transform) {
+        self.transform = Some(transform.to_vec());
+    }
+
+    /// Computes the bounding box of the default grid in the native CRS from `proj:shape` and
+    /// `proj:transform`, or `None` if either is missing.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::Other`] if the shape or transform is not valid.
+    pub fn native_bbox(&self) -> STACResult<Option<Bbox>> {
+        let Some(corners) = self.corners()? else {
+            return Ok(None);
+        };
+        let mut bbox = vec![
+            f64::INFINITY,
+            f64::INFINITY,
+            f64::NEG_INFINITY,
+            f64::NEG_INFINITY,
+        ];
+        for (x, y) in corners {
+            bbox[0] = bbox[0].min(x);
+            bbox[1] = bbox[1].min(y);
+            bbox[2] = bbox[2].max(x);
+            bbox[3] = bbox[3].max(y);
+        }
+        Ok(Some(bbox))
+    }
+
+    /// Computes the footprint of the default grid in the native CRS from `proj:shape` and
+    /// `proj:transform`, or `None` if either is missing. Unlike [`Proj::native_bbox`] this follows the
+    /// grid if the transform includes a rotation.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::Other`] if the shape or transform is not valid.
+    pub fn native_footprint(&self) -> STACResult<Option<Geometry>> {
+        let Some(corners) = self.corners()? else {
+            return Ok(None);
+        };
+        let mut ring: Vec<Vec<f64>> = corners.iter().map(|(x, y)| vec![*x, *y]).collect();
+        ring.push(ring[0].clone());
+        Ok(Some(Geometry::new(geojson::Value::Polygon(vec![ring]))))
+    }
+
+    fn corners(&self) -> STACResult<Option<[(f64, f64); 4]>> {
+        let (Some(shape), Some(transform)) = (&self.shape, self.transform()?) else {
+            return Ok(None);
+        };
+        let [rows, cols] = shape[..] else {
+            return Err(STACError::Other(format!(
+                "proj:shape must have 2 elements, got {}",
+                shape.len()
+            )));
+        };
+        #[allow(clippy::cast_precision_loss)]
+        let (rows, cols) = (rows as f64, cols as f64);
+        Ok(Some([
+            transform.to_world(0.0, 0.0),
+            transform.to_world(cols, 0.0),
+            transform.to_world(cols, rows),
+            transform.to_world(0.0, rows),
+        ]))
+    }
+}
+
+/// An affine transformation from pixel coordinates (column, row) to coordinates in the native CRS:
+///
+/// ```text
+/// x = a * col + b * row + c
+/// y = d * col + e * row + f
+/// ```
+///
+/// The coefficients are in the same order as `proj:transform` and the Python `affine` package. Use
+/// [`Transform::from_gdal`] and [`Transform::to_gdal`] to convert from and to GDAL's geotransform order.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Transform {
+    /// Pixel width, or the x scale factor.
+    pub a: f64,
+
+    /// Row rotation.
+    pub b: f64,
+
+    /// X coordinate of the upper-left corner of the upper-left pixel.
+    pub c: f64,
+
+    /// Column rotation.
+    pub d: f64,
+
+    /// Pixel height, or the y scale factor. This is usually negative.
+    pub e: f64,
+
+    /// Y coordinate of the upper-left corner of the upper-left pixel.
+    pub f: f64,
+}
+
+#[allow(clippy::many_single_char_names)]
+impl Transform {
+    /// Creates a transform from the 6 or 9 values of `proj:transform`. If 9 values are given, the last
+    /// row must be `[0, 0, 1]`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::Other`] if the slice has the wrong length or the last row is not
+    /// `[0, 0, 1]`.
+    pub fn from_slice(values: &[f64]) -> STACResult<Transform> {
+        match *values {
+            [a, b, c, d, e, f] => Ok(Transform { a, b, c, d, e, f }),
+            [a, b, c, d, e, f, g, h, i] => {
+                #[allow(clippy::float_cmp)]
+                if g != 0.0 || h != 0.0 || i != 1.0 {
+                    return Err(STACError::Other(format!(
+                        "The last row of proj:transform must be [0, 0, 1], got [{g}, {h}, {i}]"
+                    )));
+                }
+                Ok(Transform { a, b, c, d, e, f })
+            }
+            _ => Err(STACError::Other(format!(
+                "proj:transform must have 6 or 9 elements, got {}",
+                values.len()
+            ))),
+        }
+    }
+
+    /// Creates a transform from a GDAL geotransform, `[c, a, b, f, d, e]`.
+    #[must_use]
+    pub fn from_gdal(geotransform: [f64; 6]) -> Transform {
+        let [c, a, b, f, d, e] = geotransform;
+        Transform { a, b, c, d, e, f }
+    }
+
+    /// Gets the transform as a GDAL geotransform.
+    #[must_use]
+    pub fn to_gdal(&self) -> [f64; 6] {
+        [self.c, self.a, self.b, self.f, self.d, self.e]
+    }
+
+    /// Gets the 9 values of the full transformation matrix, as used in `proj:transform`.
+    #[must_use]
+    pub fn to_vec(&self) -> Vec<f64> {
+        vec![
+            self.a, self.b, self.c, self.d, self.e, self.f, 0.0, 0.0, 1.0,
+        ]
+    }
+
+    /// Converts pixel coordinates to coordinates in the native CRS.
+    #[must_use]
+    pub fn to_world(&self, col: f64, row: f64) -> (f64, f64) {
+        (
+            self.a * col + self.b * row + self.c,
+            self.d * col + self.e * row + self.f,
+        )
+    }
+
+    /// Converts coordinates in the native CRS to (fractional) pixel coordinates.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::Other`] if the transform cannot be inverted.
+    pub fn to_pixel(&self, x: f64, y: f64) -> STACResult<(f64, f64)> {
+        Ok(self.inverse()?.to_world(x, y))
+    }
+
+    /// Gets the inverse transform, which converts coordinates in the native CRS to pixel coordinates.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::Other`] if the transform cannot be inverted.
+    pub fn inverse(&self) -> STACResult<Transform> {
+        let determinant = self.a * self.e - self.b * self.d;
+        if determinant.abs() < f64::EPSILON {
+            return Err(STACError::Other(String::from(
+                "proj:transform is degenerate and cannot be inverted",
+            )));
+        }
+        let a = self.e / determinant;
+        let b = -self.b / determinant;
+        let d = -self.d / determinant;
+        let e = self.a / determinant;
+        Ok(Transform {
+            a,
+            b,
+            c: -self.c * a - self.f * b,
+            d,
+            e,
+            f: -self.c * d - self.f * e,
+        })
+    }
+}
+
+impl Extension for Proj {
+    const SCHEMA_URI: &'static str = SCHEMA_URI;
+    const PREFIX: &'static str = PREFIX;
+
+    fn null_fields(&self) -> Vec<&'static str> {
+        if self.epsg == Some(None) {
+            vec!["proj:epsg"]
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+impl Item {
+    /// Gets the extension's fields from the Item properties.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::JSONParse`] if a field does not match the extension's schema.
+    pub fn proj(&self) -> STACResult<Proj> {
+        self.get_extension()
+    }
+
+    /// Sets the extension's fields in the Item properties, removing fields that are `None`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::JSONParse`] if the fields cannot be serialized.
+    pub fn set_proj(&mut self, fields: &Proj) -> STACResult<()> {
+        self.set_extension(fields)
+    }
+
+    /// Sets the extension's fields on one of the Item's assets.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::Other`] if the Item has no asset with the given key.
+    pub fn set_asset_proj(&mut self, key: &str, fields: &Proj) -> STACResult<()> {
+        self.set_asset_extension(key, fields)
+    }
+}
+
+impl Asset {
+    /// Gets the extension's fields from the Asset. Only the Asset's own fields are returned; fields
+    /// that the Asset does not set are not filled in from the Item properties.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::JSONParse`] if a field does not match the extension's schema.
+    pub fn proj(&self) -> STACResult<Proj> {
+        self.get_extension()
+    }
+
+    /// Sets the extension's fields on the Asset, removing fields that are `None`. This does not
+    /// update the `stac_extensions` of the Item the Asset belongs to; use [`Item::set_asset_proj`]
+    /// for that.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::JSONParse`] if the fields cannot be serialized.
+    pub fn set_proj(&mut self, fields: &Proj) -> STACResult<()> {
+        self.set_extension(fields)
+    }
+}
@@ -0,0 +1,416 @@
+//! Implementation of the [Raster Extension](https://github.com/stac-extensions/raster).
+//!
+//! ```
+//! use rustac_core::extensions::raster::{Band, DataType, NoData};
+//! use serde_json::json;
+//!
+//! let band: Band = serde_json::from_value(json!({
+//!     "data_type": "uint16",
+//!     "nodata": 0,
+//!     "scale": 0.0001,
+//!     "offset": -0.1,
+//!     "unit": "reflectance"
+//! })).unwrap();
+//!
+//! assert_eq!(band.data_type, Some(DataType::UInt16));
+//! assert_eq!(band.nodata, Some(NoData::Value(0.0)));
+//! assert!(band.is_nodata(0.0));
+//! assert!((band.apply_scale(2000.0) - 0.1).abs() < 1e-9);
+//! ```
+use serde::de::{self, Deserializer};
+use serde::ser::Serializer;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use super::{Extension, Extensions};
+use crate::error::STACResult;
+use crate::{Asset, Item};
+
+/// Schema URI of the version of the extension implemented by this module.
+pub const SCHEMA_URI: &str = "https://stac-extensions.github.io/raster/v1.0.0/schema.json";
+
+/// Prefix of all fields defined by the extension.
+pub const PREFIX: &str = "raster:";
+
+/// Fields of the extension that may be used on an Asset.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct Raster {
+    /// An array of available bands, in the order they appear in the file.
+    #[serde(rename = "raster:bands")]
+    pub bands: Option<Vec<Band>>,
+}
+
+/// A band in a raster file, as described by the [Raster Band Object](https://github.com/stac-extensions/raster#raster-band-object).
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct Band {
+    /// Pixel values used to identify pixels that are nodata in the band.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nodata: Option<NoData>,
+
+    /// Whether a pixel value should be assumed to represent a sampling over the region of the pixel
+    /// or a point sample at the center of the pixel.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sampling: Option<Sampling>,
+
+    /// The data type of the pixels in the band.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data_type: Option<DataType>,
+
+    /// The actual number of bits used for this band. Normally only present when the number of bits
+    /// is non-standard for the data type, such as when a 1 bit TIFF is represented as byte.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bits_per_sample: Option<u32>,
+
+    /// Average spatial resolution (in meters) of the pixels in the band.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spatial_resolution: Option<f64>,
+
+    /// Statistics of all pixels in the band.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub statistics: Option<Statistics>,
+
+    /// Unit denomination of the pixel value.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unit: Option<String>,
+
+    /// Multiplicator factor of the pixel value to transform into the value (i.e. translate digital
+    /// number to reflectance).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scale: Option<f64>,
+
+    /// Number to be added to the pixel value (after scaling) to transform into the value (i.e.
+    /// translate digital number to reflectance).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<f64>,
+
+    /// Histogram distribution information of the pixels values in the band.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub histogram: Option<Histogram>,
+
+    /// Additional fields not covered by the extension.
+    #[serde(flatten)]
+    pub extra_fields: Map<String, Value>,
+}
+
+impl Band {
+    /// Converts a raw pixel value to its actual value by applying `scale` and then `offset`. Missing
+    /// values default to a scale of 1 and an offset of 0.
+    #[must_use]
+    pub fn apply_scale(&self, raw: f64) -> f64 {
+        raw * self.scale.unwrap_or(1.0) + self.offset.unwrap_or(0.0)
+    }
+
+    /// Converts an actual value back to a raw pixel value. This is the inverse of
+    /// [`Band::apply_scale`].
+    #[must_use]
+    pub fn remove_scale(&self, value: f64) -> f64 {
+        (value - self.offset.unwrap_or(0.0)) / self.scale.unwrap_or(1.0)
+    }
+
+    /// Applies `scale` and `offset` to the statistics of the band, which are given in raw pixel
+    /// values.
+    #[must_use]
+    pub fn scaled_statistics(&self) -> Option<Statistics> {
+        let statistics = self.statistics.as_ref()?;
+        let scale = self.scale.unwrap_or(1.0);
+        let mut scaled = Statistics {
+            mean: statistics.mean.map(|value| self.apply_scale(value)),
+            minimum: statistics.minimum.map(|value| self.apply_scale(value)),
+            maximum: statistics.maximum.map(|value| self.apply_scale(value)),
+            stddev: statistics.stddev.map(|value| value * scale.abs()),
+            valid_percent: statistics.valid_percent,
+        };
+        // A negative scale swaps the minimum and maximum
+        if scale < 0.0 {
+            std::mem::swap(&mut scaled.minimum, &mut scaled.maximum);
+        }
+        Some(scaled)
+    }
+
+    /// Returns `true` if the raw pixel value is the band's nodata value.
+    #[must_use]
+    pub fn is_nodata(&self, raw: f64) -> bool {
+        self.nodata.is_some_and(|nodata| nodata.matches(raw))
+    }
+}
+
+/// A nodata value, which may be a number or one of the special values `nan`, `inf` and `-inf`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NoData {
+    /// A numeric nodata value.
+    Value(f64),
+
+    /// Not a number (`"nan"`).
+    NaN,
+
+    /// Positive infinity (`"inf"`).
+    Inf,
+
+    /// Negative infinity (`"-inf"`).
+    NegInf,
+}
+
+impl NoData {
+    /// Gets the nodata value as a float.
+    #[must_use]
+    pub fn as_f64(&self) -> f64 {
+        match self {
+            NoData::Value(value) => *value,
+            NoData::NaN => f64::NAN,
+            NoData::Inf => f64::INFINITY,
+            NoData::NegInf => f64::NEG_INFINITY,
+        }
+    }
+
+    /// Returns `true` if the value is this nodata value. Unlike a plain comparison, `NaN` matches
+    /// `NaN`.
+    #[must_use]
+    #[allow(clippy::float_cmp)]
+    pub fn matches(&self, value: f64) -> bool {
+        match self {
+            NoData::NaN => value.is_nan(),
+            _ => self.as_f64() == value,
+        }
+    }
+}
+
+impl Serialize for NoData {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            NoData::Value(value) => serializer.serialize_f64(*value),
+            NoData::NaN => serializer.serialize_str("nan"),
+            NoData::Inf => serializer.serialize_str("inf"),
+            NoData::NegInf => serializer.serialize_str("-inf"),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for NoData {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<NoData, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            Number(f64),
+            String(String),
+        }
+
+        match Repr::deserialize(deserializer)? {
+            Repr::Number(value) => Ok(NoData::Value(value)),
+            Repr::String(value) => match value.as_str() {
+                "nan" => Ok(NoData::NaN),
+                "inf" => Ok(NoData::Inf),
+                "-inf" => Ok(NoData::NegInf),
+                _ => Err(de::Error::invalid_value(
+                    de::Unexpected::Str(&value),
+                    &"a number, \"nan\", \"inf\" or \"-inf\"",
+                )),
+            },
+        }
+    }
+}
+
+/// How a pixel value relates to the region it covers.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum Sampling {
+    /// The value represents the whole area of the pixel.
+    Area,
+
+    /// The value is a point sample at the center of the pixel.
+    Point,
+}
+
+/// The data type of the pixels in a band.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum DataType {
+    /// 8-bit integer
+    Int8,
+
+    /// 16-bit integer
+    Int16,
+
+    /// 32-bit integer
+    Int32,
+
+    /// 64-bit integer
+    Int64,
+
+    /// Unsigned 8-bit integer
+    #[serde(rename = "uint8")]
+    UInt8,
+
+    /// Unsigned 16-bit integer
+    #[serde(rename = "uint16")]
+    UInt16,
+
+    /// Unsigned 32-bit integer
+    #[serde(rename = "uint32")]
+    UInt32,
+
+    /// Unsigned 64-bit integer
+    #[serde(rename = "uint64")]
+    UInt64,
+
+    /// 16-bit float
+    Float16,
+
+    /// 32-bit float
+    Float32,
+
+    /// 64-bit float
+    Float64,
+
+    /// 16-bit complex integer
+    CInt16,
+
+    /// 32-bit complex integer
+    CInt32,
+
+    /// 32-bit complex float
+    CFloat32,
+
+    /// 64-bit complex float
+    CFloat64,
+
+    /// Other data type than the ones listed above (e.g. boolean, string, higher precision numbers)
+    Other,
+}
+
+impl DataType {
+    /// Gets the number of bits used to store a value of this type, or `None` for [`DataType::Other`].
+    #[must_use]
+    pub fn bits(&self) -> Option<u32> {
+        match self {
+            DataType::Int8 | DataType::UInt8 => Some(8),
+            DataType::Int16 | DataType::UInt16 | DataType::Float16 => Some(16),
+            DataType::Int32 | DataType::UInt32 | DataType::Float32 | DataType::CInt16 => Some(32),
+            DataType::Int64
+            | DataType::UInt64
+            | DataType::Float64
+            | DataType::CInt32
+            | DataType::CFloat32 => Some(64),
+            DataType::CFloat64 => Some(128),
+            DataType::Other => None,
+        }
+    }
+
+    /// Returns `true` for floating point types, including complex floats.
+    #[must_use]
+    pub fn is_float(&self) -> bool {
+        matches!(
+            self,
+            DataType::Float16
+                | DataType::Float32
+                | DataType::Float64
+                | DataType::CFloat32
+                | DataType::CFloat64
+        )
+    }
+
+    /// Returns `true` for complex types.
+    #[must_use]
+    pub fn is_complex(&self) -> bool {
+        matches!(
+            self,
+            DataType::CInt16 | DataType::CInt32 | DataType::CFloat32 | DataType::CFloat64
+        )
+    }
+}
+
+/// Statistics of all pixels in a band.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct Statistics {
+    /// Mean value of all the pixels in the band.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mean: Option<f64>,
+
+    /// Minimum value of all the pixels in the band.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minimum: Option<f64>,
+
+    /// Maximum value of all the pixels in the band.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maximum: Option<f64>,
+
+    /// Standard deviation value of all the pixels in the band.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stddev: Option<f64>,
+
+    /// Percentage of valid (not nodata) pixels.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub valid_percent: Option<f64>,
+}
+
+/// Histogram distribution of the pixel values in a band, in the same format as `gdalinfo -json`.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct Histogram {
+    /// Number of buckets.
+    pub count: usize,
+
+    /// Minimum value of the histogram.
+    pub min: f64,
+
+    /// Maximum value of the histogram.
+    pub max: f64,
+
+    /// Number of pixels in each bucket.
+    pub buckets: Vec<u64>,
+}
+
+impl Histogram {
+    /// Gets the range of values covered by the bucket at `index`, or `None` if there is no such
+    /// bucket.
+    #[must_use]
+    pub fn bucket_range(&self, index: usize) -> Option<(f64, f64)> {
+        if index >= self.count || self.count == 0 {
+            return None;
+        }
+        #[allow(clippy::cast_precision_loss)]
+        let width = (self.max - self.min) / self.count as f64;
+        #[allow(clippy::cast_precision_loss)]
+        let start = self.min + width * index as f64;
+        Some((start, start + width))
+    }
+}
+
+impl Extension for Raster {
+    const SCHEMA_URI: &'static str = SCHEMA_URI;
+    const PREFIX: &'static str = PREFIX;
+}
+
+impl Item {
+    /// Sets the extension's fields on one of the Item's assets.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::Other`](crate::error::STACError::Other) if the Item has no asset with the
+    /// given key.
+    pub fn set_asset_raster(&mut self, key: &str, fields: &Raster) -> STACResult<()> {
+        self.set_asset_extension(key, fields)
+    }
+}
+
+impl Asset {
+    /// Gets the extension's fields from the Asset.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::JSONParse`](crate::error::STACError::JSONParse) if a field does not match
+    /// the extension's schema.
+    pub fn raster(&self) -> STACResult<Raster> {
+        self.get_extension()
+    }
+
+    /// Sets the extension's fields on the Asset, removing fields that are `None`. This does not
+    /// update the `stac_extensions` of the Item the Asset belongs to; use
+    /// [`Item::set_asset_raster`] for that.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::JSONParse`](crate::error::STACError::JSONParse) if the fields cannot be
+    /// serialized.
+    pub fn set_raster(&mut self, fields: &Raster) -> STACResult<()> {
+        self.set_extension(fields)
+    }
+}
@@ -0,0 +1,266 @@
+//! Implementation of the [SAR Extension](https://github.com/stac-extensions/sar).
+//!
+//! ```
+//! use rustac_core::extensions::sar::{FrequencyBand, Polarization, Sar};
+//! use serde_json::json;
+//!
+//! let fields: Sar = serde_json::from_value(json!({
+//!     "sar:instrument_mode": "IW",
+//!     "sar:frequency_band": "C",
+//!     "sar:polarizations": ["VV", "VH"],
+//!     "sar:product_type": "GRD"
+//! })).unwrap();
+//!
+//! assert_eq!(fields.frequency_band, Some(FrequencyBand::C));
+//! assert_eq!(fields.polarizations, Some(vec![Polarization::VV, Polarization::VH]));
+//! assert!(fields.validate().is_ok());
+//!
+//! // Unknown values are rejected when deserializing
+//! assert!(serde_json::from_value::<Sar>(json!({"sar:frequency_band": "Q"})).is_err());
+//! ```
+use serde::{Deserialize, Serialize};
+
+use super::{Extension, Extensions};
+use crate::error::{STACError, STACResult};
+use crate::{Asset, Item};
+
+/// Schema URI of the version of the extension implemented by this module.
+pub const SCHEMA_URI: &str = "https://stac-extensions.github.io/sar/v1.0.0/schema.json";
+
+/// Prefix of all fields defined by the extension.
+pub const PREFIX: &str = "sar:";
+
+/// Fields of the extension that may be used in Item properties or on an Asset.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct Sar {
+    /// The name of the sensor acquisition mode that is commonly used, e.g. "IW" for Sentinel-1.
+    #[serde(rename = "sar:instrument_mode")]
+    pub instrument_mode: Option<String>,
+
+    /// The common name for the frequency band to make it easier to search for bands across instruments.
+    #[serde(rename = "sar:frequency_band")]
+    pub frequency_band: Option<FrequencyBand>,
+
+    /// The center frequency of the instrument, in gigahertz (GHz).
+    #[serde(rename = "sar:center_frequency")]
+    pub center_frequency: Option<f64>,
+
+    /// Any combination of polarizations.
+    #[serde(rename = "sar:polarizations")]
+    pub polarizations: Option<Vec<Polarization>>,
+
+    /// The product type, for example SSC, MGD, or SGC.
+    #[serde(rename = "sar:product_type")]
+    pub product_type: Option<String>,
+
+    /// The range resolution, which is the maximum ability to distinguish two adjacent targets
+    /// perpendicular to the flight path, in meters (m).
+    #[serde(rename = "sar:resolution_range")]
+    pub resolution_range: Option<f64>,
+
+    /// The azimuth resolution, which is the maximum ability to distinguish two adjacent targets
+    /// parallel to the flight path, in meters (m).
+    #[serde(rename = "sar:resolution_azimuth")]
+    pub resolution_azimuth: Option<f64>,
+
+    /// The range pixel spacing, which is the distance between adjacent pixels perpendicular to the
+    /// flight path, in meters (m).
+    #[serde(rename = "sar:pixel_spacing_range")]
+    pub pixel_spacing_range: Option<f64>,
+
+    /// The azimuth pixel spacing, which is the distance between adjacent pixels parallel to the
+    /// flight path, in meters (m).
+    #[serde(rename = "sar:pixel_spacing_azimuth")]
+    pub pixel_spacing_azimuth: Option<f64>,
+
+    /// Number of range looks, which is the number of groups of signal samples (looks) perpendicular
+    /// to the flight path.
+    #[serde(rename = "sar:looks_range")]
+    pub looks_range: Option<u32>,
+
+    /// Number of azimuth looks, which is the number of groups of signal samples (looks) parallel to
+    /// the flight path.
+    #[serde(rename = "sar:looks_azimuth")]
+    pub looks_azimuth: Option<u32>,
+
+    /// The equivalent number of looks (ENL).
+    #[serde(rename = "sar:looks_equivalent_number")]
+    pub looks_equivalent_number: Option<f64>,
+
+    /// Antenna pointing direction relative to the flight trajectory of the satellite.
+    #[serde(rename = "sar:observation_direction")]
+    pub observation_direction: Option<ObservationDirection>,
+}
+
+impl Sar {
+    /// Checks the values that cannot be enforced by the types of the fields: polarizations must be
+    /// unique and frequencies, resolutions, pixel spacings and looks must be positive.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::Other`] describing the first invalid field.
+    pub fn validate(&self) -> STACResult<()> {
+        if let Some(polarizations) = &self.polarizations {
+            for (index, polarization) in polarizations.iter().enumerate() {
+                if polarizations[..index].contains(polarization) {
+                    return Err(STACError::Other(format!(
+                        "sar:polarizations contains {polarization:?} more than once"
+                    )));
+                }
+            }
+        }
+        for (name, value) in [
+            ("sar:center_frequency", self.center_frequency),
+            ("sar:resolution_range", self.resolution_range),
+            ("sar:resolution_azimuth", self.resolution_azimuth),
+            ("sar:pixel_spacing_range", self.pixel_spacing_range),
+            ("sar:pixel_spacing_azimuth", self.pixel_spacing_azimuth),
+            ("sar:looks_equivalent_number", self.looks_equivalent_number),
+            ("sar:looks_range", self.looks_range.map(f64::from)),
+            ("sar:looks_azimuth", self.looks_azimuth.map(f64::from)),
+        ] {
+            if let Some(value) = value {
+                if value <= 0.0 {
+                    return Err(STACError::Other(format!(
+                        "{name} must be greater than 0, got {value}"
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The common names of radar frequency bands.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FrequencyBand {
+    /// P band, 0.25 - 0.5 GHz
+    P,
+
+    /// L band, 1 - 2 GHz
+    L,
+
+    /// S band, 2 - 4 GHz
+    S,
+
+    /// C band, 4 - 8 GHz
+    C,
+
+    /// X band, 8 - 12 GHz
+    X,
+
+    /// Ku band, 12 - 18 GHz
+    Ku,
+
+    /// K band, 18 - 26.5 GHz
+    K,
+
+    /// Ka band, 26.5 - 40 GHz
+    Ka,
+}
+
+impl FrequencyBand {
+    /// Gets the range of frequencies covered by the band, in gigahertz (GHz).
+    #[must_use]
+    pub fn frequency_range(&self) -> (f64, f64) {
+        match self {
+            FrequencyBand::P => (0.25, 0.5),
+            FrequencyBand::L => (1.0, 2.0),
+            FrequencyBand::S => (2.0, 4.0),
+            FrequencyBand::C => (4.0, 8.0),
+            FrequencyBand::X => (8.0, 12.0),
+            FrequencyBand::Ku => (12.0, 18.0),
+            FrequencyBand::K => (18.0, 26.5),
+            FrequencyBand::Ka => (26.5, 40.0),
+        }
+    }
+}
+
+/// A polarization, given as transmit and receive polarization.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Polarization {
+    /// Horizontal transmit, horizontal receive
+    HH,
+
+    /// Vertical transmit, vertical receive
+    VV,
+
+    /// Horizontal transmit, vertical receive
+    HV,
+
+    /// Vertical transmit, horizontal receive
+    VH,
+}
+
+/// Antenna pointing direction relative to the flight trajectory.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum ObservationDirection {
+    /// Left-looking
+    Left,
+
+    /// Right-looking
+    Right,
+}
+
+impl Extension for Sar {
+    const SCHEMA_URI: &'static str = SCHEMA_URI;
+    const PREFIX: &'static str = PREFIX;
+
+    fn validate(&self) -> STACResult<()> {
+        Sar::validate(self)
+    }
+}
+
+impl Item {
+    /// Gets the extension's fields from the Item properties.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::JSONParse`] if a field does not match the extension's schema, including
+    /// values that are not one of the enumerated values.
+    pub fn sar(&self) -> STACResult<Sar> {
+        self.get_extension()
+    }
+
+    /// Sets the extension's fields in the Item properties, removing fields that are `None`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::Other`] if the fields are not valid, see [`Sar::validate`].
+    pub fn set_sar(&mut self, fields: &Sar) -> STACResult<()> {
+        self.set_extension(fields)
+    }
+
+    /// Sets the extension's fields on one of the Item's assets.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::Other`] if the Item has no asset with the given key or if the fields are
+    /// not valid.
+    pub fn set_asset_sar(&mut self, key: &str, fields: &Sar) -> STACResult<()> {
+        self.set_asset_extension(key, fields)
+    }
+}
+
+impl Asset {
+    /// Gets the extension's fields from the Asset.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::JSONParse`] if a field does not match the extension's schema.
+    pub fn sar(&self) -> STACResult<Sar> {
+        self.get_extension()
+    }
+
+    /// Sets the extension's fields on the Asset, removing fields that are `None`. This does not
+    /// update the `stac_extensions` of the Item the Asset belongs to; use [`Item::set_asset_sar`] for
+    /// that.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::Other`] if the fields are not valid, see [`Sar::validate`].
+    pub fn set_sar(&mut self, fields: &Sar) -> STACResult<()> {
+        self.set_extension(fields)
+    }
+}
@@ -0,0 +1,172 @@
+//! Implementation of the [Satellite Extension](https://github.com/stac-extensions/sat).
+//!
+//! ```
+//! use rustac_core::extensions::sat::{OrbitState, Sat};
+//! use serde_json::json;
+//!
+//! let fields: Sat = serde_json::from_value(json!({
+//!     "sat:platform_international_designator": "2014-016A",
+//!     "sat:orbit_state": "ascending",
+//!     "sat:absolute_orbit": 36018,
+//!     "sat:relative_orbit": 15,
+//!     "sat:anx_datetime": "2021-01-01T17:24:05.521Z"
+//! })).unwrap();
+//!
+//! assert_eq!(fields.orbit_state, Some(OrbitState::Ascending));
+//! assert!(fields.validate().is_ok());
+//! ```
+use chrono::{DateTime, FixedOffset};
+use serde::{Deserialize, Serialize};
+
+use super::{Extension, Extensions};
+use crate::error::{STACError, STACResult};
+use crate::{Asset, Item};
+
+/// Schema URI of the version of the extension implemented by this module.
+pub const SCHEMA_URI: &str = "https://stac-extensions.github.io/sat/v1.0.0/schema.json";
+
+/// Prefix of all fields defined by the extension.
+pub const PREFIX: &str = "sat:";
+
+/// Fields of the extension that may be used in Item properties or on an Asset.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct Sat {
+    /// The state of the orbit.
+    #[serde(rename = "sat:orbit_state")]
+    pub orbit_state: Option<OrbitState>,
+
+    /// The relative orbit number at the time of acquisition.
+    #[serde(rename = "sat:relative_orbit")]
+    pub relative_orbit: Option<u32>,
+
+    /// The absolute orbit number at the time of acquisition.
+    #[serde(rename = "sat:absolute_orbit")]
+    pub absolute_orbit: Option<u32>,
+
+    /// The Ascending Node Crossing (ANX) time, in UTC.
+    #[serde(rename = "sat:anx_datetime")]
+    pub anx_datetime: Option<DateTime<FixedOffset>>,
+
+    /// The International Designator, also known as COSPAR ID, and NSSDCA ID.
+    #[serde(rename = "sat:platform_international_designator")]
+    pub platform_international_designator: Option<String>,
+}
+
+impl Sat {
+    /// Checks that orbit numbers are at least 1 and that the international designator has the form
+    /// `YYYY-NNNP{PP}`, e.g. `2014-016A`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::Other`] describing the first invalid field.
+    pub fn validate(&self) -> STACResult<()> {
+        for (name, value) in [
+            ("sat:relative_orbit", self.relative_orbit),
+            ("sat:absolute_orbit", self.absolute_orbit),
+        ] {
+            if value == Some(0) {
+                return Err(STACError::Other(format!("{name} must be at least 1")));
+            }
+        }
+        if let Some(designator) = &self.platform_international_designator {
+            if !is_international_designator(designator) {
+                return Err(STACError::Other(format!(
+                    "sat:platform_international_designator must have the form YYYY-NNNP{{PP}}, got {designator}"
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Checks for a year, a launch number and a piece of the launch, e.g. `2014-016A`.
+fn is_international_designator(designator: &str) -> bool {
+    let Some((year, launch)) = designator.split_once('-') else {
+        return false;
+    };
+    let (Some(number), Some(piece)) = (launch.get(..3), launch.get(3..)) else {
+        return false;
+    };
+    year.len() == 4
+        && year.chars().all(|c| c.is_ascii_digit())
+        && number.len() == 3
+        && number.chars().all(|c| c.is_ascii_digit())
+        && (1..=3).contains(&piece.len())
+        && piece.chars().all(|c| c.is_ascii_uppercase())
+}
+
+/// The state of the orbit of a satellite.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum OrbitState {
+    /// Moving towards the north pole
+    Ascending,
+
+    /// Moving towards the south pole
+    Descending,
+
+    /// In a geostationary orbit
+    Geostationary,
+}
+
+impl Extension for Sat {
+    const SCHEMA_URI: &'static str = SCHEMA_URI;
+    const PREFIX: &'static str = PREFIX;
+
+    fn validate(&self) -> STACResult<()> {
+        Sat::validate(self)
+    }
+}
+
+impl Item {
+    /// Gets the extension's fields from the Item properties.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::JSONParse`] if a field does not match the extension's schema, including
+    /// values that are not one of the enumerated values.
+    pub fn sat(&self) -> STACResult<Sat> {
+        self.get_extension()
+    }
+
+    /// Sets the extension's fields in the Item properties, removing fields that are `None`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::Other`] if the fields are not valid, see [`Sat::validate`].
+    pub fn set_sat(&mut self, fields: &Sat) -> STACResult<()> {
+        self.set_extension(fields)
+    }
+
+    /// Sets the extension's fields on one of the Item's assets.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::Other`] if the Item has no asset with the given key or if the fields are
+    /// not valid.
+    pub fn set_asset_sat(&mut self, key: &str, fields: &Sat) -> STACResult<()> {
+        self.set_asset_extension(key, fields)
+    }
+}
+
+impl Asset {
+    /// Gets the extension's fields from the Asset.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::JSONParse`] if a field does not match the extension's schema.
+    pub fn sat(&self) -> STACResult<Sat> {
+        self.get_extension()
+    }
+
+    /// Sets the extension's fields on the Asset, removing fields that are `None`. This does not
+    /// update the `stac_extensions` of the Item the Asset belongs to; use [`Item::set_asset_sat`] for
+    /// that.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::Other`] if the fields are not valid, see [`Sat::validate`].
+    pub fn set_sat(&mut self, fields: &Sat) -> STACResult<()> {
+        self.set_extension(fields)
+    }
+}
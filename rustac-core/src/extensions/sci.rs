@@ -0,0 +1,236 @@
+//! Implementation of the [Scientific Citation Extension](https://github.com/stac-extensions/scientific).
+//!
+//! DOIs are accepted in any of the common notations (`10.5061/dryad.s2v81.2`,
+//! `doi:10.5061/dryad.s2v81.2` or `https://doi.org/10.5061/dryad.s2v81.2`) and are stored without a
+//! prefix, as required by the extension. Setting `sci:doi` on an Item or Collection also adds a
+//! `cite-as` link that resolves the DOI.
+//!
+//! ```
+//! use rustac_core::extensions::sci::{self, Sci};
+//!
+//! assert_eq!(
+//!     sci::parse_doi("https://doi.org/10.5061/dryad.s2v81.2").unwrap(),
+//!     "10.5061/dryad.s2v81.2"
+//! );
+//! assert!(sci::parse_doi("not-a-doi").is_err());
+//!
+//! let fields = Sci {
+//!     doi: Some(String::from("doi:10.5061/dryad.s2v81.2")),
+//!     ..Default::default()
+//! };
+//! assert_eq!(
+//!     fields.doi_url().unwrap(),
+//!     Some(String::from("https://doi.org/10.5061/dryad.s2v81.2"))
+//! );
+//! ```
+use serde::{Deserialize, Serialize};
+
+use super::{Extension, Extensions};
+use crate::error::{STACError, STACResult};
+use crate::{Collection, Item, Link};
+
+/// Schema URI of the version of the extension implemented by this module.
+pub const SCHEMA_URI: &str = "https://stac-extensions.github.io/scientific/v1.0.0/schema.json";
+
+/// Prefix of all fields defined by the extension.
+pub const PREFIX: &str = "sci:";
+
+/// Relation type of the link generated for `sci:doi`.
+pub const CITE_AS: &str = "cite-as";
+
+/// Resolver used for the links generated from DOIs.
+const DOI_RESOLVER: &str = "https://doi.org/";
+
+/// Prefixes that may come before a DOI, compared case-insensitively.
+const DOI_PREFIXES: [&str; 5] = [
+    "https://doi.org/",
+    "http://doi.org/",
+    "https://dx.doi.org/",
+    "http://dx.doi.org/",
+    "doi:",
+];
+
+/// Fields of the extension that may be used in Item properties or in a Collection.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct Sci {
+    /// The DOI of the data.
+    #[serde(rename = "sci:doi")]
+    pub doi: Option<String>,
+
+    /// The recommended human-readable reference (citation) to be used by publications citing the data.
+    #[serde(rename = "sci:citation")]
+    pub citation: Option<String>,
+
+    /// List of relevant publications referencing and describing the data.
+    #[serde(rename = "sci:publications")]
+    pub publications: Option<Vec<Publication>>,
+}
+
+/// A publication referencing or describing the data.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct Publication {
+    /// The DOI of the publication.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub doi: Option<String>,
+
+    /// The recommended human-readable reference (citation) to be used by publications citing the
+    /// publication.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub citation: Option<String>,
+}
+
+impl Sci {
+    /// Returns a copy of the fields with every DOI parsed by [`parse_doi`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::Other`] if a DOI is not valid.
+    pub fn normalized(&self) -> STACResult<Sci> {
+        let mut fields = self.clone();
+        if let Some(doi) = &mut fields.doi {
+            *doi = parse_doi(doi)?;
+        }
+        for publication in fields.publications.iter_mut().flatten() {
+            if let Some(doi) = &mut publication.doi {
+                *doi = parse_doi(doi)?;
+            }
+        }
+        Ok(fields)
+    }
+
+    /// Gets the URL that resolves `sci:doi`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::Other`] if the DOI is not valid.
+    pub fn doi_url(&self) -> STACResult<Option<String>> {
+        self.doi
+            .as_deref()
+            .map(|doi| Ok(doi_url(&parse_doi(doi)?)))
+            .transpose()
+    }
+}
+
+/// Parses a DOI, removing any `doi:` or resolver URL prefix.
+///
+/// # Errors
+///
+/// Returns [`STACError::Other`] if the value is not a DOI, i.e. `10.<registrant>/<suffix>`.
+pub fn parse_doi(value: &str) -> STACResult<String> {
+    let value = value.trim();
+    let doi = DOI_PREFIXES
+        .iter()
+        .find_map(|prefix| {
+            value
+                .get(..prefix.len())
+                .filter(|start| start.eq_ignore_ascii_case(prefix))
+                .map(|_| &value[prefix.len()..])
+        })
+        .unwrap_or(value);
+
+    let valid = doi
+        .strip_prefix("10.")
+        .and_then(|rest| rest.split_once('/'))
+        .is_some_and(|(registrant, suffix)| {
+            !registrant.is_empty()
+                && registrant.chars().all(|c| c.is_ascii_digit() || c == '.')
+                && !suffix.is_empty()
+        });
+    if valid {
+        Ok(doi.into())
+    } else {
+        Err(STACError::Other(format!("{value} is not a valid DOI")))
+    }
+}
+
+/// Gets the URL that resolves a DOI.
+#[must_use]
+pub fn doi_url(doi: &str) -> String {
+    format!("{DOI_RESOLVER}{doi}")
+}
+
+/// Replaces the `cite-as` link for the `previous` DOI with one for `doi`, keeping any other
+/// `cite-as` links. A link is added only if no `cite-as` link already points to `doi`.
+fn update_cite_as(links: &mut Vec<Link>, previous: Option<&str>, doi: Option<&str>) {
+    let cites = |link: &Link, doi: &str| {
+        link.rel == CITE_AS
+            && parse_doi(&link.href).is_ok_and(|cited| cited.eq_ignore_ascii_case(doi))
+    };
+    if let Some(previous) = previous {
+        links.retain(|link| !cites(link, previous));
+    }
+    if let Some(doi) = doi {
+        if !links.iter().any(|link| cites(link, doi)) {
+            links.push(Link::new(doi_url(doi), CITE_AS));
+        }
+    }
+}
+
+/// The normalized `sci:doi` of the fields stored before an update, if it is valid.
+fn previous_doi(fields: STACResult<Sci>) -> Option<String> {
+    fields
+        .ok()
+        .and_then(|fields| fields.doi)
+        .and_then(|doi| parse_doi(&doi).ok())
+}
+
+/// Setting the fields through [`Extensions::set_extension`] checks the DOIs but stores them as given
+/// and does not update `cite-as` links; use [`Item::set_sci`] or [`Collection::set_sci`] for that.
+impl Extension for Sci {
+    const SCHEMA_URI: &'static str = SCHEMA_URI;
+    const PREFIX: &'static str = PREFIX;
+
+    fn validate(&self) -> STACResult<()> {
+        self.normalized().map(|_| ())
+    }
+}
+
+impl Item {
+    /// Gets the extension's fields from the Item properties.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::JSONParse`] if a field does not match the extension's schema.
+    pub fn sci(&self) -> STACResult<Sci> {
+        self.get_extension()
+    }
+
+    /// Sets the extension's fields in the Item properties, removing fields that are `None`. DOIs are
+    /// normalized with [`parse_doi`] and the `cite-as` link for `sci:doi` is added, updated or removed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::Other`] if a DOI is not valid.
+    pub fn set_sci(&mut self, fields: &Sci) -> STACResult<()> {
+        let fields = fields.normalized()?;
+        let previous = previous_doi(self.sci());
+        self.set_extension(&fields)?;
+        update_cite_as(&mut self.links, previous.as_deref(), fields.doi.as_deref());
+        Ok(())
+    }
+}
+
+impl Collection {
+    /// Gets the extension's fields from the Collection.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::JSONParse`] if a field does not match the extension's schema.
+    pub fn sci(&self) -> STACResult<Sci> {
+        self.get_extension()
+    }
+
+    /// Sets the extension's fields in the Collection, removing fields that are `None`. DOIs are
+    /// normalized with [`parse_doi`] and the `cite-as` link for `sci:doi` is added, updated or removed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::Other`] if a DOI is not valid.
+    pub fn set_sci(&mut self, fields: &Sci) -> STACResult<()> {
+        let fields = fields.normalized()?;
+        let previous = previous_doi(self.sci());
+        self.set_extension(&fields)?;
+        update_cite_as(&mut self.links, previous.as_deref(), fields.doi.as_deref());
+        Ok(())
+    }
+}
@@ -0,0 +1,130 @@
+//! Implementation of the [Storage Extension](https://github.com/stac-extensions/storage).
+//!
+//! Storage fields in the Item properties apply to every asset that does not set them itself, so
+//! [`Item::assets_in_tier`] falls back to the Item's `storage:tier`.
+//!
+//! ```
+//! use rustac_core::extensions::storage::Storage;
+//! use serde_json::json;
+//!
+//! let fields: Storage = serde_json::from_value(json!({
+//!     "storage:platform": "AWS",
+//!     "storage:region": "us-west-2",
+//!     "storage:requester_pays": true,
+//!     "storage:tier": "Standard"
+//! })).unwrap();
+//!
+//! assert_eq!(fields.requester_pays, Some(true));
+//! ```
+use serde::{Deserialize, Serialize};
+
+use super::{Extension, Extensions};
+use crate::error::STACResult;
+use crate::{Asset, Item};
+
+/// Schema URI of the version of the extension implemented by this module.
+pub const SCHEMA_URI: &str = "https://stac-extensions.github.io/storage/v1.0.0/schema.json";
+
+/// Prefix of all fields defined by the extension.
+pub const PREFIX: &str = "storage:";
+
+/// Fields of the extension that may be used in Item properties or on an Asset.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct Storage {
+    /// The cloud provider where data is stored, e.g. "AWS", "GCP" or "AZURE".
+    #[serde(rename = "storage:platform")]
+    pub platform: Option<String>,
+
+    /// The region where the data is stored, e.g. "us-west-2".
+    #[serde(rename = "storage:region")]
+    pub region: Option<String>,
+
+    /// Whether the requester pays for accessing the data.
+    #[serde(rename = "storage:requester_pays")]
+    pub requester_pays: Option<bool>,
+
+    /// The title of the storage tier, e.g. "Standard" or "Glacier", as used by the platform.
+    #[serde(rename = "storage:tier")]
+    pub tier: Option<String>,
+}
+
+impl Extension for Storage {
+    const SCHEMA_URI: &'static str = SCHEMA_URI;
+    const PREFIX: &'static str = PREFIX;
+}
+
+impl Item {
+    /// Gets the extension's fields from the Item properties.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::JSONParse`](crate::error::STACError::JSONParse) if a field does not
+    /// match the extension's schema.
+    pub fn storage(&self) -> STACResult<Storage> {
+        self.get_extension()
+    }
+
+    /// Sets the extension's fields in the Item properties, removing fields that are `None`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::JSONParse`](crate::error::STACError::JSONParse) if the fields cannot be
+    /// serialized.
+    pub fn set_storage(&mut self, fields: &Storage) -> STACResult<()> {
+        self.set_extension(fields)
+    }
+
+    /// Sets the extension's fields on one of the Item's assets.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::Other`](crate::error::STACError::Other) if the Item has no asset with
+    /// the given key.
+    pub fn set_asset_storage(&mut self, key: &str, fields: &Storage) -> STACResult<()> {
+        self.set_asset_extension(key, fields)
+    }
+
+    /// Gets the assets stored in the given tier, sorted by key. Tiers are compared without regard to
+    /// case, and assets without a `storage:tier` of their own are in the Item's tier.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::JSONParse`](crate::error::STACError::JSONParse) if a field does not
+    /// match the extension's schema.
+    pub fn assets_in_tier(&self, tier: &str) -> STACResult<Vec<(&str, &Asset)>> {
+        let default = self.storage()?.tier;
+        let mut assets = Vec::new();
+        for (key, asset) in &self.assets {
+            let asset_tier = asset.storage()?.tier.or_else(|| default.clone());
+            if asset_tier.is_some_and(|asset_tier| asset_tier.eq_ignore_ascii_case(tier)) {
+                assets.push((key.as_str(), asset));
+            }
+        }
+        assets.sort_by_key(|(key, _)| *key);
+        Ok(assets)
+    }
+}
+
+impl Asset {
+    /// Gets the extension's fields from the Asset.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::JSONParse`](crate::error::STACError::JSONParse) if a field does not
+    /// match the extension's schema.
+    pub fn storage(&self) -> STACResult<Storage> {
+        self.get_extension()
+    }
+
+    /// Sets the extension's fields on the Asset, removing fields that are `None`. This does not
+    /// update the `stac_extensions` of the Item the Asset belongs to; use
+    /// [`Item::set_asset_storage`] for that.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::JSONParse`](crate::error::STACError::JSONParse) if the fields cannot be
+    /// serialized.
+    pub fn set_storage(&mut self, fields: &Storage) -> STACResult<()> {
+        self.set_extension(fields)
+    }
+}
@@ -0,0 +1,147 @@
+//! Implementation of the [Timestamps Extension](https://github.com/stac-extensions/timestamps).
+//!
+//! ```
+//! use chrono::DateTime;
+//! use rustac_core::extensions::timestamps::Timestamps;
+//! use serde_json::json;
+//!
+//! let fields: Timestamps = serde_json::from_value(json!({
+//!     "published": "2021-01-01T00:00:00Z",
+//!     "expires": "2022-01-01T00:00:00Z"
+//! })).unwrap();
+//!
+//! let before = DateTime::parse_from_rfc3339("2021-06-01T00:00:00Z").unwrap();
+//! let after = DateTime::parse_from_rfc3339("2022-06-01T00:00:00Z").unwrap();
+//! assert!(fields.is_published(&before));
+//! assert!(!fields.is_expired(&before));
+//! assert!(fields.is_expired(&after));
+//! ```
+use chrono::{DateTime, FixedOffset, TimeZone};
+use serde::{Deserialize, Serialize};
+
+use super::{Extension, Extensions};
+use crate::error::STACResult;
+use crate::{Asset, Item};
+
+/// Schema URI of the version of the extension implemented by this module.
+pub const SCHEMA_URI: &str = "https://stac-extensions.github.io/timestamps/v1.1.0/schema.json";
+
+/// The extension's fields are not prefixed, see [`FIELDS`].
+pub const PREFIX: &str = "";
+
+/// Names of the fields defined by the extension.
+pub const FIELDS: [&str; 3] = ["published", "expires", "unpublished"];
+
+/// Fields of the extension that may be used in Item properties or on an Asset.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct Timestamps {
+    /// When the data was first published.
+    pub published: Option<DateTime<FixedOffset>>,
+
+    /// When the data will no longer be available, or is no longer guaranteed to be accurate.
+    pub expires: Option<DateTime<FixedOffset>>,
+
+    /// When the data was removed, or will be removed, from publication.
+    pub unpublished: Option<DateTime<FixedOffset>>,
+}
+
+impl Timestamps {
+    /// Returns `true` if `expires` is at or before `at`.
+    #[must_use]
+    pub fn is_expired<Tz: TimeZone>(&self, at: &DateTime<Tz>) -> bool {
+        self.expires.is_some_and(|expires| expires <= *at)
+    }
+
+    /// Returns `true` if the data is published at `at`: `published`, if set, is at or before `at`
+    /// and `unpublished`, if set, is after it.
+    #[must_use]
+    pub fn is_published<Tz: TimeZone>(&self, at: &DateTime<Tz>) -> bool {
+        self.published.is_none_or(|published| published <= *at)
+            && self.unpublished.is_none_or(|unpublished| unpublished > *at)
+    }
+}
+
+impl Extension for Timestamps {
+    const SCHEMA_URI: &'static str = SCHEMA_URI;
+    const PREFIX: &'static str = PREFIX;
+
+    fn is_field(key: &str) -> bool {
+        FIELDS.contains(&key)
+    }
+}
+
+impl Item {
+    /// Gets the extension's fields from the Item properties.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::JSONParse`](crate::error::STACError::JSONParse) if a field is not an
+    /// RFC 3339 datetime.
+    pub fn timestamps(&self) -> STACResult<Timestamps> {
+        self.get_extension()
+    }
+
+    /// Sets the extension's fields in the Item properties, removing fields that are `None`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::JSONParse`](crate::error::STACError::JSONParse) if the fields cannot be
+    /// serialized.
+    pub fn set_timestamps(&mut self, fields: &Timestamps) -> STACResult<()> {
+        self.set_extension(fields)
+    }
+
+    /// Sets the extension's fields on one of the Item's assets.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::Other`](crate::error::STACError::Other) if the Item has no asset with
+    /// the given key.
+    pub fn set_asset_timestamps(&mut self, key: &str, fields: &Timestamps) -> STACResult<()> {
+        self.set_asset_extension(key, fields)
+    }
+
+    /// Returns `true` if the Item's `expires` is at or before `at`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::JSONParse`](crate::error::STACError::JSONParse) if a field is not an
+    /// RFC 3339 datetime.
+    pub fn is_expired<Tz: TimeZone>(&self, at: &DateTime<Tz>) -> STACResult<bool> {
+        Ok(self.timestamps()?.is_expired(at))
+    }
+}
+
+impl Asset {
+    /// Gets the extension's fields from the Asset.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::JSONParse`](crate::error::STACError::JSONParse) if a field is not an
+    /// RFC 3339 datetime.
+    pub fn timestamps(&self) -> STACResult<Timestamps> {
+        self.get_extension()
+    }
+
+    /// Sets the extension's fields on the Asset, removing fields that are `None`. This does not
+    /// update the `stac_extensions` of the Item the Asset belongs to; use
+    /// [`Item::set_asset_timestamps`] for that.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::JSONParse`](crate::error::STACError::JSONParse) if the fields cannot be
+    /// serialized.
+    pub fn set_timestamps(&mut self, fields: &Timestamps) -> STACResult<()> {
+        self.set_extension(fields)
+    }
+
+    /// Returns `true` if the Asset's `expires` is at or before `at`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::JSONParse`](crate::error::STACError::JSONParse) if a field is not an
+    /// RFC 3339 datetime.
+    pub fn is_expired<Tz: TimeZone>(&self, at: &DateTime<Tz>) -> STACResult<bool> {
+        Ok(self.timestamps()?.is_expired(at))
+    }
+}
@@ -0,0 +1,307 @@
+//! Implementation of the [Versioning Indicators Extension](https://github.com/stac-extensions/version).
+//!
+//! Besides typed access to `version` and `deprecated`, this module manages the links between
+//! versions of an Item or Collection. [`Item::new_version`] creates a new version of an Item and
+//! links both versions to each other; [`link_versions`] does the same for two Items that already
+//! exist.
+//!
+//! ```
+//! use rustac_core::extensions::version::{Relation, Version};
+//! use rustac_core::Item;
+//! use serde_json::json;
+//!
+//! let mut item: Item = serde_json::from_value(json!({
+//!     "stac_version": "1.0.0",
+//!     "type": "Feature",
+//!     "id": "scene-v1",
+//!     "bbox": [0.0, 0.0, 1.0, 1.0],
+//!     "geometry": {"type": "Point", "coordinates": [0.0, 0.0]},
+//!     "properties": {"datetime": "2021-01-01T00:00:00Z", "version": "1"},
+//!     "links": [],
+//!     "assets": {}
+//! })).unwrap();
+//!
+//! let new = item.new_version("scene-v2", "2", "./scene-v1.json", "./scene-v2.json").unwrap();
+//! assert_eq!(new.version().unwrap().version, Some(String::from("2")));
+//! assert_eq!(new.version_link(Relation::Predecessor).unwrap().href, "./scene-v1.json");
+//! assert_eq!(item.version_link(Relation::Successor).unwrap().href, "./scene-v2.json");
+//!
+//! // Marking the old version as deprecated is up to the caller
+//! item.set_version(&Version { deprecated: Some(true), ..item.version().unwrap() }).unwrap();
+//! ```
+use serde::{Deserialize, Serialize};
+
+use super::{sync_schema_uri, Extension, Extensions};
+use crate::error::STACResult;
+use crate::{Collection, Item, Link};
+
+/// Schema URI of the version of the extension implemented by this module.
+pub const SCHEMA_URI: &str = "https://stac-extensions.github.io/version/v1.0.0/schema.json";
+
+/// The extension's fields are not prefixed, see [`FIELDS`].
+pub const PREFIX: &str = "";
+
+/// Names of the fields defined by the extension.
+pub const FIELDS: [&str; 2] = ["version", "deprecated"];
+
+/// Fields of the extension that may be used in Item properties or in a Collection.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct Version {
+    /// Version of the context this field is used in.
+    pub version: Option<String>,
+
+    /// Specifies that the context this field is used in is deprecated with the potential to be
+    /// removed.
+    pub deprecated: Option<bool>,
+}
+
+/// Relation types of the links between versions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Relation {
+    /// `latest-version`, the latest version of the object
+    Latest,
+
+    /// `predecessor-version`, the version before this one
+    Predecessor,
+
+    /// `successor-version`, the version after this one
+    Successor,
+}
+
+impl Relation {
+    /// The `rel` of links with this relation type.
+    #[must_use]
+    pub fn rel(self) -> &'static str {
+        match self {
+            Relation::Latest => "latest-version",
+            Relation::Predecessor => "predecessor-version",
+            Relation::Successor => "successor-version",
+        }
+    }
+}
+
+impl Extension for Version {
+    const SCHEMA_URI: &'static str = SCHEMA_URI;
+    const PREFIX: &'static str = PREFIX;
+
+    fn is_field(key: &str) -> bool {
+        FIELDS.contains(&key)
+    }
+}
+
+fn find_link(links: &[Link], relation: Relation) -> Option<&Link> {
+    links.iter().find(|link| link.rel == relation.rel())
+}
+
+/// Replaces any links with the relation type by a single link to `href`, or removes them if `href`
+/// is `None`.
+fn replace_link(links: &mut Vec<Link>, relation: Relation, href: Option<&str>) {
+    links.retain(|link| link.rel != relation.rel());
+    if let Some(href) = href {
+        links.push(Link::new(href, relation.rel()));
+    }
+}
+
+/// Points the `self` link, if there is one, at `href`.
+fn update_self_link(links: &mut [Link], href: &str) {
+    for link in links.iter_mut().filter(|link| link.rel == "self") {
+        link.href = href.into();
+    }
+}
+
+/// Links two existing versions of an Item to each other. The predecessor gets `successor-version`
+/// and `latest-version` links to the successor; the successor gets a `predecessor-version` link and
+/// loses any `latest-version` link, since it is now the latest version. Only links are changed:
+/// whether the predecessor is also `deprecated` is left to the caller.
+pub fn link_versions(
+    predecessor: &mut Item,
+    predecessor_href: &str,
+    successor: &mut Item,
+    successor_href: &str,
+) {
+    mark_superseded(&mut predecessor.links, successor_href);
+    predecessor.sync_version();
+    mark_latest(&mut successor.links, predecessor_href);
+    successor.sync_version();
+}
+
+/// Links two existing versions of a Collection to each other, see [`link_versions`].
+pub fn link_collection_versions(
+    predecessor: &mut Collection,
+    predecessor_href: &str,
+    successor: &mut Collection,
+    successor_href: &str,
+) {
+    mark_superseded(&mut predecessor.links, successor_href);
+    predecessor.sync_version();
+    mark_latest(&mut successor.links, predecessor_href);
+    successor.sync_version();
+}
+
+fn mark_superseded(links: &mut Vec<Link>, successor_href: &str) {
+    replace_link(links, Relation::Successor, Some(successor_href));
+    replace_link(links, Relation::Latest, Some(successor_href));
+}
+
+fn mark_latest(links: &mut Vec<Link>, predecessor_href: &str) {
+    replace_link(links, Relation::Predecessor, Some(predecessor_href));
+    replace_link(links, Relation::Successor, None);
+    replace_link(links, Relation::Latest, None);
+}
+
+/// Checks whether any of the links has one of the extension's relation types.
+fn has_version_link(links: &[Link]) -> bool {
+    [Relation::Latest, Relation::Predecessor, Relation::Successor]
+        .iter()
+        .any(|relation| find_link(links, *relation).is_some())
+}
+
+impl Item {
+    /// Gets the extension's fields from the Item properties.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::JSONParse`](crate::error::STACError::JSONParse) if a field does not
+    /// match the extension's schema.
+    pub fn version(&self) -> STACResult<Version> {
+        self.get_extension()
+    }
+
+    /// Sets the extension's fields in the Item properties, removing fields that are `None`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::JSONParse`](crate::error::STACError::JSONParse) if the fields cannot be
+    /// serialized.
+    pub fn set_version(&mut self, fields: &Version) -> STACResult<()> {
+        self.set_extension(fields)?;
+        self.sync_version();
+        Ok(())
+    }
+
+    /// Gets the first link with the given version relation type.
+    #[must_use]
+    pub fn version_link(&self, relation: Relation) -> Option<&Link> {
+        find_link(&self.links, relation)
+    }
+
+    /// Replaces the links with the given version relation type by a single link to `href`, or
+    /// removes them if `href` is `None`.
+    pub fn set_version_link(&mut self, relation: Relation, href: Option<&str>) {
+        replace_link(&mut self.links, relation, href);
+        self.sync_version();
+    }
+
+    /// Adds or removes the extension's schema URI depending on whether the fields or any version
+    /// links are used.
+    fn sync_version(&mut self) {
+        self.sync_extension::<Version>();
+        if has_version_link(&self.links) {
+            sync_schema_uri(&mut self.stac_extensions, SCHEMA_URI, true);
+        }
+    }
+
+    /// Creates a new version of the Item with the given `id` and `version`, and links both versions
+    /// to each other with [`link_versions`]. The new Item is a copy of this one; its `self` link, if
+    /// any, is pointed at `href`. `previous_href` is the location of this Item.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::JSONParse`](crate::error::STACError::JSONParse) if the Item cannot be
+    /// copied.
+    pub fn new_version<I, V>(
+        &mut self,
+        id: I,
+        version: V,
+        previous_href: &str,
+        href: &str,
+    ) -> STACResult<Item>
+    where
+        I: Into<String>,
+        V: Into<String>,
+    {
+        let mut item: Item = serde_json::from_value(serde_json::to_value(&*self)?)?;
+        item.id = id.into();
+        item.set_version(&Version {
+            version: Some(version.into()),
+            deprecated: None,
+        })?;
+        update_self_link(&mut item.links, href);
+        link_versions(self, previous_href, &mut item, href);
+        Ok(item)
+    }
+}
+
+impl Collection {
+    /// Gets the extension's fields from the Collection.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::JSONParse`](crate::error::STACError::JSONParse) if a field does not
+    /// match the extension's schema.
+    pub fn version(&self) -> STACResult<Version> {
+        self.get_extension()
+    }
+
+    /// Sets the extension's fields in the Collection, removing fields that are `None`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::JSONParse`](crate::error::STACError::JSONParse) if the fields cannot be
+    /// serialized.
+    pub fn set_version(&mut self, fields: &Version) -> STACResult<()> {
+        self.set_extension(fields)?;
+        self.sync_version();
+        Ok(())
+    }
+
+    /// Gets the first link with the given version relation type.
+    #[must_use]
+    pub fn version_link(&self, relation: Relation) -> Option<&Link> {
+        find_link(&self.links, relation)
+    }
+
+    /// Replaces the links with the given version relation type by a single link to `href`, or
+    /// removes them if `href` is `None`.
+    pub fn set_version_link(&mut self, relation: Relation, href: Option<&str>) {
+        replace_link(&mut self.links, relation, href);
+        self.sync_version();
+    }
+
+    /// Adds or removes the extension's schema URI depending on whether the fields or any version
+    /// links are used.
+    fn sync_version(&mut self) {
+        self.sync_extension::<Version>();
+        if has_version_link(&self.links) {
+            sync_schema_uri(&mut self.stac_extensions, SCHEMA_URI, true);
+        }
+    }
+
+    /// Creates a new version of the Collection, see [`Item::new_version`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::JSONParse`](crate::error::STACError::JSONParse) if the Collection
+    /// cannot be copied.
+    pub fn new_version<I, V>(
+        &mut self,
+        id: I,
+        version: V,
+        previous_href: &str,
+        href: &str,
+    ) -> STACResult<Collection>
+    where
+        I: Into<String>,
+        V: Into<String>,
+    {
+        let mut collection: Collection = serde_json::from_value(serde_json::to_value(&*self)?)?;
+        collection.id = id.into();
+        collection.set_version(&Version {
+            version: Some(version.into()),
+            deprecated: None,
+        })?;
+        update_self_link(&mut collection.links, href);
+        link_collection_versions(self, previous_href, &mut collection, href);
+        Ok(collection)
+    }
+}
@@ -0,0 +1,147 @@
+//! Implementation of the [View Geometry Extension](https://github.com/stac-extensions/view).
+//!
+//! ```
+//! use rustac_core::extensions::view::View;
+//!
+//! let fields = View {
+//!     off_nadir: Some(3.8),
+//!     sun_elevation: Some(54.9),
+//!     ..Default::default()
+//! };
+//! assert!(fields.validate().is_ok());
+//!
+//! let invalid = View {
+//!     azimuth: Some(361.0),
+//!     ..Default::default()
+//! };
+//! assert!(invalid.validate().is_err());
+//! ```
+use serde::{Deserialize, Serialize};
+use std::ops::RangeInclusive;
+
+use super::{Extension, Extensions};
+use crate::error::{STACError, STACResult};
+use crate::{Asset, Item};
+
+/// Schema URI of the version of the extension implemented by this module.
+pub const SCHEMA_URI: &str = "https://stac-extensions.github.io/view/v1.0.0/schema.json";
+
+/// Prefix of all fields defined by the extension.
+pub const PREFIX: &str = "view:";
+
+/// Fields of the extension that may be used in Item properties or on an Asset. All angles are in
+/// degrees.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct View {
+    /// The angle from the sensor between nadir (straight down) and the scene center, between 0 and 90.
+    #[serde(rename = "view:off_nadir")]
+    pub off_nadir: Option<f64>,
+
+    /// The incidence angle is the angle between the vertical (normal) to the intercepting surface and
+    /// the line of sight back to the satellite at the scene center, between 0 and 90.
+    #[serde(rename = "view:incidence_angle")]
+    pub incidence_angle: Option<f64>,
+
+    /// Viewing azimuth angle, measured clockwise from true north, between 0 and 360.
+    #[serde(rename = "view:azimuth")]
+    pub azimuth: Option<f64>,
+
+    /// Sun azimuth angle, measured clockwise from true north, between 0 and 360.
+    #[serde(rename = "view:sun_azimuth")]
+    pub sun_azimuth: Option<f64>,
+
+    /// Sun elevation angle, the angle from the tangent of the scene center point to the sun, between
+    /// -90 and 90.
+    #[serde(rename = "view:sun_elevation")]
+    pub sun_elevation: Option<f64>,
+}
+
+impl View {
+    /// Checks that all angles are within the ranges allowed by the extension.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::Other`] describing the first angle that is out of range.
+    pub fn validate(&self) -> STACResult<()> {
+        let angles: [(&str, Option<f64>, RangeInclusive<f64>); 5] = [
+            ("view:off_nadir", self.off_nadir, 0.0..=90.0),
+            ("view:incidence_angle", self.incidence_angle, 0.0..=90.0),
+            ("view:azimuth", self.azimuth, 0.0..=360.0),
+            ("view:sun_azimuth", self.sun_azimuth, 0.0..=360.0),
+            ("view:sun_elevation", self.sun_elevation, -90.0..=90.0),
+        ];
+        for (name, value, range) in angles {
+            if let Some(value) = value {
+                if !range.contains(&value) {
+                    return Err(STACError::Other(format!(
+                        "{name} must be between {} and {}, got {value}",
+                        range.start(),
+                        range.end()
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Extension for View {
+    const SCHEMA_URI: &'static str = SCHEMA_URI;
+    const PREFIX: &'static str = PREFIX;
+
+    fn validate(&self) -> STACResult<()> {
+        View::validate(self)
+    }
+}
+
+impl Item {
+    /// Gets the extension's fields from the Item properties.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::JSONParse`] if a field does not match the extension's schema.
+    pub fn view(&self) -> STACResult<View> {
+        self.get_extension()
+    }
+
+    /// Sets the extension's fields in the Item properties, removing fields that are `None`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::Other`] if an angle is out of range, see [`View::validate`].
+    pub fn set_view(&mut self, fields: &View) -> STACResult<()> {
+        self.set_extension(fields)
+    }
+
+    /// Sets the extension's fields on one of the Item's assets.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::Other`] if the Item has no asset with the given key or if an angle is
+    /// out of range.
+    pub fn set_asset_view(&mut self, key: &str, fields: &View) -> STACResult<()> {
+        self.set_asset_extension(key, fields)
+    }
+}
+
+impl Asset {
+    /// Gets the extension's fields from the Asset.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::JSONParse`] if a field does not match the extension's schema.
+    pub fn view(&self) -> STACResult<View> {
+        self.get_extension()
+    }
+
+    /// Sets the extension's fields on the Asset, removing fields that are `None`. This does not
+    /// update the `stac_extensions` of the Item the Asset belongs to; use [`Item::set_asset_view`]
+    /// for that.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::Other`] if an angle is out of range, see [`View::validate`].
+    pub fn set_view(&mut self, fields: &View) -> STACResult<()> {
+        self.set_extension(fields)
+    }
+}
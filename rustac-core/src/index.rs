@@ -0,0 +1,485 @@
+//! An in-memory index of Items by bounding box and datetime, for finding Items without scanning all
+//! of them.
+//!
+//! [`ItemIndex`] keeps each Item's `bbox` in an R-tree and its datetime, or its
+//! `start_datetime`/`end_datetime` range, in an interval tree. Items can be added and removed one
+//! at a time, and the index can be written to disk and read back without rebuilding it.
+//!
+//! ```
+//! use rustac_core::index::{Bounds, Interval, ItemIndex, Relation};
+//! use rustac_core::Item;
+//! use serde_json::json;
+//!
+//! let item: Item = serde_json::from_value(json!({
+//!     "stac_version": "1.0.0",
+//!     "type": "Feature",
+//!     "id": "an-item",
+//!     "bbox": [0.0, 0.0, 1.0, 1.0],
+//!     "geometry": {"type": "Point", "coordinates": [0.5, 0.5]},
+//!     "properties": {"datetime": "2021-06-01T00:00:00Z"},
+//!     "links": [],
+//!     "assets": {}
+//! })).unwrap();
+//!
+//! let mut index = ItemIndex::new();
+//! index.insert(&item).unwrap();
+//!
+//! let tile = Bounds::new(0.25, 0.25, 0.5, 0.5);
+//! let june = Interval::parse("2021-06-01T00:00:00Z/2021-06-30T23:59:59Z").unwrap();
+//! assert_eq!(index.search(Some(&tile), None, Relation::Contains), vec!["an-item"]);
+//! assert_eq!(index.search(Some(&tile), Some(&june), Relation::Intersects), vec!["an-item"]);
+//! assert!(index.search(Some(&tile), None, Relation::Within).is_empty());
+//! ```
+use chrono::{DateTime, TimeZone};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+use crate::error::{STACError, STACResult};
+use crate::Item;
+
+use interval_tree::IntervalTree;
+use rtree::RTree;
+
+mod interval_tree;
+mod rtree;
+
+/// A two-dimensional bounding box. Bounds whose western edge is east of their eastern edge cross
+/// the antimeridian, as the STAC spec allows for bboxes.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+pub struct Bounds {
+    /// The western edge.
+    pub min_x: f64,
+
+    /// The southern edge.
+    pub min_y: f64,
+
+    /// The eastern edge.
+    pub max_x: f64,
+
+    /// The northern edge.
+    pub max_y: f64,
+}
+
+/// A closed interval of time, in microseconds since the Unix epoch. Open ends are represented by
+/// [`i64::MIN`] and [`i64::MAX`], and instants by intervals that start and end at the same time.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Interval {
+    /// The start of the interval.
+    pub start: i64,
+
+    /// The end of the interval.
+    pub end: i64,
+}
+
+/// How the bounds or interval of an indexed Item must relate to those of a query.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Relation {
+    /// The Item and the query have at least one point in common.
+    Intersects,
+
+    /// The Item contains the whole query, e.g. to find Items that cover a tile.
+    Contains,
+
+    /// The Item lies entirely within the query.
+    Within,
+}
+
+impl Bounds {
+    /// Creates bounds from its edges.
+    #[must_use]
+    pub fn new(min_x: f64, min_y: f64, max_x: f64, max_y: f64) -> Bounds {
+        Bounds {
+            min_x,
+            min_y,
+            max_x,
+            max_y,
+        }
+    }
+
+    /// Creates bounds from a bbox with 4 or 6 numbers, ignoring elevations. Returns `None` if the
+    /// bbox has another length or its southern edge is north of its northern edge. A western edge
+    /// east of the eastern edge is allowed, for bboxes that cross the antimeridian.
+    #[must_use]
+    pub fn from_bbox(bbox: &[f64]) -> Option<Bounds> {
+        let ([min_x, min_y, max_x, max_y] | [min_x, min_y, _, max_x, max_y, _]) = *bbox else {
+            return None;
+        };
+        Some(Bounds::new(min_x, min_y, max_x, max_y)).filter(|bounds| bounds.min_y <= bounds.max_y)
+    }
+
+    /// Returns `true` if the bounds cross the antimeridian, i.e. their western edge is east of
+    /// their eastern edge.
+    #[must_use]
+    pub fn crosses_antimeridian(&self) -> bool {
+        self.min_x > self.max_x
+    }
+
+    /// Splits bounds that cross the antimeridian into the parts east and west of it, or returns
+    /// the bounds themselves if they do not cross it.
+    #[must_use]
+    pub fn split_at_antimeridian(&self) -> Vec<Bounds> {
+        if self.crosses_antimeridian() {
+            vec![
+                Bounds::new(self.min_x, self.min_y, 180.0, self.max_y),
+                Bounds::new(-180.0, self.min_y, self.max_x, self.max_y),
+            ]
+        } else {
+            vec![*self]
+        }
+    }
+
+    /// Returns `true` if the bounds have at least one point in common.
+    #[must_use]
+    pub fn intersects(&self, other: &Bounds) -> bool {
+        if self.crosses_antimeridian() || other.crosses_antimeridian() {
+            let others = other.split_at_antimeridian();
+            return self
+                .split_at_antimeridian()
+                .iter()
+                .any(|part| others.iter().any(|other| part.intersects(other)));
+        }
+        self.min_x <= other.max_x
+            && self.max_x >= other.min_x
+            && self.min_y <= other.max_y
+            && self.max_y >= other.min_y
+    }
+
+    /// Returns `true` if `other` lies entirely within these bounds.
+    #[must_use]
+    pub fn contains(&self, other: &Bounds) -> bool {
+        if self.crosses_antimeridian() || other.crosses_antimeridian() {
+            let parts = self.split_at_antimeridian();
+            return other
+                .split_at_antimeridian()
+                .iter()
+                .all(|other| parts.iter().any(|part| part.contains(other)));
+        }
+        self.min_x <= other.min_x
+            && self.max_x >= other.max_x
+            && self.min_y <= other.min_y
+            && self.max_y >= other.max_y
+    }
+
+    fn union(&self, other: &Bounds) -> Bounds {
+        Bounds::new(
+            self.min_x.min(other.min_x),
+            self.min_y.min(other.min_y),
+            self.max_x.max(other.max_x),
+            self.max_y.max(other.max_y),
+        )
+    }
+
+    fn area(&self) -> f64 {
+        (self.max_x - self.min_x) * (self.max_y - self.min_y)
+    }
+}
+
+impl Interval {
+    /// Creates an interval between two datetimes, either of which may be open.
+    #[must_use]
+    pub fn new<Tz: TimeZone>(start: Option<&DateTime<Tz>>, end: Option<&DateTime<Tz>>) -> Interval {
+        Interval {
+            start: start.map_or(i64::MIN, DateTime::timestamp_micros),
+            end: end.map_or(i64::MAX, DateTime::timestamp_micros),
+        }
+    }
+
+    /// Creates an interval that starts and ends at `datetime`.
+    #[must_use]
+    pub fn instant<Tz: TimeZone>(datetime: &DateTime<Tz>) -> Interval {
+        Interval::new(Some(datetime), Some(datetime))
+    }
+
+    /// Parses a datetime or an interval written as in an Item Search, e.g. `2021-01-01T00:00:00Z`,
+    /// `2021-01-01T00:00:00Z/2021-02-01T00:00:00Z` or `../2021-02-01T00:00:00Z`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::Other`] if the interval is not valid.
+    pub fn parse(interval: &str) -> STACResult<Interval> {
+        let parse = |datetime: &str| match datetime {
+            "" | ".." => Ok(None),
+            datetime => DateTime::parse_from_rfc3339(datetime)
+                .map(Some)
+                .map_err(|err| STACError::Other(format!("Invalid datetime {datetime}: {err}"))),
+        };
+        let parsed = match interval.split('/').collect::<Vec<_>>().as_slice() {
+            [instant] => parse(instant)?.map(|instant| Interval::instant(&instant)),
+            [start, end] => match (parse(start)?, parse(end)?) {
+                (None, None) => None,
+                (start, end) => Some(Interval::new(start.as_ref(), end.as_ref())),
+            },
+            _ => None,
+        };
+        parsed
+            .filter(|parsed| parsed.start <= parsed.end)
+            .ok_or_else(|| STACError::Other(format!("Invalid interval {interval}")))
+    }
+
+    /// Gets the interval of an Item from its `datetime`, or from its `start_datetime` and
+    /// `end_datetime` if `datetime` is `null`.
+    #[must_use]
+    pub fn from_item(item: &Item) -> Option<Interval> {
+        let common = &item.properties.common;
+        match (
+            &common.datetime,
+            &common.start_datetime,
+            &common.end_datetime,
+        ) {
+            (Some(datetime), ..) => Some(Interval::instant(datetime)),
+            (None, Some(start), Some(end)) => Some(Interval::new(Some(start), Some(end))),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if the intervals have at least one instant in common.
+    #[must_use]
+    pub fn intersects(&self, other: &Interval) -> bool {
+        self.start <= other.end && self.end >= other.start
+    }
+
+    /// Returns `true` if `other` lies entirely within this interval.
+    #[must_use]
+    pub fn contains(&self, other: &Interval) -> bool {
+        self.start <= other.start && self.end >= other.end
+    }
+}
+
+impl Relation {
+    fn test<T>(self, item: &T, query: &T, contains: fn(&T, &T) -> bool) -> bool {
+        match self {
+            Relation::Intersects => true,
+            Relation::Contains => contains(item, query),
+            Relation::Within => contains(query, item),
+        }
+    }
+}
+
+/// The bounds and interval an Item was indexed with. Bounds that cross the antimeridian are stored
+/// in the R-tree as two parts.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+struct Entry {
+    bounds: Bounds,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    interval: Option<Interval>,
+}
+
+/// An index of Items by bounding box and datetime, identified by their ids. Items without a
+/// datetime are only found by spatial searches.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct ItemIndex {
+    entries: HashMap<String, Entry>,
+    spatial: RTree<String>,
+    temporal: IntervalTree<String>,
+}
+
+impl ItemIndex {
+    /// Creates an empty index.
+    #[must_use]
+    pub fn new() -> ItemIndex {
+        ItemIndex::default()
+    }
+
+    /// Creates an index of the given Items.
+    ///
+    /// # Errors
+    ///
+    /// See [`ItemIndex::insert`].
+    pub fn from_items<'a, I: IntoIterator<Item = &'a Item>>(items: I) -> STACResult<ItemIndex> {
+        let mut index = ItemIndex::new();
+        for item in items {
+            index.insert(item)?;
+        }
+        Ok(index)
+    }
+
+    /// The number of indexed Items.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if no Items are indexed.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns `true` if an Item with this id is indexed.
+    #[must_use]
+    pub fn contains(&self, id: &str) -> bool {
+        self.entries.contains_key(id)
+    }
+
+    /// Adds an Item to the index, replacing any indexed Item with the same id.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::Other`] if the Item's bbox does not have 4 or 6 numbers, or its
+    /// southern edge is north of its northern edge.
+    pub fn insert(&mut self, item: &Item) -> STACResult<()> {
+        let bounds = Bounds::from_bbox(&item.bbox)
+            .ok_or_else(|| STACError::Other(format!("Item {} has an invalid bbox", item.id)))?;
+        self.remove(&item.id);
+        let entry = Entry {
+            bounds,
+            interval: Interval::from_item(item),
+        };
+        for part in entry.bounds.split_at_antimeridian() {
+            self.spatial.insert(part, item.id.clone());
+        }
+        if let Some(interval) = entry.interval {
+            self.temporal.insert(interval, item.id.clone());
+        }
+        self.entries.insert(item.id.clone(), entry);
+        Ok(())
+    }
+
+    /// Removes the Item with this id, returning `true` if it was indexed.
+    pub fn remove(&mut self, id: &str) -> bool {
+        let Some((id, entry)) = self.entries.remove_entry(id) else {
+            return false;
+        };
+        for part in entry.bounds.split_at_antimeridian() {
+            self.spatial.remove(&part, &id);
+        }
+        if let Some(interval) = &entry.interval {
+            self.temporal.remove(interval, &id);
+        }
+        true
+    }
+
+    /// Finds the ids of the Items whose bbox has the given relation to `bounds`, sorted. Either
+    /// may cross the antimeridian.
+    #[must_use]
+    pub fn search_bounds(&self, bounds: &Bounds, relation: Relation) -> Vec<&str> {
+        let mut parts = bounds.split_at_antimeridian();
+        if relation == Relation::Contains {
+            // An Item that contains all of the query contains its first part.
+            parts.truncate(1);
+        }
+        let mut ids: Vec<&str> = Vec::new();
+        for part in &parts {
+            let prune = |node: &Bounds| match relation {
+                Relation::Contains => node.contains(part),
+                Relation::Intersects | Relation::Within => node.intersects(part),
+            };
+            let found = self.spatial.search(prune, |item| item.intersects(part));
+            ids.extend(found.into_iter().map(String::as_str));
+        }
+        ids.sort_unstable();
+        ids.dedup();
+        ids.retain(|id| {
+            self.entries
+                .get(*id)
+                .is_some_and(|entry| relation.test(&entry.bounds, bounds, Bounds::contains))
+        });
+        ids
+    }
+
+    /// Finds the ids of the Items whose interval has the given relation to `interval`, sorted.
+    #[must_use]
+    pub fn search_interval(&self, interval: &Interval, relation: Relation) -> Vec<&str> {
+        let mut ids: Vec<&str> = self
+            .temporal
+            .overlapping(interval)
+            .into_iter()
+            .filter(|(item, _)| relation.test(*item, interval, Interval::contains))
+            .map(|(_, id)| id.as_str())
+            .collect();
+        ids.sort_unstable();
+        ids
+    }
+
+    /// Finds the ids of the Items that have the given relation to both `bounds` and `interval`,
+    /// sorted. A missing `bounds` or `interval` does not restrict the results, so searching with
+    /// neither returns every Item.
+    #[must_use]
+    pub fn search(
+        &self,
+        bounds: Option<&Bounds>,
+        interval: Option<&Interval>,
+        relation: Relation,
+    ) -> Vec<&str> {
+        match (bounds, interval) {
+            (Some(bounds), Some(interval)) => {
+                let temporal: HashSet<&str> = self
+                    .search_interval(interval, relation)
+                    .into_iter()
+                    .collect();
+                self.search_bounds(bounds, relation)
+                    .into_iter()
+                    .filter(|id| temporal.contains(id))
+                    .collect()
+            }
+            (Some(bounds), None) => self.search_bounds(bounds, relation),
+            (None, Some(interval)) => self.search_interval(interval, relation),
+            (None, None) => {
+                let mut ids: Vec<&str> = self.entries.keys().map(String::as_str).collect();
+                ids.sort_unstable();
+                ids
+            }
+        }
+    }
+
+    /// Writes the index to a JSON file, so it can be reused with [`ItemIndex::read`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::JSONParse`] if the index cannot be serialized, or [`STACError::IO`]
+    /// if the file cannot be written.
+    pub fn write<P: AsRef<Path>>(&self, path: P) -> STACResult<()> {
+        fs::write(path, serde_json::to_string(self)?)?;
+        Ok(())
+    }
+
+    /// Reads an index written by [`ItemIndex::write`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::IO`] if the file cannot be read, [`STACError::JSONParse`] if it does
+    /// not contain an index, or [`STACError::Other`] if the trees in the file do not match its
+    /// Items.
+    pub fn read<P: AsRef<Path>>(path: P) -> STACResult<ItemIndex> {
+        let index: ItemIndex = serde_json::from_str(&fs::read_to_string(&path)?)?;
+        let temporal = index
+            .entries
+            .values()
+            .filter(|entry| entry.interval.is_some())
+            .count();
+        let spatial: usize = index
+            .entries
+            .values()
+            .map(|entry| entry.bounds.split_at_antimeridian().len())
+            .sum();
+        // With the same sizes, finding every entry in the trees means they hold nothing else
+        let indexed = |id: &String, entry: &Entry| {
+            let spatial = entry.bounds.split_at_antimeridian().iter().all(|part| {
+                index
+                    .spatial
+                    .search(|node| node.contains(part), |bounds| bounds == part)
+                    .contains(&id)
+            });
+            let temporal = entry.interval.is_none_or(|interval| {
+                index
+                    .temporal
+                    .overlapping(&interval)
+                    .into_iter()
+                    .any(|found| found == (&interval, id))
+            });
+            spatial && temporal
+        };
+        if index.spatial.len() != spatial
+            || index.temporal.len() != temporal
+            || !index.entries.iter().all(|(id, entry)| indexed(id, entry))
+        {
+            return Err(STACError::Other(format!(
+                "Index in {} is inconsistent",
+                path.as_ref().display()
+            )));
+        }
+        Ok(index)
+    }
+}
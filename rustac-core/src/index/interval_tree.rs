@@ -0,0 +1,220 @@
+//! An interval tree, stored as an AVL tree ordered by interval start and augmented with the latest
+//! end in each subtree.
+//!
+//! Nodes are boxed so they can be moved between subtrees during rotations.
+#![allow(clippy::unnecessary_box_returns)]
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+use super::Interval;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub(crate) struct IntervalTree<T> {
+    root: Option<Box<Node<T>>>,
+    len: usize,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+struct Node<T> {
+    interval: Interval,
+    value: T,
+    /// The latest end of any interval in this subtree.
+    max_end: i64,
+    height: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    left: Option<Box<Node<T>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    right: Option<Box<Node<T>>>,
+}
+
+impl<T> Default for IntervalTree<T> {
+    fn default() -> IntervalTree<T> {
+        IntervalTree { root: None, len: 0 }
+    }
+}
+
+type Link<T> = Option<Box<Node<T>>>;
+
+fn height<T>(node: Option<&Node<T>>) -> u32 {
+    node.map_or(0, |node| node.height)
+}
+
+fn max_end<T>(node: Option<&Node<T>>) -> i64 {
+    node.map_or(i64::MIN, |node| node.max_end)
+}
+
+impl<T: Ord> IntervalTree<T> {
+    pub(crate) fn len(&self) -> usize {
+        self.len
+    }
+
+    pub(crate) fn insert(&mut self, interval: Interval, value: T) {
+        self.root = Some(insert(self.root.take(), interval, value));
+        self.len += 1;
+    }
+
+    /// Removes the entry with this interval and value, returning `true` if it was found.
+    pub(crate) fn remove(&mut self, interval: &Interval, value: &T) -> bool {
+        let mut removed = false;
+        self.root = remove(self.root.take(), interval, value, &mut removed);
+        if removed {
+            self.len -= 1;
+        }
+        removed
+    }
+
+    /// Returns the values of all intervals that overlap `query`.
+    pub(crate) fn overlapping(&self, query: &Interval) -> Vec<(&Interval, &T)> {
+        let mut found = Vec::new();
+        overlapping(self.root.as_deref(), query, &mut found);
+        found
+    }
+}
+
+fn key_cmp<T: Ord>(node: &Node<T>, interval: &Interval, value: &T) -> Ordering {
+    (interval.start, interval.end, value).cmp(&(
+        node.interval.start,
+        node.interval.end,
+        &node.value,
+    ))
+}
+
+impl<T> Node<T> {
+    fn update(&mut self) {
+        self.height = 1 + height(self.left.as_deref()).max(height(self.right.as_deref()));
+        self.max_end = self
+            .interval
+            .end
+            .max(max_end(self.left.as_deref()))
+            .max(max_end(self.right.as_deref()));
+    }
+
+    fn balance_factor(&self) -> i64 {
+        i64::from(height(self.left.as_deref())) - i64::from(height(self.right.as_deref()))
+    }
+}
+
+fn rotate_right<T>(mut node: Box<Node<T>>) -> Box<Node<T>> {
+    match node.left.take() {
+        Some(mut left) => {
+            node.left = left.right.take();
+            node.update();
+            left.right = Some(node);
+            left.update();
+            left
+        }
+        None => node,
+    }
+}
+
+fn rotate_left<T>(mut node: Box<Node<T>>) -> Box<Node<T>> {
+    match node.right.take() {
+        Some(mut right) => {
+            node.right = right.left.take();
+            node.update();
+            right.left = Some(node);
+            right.update();
+            right
+        }
+        None => node,
+    }
+}
+
+fn rebalance<T>(mut node: Box<Node<T>>) -> Box<Node<T>> {
+    node.update();
+    let balance = node.balance_factor();
+    if balance > 1 {
+        if node
+            .left
+            .as_ref()
+            .is_some_and(|left| left.balance_factor() < 0)
+        {
+            node.left = node.left.take().map(rotate_left);
+        }
+        return rotate_right(node);
+    }
+    if balance < -1 {
+        if node
+            .right
+            .as_ref()
+            .is_some_and(|right| right.balance_factor() > 0)
+        {
+            node.right = node.right.take().map(rotate_right);
+        }
+        return rotate_left(node);
+    }
+    node
+}
+
+fn insert<T: Ord>(node: Link<T>, interval: Interval, value: T) -> Box<Node<T>> {
+    let Some(mut node) = node else {
+        return Box::new(Node {
+            max_end: interval.end,
+            interval,
+            value,
+            height: 1,
+            left: None,
+            right: None,
+        });
+    };
+    if key_cmp(&node, &interval, &value) == Ordering::Less {
+        node.left = Some(insert(node.left.take(), interval, value));
+    } else {
+        node.right = Some(insert(node.right.take(), interval, value));
+    }
+    rebalance(node)
+}
+
+/// Removes the leftmost node of a subtree, returning the remaining subtree and the removed node.
+fn remove_min<T>(mut node: Box<Node<T>>) -> (Link<T>, Box<Node<T>>) {
+    match node.left.take() {
+        Some(left) => {
+            let (rest, min) = remove_min(left);
+            node.left = rest;
+            (Some(rebalance(node)), min)
+        }
+        None => (node.right.take(), node),
+    }
+}
+
+fn remove<T: Ord>(node: Link<T>, interval: &Interval, value: &T, removed: &mut bool) -> Link<T> {
+    let mut node = node?;
+    match key_cmp(&node, interval, value) {
+        Ordering::Less => node.left = remove(node.left.take(), interval, value, removed),
+        Ordering::Greater => node.right = remove(node.right.take(), interval, value, removed),
+        Ordering::Equal => {
+            *removed = true;
+            return match (node.left.take(), node.right.take()) {
+                (None, None) => None,
+                (Some(child), None) | (None, Some(child)) => Some(child),
+                (Some(left), Some(right)) => {
+                    let (rest, mut successor) = remove_min(right);
+                    successor.left = Some(left);
+                    successor.right = rest;
+                    Some(rebalance(successor))
+                }
+            };
+        }
+    }
+    Some(rebalance(node))
+}
+
+fn overlapping<'a, T>(
+    node: Option<&'a Node<T>>,
+    query: &Interval,
+    found: &mut Vec<(&'a Interval, &'a T)>,
+) {
+    let Some(node) = node else {
+        return;
+    };
+    if node.max_end < query.start {
+        return;
+    }
+    overlapping(node.left.as_deref(), query, found);
+    if node.interval.intersects(query) {
+        found.push((&node.interval, &node.value));
+    }
+    if node.interval.start <= query.end {
+        overlapping(node.right.as_deref(), query, found);
+    }
+}
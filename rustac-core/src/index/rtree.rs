@@ -0,0 +1,234 @@
+//! An R-tree of bounding boxes.
+use serde::{Deserialize, Serialize};
+
+use super::Bounds;
+
+/// The maximum number of entries in a node before it is split.
+const MAX_ENTRIES: usize = 16;
+
+/// The minimum number of entries in a node other than the root. Nodes with fewer entries after a
+/// removal are dissolved and their values reinserted.
+const MIN_ENTRIES: usize = 4;
+
+/// An R-tree mapping bounding boxes to values, with nodes split along their widest axis.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub(crate) struct RTree<T> {
+    root: Node<T>,
+    len: usize,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "lowercase")]
+enum Node<T> {
+    Leaf(Vec<(Bounds, T)>),
+    Internal(Vec<(Bounds, Node<T>)>),
+}
+
+impl<T> Default for RTree<T> {
+    fn default() -> RTree<T> {
+        RTree {
+            root: Node::Leaf(Vec::new()),
+            len: 0,
+        }
+    }
+}
+
+impl<T: PartialEq> RTree<T> {
+    pub(crate) fn len(&self) -> usize {
+        self.len
+    }
+
+    pub(crate) fn insert(&mut self, bounds: Bounds, value: T) {
+        if let Some(sibling) = self.root.insert(bounds, value) {
+            let root = std::mem::replace(&mut self.root, Node::Internal(Vec::new()));
+            self.root = Node::Internal(vec![(root.bounds(), root), sibling]);
+        }
+        self.len += 1;
+    }
+
+    /// Removes the entry with these bounds and value, returning `true` if it was found.
+    pub(crate) fn remove(&mut self, bounds: &Bounds, value: &T) -> bool {
+        let mut orphans = Vec::new();
+        if !self.root.remove(bounds, value, &mut orphans) {
+            return false;
+        }
+        self.len -= 1;
+        loop {
+            match &mut self.root {
+                Node::Internal(children) if children.len() == 1 => {
+                    let (_, child) = children.remove(0);
+                    self.root = child;
+                }
+                Node::Internal(children) if children.is_empty() => {
+                    self.root = Node::Leaf(Vec::new());
+                }
+                _ => break,
+            }
+        }
+        for (bounds, value) in orphans {
+            self.len -= 1;
+            self.insert(bounds, value);
+        }
+        true
+    }
+
+    /// Returns the values of the entries whose bounds satisfy `test`. `prune` is called with the
+    /// bounds of each node and must return `true` if the node may contain matching entries.
+    pub(crate) fn search<P, F>(&self, prune: P, test: F) -> Vec<&T>
+    where
+        P: Fn(&Bounds) -> bool,
+        F: Fn(&Bounds) -> bool,
+    {
+        let mut found = Vec::new();
+        self.root.search(&prune, &test, &mut found);
+        found
+    }
+}
+
+impl<T: PartialEq> Node<T> {
+    fn len(&self) -> usize {
+        match self {
+            Node::Leaf(entries) => entries.len(),
+            Node::Internal(children) => children.len(),
+        }
+    }
+
+    fn bounds(&self) -> Bounds {
+        let all: Vec<Bounds> = match self {
+            Node::Leaf(entries) => entries.iter().map(|(bounds, _)| *bounds).collect(),
+            Node::Internal(children) => children.iter().map(|(bounds, _)| *bounds).collect(),
+        };
+        union(&all)
+    }
+
+    /// Inserts an entry, returning the new sibling of this node if it had to be split.
+    fn insert(&mut self, bounds: Bounds, value: T) -> Option<(Bounds, Node<T>)> {
+        match self {
+            Node::Leaf(entries) => {
+                entries.push((bounds, value));
+                if entries.len() <= MAX_ENTRIES {
+                    return None;
+                }
+                let sibling = Node::Leaf(split(entries));
+                Some((sibling.bounds(), sibling))
+            }
+            Node::Internal(children) => {
+                let index = choose_child(children, &bounds);
+                let (child_bounds, child) = &mut children[index];
+                let split_off = child.insert(bounds, value);
+                *child_bounds = child.bounds();
+                if let Some(sibling) = split_off {
+                    children.push(sibling);
+                }
+                if children.len() <= MAX_ENTRIES {
+                    return None;
+                }
+                let sibling = Node::Internal(split(children));
+                Some((sibling.bounds(), sibling))
+            }
+        }
+    }
+
+    /// Removes an entry, collecting the entries of dissolved nodes in `orphans`.
+    fn remove(&mut self, bounds: &Bounds, value: &T, orphans: &mut Vec<(Bounds, T)>) -> bool {
+        match self {
+            Node::Leaf(entries) => {
+                match entries
+                    .iter()
+                    .position(|(entry, entry_value)| entry == bounds && entry_value == value)
+                {
+                    Some(index) => {
+                        entries.swap_remove(index);
+                        true
+                    }
+                    None => false,
+                }
+            }
+            Node::Internal(children) => {
+                for index in 0..children.len() {
+                    let (child_bounds, child) = &mut children[index];
+                    if !child_bounds.contains(bounds) || !child.remove(bounds, value, orphans) {
+                        continue;
+                    }
+                    if child.len() < MIN_ENTRIES {
+                        let (_, child) = children.swap_remove(index);
+                        child.into_entries(orphans);
+                    } else {
+                        *child_bounds = child.bounds();
+                    }
+                    return true;
+                }
+                false
+            }
+        }
+    }
+
+    fn into_entries(self, entries: &mut Vec<(Bounds, T)>) {
+        match self {
+            Node::Leaf(leaf) => entries.extend(leaf),
+            Node::Internal(children) => {
+                for (_, child) in children {
+                    child.into_entries(entries);
+                }
+            }
+        }
+    }
+
+    fn search<'a, P, F>(&'a self, prune: &P, test: &F, found: &mut Vec<&'a T>)
+    where
+        P: Fn(&Bounds) -> bool,
+        F: Fn(&Bounds) -> bool,
+    {
+        match self {
+            Node::Leaf(entries) => found.extend(
+                entries
+                    .iter()
+                    .filter(|(bounds, _)| test(bounds))
+                    .map(|(_, value)| value),
+            ),
+            Node::Internal(children) => {
+                for (bounds, child) in children {
+                    if prune(bounds) {
+                        child.search(prune, test, found);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Chooses the child whose bounds grow the least to include `bounds`, preferring smaller children.
+fn choose_child<T>(children: &[(Bounds, Node<T>)], bounds: &Bounds) -> usize {
+    let cost = |child: &Bounds| (child.union(bounds).area() - child.area(), child.area());
+    let mut best = 0;
+    for index in 1..children.len() {
+        if cost(&children[index].0) < cost(&children[best].0) {
+            best = index;
+        }
+    }
+    best
+}
+
+/// Sorts entries by their centers along the widest axis of their union, keeps the first half in
+/// `entries` and returns the second half.
+fn split<V>(entries: &mut Vec<(Bounds, V)>) -> Vec<(Bounds, V)> {
+    let all: Vec<Bounds> = entries.iter().map(|(bounds, _)| *bounds).collect();
+    let union = union(&all);
+    let horizontal = union.max_x - union.min_x >= union.max_y - union.min_y;
+    let center = |bounds: &Bounds| {
+        if horizontal {
+            bounds.min_x + bounds.max_x
+        } else {
+            bounds.min_y + bounds.max_y
+        }
+    };
+    entries.sort_by(|(a, _), (b, _)| center(a).total_cmp(&center(b)));
+    entries.split_off(entries.len() / 2)
+}
+
+/// The union of some bounds, or empty bounds at the origin if there are none.
+fn union(all: &[Bounds]) -> Bounds {
+    let mut all = all.iter();
+    let first = all.next().copied().unwrap_or_default();
+    all.fold(first, |union, bounds| union.union(bounds))
+}
@@ -34,8 +34,19 @@
 //!
 //! STAC objects may contain fields not included in the core STAC spec itself. These fields are not serialized into
 //! "primitive" Rust types, but instead are flattened into a [`serde_json::Value`] in the `extra_fields` field
-//! on the `struct`. *This includes fields that may be defined as part of a [STAC Extension].* I plan on adding support
-//! for accessing extension fields as Rust types in a separate `stac-extensions` crate.
+//! on the `struct`. *This includes fields that may be defined as part of a [STAC Extension].* Typed access to the fields
+//! of supported extensions is provided by the modules in [`extensions`].
+//!
+//! ## Migration
+//!
+//! Objects written against older versions of the spec can be upgraded with [`migrate::migrate`], which
+//! returns the migrated JSON along with a log of every change that was made.
+//!
+//! ## Filtering
+//!
+//! [`cql2`] parses CQL2 filter expressions, written as CQL2-Text or CQL2-JSON, and evaluates them against Items.
+//! [`search`] runs STAC API Item Searches against Items in memory, such as those read from a Catalog on disk.
+//! [`index`] finds Items by bounding box and datetime without scanning all of them.
 //!
 //! ## Validation
 //!
@@ -124,9 +135,15 @@
 pub use types::{
     catalog::Catalog,
     collection::{Collection, Extent, SpatialExtent, TemporalExtent},
-    common::{Asset, Link, Provider},
-    item::Item,
+    common::{Asset, Link, Provider, ProviderRole},
+    item::{Item, ItemProperties},
 };
 
+pub mod cql2;
 pub mod error;
+pub mod extensions;
+pub mod index;
+pub mod migrate;
+pub mod pointer;
+pub mod search;
 mod types;
@@ -0,0 +1,606 @@
+//! Migrates STAC objects written against older versions of the spec.
+//!
+//! [`migrate`] works on the raw JSON of an Item, Collection or Catalog so that objects which no longer
+//! deserialize into the types in this crate can still be upgraded. Each change to the spec between the
+//! object's `stac_version` and the target version is applied in order and every modification is recorded
+//! in the returned [`Migration`]:
+//!
+//! | Version        | Changes |
+//! |----------------|---------|
+//! | `0.8.0`        | Collection extents use `{"bbox": [...]}` and `{"interval": [...]}` objects; an Item's `collection` moves out of `properties` |
+//! | `0.9.0`        | `eo:` fields that moved to the core spec, the View extension and the Projection extension are renamed; band indices on assets are replaced by the band objects; `gsd` and `accuracy` are removed from bands; `dtr:` fields move to the core spec; deprecated GeoTIFF media types are replaced |
+//! | `1.0.0-beta.1` | Collection `properties` (the Commons extension) move to `summaries`; `checksum:multihash` becomes `file:checksum` |
+//! | `1.0.0-rc.1`   | Catalogs and Collections get a `type`; `min`/`max` summaries become `minimum`/`maximum`; an Item's `collection` is removed if it has no `collection` link; short extension IDs are replaced by schema URIs |
+//!
+//! ```
+//! use rustac_core::migrate::migrate;
+//! use rustac_core::Collection;
+//! use semver::Version;
+//! use serde_json::json;
+//!
+//! let old = json!({
+//!     "stac_version": "0.7.0",
+//!     "id": "old-collection",
+//!     "description": "A collection from before 0.8.0",
+//!     "license": "CC-BY-4.0",
+//!     "extent": {
+//!         "spatial": [-180.0, -90.0, 180.0, 90.0],
+//!         "temporal": ["2019-01-01T00:00:00Z", null]
+//!     },
+//!     "links": []
+//! });
+//!
+//! let migration = migrate(&old, &Version::parse("1.0.0").unwrap()).unwrap();
+//! for change in &migration.changes {
+//!     println!("{}", change);
+//! }
+//!
+//! let collection: Collection = serde_json::from_value(migration.value).unwrap();
+//! assert_eq!(collection.r#type, "Collection");
+//! ```
+use semver::Version;
+use serde_json::{json, Map, Value};
+use std::convert::TryFrom;
+use std::fmt;
+
+use crate::error::{STACError, STACResult};
+use crate::extensions::{
+    datacube, eo, file, item_assets, label, pointcloud, processing, proj, raster, sar, sat, sci,
+    storage, timestamps, version, view,
+};
+use crate::pointer::escape as escape_pointer;
+
+/// The most recent version of the spec that objects can be migrated to.
+pub const LATEST_VERSION: &str = "1.0.0";
+
+/// Extensions whose fields are renamed by the `0.9.0` migration.
+const EO_RENAMES: [(&str, &str, Option<&str>); 10] = [
+    ("eo:gsd", "gsd", None),
+    ("eo:platform", "platform", None),
+    ("eo:constellation", "constellation", None),
+    ("eo:instrument", "instruments", None),
+    ("eo:off_nadir", "view:off_nadir", Some("view")),
+    ("eo:azimuth", "view:azimuth", Some("view")),
+    ("eo:incidence_angle", "view:incidence_angle", Some("view")),
+    ("eo:sun_azimuth", "view:sun_azimuth", Some("view")),
+    ("eo:sun_elevation", "view:sun_elevation", Some("view")),
+    ("eo:epsg", "proj:epsg", Some("projection")),
+];
+
+/// Media types that were replaced in `0.9.0`.
+const MEDIA_TYPES: [(&str, &str); 3] = [
+    (
+        "image/vnd.stac.geotiff; cloud-optimized=true",
+        "image/tiff; application=geotiff; profile=cloud-optimized",
+    ),
+    ("image/vnd.stac.geotiff", "image/tiff; application=geotiff"),
+    ("image/x.geotiff", "image/tiff; application=geotiff"),
+];
+
+/// Schema URIs for the short extension IDs used before `1.0.0-rc.1`, taken from the typed modules so
+/// that migrated objects declare the versions those modules read and write.
+const EXTENSION_URIS: [(&str, &str); 20] = [
+    ("eo", eo::SCHEMA_URI),
+    ("projection", proj::SCHEMA_URI),
+    ("proj", proj::SCHEMA_URI),
+    ("scientific", sci::SCHEMA_URI),
+    ("sci", sci::SCHEMA_URI),
+    ("view", view::SCHEMA_URI),
+    ("sat", sat::SCHEMA_URI),
+    ("sar", sar::SCHEMA_URI),
+    ("label", label::SCHEMA_URI),
+    ("pointcloud", pointcloud::SCHEMA_URI),
+    ("pc", pointcloud::SCHEMA_URI),
+    ("version", version::SCHEMA_URI),
+    ("file", file::SCHEMA_URI),
+    ("timestamps", timestamps::SCHEMA_URI),
+    ("processing", processing::SCHEMA_URI),
+    ("item-assets", item_assets::SCHEMA_URI),
+    ("asset", item_assets::SCHEMA_URI),
+    ("datacube", datacube::SCHEMA_URI),
+    ("raster", raster::SCHEMA_URI),
+    ("storage", storage::SCHEMA_URI),
+];
+
+/// The result of migrating an object with [`migrate`].
+#[derive(Debug, Clone)]
+pub struct Migration {
+    /// The migrated object.
+    pub value: Value,
+
+    /// Every change that was made, in the order it was applied.
+    pub changes: Vec<Change>,
+}
+
+/// A single modification made while migrating an object.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Change {
+    /// The version of the spec that required the change.
+    pub version: Version,
+
+    /// JSON Pointer to the attribute that was changed, in the migrated object.
+    pub pointer: String,
+
+    /// Description of the change.
+    pub description: String,
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "[{}] {}: {}",
+            self.version, self.pointer, self.description
+        )
+    }
+}
+
+/// Migrates a STAC Item, Collection or Catalog to `target_version`, returning the migrated object along
+/// with a log of the changes that were made. The input is not modified.
+///
+/// # Errors
+///
+/// * [`STACError::Other`] if the value is not a STAC Item, Collection or Catalog, has no `stac_version`,
+///   or if `target_version` is older than the object or newer than [`LATEST_VERSION`].
+/// * [`STACError::SemVer`] if the object's `stac_version` is not a valid version.
+pub fn migrate(value: &Value, target_version: &Version) -> STACResult<Migration> {
+    let mut object = value
+        .as_object()
+        .cloned()
+        .ok_or_else(|| STACError::Other(String::from("STAC objects must be JSON objects")))?;
+    let kind = Kind::of(&object)?;
+    let version = match object.get("stac_version") {
+        Some(Value::String(version)) => Version::parse(version)?,
+        _ => {
+            return Err(STACError::Other(String::from(
+                "Cannot migrate an object without a stac_version",
+            )))
+        }
+    };
+    if *target_version < version {
+        return Err(STACError::Other(format!(
+            "Cannot migrate from {version} to the older version {target_version}"
+        )));
+    }
+    if *target_version > Version::parse(LATEST_VERSION)? {
+        return Err(STACError::Other(format!(
+            "Cannot migrate to {target_version}, the latest supported version is {LATEST_VERSION}"
+        )));
+    }
+
+    let steps: [(&str, Step); 12] = [
+        ("0.8.0", extent_objects),
+        ("0.8.0", collection_property),
+        ("0.9.0", eo_renames),
+        ("0.9.0", eo_bands),
+        ("0.9.0", datetime_range),
+        ("0.9.0", media_types),
+        ("1.0.0-beta.1", commons),
+        ("1.0.0-beta.1", checksum),
+        ("1.0.0-rc.1", object_type),
+        ("1.0.0-rc.1", summary_ranges),
+        ("1.0.0-rc.1", collection_link),
+        ("1.0.0-rc.1", extension_uris),
+    ];
+
+    let mut log = Log {
+        kind,
+        version: version.clone(),
+        changes: Vec::new(),
+    };
+    for (step_version, step) in &steps {
+        let step_version = Version::parse(step_version)?;
+        if version < step_version && step_version <= *target_version {
+            log.version = step_version;
+            step(&mut object, &mut log);
+        }
+    }
+
+    if version != *target_version {
+        log.version = target_version.clone();
+        object.insert(
+            String::from("stac_version"),
+            Value::String(target_version.to_string()),
+        );
+        log.push(&["stac_version"], format!("set to {target_version}"));
+    }
+
+    Ok(Migration {
+        value: Value::Object(object),
+        changes: log.changes,
+    })
+}
+
+type Step = fn(&mut Map<String, Value>, &mut Log);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Item,
+    Collection,
+    Catalog,
+}
+
+impl Kind {
+    fn of(object: &Map<String, Value>) -> STACResult<Kind> {
+        match object.get("type").and_then(Value::as_str) {
+            Some("Feature") => Ok(Kind::Item),
+            Some("Collection") => Ok(Kind::Collection),
+            Some("Catalog") => Ok(Kind::Catalog),
+            Some(other) => Err(STACError::Other(format!(
+                "Cannot migrate an object of type {other}"
+            ))),
+            // Catalogs and Collections had no type before 1.0.0-rc.1
+            None if object.contains_key("extent") => Ok(Kind::Collection),
+            None => Ok(Kind::Catalog),
+        }
+    }
+}
+
+struct Log {
+    kind: Kind,
+    version: Version,
+    changes: Vec<Change>,
+}
+
+impl Log {
+    fn push(&mut self, path: &[&str], description: String) {
+        let mut pointer = String::new();
+        for part in path {
+            pointer.push('/');
+            pointer.push_str(&escape_pointer(part));
+        }
+        self.changes.push(Change {
+            version: self.version.clone(),
+            pointer,
+            description,
+        });
+    }
+}
+
+/// Moves the value at `from` to `to` within the same object, unless `to` is already set, in which case
+/// the old attribute is dropped. Returns `true` if anything was changed.
+fn rename(
+    object: &mut Map<String, Value>,
+    path: &[&str],
+    from: &str,
+    to: &str,
+    log: &mut Log,
+) -> bool {
+    let Some(value) = object.remove(from) else {
+        return false;
+    };
+    let old_path = [path, &[from]].concat();
+    if object.contains_key(to) {
+        log.push(&old_path, format!("removed because {to} is already set"));
+    } else {
+        object.insert(to.into(), value);
+        log.push(&[path, &[to]].concat(), format!("renamed from {from}"));
+    }
+    true
+}
+
+fn add_extension(object: &mut Map<String, Value>, id: &str, log: &mut Log) {
+    let extensions = object.entry("stac_extensions").or_insert_with(|| json!([]));
+    if let Some(extensions) = extensions.as_array_mut() {
+        if !extensions.iter().any(|extension| extension == id) {
+            extensions.push(Value::String(id.into()));
+            log.push(&["stac_extensions"], format!("added {id}"));
+        }
+    }
+}
+
+fn remove_extension(object: &mut Map<String, Value>, id: &str, log: &mut Log) {
+    if let Some(extensions) = object
+        .get_mut("stac_extensions")
+        .and_then(Value::as_array_mut)
+    {
+        let count = extensions.len();
+        extensions.retain(|extension| extension != id);
+        if extensions.len() != count {
+            log.push(&["stac_extensions"], format!("removed {id}"));
+        }
+    }
+}
+
+/// Calls `f` for each asset and link of the object, along with the path to it.
+fn for_each_asset_and_link<F>(object: &mut Map<String, Value>, mut f: F)
+where
+    F: FnMut(&mut Map<String, Value>, &[&str]),
+{
+    if let Some(assets) = object.get_mut("assets").and_then(Value::as_object_mut) {
+        for (key, asset) in assets.iter_mut() {
+            if let Some(asset) = asset.as_object_mut() {
+                f(asset, &["assets", key]);
+            }
+        }
+    }
+    if let Some(links) = object.get_mut("links").and_then(Value::as_array_mut) {
+        for (index, link) in links.iter_mut().enumerate() {
+            if let Some(link) = link.as_object_mut() {
+                f(link, &["links", &index.to_string()]);
+            }
+        }
+    }
+}
+
+fn extent_objects(object: &mut Map<String, Value>, log: &mut Log) {
+    if log.kind != Kind::Collection {
+        return;
+    }
+    let Some(extent) = object.get_mut("extent").and_then(Value::as_object_mut) else {
+        return;
+    };
+    for (key, field) in [("spatial", "bbox"), ("temporal", "interval")] {
+        if let Some(Value::Array(values)) = extent.get(key) {
+            let value = json!({ field: [values] });
+            extent.insert(key.into(), value);
+            log.push(
+                &["extent", key, field],
+                format!("wrapped the {key} extent in an object"),
+            );
+        }
+    }
+}
+
+fn collection_property(object: &mut Map<String, Value>, log: &mut Log) {
+    if log.kind != Kind::Item {
+        return;
+    }
+    let collection = object
+        .get_mut("properties")
+        .and_then(Value::as_object_mut)
+        .and_then(|properties| properties.remove("collection"));
+    if let Some(collection) = collection {
+        if object.contains_key("collection") {
+            log.push(
+                &["properties", "collection"],
+                String::from("removed because collection is already set"),
+            );
+        } else {
+            object.insert(String::from("collection"), collection);
+            log.push(&["collection"], String::from("moved from properties"));
+        }
+    }
+}
+
+fn eo_renames(object: &mut Map<String, Value>, log: &mut Log) {
+    let Some(properties) = object.get_mut("properties").and_then(Value::as_object_mut) else {
+        return;
+    };
+    let mut extensions = Vec::new();
+    for (from, to, extension) in &EO_RENAMES {
+        if rename(properties, &["properties"], from, to, log) {
+            if let Some(extension) = extension {
+                extensions.push(*extension);
+            }
+        }
+    }
+    if let Some(instrument @ Value::String(_)) = properties.get_mut("instruments") {
+        *instrument = json!([instrument]);
+        log.push(
+            &["properties", "instruments"],
+            String::from("wrapped the instrument in an array"),
+        );
+    }
+    for extension in extensions {
+        add_extension(object, extension, log);
+    }
+}
+
+fn eo_bands(object: &mut Map<String, Value>, log: &mut Log) {
+    let bands = object
+        .get("properties")
+        .and_then(|properties| properties.get("eo:bands"))
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default();
+    if let Some(assets) = object.get_mut("assets").and_then(Value::as_object_mut) {
+        for (key, asset) in assets.iter_mut() {
+            let Some(Value::Array(asset_bands)) = asset.get_mut("eo:bands") else {
+                continue;
+            };
+            if !asset_bands.iter().all(Value::is_u64) {
+                continue;
+            }
+            let resolved: Option<Vec<Value>> = asset_bands
+                .iter()
+                .map(|index| {
+                    let index = usize::try_from(index.as_u64()?).ok()?;
+                    bands.get(index).cloned()
+                })
+                .collect();
+            if let Some(resolved) = resolved {
+                *asset_bands = resolved;
+                log.push(
+                    &["assets", key, "eo:bands"],
+                    String::from("replaced band indices with band objects"),
+                );
+            }
+        }
+    }
+
+    if let Some(Value::Array(bands)) = object
+        .get_mut("properties")
+        .and_then(|properties| properties.get_mut("eo:bands"))
+    {
+        for (index, band) in bands.iter_mut().enumerate() {
+            remove_band_fields(band, &["properties", "eo:bands", &index.to_string()], log);
+        }
+    }
+    if let Some(assets) = object.get_mut("assets").and_then(Value::as_object_mut) {
+        for (key, asset) in assets.iter_mut() {
+            if let Some(Value::Array(bands)) = asset.get_mut("eo:bands") {
+                for (index, band) in bands.iter_mut().enumerate() {
+                    remove_band_fields(band, &["assets", key, "eo:bands", &index.to_string()], log);
+                }
+            }
+        }
+    }
+}
+
+fn remove_band_fields(band: &mut Value, path: &[&str], log: &mut Log) {
+    let Some(band) = band.as_object_mut() else {
+        return;
+    };
+    for field in ["gsd", "accuracy"] {
+        if band.remove(field).is_some() {
+            log.push(
+                &[path, &[field]].concat(),
+                String::from("removed deprecated band field"),
+            );
+        }
+    }
+}
+
+fn datetime_range(object: &mut Map<String, Value>, log: &mut Log) {
+    if let Some(properties) = object.get_mut("properties").and_then(Value::as_object_mut) {
+        rename(
+            properties,
+            &["properties"],
+            "dtr:start_datetime",
+            "start_datetime",
+            log,
+        );
+        rename(
+            properties,
+            &["properties"],
+            "dtr:end_datetime",
+            "end_datetime",
+            log,
+        );
+    }
+    remove_extension(object, "datetime-range", log);
+    remove_extension(object, "dtr", log);
+}
+
+fn media_types(object: &mut Map<String, Value>, log: &mut Log) {
+    for_each_asset_and_link(object, |target, path| {
+        let Some(Value::String(media_type)) = target.get_mut("type") else {
+            return;
+        };
+        if let Some((old, new)) = MEDIA_TYPES.iter().find(|(old, _)| media_type == old) {
+            *media_type = String::from(*new);
+            log.push(&[path, &["type"]].concat(), format!("replaced {old}"));
+        }
+    });
+}
+
+fn commons(object: &mut Map<String, Value>, log: &mut Log) {
+    if log.kind == Kind::Collection {
+        if let Some(Value::Object(properties)) = object.remove("properties") {
+            log.push(
+                &["properties"],
+                String::from("removed the Commons extension properties"),
+            );
+            let summaries = object.entry("summaries").or_insert_with(|| json!({}));
+            if let Some(summaries) = summaries.as_object_mut() {
+                for (key, value) in properties {
+                    if !summaries.contains_key(&key) {
+                        log.push(&["summaries", &key], String::from("moved from properties"));
+                        summaries.insert(key, json!([value]));
+                    }
+                }
+            }
+        }
+    }
+    remove_extension(object, "commons", log);
+}
+
+fn checksum(object: &mut Map<String, Value>, log: &mut Log) {
+    let mut renamed = false;
+    for_each_asset_and_link(object, |target, path| {
+        renamed |= rename(target, path, "checksum:multihash", "file:checksum", log);
+    });
+    remove_extension(object, "checksum", log);
+    if renamed {
+        add_extension(object, "file", log);
+    }
+}
+
+fn object_type(object: &mut Map<String, Value>, log: &mut Log) {
+    let object_type = match log.kind {
+        Kind::Item => return,
+        Kind::Collection => "Collection",
+        Kind::Catalog => "Catalog",
+    };
+    if object.get("type").and_then(Value::as_str) != Some(object_type) {
+        object.insert(String::from("type"), Value::String(object_type.into()));
+        log.push(&["type"], format!("set to {object_type}"));
+    }
+}
+
+fn summary_ranges(object: &mut Map<String, Value>, log: &mut Log) {
+    let Some(summaries) = object.get_mut("summaries").and_then(Value::as_object_mut) else {
+        return;
+    };
+    for (key, summary) in summaries.iter_mut() {
+        if let Some(summary) = summary.as_object_mut() {
+            let path = ["summaries", key.as_str()];
+            rename(summary, &path, "min", "minimum", log);
+            rename(summary, &path, "max", "maximum", log);
+        }
+    }
+}
+
+fn collection_link(object: &mut Map<String, Value>, log: &mut Log) {
+    if log.kind != Kind::Item || !object.contains_key("collection") {
+        return;
+    }
+    let has_link = object
+        .get("links")
+        .and_then(Value::as_array)
+        .is_some_and(|links| {
+            links
+                .iter()
+                .any(|link| link.get("rel").and_then(Value::as_str) == Some("collection"))
+        });
+    if !has_link {
+        object.remove("collection");
+        log.push(
+            &["collection"],
+            String::from("removed because the Item has no collection link"),
+        );
+    }
+}
+
+fn extension_uris(object: &mut Map<String, Value>, log: &mut Log) {
+    let Some(extensions) = object
+        .get_mut("stac_extensions")
+        .and_then(Value::as_array_mut)
+    else {
+        return;
+    };
+    // Aliases like `proj` and `projection` map to the same URI, so duplicates are removed before
+    // logging the index each replacement ends up at
+    let mut migrated: Vec<Value> = Vec::with_capacity(extensions.len());
+    for extension in extensions.drain(..) {
+        let id = extension.as_str().map(String::from);
+        let uri = id.as_deref().and_then(|id| {
+            EXTENSION_URIS
+                .iter()
+                .find(|(short, _)| *short == id)
+                .map(|(_, uri)| Value::String(String::from(*uri)))
+        });
+        let replaced = uri.is_some();
+        let value = uri.unwrap_or(extension);
+        let described = id.unwrap_or_else(|| value.to_string());
+        if migrated.contains(&value) {
+            let listed = value
+                .as_str()
+                .map_or_else(|| value.to_string(), String::from);
+            log.push(
+                &["stac_extensions"],
+                format!("removed {described} because {listed} is already listed"),
+            );
+        } else {
+            if replaced {
+                log.push(
+                    &["stac_extensions", &migrated.len().to_string()],
+                    format!("replaced {described} with its schema URI"),
+                );
+            }
+            migrated.push(value);
+        }
+    }
+    *extensions = migrated;
+}
@@ -0,0 +1,14 @@
+//! Helpers for [JSON Pointers](https://www.rfc-editor.org/rfc/rfc6901), which are used to locate
+//! attributes in migration logs and validation reports.
+//!
+//! ```
+//! use rustac_core::pointer;
+//!
+//! assert_eq!(pointer::escape("a/b~c"), "a~1b~0c");
+//! ```
+
+/// Escapes a key for use as a JSON Pointer reference token.
+#[must_use]
+pub fn escape(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
@@ -0,0 +1,801 @@
+//! Describes [Item Searches](https://github.com/radiantearth/stac-api-spec/tree/v1.0.0/item-search)
+//! and runs them against Items in memory.
+//!
+//! A [`Search`] holds the same parameters that are sent to a STAC API, including the
+//! [Query](https://github.com/stac-api-extensions/query),
+//! [Filter](https://github.com/stac-api-extensions/filter),
+//! [Sort](https://github.com/stac-api-extensions/sort) and
+//! [Fields](https://github.com/stac-api-extensions/fields) extensions, so Items read from disk with
+//! [`read_items`] can be searched with the same semantics as an API:
+//!
+//! ```
+//! use rustac_core::search::{Search, SortBy};
+//! use rustac_core::Item;
+//! use serde_json::json;
+//!
+//! let items: Vec<Item> = (1..=3)
+//!     .map(|i| {
+//!         serde_json::from_value(json!({
+//!             "stac_version": "1.0.0",
+//!             "type": "Feature",
+//!             "id": format!("item-{i}"),
+//!             "bbox": [0.0, 0.0, 1.0, 1.0],
+//!             "geometry": {"type": "Point", "coordinates": [0.5, 0.5]},
+//!             "properties": {"datetime": format!("2021-0{i}-01T00:00:00Z"), "eo:cloud_cover": i * 10},
+//!             "links": [],
+//!             "assets": {}
+//!         }))
+//!         .unwrap()
+//!     })
+//!     .collect();
+//!
+//! let search = Search::new()
+//!     .bbox(vec![0.0, 0.0, 2.0, 2.0])
+//!     .datetime("2021-02-01T00:00:00Z/..")
+//!     .filter("\"eo:cloud_cover\" > 5".parse().unwrap())
+//!     .sortby(vec![SortBy::desc("datetime")]);
+//! let ids: Vec<_> = search.search(items).unwrap().into_iter().map(|item| item.id).collect();
+//! assert_eq!(ids, vec!["item-3", "item-2"]);
+//! ```
+use geojson::Geometry;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::convert::TryFrom;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::cql2::{Expr, Op};
+use crate::error::{STACError, STACResult};
+use crate::{Collection, Item, Link};
+
+/// The parameters of an Item Search. Parameters that are not set are not sent to an API and do not
+/// restrict the Items matched locally.
+///
+/// ```
+/// use rustac_core::search::Search;
+///
+/// let search = Search::new()
+///     .collections(vec!["sentinel-2-l2a"])
+///     .bbox(vec![-105.1, 39.9, -104.9, 40.1])
+///     .datetime("2021-01-01T00:00:00Z/..")
+///     .limit(10);
+///
+/// assert_eq!(
+///     search.to_query(),
+///     vec![
+///         (String::from("bbox"), String::from("-105.1,39.9,-104.9,40.1")),
+///         (String::from("datetime"), String::from("2021-01-01T00:00:00Z/..")),
+///         (String::from("collections"), String::from("sentinel-2-l2a")),
+///         (String::from("limit"), String::from("10")),
+///     ]
+/// );
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct Search {
+    /// Only return Items that intersect this bounding box, with 4 or 6 numbers.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bbox: Option<Vec<f64>>,
+
+    /// Only return Items that intersect this geometry. Must not be combined with `bbox`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub intersects: Option<Geometry>,
+
+    /// Only return Items with a datetime or datetime range that intersects this datetime or
+    /// interval, e.g. `2021-01-01T00:00:00Z` or `2021-01-01T00:00:00Z/..`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub datetime: Option<String>,
+
+    /// Only return Items with one of these ids.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ids: Option<Vec<String>>,
+
+    /// Only return Items in one of these Collections.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub collections: Option<Vec<String>>,
+
+    /// The maximum number of Items on each page of results. Local searches return at most this many
+    /// Items.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u64>,
+
+    /// Only return Items whose properties match these conditions, from the Query extension, e.g.
+    /// `{"eo:cloud_cover": {"lt": 10}}`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub query: Option<Map<String, Value>>,
+
+    /// Only return Items matching this CQL2 expression, from the Filter extension.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filter: Option<Expr>,
+
+    /// The fields to sort Items by, from the Sort extension.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sortby: Option<Vec<SortBy>>,
+
+    /// The fields to include in or exclude from each Item, from the Fields extension.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fields: Option<Fields>,
+}
+
+/// A field to sort by, and the direction to sort in.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SortBy {
+    /// The name of the field, e.g. `properties.eo:cloud_cover`. Fields that are not `id` or
+    /// `collection` are looked up in the Item's properties, with or without a `properties.` prefix.
+    pub field: String,
+
+    /// The direction to sort in.
+    pub direction: Direction,
+}
+
+/// The direction of a [`SortBy`].
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Direction {
+    /// Smallest values first.
+    Asc,
+
+    /// Largest values first.
+    Desc,
+}
+
+/// The fields to include in or exclude from Items, as dot-separated paths such as
+/// `properties.eo:cloud_cover`.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct Fields {
+    /// If not empty, only these fields are returned.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub include: Vec<String>,
+
+    /// These fields are removed, unless they are also included.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclude: Vec<String>,
+}
+
+impl SortBy {
+    /// Sorts by `field` in ascending order.
+    pub fn asc<S: Into<String>>(field: S) -> SortBy {
+        SortBy {
+            field: field.into(),
+            direction: Direction::Asc,
+        }
+    }
+
+    /// Sorts by `field` in descending order.
+    pub fn desc<S: Into<String>>(field: S) -> SortBy {
+        SortBy {
+            field: field.into(),
+            direction: Direction::Desc,
+        }
+    }
+}
+
+impl Fields {
+    /// Applies the fields to the JSON of an Item. Included fields are copied from `value` in the
+    /// order they are listed, then excluded fields are removed.
+    #[must_use]
+    pub fn apply(&self, value: Value) -> Value {
+        let mut value = if self.include.is_empty() {
+            value
+        } else {
+            let mut included = Value::Object(Map::new());
+            for path in &self.include {
+                if let Some(field) = get_path(&value, path) {
+                    set_path(&mut included, path, field.clone());
+                }
+            }
+            included
+        };
+        for path in self
+            .exclude
+            .iter()
+            .filter(|path| !self.include.contains(path))
+        {
+            remove_path(&mut value, path);
+        }
+        value
+    }
+}
+
+fn get_path<'a>(value: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.').try_fold(value, |value, key| value.get(key))
+}
+
+fn set_path(value: &mut Value, path: &str, field: Value) {
+    let mut keys: Vec<&str> = path.split('.').collect();
+    let last = keys.pop().unwrap_or_default();
+    let mut object = value;
+    for key in keys {
+        let Some(map) = object.as_object_mut() else {
+            return;
+        };
+        object = map.entry(key).or_insert_with(|| Value::Object(Map::new()));
+    }
+    if let Some(map) = object.as_object_mut() {
+        map.insert(String::from(last), field);
+    }
+}
+
+fn remove_path(value: &mut Value, path: &str) {
+    let (parent, key) = match path.rsplit_once('.') {
+        Some((parent, key)) => (
+            parent
+                .split('.')
+                .try_fold(value, |value, key| value.get_mut(key)),
+            key,
+        ),
+        None => (Some(value), path),
+    };
+    if let Some(Value::Object(map)) = parent {
+        map.remove(key);
+    }
+}
+
+impl Search {
+    /// Creates a search without any parameters, which matches all Items.
+    #[must_use]
+    pub fn new() -> Search {
+        Search::default()
+    }
+
+    /// Sets the bounding box.
+    #[must_use]
+    pub fn bbox(mut self, bbox: Vec<f64>) -> Search {
+        self.bbox = Some(bbox);
+        self
+    }
+
+    /// Sets the geometry Items must intersect.
+    #[must_use]
+    pub fn intersects(mut self, intersects: Geometry) -> Search {
+        self.intersects = Some(intersects);
+        self
+    }
+
+    /// Sets the datetime or interval.
+    #[must_use]
+    pub fn datetime<S: Into<String>>(mut self, datetime: S) -> Search {
+        self.datetime = Some(datetime.into());
+        self
+    }
+
+    /// Sets the Item ids.
+    #[must_use]
+    pub fn ids<S: Into<String>>(mut self, ids: Vec<S>) -> Search {
+        self.ids = Some(ids.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Sets the Collection ids.
+    #[must_use]
+    pub fn collections<S: Into<String>>(mut self, collections: Vec<S>) -> Search {
+        self.collections = Some(collections.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Sets the page size.
+    #[must_use]
+    pub fn limit(mut self, limit: u64) -> Search {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Sets the property query.
+    #[must_use]
+    pub fn query(mut self, query: Map<String, Value>) -> Search {
+        self.query = Some(query);
+        self
+    }
+
+    /// Sets the CQL2 filter.
+    #[must_use]
+    pub fn filter(mut self, filter: Expr) -> Search {
+        self.filter = Some(filter);
+        self
+    }
+
+    /// Sets the fields to sort by.
+    #[must_use]
+    pub fn sortby(mut self, sortby: Vec<SortBy>) -> Search {
+        self.sortby = Some(sortby);
+        self
+    }
+
+    /// Sets the fields to include and exclude.
+    #[must_use]
+    pub fn fields(mut self, fields: Fields) -> Search {
+        self.fields = Some(fields);
+        self
+    }
+
+    /// Encodes the parameters as query parameters for a `GET` request. Arrays are joined with
+    /// commas, `intersects` and `query` are written as JSON, `filter` is written as CQL2-Text, and
+    /// `sortby` and `fields` use `-` (and `+`) prefixes.
+    #[must_use]
+    pub fn to_query(&self) -> Vec<(String, String)> {
+        let join = |values: &[String]| values.join(",");
+        let mut query = Vec::new();
+        let mut push = |name: &str, value: Option<String>| {
+            if let Some(value) = value {
+                query.push((String::from(name), value));
+            }
+        };
+        push(
+            "bbox",
+            self.bbox.as_ref().map(|bbox| {
+                bbox.iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(",")
+            }),
+        );
+        push(
+            "intersects",
+            self.intersects.as_ref().map(ToString::to_string),
+        );
+        push("datetime", self.datetime.clone());
+        push("ids", self.ids.as_deref().map(join));
+        push("collections", self.collections.as_deref().map(join));
+        push("limit", self.limit.map(|limit| limit.to_string()));
+        push(
+            "query",
+            self.query
+                .as_ref()
+                .map(|query| Value::Object(query.clone()).to_string()),
+        );
+        push("filter", self.filter.as_ref().map(Expr::to_text));
+        push(
+            "filter-lang",
+            self.filter.as_ref().map(|_| String::from("cql2-text")),
+        );
+        push(
+            "sortby",
+            self.sortby.as_ref().map(|sortby| {
+                sortby
+                    .iter()
+                    .map(|sortby| match sortby.direction {
+                        Direction::Asc => format!("+{}", sortby.field),
+                        Direction::Desc => format!("-{}", sortby.field),
+                    })
+                    .collect::<Vec<_>>()
+                    .join(",")
+            }),
+        );
+        push(
+            "fields",
+            self.fields.as_ref().map(|fields| {
+                fields
+                    .include
+                    .iter()
+                    .cloned()
+                    .chain(fields.exclude.iter().map(|field| format!("-{field}")))
+                    .collect::<Vec<_>>()
+                    .join(",")
+            }),
+        );
+        query
+    }
+
+    /// Decodes the query parameters of a `GET` request, as written by [`Search::to_query`].
+    /// `filter` is parsed as CQL2-Text unless `filter-lang` is `cql2-json`, and parameters that are
+    /// not part of a search are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::Other`] if a parameter cannot be decoded.
+    pub fn from_query(query: &[(String, String)]) -> STACResult<Search> {
+        let invalid = |name: &str, value: &str, err: &dyn std::fmt::Display| {
+            STACError::Other(format!("Invalid {name} {value}: {err}"))
+        };
+        let split = |value: &str| -> Vec<String> {
+            value
+                .split(',')
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(String::from)
+                .collect()
+        };
+        let filter_lang = query
+            .iter()
+            .find(|(name, _)| name == "filter-lang")
+            .map_or("cql2-text", |(_, lang)| lang.as_str());
+        let mut search = Search::new();
+        for (name, value) in query {
+            match name.as_str() {
+                "bbox" => {
+                    let bbox = split(value)
+                        .iter()
+                        .map(|number| number.parse::<f64>())
+                        .collect::<Result<Vec<_>, _>>()
+                        .map_err(|err| invalid(name, value, &err))?;
+                    search.bbox = Some(bbox);
+                }
+                "intersects" => {
+                    let json =
+                        serde_json::from_str(value).map_err(|err| invalid(name, value, &err))?;
+                    let intersects = Geometry::from_json_value(json)
+                        .map_err(|err| invalid(name, value, &err))?;
+                    search.intersects = Some(intersects);
+                }
+                "datetime" => search.datetime = Some(value.clone()),
+                "ids" => search.ids = Some(split(value)),
+                "collections" => search.collections = Some(split(value)),
+                "limit" => {
+                    let limit = value.parse().map_err(|err| invalid(name, value, &err))?;
+                    search.limit = Some(limit);
+                }
+                "query" => {
+                    let query =
+                        serde_json::from_str(value).map_err(|err| invalid(name, value, &err))?;
+                    search.query = Some(query);
+                }
+                "filter" => {
+                    let filter = match filter_lang {
+                        "cql2-text" => Expr::from_text(value),
+                        "cql2-json" => serde_json::from_str(value)
+                            .map_err(|err| crate::cql2::ParseError::new(err.to_string(), None))
+                            .and_then(|value| Expr::from_json(&value)),
+                        lang => {
+                            return Err(STACError::Other(format!("Unsupported filter-lang {lang}")))
+                        }
+                    };
+                    search.filter = Some(filter.map_err(|err| invalid(name, value, &err))?);
+                }
+                "sortby" => {
+                    let sortby = split(value)
+                        .into_iter()
+                        .map(|field| match field.strip_prefix('-') {
+                            Some(field) => SortBy::desc(field),
+                            None => SortBy::asc(field.trim_start_matches('+')),
+                        })
+                        .collect();
+                    search.sortby = Some(sortby);
+                }
+                "fields" => {
+                    let (exclude, include): (Vec<_>, Vec<_>) = split(value)
+                        .into_iter()
+                        .partition(|field| field.starts_with('-'));
+                    search.fields = Some(Fields {
+                        include: include
+                            .into_iter()
+                            .map(|field| field.trim_start_matches('+').to_string())
+                            .collect(),
+                        exclude: exclude
+                            .into_iter()
+                            .map(|field| field[1..].to_string())
+                            .collect(),
+                    });
+                }
+                _ => {}
+            }
+        }
+        Ok(search)
+    }
+
+    /// Combines the parameters that restrict which Items match into a single CQL2 expression:
+    /// `bbox` and `intersects` become `S_INTERSECTS` on `geometry`, `datetime` becomes
+    /// `T_INTERSECTS` on `datetime`, `ids` and `collections` become `IN` conditions, and `query`
+    /// becomes comparisons and `LIKE` conditions. A search without any of these is `TRUE`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::Other`] if the `bbox`, `datetime` or `query` is invalid.
+    pub fn to_cql2(&self) -> STACResult<Expr> {
+        let property = |name: &str| Expr::Property(String::from(name));
+        let operation = |op: Op, args: Vec<Expr>| Expr::Operation { op, args };
+        let strings =
+            |values: &[String]| Expr::Array(values.iter().cloned().map(Expr::String).collect());
+        let mut conditions = Vec::new();
+        if let Some(bbox) = &self.bbox {
+            if bbox.len() != 4 && bbox.len() != 6 {
+                return Err(STACError::Other(format!(
+                    "bbox must have 4 or 6 numbers, found {}",
+                    bbox.len()
+                )));
+            }
+            conditions.push(operation(
+                Op::SIntersects,
+                vec![property("geometry"), Expr::Bbox(bbox.clone())],
+            ));
+        }
+        if let Some(intersects) = &self.intersects {
+            conditions.push(operation(
+                Op::SIntersects,
+                vec![property("geometry"), Expr::Geometry(intersects.clone())],
+            ));
+        }
+        if let Some(datetime) = &self.datetime {
+            conditions.push(operation(
+                Op::TIntersects,
+                vec![property("datetime"), datetime_expr(datetime)?],
+            ));
+        }
+        if let Some(ids) = &self.ids {
+            conditions.push(operation(Op::In, vec![property("id"), strings(ids)]));
+        }
+        if let Some(collections) = &self.collections {
+            conditions.push(operation(
+                Op::In,
+                vec![property("collection"), strings(collections)],
+            ));
+        }
+        if let Some(query) = &self.query {
+            for (name, predicates) in query {
+                let Value::Object(predicates) = predicates else {
+                    return Err(STACError::Other(format!(
+                        "query for {name} must be an object, found {predicates}"
+                    )));
+                };
+                for (op, value) in predicates {
+                    conditions.push(query_expr(name, op, value)?);
+                }
+            }
+        }
+        if let Some(filter) = &self.filter {
+            conditions.push(filter.clone());
+        }
+        Ok(match conditions.len() {
+            0 => Expr::Bool(true),
+            1 => conditions.remove(0),
+            _ => operation(Op::And, conditions),
+        })
+    }
+
+    /// Checks whether an Item matches the search, see [`Search::to_cql2`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::Other`] if the search is invalid or cannot be evaluated against the
+    /// Item, see [`Expr::matches`].
+    pub fn matches(&self, item: &Item) -> STACResult<bool> {
+        self.to_cql2()?.matches(item)
+    }
+
+    /// Runs the search against Items in memory: keeps the Items that match, sorts them by
+    /// `sortby`, and returns at most `limit` of them. Items that are missing a `sortby` field are
+    /// sorted last. `fields` is not applied, see [`Search::project`].
+    ///
+    /// # Errors
+    ///
+    /// See [`Search::matches`].
+    pub fn search<I: IntoIterator<Item = Item>>(&self, items: I) -> STACResult<Vec<Item>> {
+        let expr = self.to_cql2()?;
+        let mut matched = Vec::new();
+        for item in items {
+            let value = serde_json::to_value(&item)?;
+            if expr.matches_json(&value)? {
+                matched.push((value, item));
+            }
+        }
+        if let Some(sortby) = &self.sortby {
+            matched.sort_by(|(a, _), (b, _)| {
+                sortby
+                    .iter()
+                    .map(|sortby| {
+                        compare_fields(
+                            sort_field(a, &sortby.field),
+                            sort_field(b, &sortby.field),
+                            sortby.direction,
+                        )
+                    })
+                    .find(|ordering| *ordering != Ordering::Equal)
+                    .unwrap_or(Ordering::Equal)
+            });
+        }
+        let limit = self
+            .limit
+            .and_then(|limit| usize::try_from(limit).ok())
+            .unwrap_or(usize::MAX);
+        Ok(matched
+            .into_iter()
+            .take(limit)
+            .map(|(_, item)| item)
+            .collect())
+    }
+
+    /// Applies `fields` to an Item, returning its JSON with only the requested fields.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::JSONParse`] if the Item cannot be serialized.
+    pub fn project(&self, item: &Item) -> STACResult<Value> {
+        let value = serde_json::to_value(item)?;
+        Ok(match &self.fields {
+            Some(fields) => fields.apply(value),
+            None => value,
+        })
+    }
+}
+
+/// Converts a `datetime` parameter into an instant or interval. Intervals are separated by `/`, and
+/// open ends are written as `..` or left empty.
+fn datetime_expr(datetime: &str) -> STACResult<Expr> {
+    let bound = |bound: &str| match bound {
+        "" | ".." => Expr::String(String::from("..")),
+        bound => Expr::Timestamp(String::from(bound)),
+    };
+    match datetime.split('/').collect::<Vec<_>>().as_slice() {
+        [instant] if !instant.is_empty() && *instant != ".." => {
+            Ok(Expr::Timestamp(String::from(*instant)))
+        }
+        [start, end] if !(matches!(*start, "" | "..") && matches!(*end, "" | "..")) => {
+            Ok(Expr::Interval(Box::new(bound(start)), Box::new(bound(end))))
+        }
+        _ => Err(STACError::Other(format!("Invalid datetime {datetime}"))),
+    }
+}
+
+/// Converts a condition of the Query extension into an expression.
+fn query_expr(name: &str, op: &str, value: &Value) -> STACResult<Expr> {
+    let parse = || {
+        Expr::from_json(value)
+            .map_err(|err| STACError::Other(format!("Invalid query value for {name}: {err}")))
+    };
+    let like = |prefix: &str, suffix: &str| match value.as_str() {
+        Some(value) => {
+            let escaped = value
+                .replace('\\', "\\\\")
+                .replace('%', "\\%")
+                .replace('_', "\\_");
+            Ok((Op::Like, Expr::String(format!("{prefix}{escaped}{suffix}"))))
+        }
+        None => Err(STACError::Other(format!(
+            "query {op} for {name} must be a string, found {value}"
+        ))),
+    };
+    let (op, argument) = match op {
+        "eq" => (Op::Eq, parse()?),
+        "neq" => (Op::Ne, parse()?),
+        "lt" => (Op::Lt, parse()?),
+        "lte" => (Op::Le, parse()?),
+        "gt" => (Op::Gt, parse()?),
+        "gte" => (Op::Ge, parse()?),
+        "startsWith" => like("", "%")?,
+        "endsWith" => like("%", "")?,
+        "contains" => like("%", "%")?,
+        "in" if value.is_array() => (Op::In, parse()?),
+        _ => {
+            return Err(STACError::Other(format!(
+                "Unsupported query {op} for {name} with value {value}"
+            )))
+        }
+    };
+    Ok(Expr::Operation {
+        op,
+        args: vec![Expr::Property(String::from(name)), argument],
+    })
+}
+
+/// Looks up a field to sort by in the JSON of an Item.
+fn sort_field<'a>(item: &'a Value, field: &str) -> Option<&'a Value> {
+    let value = match field {
+        "id" | "collection" => item.get(field),
+        field => {
+            let properties = item.get("properties");
+            let name = field.strip_prefix("properties.").unwrap_or(field);
+            properties.and_then(|properties| properties.get(name))
+        }
+    };
+    value.filter(|value| !value.is_null())
+}
+
+fn compare_fields(a: Option<&Value>, b: Option<&Value>, direction: Direction) -> Ordering {
+    let (a, b) = match (a, b) {
+        (Some(a), Some(b)) => (a, b),
+        (Some(_), None) => return Ordering::Less,
+        (None, Some(_)) => return Ordering::Greater,
+        (None, None) => return Ordering::Equal,
+    };
+    let ordering = match (a, b) {
+        (Value::Number(a), Value::Number(b)) => a
+            .as_f64()
+            .partial_cmp(&b.as_f64())
+            .unwrap_or(Ordering::Equal),
+        (Value::String(a), Value::String(b)) => a.cmp(b),
+        (Value::Bool(a), Value::Bool(b)) => a.cmp(b),
+        (a, b) => a.to_string().cmp(&b.to_string()),
+    };
+    match direction {
+        Direction::Asc => ordering,
+        Direction::Desc => ordering.reverse(),
+    }
+}
+
+/// The Collections and Items of a static catalog, see [`read_catalog`].
+#[derive(Debug, Default)]
+pub struct Found {
+    /// The Collections, in the order they were found.
+    pub collections: Vec<Collection>,
+
+    /// The Items in the order they were found, each with the id of the nearest Collection whose
+    /// links led to it, if any.
+    pub items: Vec<(Item, Option<String>)>,
+}
+
+/// Reads Items from a file on disk. The file may be an Item, an ItemCollection (a GeoJSON
+/// FeatureCollection of Items), or a Catalog or Collection, in which case `item` and `child` links
+/// with local hrefs are followed recursively. Relative hrefs are resolved against the directory of
+/// the file that contains the link, and each file is read at most once.
+///
+/// # Errors
+///
+/// See [`read_catalog`].
+pub fn read_items<P: AsRef<Path>>(path: P) -> STACResult<Vec<Item>> {
+    Ok(read_catalog(path)?
+        .items
+        .into_iter()
+        .map(|(item, _)| item)
+        .collect())
+}
+
+/// Reads the Collections and Items of a static catalog on disk, following links as in
+/// [`read_items`]. Each Item is reported with the id of the Collection it was found in.
+///
+/// # Errors
+///
+/// Returns [`STACError::IO`] if a file cannot be read, [`STACError::JSONParse`] if it is not valid
+/// JSON or does not contain valid Items or Collections, or [`STACError::Other`] if a link has an
+/// href that is not a local file.
+pub fn read_catalog<P: AsRef<Path>>(path: P) -> STACResult<Found> {
+    let mut found = Found::default();
+    let mut visited = HashSet::new();
+    read_into(path.as_ref(), None, &mut found, &mut visited)?;
+    Ok(found)
+}
+
+fn read_into(
+    path: &Path,
+    collection: Option<&str>,
+    found: &mut Found,
+    visited: &mut HashSet<PathBuf>,
+) -> STACResult<()> {
+    if !visited.insert(fs::canonicalize(path)?) {
+        return Ok(());
+    }
+    let value: Value = serde_json::from_str(&fs::read_to_string(path)?)?;
+    let with_collection = |item| (item, collection.map(String::from));
+    match value.get("type").and_then(Value::as_str) {
+        Some("Feature") => found
+            .items
+            .push(with_collection(serde_json::from_value(value)?)),
+        Some("FeatureCollection") => {
+            let features = value.get("features").cloned().unwrap_or_default();
+            let items: Vec<Item> = serde_json::from_value(features)?;
+            found.items.extend(items.into_iter().map(with_collection));
+        }
+        kind => {
+            let links: Vec<Link> = match value.get("links") {
+                Some(links) => serde_json::from_value(links.clone())?,
+                None => Vec::new(),
+            };
+            let collection = if kind == Some("Collection") {
+                let read: Collection = serde_json::from_value(value)?;
+                let id = read.id.clone();
+                found.collections.push(read);
+                Some(id)
+            } else {
+                collection.map(String::from)
+            };
+            let base = path.parent().unwrap_or_else(|| Path::new(""));
+            for link in links
+                .iter()
+                .filter(|link| link.rel == "item" || link.rel == "child")
+            {
+                let href = link.href.as_str();
+                let local = match href.strip_prefix("file://") {
+                    Some(local) => local,
+                    None if href.contains("://") => {
+                        return Err(STACError::Other(format!(
+                            "Link href {href} in {} is not a local file",
+                            path.display()
+                        )))
+                    }
+                    None => href,
+                };
+                read_into(&base.join(local), collection.as_deref(), found, visited)?;
+            }
+        }
+    }
+    Ok(())
+}
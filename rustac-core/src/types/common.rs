@@ -1,6 +1,6 @@
 use chrono::{DateTime, FixedOffset};
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
+use serde_json::{Map, Value};
 
 /// Attributes described by the [STAC Common Metadata spec](https://github.com/radiantearth/stac-spec/blob/v1.0.0-rc.1/item-spec/common-metadata.md).
 /// These attributes may apply to a STAC Item or Asset.
@@ -133,6 +133,19 @@ pub struct Link {
     pub extra_fields: Value,
 }
 
+impl Link {
+    /// Creates a link with the given `href` and `rel` and no other attributes.
+    pub fn new<H: Into<String>, R: Into<String>>(href: H, rel: R) -> Link {
+        Link {
+            href: href.into(),
+            rel: rel.into(),
+            r#type: None,
+            title: None,
+            extra_fields: Value::Object(Map::new()),
+        }
+    }
+}
+
 /// Represents a [Provider Object](https://github.com/radiantearth/stac-spec/blob/v1.0.0-rc.1/collection-spec/collection-spec.md#provider-object). This object
 /// may be used in the `"providers"` attribute of a Collection and the Common Metadata of an Item.
 #[derive(Serialize, Deserialize, Debug)]
@@ -154,7 +167,7 @@ pub struct Provider {
 }
 
 /// Allowed `"roles"` values for a [`Provider`] object.
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[serde(rename_all = "lowercase")]
 pub enum ProviderRole {
     /// Maps to the `"licensor"` value
@@ -177,6 +190,7 @@ mod optional_datetime {
 
     const FORMAT: &str = "%Y-%m-%dT%H:%M:%SZ";
 
+    #[allow(clippy::ref_option)]
     pub fn serialize<S>(
         datetime: &Option<DateTime<FixedOffset>>,
         serializer: S,
@@ -232,7 +246,7 @@ mod optional_datetime {
 
     struct DateTimeFromRFC3339Visitor;
 
-    impl<'de> de::Visitor<'de> for DateTimeFromRFC3339Visitor {
+    impl de::Visitor<'_> for DateTimeFromRFC3339Visitor {
         type Value = DateTime<FixedOffset>;
 
         fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
@@ -243,7 +257,7 @@ mod optional_datetime {
         where
             E: de::Error,
         {
-            DateTime::parse_from_rfc3339(&value).map_err(E::custom)
+            DateTime::parse_from_rfc3339(value).map_err(E::custom)
         }
     }
 }
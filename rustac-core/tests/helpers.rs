@@ -1,3 +1,4 @@
+use serde_json::{json, Value};
 use std::fs;
 
 #[allow(dead_code)]
@@ -5,3 +6,19 @@ pub(crate) fn get_test_example(filename: &str) -> String {
     let path = format!("./stac-examples/{}", filename);
     fs::read_to_string(&path).unwrap_or_else(|_| panic!("Could not open {}", &path.as_str()))
 }
+
+/// A minimal Item with the given id and properties. It is returned as JSON so that tests can set
+/// other fields, e.g. `stac_extensions` or `assets`, before deserializing it.
+#[allow(dead_code)]
+pub(crate) fn item(id: &str, properties: Value) -> Value {
+    json!({
+        "stac_version": "1.0.0",
+        "type": "Feature",
+        "id": id,
+        "bbox": [0.0, 0.0, 1.0, 1.0],
+        "geometry": {"type": "Point", "coordinates": [0.0, 0.0]},
+        "properties": properties,
+        "links": [],
+        "assets": {}
+    })
+}
@@ -0,0 +1,271 @@
+mod helpers;
+use rustac_core::cql2::{Expr, Op};
+use rustac_core::Item;
+use serde_json::json;
+
+fn get_item() -> Item {
+    let mut item = helpers::item(
+        "S2A_10TFK_20210601",
+        json!({
+            "datetime": "2021-06-01T10:30:00Z",
+            "platform": "sentinel-2a",
+            "eo:cloud_cover": 12.5,
+            "instruments": ["msi", "tirs"],
+            "view:off_nadir": null
+        }),
+    );
+    item["collection"] = json!("sentinel-2-l2a");
+    item["bbox"] = json!([0.0, 0.0, 2.0, 2.0]);
+    item["geometry"] = json!({
+        "type": "Polygon",
+        "coordinates": [[[0.0, 0.0], [2.0, 0.0], [2.0, 2.0], [0.0, 2.0], [0.0, 0.0]]]
+    });
+    serde_json::from_value(item).unwrap()
+}
+
+fn matches(text: &str) -> bool {
+    let expr: Expr = text.parse().unwrap();
+    let result = expr.matches(&get_item()).unwrap();
+    let json = Expr::from_json(&expr.to_json()).unwrap();
+    assert_eq!(json.matches(&get_item()).unwrap(), result, "{}", text);
+    result
+}
+
+#[test]
+fn test_comparisons() {
+    assert!(matches("\"eo:cloud_cover\" < 20"));
+    assert!(matches("eo:cloud_cover BETWEEN 10 AND 12.5"));
+    assert!(!matches("eo:cloud_cover NOT BETWEEN 10 AND 12.5"));
+    assert!(matches(
+        "platform = 'sentinel-2a' AND collection <> 'landsat'"
+    ));
+    assert!(matches("platform LIKE 'sentinel-2_'"));
+    assert!(!matches("CASEI(platform) LIKE 'SENTINEL%'"));
+    assert!(matches("CASEI(platform) LIKE CASEI('SENTINEL%')"));
+    assert!(matches("id IN ('a', 'S2A_10TFK_20210601')"));
+    assert!(matches("view:off_nadir IS NULL AND missing IS NULL"));
+    assert!(matches("platform IS NOT NULL"));
+    assert!(!matches("missing = 1 OR missing <> 1"));
+    assert!(matches(
+        "NOT (eo:cloud_cover > 50 OR platform = 'landsat-8')"
+    ));
+}
+
+#[test]
+fn test_temporal() {
+    assert!(matches(
+        "T_INTERSECTS(datetime, INTERVAL('2021-01-01', '..'))"
+    ));
+    assert!(matches(
+        "T_DURING(datetime, INTERVAL('2021-05-01T00:00:00Z', '2021-07-01T00:00:00Z'))"
+    ));
+    assert!(matches("T_DURING(datetime, DATE('2021-06-01'))"));
+    assert!(matches(
+        "T_AFTER(datetime, TIMESTAMP('2021-06-01T10:29:59Z'))"
+    ));
+    assert!(!matches("T_BEFORE(datetime, DATE('2021-06-01'))"));
+    assert!(matches(
+        "T_EQUALS(datetime, TIMESTAMP('2021-06-01T12:30:00+02:00'))"
+    ));
+    assert!(matches("datetime >= TIMESTAMP('2021-06-01T00:00:00Z')"));
+}
+
+#[test]
+fn test_spatial() {
+    assert!(matches("S_INTERSECTS(geometry, POINT(1 1))"));
+    assert!(matches("S_INTERSECTS(geometry, POINT(2 1))"));
+    assert!(!matches("S_INTERSECTS(geometry, POINT(3 1))"));
+    assert!(matches("S_INTERSECTS(geometry, LINESTRING(-1 1, 3 1))"));
+    assert!(matches("S_INTERSECTS(geometry, BBOX(1.5, 1.5, 5, 5))"));
+    assert!(matches(
+        "S_DISJOINT(geometry, POLYGON((3 3, 4 3, 4 4, 3 4, 3 3)))"
+    ));
+    assert!(matches("S_WITHIN(geometry, BBOX(-1, -1, 3, 3))"));
+    assert!(!matches("S_WITHIN(geometry, BBOX(1, -1, 3, 3))"));
+    assert!(matches(
+        "S_CONTAINS(geometry, MULTIPOINT((1 1), (0.5 0.5)))"
+    ));
+    assert!(!matches(
+        "S_WITHIN(geometry, POLYGON((-1 -1, 3 -1, 3 3, -1 3, -1 -1), (0.5 0.5, 1 0.5, 1 1, 0.5 0.5)))"
+    ));
+    assert!(matches("S_INTERSECTS(bbox, POINT(0 0))"));
+}
+
+#[test]
+fn test_arrays() {
+    assert!(matches("A_CONTAINS(instruments, ('msi'))"));
+    assert!(matches(
+        "A_CONTAINEDBY(instruments, ('msi', 'tirs', 'oli'))"
+    ));
+    assert!(matches("A_EQUALS(instruments, ('tirs', 'msi'))"));
+    assert!(matches("A_OVERLAPS(instruments, ('oli', 'tirs'))"));
+    assert!(!matches("A_OVERLAPS(instruments, ('oli'))"));
+}
+
+#[test]
+fn test_text_round_trip() {
+    for text in [
+        "a = 1 AND (b = 2 OR c = 3)",
+        "NOT (a = 1 AND b = 2)",
+        "\"eo:cloud_cover\" NOT LIKE 'a''b%'",
+        "a IS NOT NULL OR b NOT IN ('x', 'y')",
+        "S_INTERSECTS(geometry, POLYGON((0 0, 1 0, 1 1, 0 0)))",
+        "T_FINISHEDBY(datetime, INTERVAL('..', '2021-01-01T00:00:00Z'))",
+        "A_CONTAINS(\"and\", (1))",
+    ] {
+        let expr: Expr = text.parse().unwrap();
+        let written = expr.to_text();
+        assert_eq!(written.parse::<Expr>().unwrap(), expr, "{}", written);
+    }
+    let expr: Expr = "\"eo:cloud_cover\" NOT LIKE 'a''b%'".parse().unwrap();
+    assert_eq!(expr.to_text(), "eo:cloud_cover NOT LIKE 'a''b%'");
+}
+
+#[test]
+fn test_json() {
+    let json = json!({
+        "op": "and",
+        "args": [
+            {"op": "s_intersects", "args": [{"property": "geometry"}, {"type": "Point", "coordinates": [1.0, 1.0]}]},
+            {"op": "t_intersects", "args": [{"property": "datetime"}, {"interval": ["2021-01-01", ".."]}]},
+            {"op": "in", "args": [{"property": "platform"}, ["sentinel-2a", "sentinel-2b"]]},
+            {"op": "not", "args": [{"op": "isNull", "args": [{"property": "platform"}]}]}
+        ]
+    });
+    let expr = Expr::from_json(&json).unwrap();
+    assert!(expr.matches(&get_item()).unwrap());
+    assert_eq!(expr.to_json(), json);
+    assert_eq!(
+        expr.to_text(),
+        "S_INTERSECTS(geometry, POINT(1 1)) AND T_INTERSECTS(datetime, INTERVAL('2021-01-01', '..')) \
+         AND platform IN ('sentinel-2a', 'sentinel-2b') AND platform IS NOT NULL"
+    );
+    let deserialized: Expr = serde_json::from_value(json.clone()).unwrap();
+    assert_eq!(serde_json::to_value(&deserialized).unwrap(), json);
+}
+
+#[test]
+fn test_parse_errors() {
+    let error = "a = ".parse::<Expr>().unwrap_err();
+    assert_eq!(error.position, Some(4));
+    assert_eq!(
+        error.to_string(),
+        "Expected a value, found end of input at position 4"
+    );
+
+    let error = "a = 'unterminated".parse::<Expr>().unwrap_err();
+    assert_eq!(error.to_string(), "Unterminated ' quote at position 4");
+
+    let error = "S_INTERSECTS(geometry)".parse::<Expr>().unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "s_intersects takes 2 arguments, found 1 at position 0"
+    );
+
+    let error = "a = 1 b".parse::<Expr>().unwrap_err();
+    assert_eq!(error.position, Some(6));
+
+    let error = Expr::from_json(&json!({"op": "not", "args": []})).unwrap_err();
+    assert_eq!(error.to_string(), "not takes 1 argument, found 0");
+    assert_eq!(error.position, None);
+
+    assert!(Expr::from_json(&json!({"interval": ["2021-01-01"]})).is_err());
+    assert_eq!(Op::from_name("T_METBY"), Some(Op::TMetBy));
+}
+
+#[test]
+fn test_evaluation_errors() {
+    let item = get_item();
+    let expr: Expr = "platform > 1".parse().unwrap();
+    assert!(expr.matches(&item).is_err());
+    let expr: Expr = "ACCENTI(platform) = 'a'".parse().unwrap();
+    assert!(expr.matches(&item).is_err());
+    let expr: Expr = "S_TOUCHES(geometry, POINT(0 0))".parse().unwrap();
+    assert!(expr.matches(&item).is_err());
+    let expr: Expr = "eo:cloud_cover".parse().unwrap();
+    assert!(expr.matches(&item).is_err());
+
+    // Operations built by hand are not checked until they are evaluated
+    for (op, args) in [
+        (Op::Not, vec![]),
+        (Op::Eq, vec![Expr::Number(1.0)]),
+        (Op::Between, vec![Expr::Number(1.0), Expr::Number(2.0)]),
+    ] {
+        let expr = Expr::Operation { op, args };
+        assert!(expr.matches(&item).is_err());
+    }
+    let expr = Expr::Operation {
+        op: Op::Not,
+        args: vec![],
+    };
+    assert_eq!(
+        expr.matches(&item).unwrap_err().to_string(),
+        "not takes 1 argument, found 0"
+    );
+}
+
+#[test]
+fn test_nesting_limit() {
+    let nested = |depth: usize| format!("{}a = 1{}", "(".repeat(depth), ")".repeat(depth));
+    assert!(nested(60).parse::<Expr>().is_ok());
+    for text in [
+        nested(2000),
+        format!("{}a = 1", "NOT ".repeat(2000)),
+        format!("{}a = 1{}", "NOT (".repeat(1000), ")".repeat(1000)),
+        format!("a IN {}1{}", "(".repeat(2000), ")".repeat(2000)),
+        format!("{}1{} = 1", "ABS(".repeat(2000), ")".repeat(2000)),
+    ] {
+        let error = text.parse::<Expr>().unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            format!(
+                "Expressions cannot be nested more than 64 levels deep at position {}",
+                error.position.unwrap()
+            )
+        );
+    }
+}
+
+#[test]
+fn test_like() {
+    assert!(matches("platform LIKE '%2%'"));
+    assert!(matches("platform LIKE 's%n%l%'"));
+    assert!(!matches("platform LIKE 's%x%'"));
+    assert!(matches("platform LIKE '%'"));
+    assert!(matches("'50%' LIKE '50\\%'"));
+    assert!(!matches("'500' LIKE '50\\%'"));
+    assert!(matches("'a_b' LIKE '%\\_%'"));
+
+    let value = "a".repeat(40);
+    let pattern = format!("{}b", "%a".repeat(20));
+    let expr: Expr = format!("'{value}' LIKE '{pattern}'").parse().unwrap();
+    assert!(!expr.matches(&get_item()).unwrap());
+    let expr: Expr = format!("'{value}b' LIKE '{pattern}'").parse().unwrap();
+    assert!(expr.matches(&get_item()).unwrap());
+}
+
+#[test]
+fn test_unordered_comparisons() {
+    let item = get_item();
+    let json = |op: &str, left: serde_json::Value, right: serde_json::Value| {
+        Expr::from_json(&json!({"op": op, "args": [left, right]})).unwrap()
+    };
+    assert!(json("=", json!([1, 2]), json!([1, 2]))
+        .matches(&item)
+        .unwrap());
+    assert!(json("<>", json!([1, 2]), json!([3]))
+        .matches(&item)
+        .unwrap());
+    for op in ["<", "<=", ">", ">="] {
+        let error = json(op, json!([1, 2]), json!([3]))
+            .matches(&item)
+            .unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("Values of type array cannot be ordered"),
+            "{}",
+            error
+        );
+    }
+}
@@ -0,0 +1,162 @@
+mod helpers;
+
+use helpers::get_test_example;
+use rustac_core::extensions::datacube::{
+    self, AdditionalDimension, Datacube, Dimension, HorizontalAxis, Variable, VariableType,
+};
+use rustac_core::Collection;
+use serde_json::json;
+
+fn get_collection() -> Collection {
+    let data = get_test_example("core/collection.json");
+    serde_json::from_str(data.as_str()).unwrap()
+}
+
+fn get_datacube() -> Datacube {
+    serde_json::from_value(json!({
+        "cube:dimensions": {
+            "x": {
+                "type": "spatial",
+                "axis": "x",
+                "extent": [-180.0, 180.0],
+                "step": 0.25,
+                "reference_system": 4326
+            },
+            "y": {"type": "spatial", "axis": "y", "extent": [-90.0, 90.0], "step": 0.25},
+            "level": {
+                "type": "spatial",
+                "axis": "z",
+                "extent": [0.0, 1000.0],
+                "unit": "hPa",
+                "values": [1000, 500, 0]
+            },
+            "time": {
+                "type": "temporal",
+                "extent": ["2020-01-01T00:00:00Z", "2020-12-31T00:00:00Z"],
+                "step": "P1D"
+            },
+            "bands": {"type": "bands", "values": ["red", "green", "blue"]}
+        },
+        "cube:variables": {
+            "temperature": {
+                "type": "data",
+                "dimensions": ["time", "level", "y", "x"],
+                "unit": "K"
+            },
+            "lat": {"type": "auxiliary", "dimensions": ["y"]}
+        }
+    }))
+    .unwrap()
+}
+
+#[test]
+fn test_dimension_kinds() {
+    let cube = get_datacube();
+    let dimensions = cube.dimensions.as_ref().unwrap();
+
+    let Dimension::Horizontal(x) = &dimensions["x"] else {
+        panic!("x is not horizontal");
+    };
+    assert_eq!(x.axis, HorizontalAxis::X);
+    assert_eq!(x.reference_system, Some(json!(4326)));
+
+    let Dimension::Vertical(level) = &dimensions["level"] else {
+        panic!("level is not vertical");
+    };
+    assert_eq!(level.unit, Some(String::from("hPa")));
+    assert!(level.extra_fields.is_empty());
+
+    let Dimension::Temporal(time) = &dimensions["time"] else {
+        panic!("time is not temporal");
+    };
+    assert_eq!(time.step, Some(String::from("P1D")));
+
+    assert_eq!(dimensions["bands"].r#type(), "bands");
+    assert_eq!(
+        cube.variables.as_ref().unwrap()["lat"].r#type,
+        VariableType::Auxiliary
+    );
+    assert!(cube.validate().is_ok());
+
+    let value = serde_json::to_value(&cube).unwrap();
+    assert_eq!(value["cube:dimensions"]["level"]["axis"], json!("z"));
+    assert_eq!(value["cube:dimensions"]["level"]["type"], json!("spatial"));
+    assert_eq!(value["cube:dimensions"]["time"]["type"], json!("temporal"));
+    assert_eq!(value["cube:dimensions"]["bands"]["type"], json!("bands"));
+    assert_eq!(serde_json::from_value::<Datacube>(value).unwrap(), cube);
+}
+
+#[test]
+fn test_unknown_dimensions() {
+    let mut cube = get_datacube();
+    cube.variables.as_mut().unwrap().insert(
+        String::from("pressure"),
+        Variable {
+            dimensions: vec![String::from("time"), String::from("depth")],
+            ..Default::default()
+        },
+    );
+    assert_eq!(
+        cube.unknown_dimensions(),
+        vec![(String::from("pressure"), String::from("depth"))]
+    );
+    let err = cube.validate().unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "cube:variables \"pressure\" references unknown dimension \"depth\""
+    );
+
+    cube.dimensions = None;
+    assert_eq!(cube.unknown_dimensions().len(), 7);
+}
+
+#[test]
+fn test_invalid_datacube() {
+    let mut cube = get_datacube();
+    cube.variables
+        .as_mut()
+        .unwrap()
+        .get_mut("lat")
+        .unwrap()
+        .dimensions
+        .push(String::from("y"));
+    assert!(cube.validate().is_err());
+
+    let mut cube = get_datacube();
+    cube.dimensions.as_mut().unwrap().insert(
+        String::from("bands"),
+        Dimension::Additional(AdditionalDimension {
+            r#type: String::from("bands"),
+            extent: Some([Some(10.0), Some(1.0)]),
+            ..Default::default()
+        }),
+    );
+    assert!(cube.validate().is_err());
+
+    let mut cube = get_datacube();
+    if let Some(Dimension::Temporal(time)) = cube.dimensions.as_mut().unwrap().get_mut("time") {
+        time.extent = [Some(String::from("2021-01-01")), None];
+    }
+    assert!(cube.validate().is_err());
+
+    assert!(serde_json::from_value::<Dimension>(json!({"axis": "x"})).is_err());
+    assert!(serde_json::from_value::<Dimension>(json!({"type": "spatial", "axis": "x"})).is_err());
+}
+
+#[test]
+fn test_set_datacube() {
+    let mut collection = get_collection();
+    let cube = get_datacube();
+    collection.set_datacube(&cube).unwrap();
+    assert_eq!(collection.datacube().unwrap(), cube);
+    assert!(collection
+        .stac_extensions
+        .as_ref()
+        .unwrap()
+        .contains(&String::from(datacube::SCHEMA_URI)));
+
+    let mut invalid = get_datacube();
+    invalid.dimensions = None;
+    assert!(collection.set_datacube(&invalid).is_err());
+    assert_eq!(collection.datacube().unwrap(), cube);
+}
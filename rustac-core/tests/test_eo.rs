@@ -0,0 +1,113 @@
+mod helpers;
+
+use helpers::get_test_example;
+use rustac_core::extensions::eo::{self, Band, CommonBandName, Eo, EoSummaries};
+use rustac_core::extensions::Range;
+use rustac_core::{Collection, Item};
+use serde_json::json;
+
+fn get_item() -> Item {
+    let data = get_test_example("extensions/eo/item.json");
+    serde_json::from_str(data.as_str()).unwrap()
+}
+
+#[test]
+fn test_get_eo() {
+    let item = get_item();
+
+    let fields = item.eo().unwrap();
+    assert_eq!(fields.cloud_cover, Some(1.2));
+    assert!(fields.bands.is_none());
+
+    let bands = item.assets["analytic"].eo().unwrap().bands.unwrap();
+    assert_eq!(bands.len(), 4);
+    assert_eq!(bands[3].name, Some(String::from("band4")));
+    assert_eq!(bands[3].common_name, Some(CommonBandName::Nir));
+    assert_eq!(bands[3].full_width_half_max, Some(152.0));
+}
+
+#[test]
+fn test_set_eo() {
+    let mut item = get_item();
+    item.stac_extensions = None;
+
+    let fields = Eo {
+        cloud_cover: Some(20.0),
+        snow_cover: Some(5.0),
+        ..Default::default()
+    };
+    item.set_eo(&fields).unwrap();
+    assert_eq!(item.eo().unwrap(), fields);
+    assert_eq!(item.properties.extra_fields["eo:snow_cover"], json!(5.0));
+    assert_eq!(
+        item.stac_extensions,
+        Some(vec![String::from(eo::SCHEMA_URI)])
+    );
+
+    let invalid = Eo {
+        cloud_cover: Some(101.0),
+        ..Default::default()
+    };
+    assert!(item.set_eo(&invalid).is_err());
+    assert_eq!(item.eo().unwrap(), fields);
+
+    // The URI is kept while assets still have bands
+    item.set_eo(&Eo::default()).unwrap();
+    assert!(item.properties.extra_fields.get("eo:cloud_cover").is_none());
+    assert_eq!(
+        item.stac_extensions,
+        Some(vec![String::from(eo::SCHEMA_URI)])
+    );
+
+    item.set_asset_eo("analytic", &Eo::default()).unwrap();
+    item.set_asset_eo("visual", &Eo::default()).unwrap();
+    assert_eq!(item.stac_extensions, Some(Vec::new()));
+
+    assert!(item.set_asset_eo("missing", &Eo::default()).is_err());
+}
+
+#[test]
+fn test_band_extra_fields() {
+    let band: Band = serde_json::from_value(json!({
+        "name": "B01",
+        "common_name": "coastal",
+        "gsd": 60
+    }))
+    .unwrap();
+    assert_eq!(band.common_name, Some(CommonBandName::Coastal));
+    assert_eq!(band.extra_fields["gsd"], json!(60));
+    assert_eq!(
+        serde_json::to_value(&band).unwrap(),
+        json!({"name": "B01", "common_name": "coastal", "gsd": 60})
+    );
+}
+
+#[test]
+fn test_eo_summaries() {
+    let data = get_test_example("core/collection.json");
+    let mut collection: Collection = serde_json::from_str(data.as_str()).unwrap();
+    assert_eq!(collection.eo_summaries().unwrap(), EoSummaries::default());
+
+    let summaries = EoSummaries {
+        bands: Some(vec![Band {
+            name: Some(String::from("red")),
+            common_name: Some(CommonBandName::Red),
+            ..Default::default()
+        }]),
+        cloud_cover: Some(Range {
+            minimum: 0.0,
+            maximum: 30.0,
+        }),
+        snow_cover: None,
+    };
+    collection.set_eo_summaries(&summaries).unwrap();
+    assert_eq!(collection.eo_summaries().unwrap(), summaries);
+    assert!(collection
+        .stac_extensions
+        .as_ref()
+        .unwrap()
+        .contains(&String::from(eo::SCHEMA_URI)));
+
+    // Other summaries are left alone
+    assert!(collection.summaries.as_ref().unwrap().contains_key("gsd"));
+}
@@ -0,0 +1,170 @@
+mod helpers;
+
+use helpers::get_test_example;
+use rustac_core::error::{STACError, STACResult};
+use rustac_core::extensions::eo::{self, Eo};
+use rustac_core::extensions::{Extension, Extensions};
+use rustac_core::{Catalog, Collection, Item};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+const SCHEMA_URI: &str = "https://example.com/acquisition/v1.0.0/schema.json";
+
+/// An extension that is not supported by the crate itself.
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq)]
+struct Acquisition {
+    #[serde(rename = "acq:operator")]
+    operator: Option<String>,
+
+    #[serde(rename = "acq:attempts")]
+    attempts: Option<u32>,
+}
+
+impl Extension for Acquisition {
+    const SCHEMA_URI: &'static str = SCHEMA_URI;
+    const PREFIX: &'static str = "acq:";
+
+    fn validate(&self) -> STACResult<()> {
+        if self.attempts == Some(0) {
+            return Err(STACError::Other(String::from(
+                "acq:attempts must be at least 1",
+            )));
+        }
+        Ok(())
+    }
+}
+
+fn acquisition() -> Acquisition {
+    Acquisition {
+        operator: Some(String::from("Jane")),
+        attempts: Some(2),
+    }
+}
+
+fn get_item() -> Item {
+    let data = get_test_example("core/simple-item.json");
+    serde_json::from_str(data.as_str()).unwrap()
+}
+
+fn lists(stac_extensions: Option<&Vec<String>>, uri: &str) -> bool {
+    stac_extensions.is_some_and(|extensions| extensions.iter().any(|extension| extension == uri))
+}
+
+#[test]
+fn test_item_extension() {
+    let mut item = get_item();
+    assert!(!item.has_extension::<Acquisition>());
+
+    item.set_extension(&acquisition()).unwrap();
+    assert!(item.has_extension::<Acquisition>());
+    assert!(item.properties.has_extension::<Acquisition>());
+    assert_eq!(item.get_extension::<Acquisition>().unwrap(), acquisition());
+    assert_eq!(item.properties.extra_fields["acq:operator"], json!("Jane"));
+
+    item.set_extension(&Acquisition {
+        attempts: Some(3),
+        ..Default::default()
+    })
+    .unwrap();
+    assert!(item.properties.extra_fields.get("acq:operator").is_none());
+    assert!(lists(item.stac_extensions.as_ref(), SCHEMA_URI));
+
+    item.set_extension(&Acquisition::default()).unwrap();
+    assert!(!item.has_extension::<Acquisition>());
+}
+
+#[test]
+fn test_validate_on_set() {
+    let mut item = get_item();
+    let invalid = Acquisition {
+        attempts: Some(0),
+        ..Default::default()
+    };
+    assert!(item.set_extension(&invalid).is_err());
+    assert!(!item.properties.has_extension::<Acquisition>());
+    assert!(!item.has_extension::<Acquisition>());
+}
+
+#[test]
+fn test_asset_extension() {
+    let mut item = get_item();
+    let key = item.assets.keys().next().unwrap().clone();
+
+    item.set_asset_extension(&key, &acquisition()).unwrap();
+    assert!(item.has_extension::<Acquisition>());
+    assert!(item.assets[&key].has_extension::<Acquisition>());
+    assert!(!item.properties.has_extension::<Acquisition>());
+    assert_eq!(
+        item.assets[&key].get_extension::<Acquisition>().unwrap(),
+        acquisition()
+    );
+    assert!(item
+        .set_asset_extension("not-an-asset", &acquisition())
+        .is_err());
+
+    item.remove_extension::<Acquisition>();
+    assert!(!item.assets[&key].has_extension::<Acquisition>());
+    assert!(!lists(item.stac_extensions.as_ref(), SCHEMA_URI));
+}
+
+#[test]
+fn test_supported_extensions_implement_extension() {
+    let mut item = get_item();
+    let fields = Eo {
+        cloud_cover: Some(12.0),
+        ..Default::default()
+    };
+    item.set_extension(&fields).unwrap();
+    assert_eq!(item.eo().unwrap(), fields);
+    assert!(lists(item.stac_extensions.as_ref(), eo::SCHEMA_URI));
+
+    item.remove_extension::<Eo>();
+    assert!(item.eo().unwrap().cloud_cover.is_none());
+    assert!(!lists(item.stac_extensions.as_ref(), eo::SCHEMA_URI));
+}
+
+#[test]
+fn test_collection_extension() {
+    let data = get_test_example("core/collection.json");
+    let mut collection: Collection = serde_json::from_str(data.as_str()).unwrap();
+    collection
+        .summaries
+        .get_or_insert_with(Default::default)
+        .insert(String::from("acq:operator"), json!(["Jane", "John"]));
+
+    collection.set_extension(&acquisition()).unwrap();
+    assert!(collection.has_extension::<Acquisition>());
+    assert_eq!(
+        collection.get_extension::<Acquisition>().unwrap(),
+        acquisition()
+    );
+
+    collection.remove_extension::<Acquisition>();
+    assert!(!collection.has_extension::<Acquisition>());
+    assert!(collection.extra_fields.get("acq:operator").is_none());
+    assert!(!collection.summaries.unwrap().contains_key("acq:operator"));
+}
+
+#[test]
+fn test_catalog_extension() {
+    let data = get_test_example("core/catalog.json");
+    let mut catalog: Catalog = serde_json::from_str(data.as_str()).unwrap();
+    assert!(catalog.stac_extensions.is_none());
+
+    catalog.set_extension(&acquisition()).unwrap();
+    assert_eq!(
+        catalog.stac_extensions,
+        Some(vec![String::from(SCHEMA_URI)])
+    );
+    assert_eq!(
+        catalog.get_extension::<Acquisition>().unwrap(),
+        acquisition()
+    );
+
+    catalog.remove_extension::<Acquisition>();
+    assert_eq!(catalog.stac_extensions, Some(vec![]));
+    assert_eq!(
+        catalog.get_extension::<Acquisition>().unwrap(),
+        Acquisition::default()
+    );
+}
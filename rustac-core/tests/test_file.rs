@@ -0,0 +1,124 @@
+mod helpers;
+use rustac_core::extensions::file::{self, ByteOrder, File, HashFunction, Multihash};
+use rustac_core::Item;
+use serde_json::json;
+use std::fs;
+use std::path::PathBuf;
+
+/// Creates a directory holding `data.txt`, unique to the calling test.
+fn data_dir(test: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("rustac-file-{}-{test}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("data.txt"), "hello world").unwrap();
+    dir
+}
+
+fn get_item() -> Item {
+    let mut item = helpers::item("an-item", json!({"datetime": "2021-01-01T00:00:00Z"}));
+    item["assets"] = json!({
+        "data": {"href": "./data.txt", "file:byte_order": "little-endian"},
+        "remote": {"href": "https://example.com/data.txt"}
+    });
+    serde_json::from_value(item).unwrap()
+}
+
+#[test]
+fn test_multihash() {
+    for (function, expected) in [
+        (
+            HashFunction::Sha2_256,
+            "1220b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9",
+        ),
+        (HashFunction::Md5, "d501105eb63bbbe01eeed093cb22bb8f5acdc3"),
+        (
+            HashFunction::Blake2b_256,
+            "a0e40220256c83b297114d201b30179f3f0ef0cace9783622da5974326b436178aeef610",
+        ),
+    ] {
+        let multihash = Multihash::digest(function, b"hello world");
+        assert_eq!(multihash.to_string(), expected);
+        assert_eq!(Multihash::parse(expected).unwrap(), multihash);
+    }
+
+    let sha512 = Multihash::digest(HashFunction::Sha2_512, b"hello world");
+    assert!(sha512.to_string().starts_with("1340"));
+    let blake2b = Multihash::digest(HashFunction::Blake2b_512, b"hello world");
+    assert!(blake2b.to_string().starts_with("c0e40240"));
+
+    assert!(Multihash::parse("1220b94d").is_err());
+    assert!(Multihash::parse("zz").is_err());
+    assert!(Multihash::parse("1101ff").is_err());
+}
+
+#[test]
+fn test_get_file() {
+    let item = get_item();
+    let fields = item.assets["data"].file().unwrap();
+    assert_eq!(fields.byte_order, Some(ByteOrder::LittleEndian));
+    assert!(fields.checksum.is_none());
+
+    let mut asset = item.assets.into_iter().next().unwrap().1;
+    let invalid = File {
+        checksum: Some(String::from("1220")),
+        ..Default::default()
+    };
+    assert!(asset.set_file(&invalid).is_err());
+}
+
+#[test]
+fn test_update_file_info() {
+    let dir = data_dir("update");
+    let mut item = get_item();
+    item.update_file_info(&dir, HashFunction::Md5).unwrap();
+
+    let fields = item.assets["data"].file().unwrap();
+    assert_eq!(fields.size, Some(11));
+    assert_eq!(
+        fields.checksum,
+        Some(String::from("d501105eb63bbbe01eeed093cb22bb8f5acdc3"))
+    );
+    assert_eq!(fields.byte_order, Some(ByteOrder::LittleEndian));
+    assert!(item.assets["remote"].file().unwrap().size.is_none());
+    assert_eq!(
+        item.stac_extensions,
+        Some(vec![String::from(file::SCHEMA_URI)])
+    );
+
+    assert!(item.verify_file_info(&dir).unwrap().is_empty());
+}
+
+#[test]
+fn test_verify_file_info() {
+    let dir = data_dir("verify");
+    let mut item = get_item();
+    item.update_file_info(&dir, HashFunction::Blake2b_256)
+        .unwrap();
+    fs::write(dir.join("data.txt"), "hello there world").unwrap();
+
+    let mismatches = item.verify_file_info(&dir).unwrap();
+    assert_eq!(mismatches.len(), 2);
+    assert_eq!(mismatches[0].asset, "data");
+    assert_eq!(mismatches[0].field, "file:size");
+    assert_eq!(mismatches[0].expected, "11");
+    assert_eq!(mismatches[0].actual, "17");
+    assert_eq!(mismatches[1].field, "file:checksum");
+    assert!(mismatches[1].actual.starts_with("a0e40220"));
+    assert_eq!(
+        mismatches[1].expected,
+        item.assets["data"].file().unwrap().checksum.unwrap()
+    );
+}
+
+#[test]
+fn test_missing_file() {
+    let dir = data_dir("missing");
+    let mut item = get_item();
+    item.assets.get_mut("data").unwrap().href = String::from("missing.txt");
+    assert!(item.update_file_info(&dir, HashFunction::Sha2_256).is_err());
+    assert!(item
+        .assets
+        .get_mut("remote")
+        .unwrap()
+        .update_file_info(&dir, HashFunction::Sha2_256)
+        .is_err());
+}
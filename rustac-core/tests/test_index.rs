@@ -0,0 +1,255 @@
+mod helpers;
+use rustac_core::index::{Bounds, Interval, ItemIndex, Relation};
+use rustac_core::Item;
+use serde_json::json;
+
+/// Creates an Item on a grid, with a datetime or a range depending on its number.
+fn get_item(i: usize) -> Item {
+    let x = (i % 20) as f64;
+    let y = (i / 20) as f64;
+    let day = 1 + i % 28;
+    let properties = if i.is_multiple_of(3) {
+        json!({
+            "datetime": null,
+            "start_datetime": format!("2021-01-{day:02}T00:00:00Z"),
+            "end_datetime": format!("2021-02-{day:02}T00:00:00Z")
+        })
+    } else {
+        json!({"datetime": format!("2021-01-{day:02}T12:00:00Z")})
+    };
+    let mut item = helpers::item(&format!("item-{i:03}"), properties);
+    item["bbox"] = json!([x, y, x + 1.5, y + 1.5]);
+    item["geometry"] = json!({"type": "Point", "coordinates": [x, y]});
+    serde_json::from_value(item).unwrap()
+}
+
+/// Finds the Items matching a query by checking each of them.
+fn scan<'a>(
+    items: &'a [Item],
+    bounds: Option<&Bounds>,
+    interval: Option<&Interval>,
+    relation: Relation,
+) -> Vec<&'a str> {
+    let test = |contains: bool, intersects: bool, within: bool| match relation {
+        Relation::Intersects => intersects,
+        Relation::Contains => contains,
+        Relation::Within => within,
+    };
+    items
+        .iter()
+        .filter(|item| {
+            let item_bounds = Bounds::from_bbox(&item.bbox).unwrap();
+            let spatial = bounds.is_none_or(|bounds| {
+                test(
+                    item_bounds.contains(bounds),
+                    item_bounds.intersects(bounds),
+                    bounds.contains(&item_bounds),
+                )
+            });
+            let temporal = interval.is_none_or(|interval| {
+                Interval::from_item(item).is_some_and(|item_interval| {
+                    test(
+                        item_interval.contains(interval),
+                        item_interval.intersects(interval),
+                        interval.contains(&item_interval),
+                    )
+                })
+            });
+            spatial && temporal
+        })
+        .map(|item| item.id.as_str())
+        .collect()
+}
+
+fn queries() -> Vec<(Option<Bounds>, Option<Interval>)> {
+    let january = Interval::parse("2021-01-05T00:00:00Z/2021-01-20T00:00:00Z").unwrap();
+    let instant = Interval::parse("2021-01-10T12:00:00Z").unwrap();
+    vec![
+        (Some(Bounds::new(2.2, 3.2, 2.4, 3.4)), None),
+        (Some(Bounds::new(0.0, 0.0, 6.0, 4.0)), None),
+        (None, Some(january)),
+        (None, Some(instant)),
+        (Some(Bounds::new(5.0, 1.0, 12.0, 8.0)), Some(january)),
+        (None, None),
+    ]
+}
+
+#[test]
+fn test_search_matches_scan() {
+    let items: Vec<Item> = (0..400).map(get_item).collect();
+    let index = ItemIndex::from_items(&items).unwrap();
+    assert_eq!(index.len(), 400);
+    for (bounds, interval) in queries() {
+        for relation in [Relation::Intersects, Relation::Contains, Relation::Within] {
+            assert_eq!(
+                index.search(bounds.as_ref(), interval.as_ref(), relation),
+                scan(&items, bounds.as_ref(), interval.as_ref(), relation),
+                "{:?} {:?} {:?}",
+                bounds,
+                interval,
+                relation
+            );
+        }
+    }
+}
+
+#[test]
+fn test_insert_remove() {
+    let mut items: Vec<Item> = (0..300).map(get_item).collect();
+    let mut index = ItemIndex::from_items(&items).unwrap();
+    for i in (0..300).step_by(3) {
+        assert!(index.remove(&format!("item-{i:03}")));
+    }
+    assert!(!index.remove("item-000"));
+    items.retain(|item| index.contains(&item.id));
+    assert_eq!(index.len(), 200);
+    assert_eq!(items.len(), 200);
+
+    let mut moved = get_item(7);
+    moved.bbox = vec![100.0, 100.0, 101.0, 101.0];
+    index.insert(&moved).unwrap();
+    assert_eq!(index.len(), 200);
+    assert_eq!(
+        index.search_bounds(&Bounds::new(100.5, 100.5, 100.5, 100.5), Relation::Contains),
+        vec!["item-007"]
+    );
+    items.retain(|item| item.id != "item-007");
+    items.push(moved);
+    items.sort_by(|a, b| a.id.cmp(&b.id));
+
+    for (bounds, interval) in queries() {
+        assert_eq!(
+            index.search(bounds.as_ref(), interval.as_ref(), Relation::Intersects),
+            scan(
+                &items,
+                bounds.as_ref(),
+                interval.as_ref(),
+                Relation::Intersects
+            )
+        );
+    }
+
+    for item in &items {
+        assert!(index.remove(&item.id));
+    }
+    assert!(index.is_empty());
+    assert!(index
+        .search(
+            Some(&Bounds::new(0.0, 0.0, 20.0, 20.0)),
+            None,
+            Relation::Intersects
+        )
+        .is_empty());
+}
+
+#[test]
+fn test_write_read() {
+    let items: Vec<Item> = (0..100).map(get_item).collect();
+    let index = ItemIndex::from_items(&items).unwrap();
+    let path = std::env::temp_dir().join(format!("rustac-index-{}.json", std::process::id()));
+    index.write(&path).unwrap();
+    let read = ItemIndex::read(&path).unwrap();
+    assert_eq!(read, index);
+    let bounds = Bounds::new(3.0, 1.0, 5.0, 2.0);
+    assert_eq!(
+        read.search(Some(&bounds), None, Relation::Intersects),
+        index.search(Some(&bounds), None, Relation::Intersects)
+    );
+
+    std::fs::write(&path, "{}").unwrap();
+    assert!(ItemIndex::read(&path).is_err());
+
+    // Entries that do not match the trees, with the same number of each
+    let written = serde_json::to_value(&index).unwrap();
+    let mut renamed = written.clone();
+    let entries = renamed["entries"].as_object_mut().unwrap();
+    let entry = entries.remove("item-001").unwrap();
+    entries.insert(String::from("item-999"), entry);
+    let mut moved = written;
+    moved["entries"]["item-001"]["bounds"]["min_x"] = json!(-50.0);
+    for value in [renamed, moved] {
+        std::fs::write(&path, value.to_string()).unwrap();
+        assert!(ItemIndex::read(&path).is_err());
+        let index: ItemIndex = serde_json::from_value(value).unwrap();
+        let _ = index.search(
+            Some(&Bounds::new(-180.0, -90.0, 180.0, 90.0)),
+            None,
+            Relation::Intersects,
+        );
+    }
+}
+
+#[test]
+fn test_invalid() {
+    let mut item = get_item(0);
+    item.bbox = vec![1.0, 1.0, 0.0, 0.0];
+    assert!(ItemIndex::new().insert(&item).is_err());
+    assert!(Interval::parse("../..").is_err());
+    assert!(Interval::parse("2021-02-01T00:00:00Z/2021-01-01T00:00:00Z").is_err());
+    assert!(Interval::parse("yesterday").is_err());
+    assert_eq!(
+        Interval::parse("2021-01-01T00:00:00Z/..").unwrap().end,
+        i64::MAX
+    );
+}
+
+#[test]
+fn test_antimeridian() {
+    let bboxes = [
+        ("crossing", [175.0, 0.0, -175.0, 5.0]),
+        ("east", [170.0, 0.0, 179.0, 5.0]),
+        ("west", [-179.0, 0.0, -170.0, 5.0]),
+        ("prime", [-5.0, 0.0, 5.0, 5.0]),
+    ];
+    let items: Vec<Item> = bboxes
+        .iter()
+        .map(|(id, bbox)| {
+            let mut item = get_item(0);
+            item.id = String::from(*id);
+            item.bbox = bbox.to_vec();
+            item
+        })
+        .collect();
+    let mut index = ItemIndex::from_items(&items).unwrap();
+    for (bounds, relation, expected) in [
+        (
+            Bounds::new(-178.0, 1.0, -176.0, 2.0),
+            Relation::Intersects,
+            vec!["crossing", "west"],
+        ),
+        (
+            Bounds::new(178.0, 1.0, -178.0, 2.0),
+            Relation::Contains,
+            vec!["crossing"],
+        ),
+        (
+            Bounds::new(170.0, 0.0, -170.0, 5.0),
+            Relation::Within,
+            vec!["crossing", "east", "west"],
+        ),
+        (
+            Bounds::new(-180.0, -90.0, 180.0, 90.0),
+            Relation::Within,
+            vec!["crossing", "east", "prime", "west"],
+        ),
+        (
+            Bounds::new(0.0, 0.0, 174.0, 5.0),
+            Relation::Intersects,
+            vec!["east", "prime"],
+        ),
+    ] {
+        assert_eq!(index.search(Some(&bounds), None, relation), expected);
+        let mut scanned = scan(&items, Some(&bounds), None, relation);
+        scanned.sort_unstable();
+        assert_eq!(scanned, expected, "{:?}", bounds);
+    }
+    assert!(index.remove("crossing"));
+    assert_eq!(
+        index.search(
+            Some(&Bounds::new(-178.0, 1.0, -176.0, 2.0)),
+            None,
+            Relation::Intersects
+        ),
+        vec!["west"]
+    );
+}
@@ -0,0 +1,198 @@
+mod helpers;
+
+use helpers::get_test_example;
+use rustac_core::extensions::item_assets::{self, ItemAsset, ItemAssets, Nonconformance};
+use rustac_core::{Collection, Item};
+use serde_json::json;
+use std::collections::HashMap;
+
+fn get_collection() -> Collection {
+    let data = get_test_example("core/collection.json");
+    serde_json::from_str(data.as_str()).unwrap()
+}
+
+fn item(id: &str, checksum: &str) -> Item {
+    let mut item = helpers::item(id, json!({"datetime": "2021-01-01T00:00:00Z"}));
+    item["assets"] = json!({
+        "data": {
+            "href": format!("./{id}.tif"),
+            "title": "Data",
+            "type": "image/tiff; application=geotiff",
+            "roles": ["data"],
+            "eo:bands": [{"name": "red"}],
+            "file:checksum": checksum
+        },
+        "thumbnail": {
+            "href": format!("./{id}.png"),
+            "title": format!("Thumbnail of {id}"),
+            "type": "image/png",
+            "roles": ["thumbnail"]
+        }
+    });
+    serde_json::from_value(item).unwrap()
+}
+
+#[test]
+fn test_from_items() {
+    let items = vec![item("a", "1220aa"), item("b", "1220bb")];
+    let definitions = ItemAssets::from_items(&items);
+    let assets = definitions.assets.unwrap();
+    assert_eq!(assets.len(), 2);
+
+    let data = &assets["data"];
+    assert_eq!(data.title, Some(String::from("Data")));
+    assert_eq!(data.roles, Some(vec![String::from("data")]));
+    assert_eq!(data.extra_fields["eo:bands"], json!([{"name": "red"}]));
+    assert!(!data.extra_fields.contains_key("file:checksum"));
+    assert!(!data.extra_fields.contains_key("href"));
+
+    let thumbnail = &assets["thumbnail"];
+    assert!(thumbnail.title.is_none());
+    assert_eq!(thumbnail.r#type, Some(String::from("image/png")));
+
+    assert!(ItemAssets::from_items(&[]).assets.is_none());
+}
+
+#[test]
+fn test_set_item_assets() {
+    let mut collection = get_collection();
+    let items = vec![item("a", "1220aa"), item("b", "1220bb")];
+    let definitions = ItemAssets::from_items(&items);
+    collection.set_item_assets(&definitions).unwrap();
+
+    assert_eq!(collection.item_assets().unwrap(), definitions);
+    assert!(collection
+        .stac_extensions
+        .as_ref()
+        .unwrap()
+        .contains(&String::from(item_assets::SCHEMA_URI)));
+    for item in &items {
+        assert!(collection.check_item_assets(item).unwrap().is_empty());
+    }
+
+    collection.set_item_assets(&ItemAssets::default()).unwrap();
+    assert!(collection.extra_fields.get("item_assets").is_none());
+    assert!(!collection
+        .stac_extensions
+        .as_ref()
+        .unwrap()
+        .contains(&String::from(item_assets::SCHEMA_URI)));
+}
+
+#[test]
+fn test_invalid_definitions() {
+    let mut collection = get_collection();
+    let mut assets = HashMap::new();
+    assets.insert(
+        String::from("data"),
+        ItemAsset {
+            roles: Some(vec![String::from("data")]),
+            ..Default::default()
+        },
+    );
+    let mut definitions = ItemAssets {
+        assets: Some(assets),
+    };
+    assert!(collection.set_item_assets(&definitions).is_err());
+
+    let data = definitions
+        .assets
+        .as_mut()
+        .unwrap()
+        .get_mut("data")
+        .unwrap();
+    data.title = Some(String::from("Data"));
+    data.extra_fields
+        .insert(String::from("href"), json!("./data.tif"));
+    assert!(collection.set_item_assets(&definitions).is_err());
+}
+
+#[test]
+fn test_check_item() {
+    let definitions = ItemAssets::from_items(&[item("a", "1220aa")]);
+    let mut item = item("b", "1220bb");
+    item.assets.remove("thumbnail");
+    let data = item.assets.get_mut("data").unwrap();
+    data.r#type = Some(String::from("image/jpeg"));
+    data.roles = Some(vec![String::from("data"), String::from("visual")]);
+    let extra = item.assets["data"].href.clone();
+    item.assets.insert(
+        String::from("extra"),
+        serde_json::from_value(json!({ "href": extra })).unwrap(),
+    );
+
+    let nonconformances = definitions.check_item(&item);
+    assert_eq!(
+        nonconformances,
+        vec![
+            Nonconformance::Type {
+                key: String::from("data"),
+                expected: String::from("image/tiff; application=geotiff"),
+                actual: Some(String::from("image/jpeg")),
+            },
+            Nonconformance::Roles {
+                key: String::from("data"),
+                expected: vec![String::from("data")],
+                actual: vec![String::from("data"), String::from("visual")],
+            },
+            Nonconformance::Undefined {
+                key: String::from("extra")
+            },
+            Nonconformance::Missing {
+                key: String::from("thumbnail")
+            },
+        ]
+    );
+    assert_eq!(
+        nonconformances[3].to_string(),
+        "asset \"thumbnail\" is defined in item_assets but missing"
+    );
+}
+
+#[test]
+fn test_from_items_partial() {
+    let mut a = item("a", "1220aa");
+    let mut b = item("b", "1220bb");
+    a.assets.insert(
+        String::from("metadata"),
+        serde_json::from_value(
+            json!({"href": "./a.xml", "type": "application/xml", "roles": ["metadata"]}),
+        )
+        .unwrap(),
+    );
+    b.assets.get_mut("thumbnail").unwrap().roles = Some(vec![String::from("overview")]);
+    let items = vec![a, b];
+
+    let definitions = ItemAssets::from_items(&items);
+    let assets = definitions.assets.as_ref().unwrap();
+    assert_eq!(assets.len(), 1);
+    assert!(assets.contains_key("data"));
+    assert!(definitions.validate().is_ok());
+    for item in &items {
+        assert!(definitions
+            .check_item(item)
+            .iter()
+            .all(|nonconformance| matches!(nonconformance, Nonconformance::Undefined { .. })));
+    }
+}
+
+#[test]
+fn test_from_items_roles_in_any_order() {
+    let mut a = item("a", "1220aa");
+    let mut b = item("b", "1220bb");
+    b.assets.get_mut("data").unwrap().roles =
+        Some(vec![String::from("reflectance"), String::from("data")]);
+    a.assets.get_mut("data").unwrap().roles =
+        Some(vec![String::from("data"), String::from("reflectance")]);
+    let items = vec![a, b];
+
+    let definitions = ItemAssets::from_items(&items);
+    let data = &definitions.assets.as_ref().unwrap()["data"];
+    assert_eq!(
+        data.roles,
+        Some(vec![String::from("data"), String::from("reflectance")])
+    );
+    for item in &items {
+        assert!(definitions.check_item(item).is_empty());
+    }
+}
@@ -0,0 +1,183 @@
+mod helpers;
+use rustac_core::extensions::label::{self, Class, Count, Label, LabelType, Overview, Statistic};
+use rustac_core::Item;
+use serde_json::json;
+use std::fs;
+use std::path::PathBuf;
+
+/// Creates a directory holding `labels.geojson`, unique to the calling test.
+fn label_dir(test: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("rustac-label-{}-{test}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let feature = |class: &str, score: u8| {
+        json!({
+            "type": "Feature",
+            "geometry": {"type": "Point", "coordinates": [0.5, 0.5]},
+            "properties": {"class": class, "score": score}
+        })
+    };
+    let labels = json!({
+        "type": "FeatureCollection",
+        "features": [
+            feature("building", 1),
+            feature("road", 2),
+            feature("building", 1),
+            {
+                "type": "Feature",
+                "geometry": {"type": "Point", "coordinates": [0.5, 0.5]},
+                "properties": {"class": null}
+            }
+        ]
+    });
+    fs::write(dir.join("labels.geojson"), labels.to_string()).unwrap();
+    dir
+}
+
+fn get_item() -> Item {
+    let mut item = helpers::item(
+        "labels",
+        json!({
+            "datetime": "2021-01-01T00:00:00Z",
+            "label:properties": ["class", "score"],
+            "label:description": "Building and road footprints",
+            "label:type": "vector",
+            "label:tasks": ["segmentation"],
+            "label:methods": ["manual"],
+            "label:classes": [{"name": "class", "classes": ["building", "road", "water"]}],
+            "label:overviews": [{
+                "property_key": "score",
+                "statistics": [{"name": "mean", "value": 1.33}]
+            }]
+        }),
+    );
+    item["stac_extensions"] = json!([label::SCHEMA_URI]);
+    item["assets"] = json!({
+        "labels": {"href": "./labels.geojson", "type": "application/geo+json"}
+    });
+    serde_json::from_value(item).unwrap()
+}
+
+#[test]
+fn test_get_label() {
+    let item = get_item();
+    let fields = item.label().unwrap();
+    assert_eq!(fields.r#type, Some(LabelType::Vector));
+    assert_eq!(fields.methods, Some(vec![String::from("manual")]));
+    assert_eq!(
+        fields.classes.unwrap()[0].classes,
+        vec![json!("building"), json!("road"), json!("water")]
+    );
+    assert_eq!(
+        fields.overviews.unwrap()[0].statistics,
+        Some(vec![Statistic {
+            name: String::from("mean"),
+            value: 1.33
+        }])
+    );
+}
+
+#[test]
+fn test_invalid_label() {
+    let mut item = get_item();
+    let raster = Label {
+        r#type: Some(LabelType::Raster),
+        ..item.label().unwrap()
+    };
+    assert!(item.set_label(&raster).is_err());
+
+    let unnamed = Label {
+        classes: Some(vec![Class {
+            name: None,
+            classes: vec![json!(1)],
+        }]),
+        ..item.label().unwrap()
+    };
+    assert!(item.set_label(&unnamed).is_err());
+
+    let unknown_key = Label {
+        overviews: Some(vec![Overview {
+            property_key: Some(String::from("other")),
+            ..Default::default()
+        }]),
+        ..item.label().unwrap()
+    };
+    assert!(item.set_label(&unknown_key).is_err());
+}
+
+#[test]
+fn test_label_sources() {
+    let mut item = get_item();
+    assert!(item.label_sources().is_empty());
+    item.add_label_source("./imagery.json", Some(vec![String::from("visual")]));
+    item.add_label_source("./other.json", None);
+
+    let sources = item.label_sources();
+    assert_eq!(sources.len(), 2);
+    assert_eq!(sources[0].href, "./imagery.json");
+    assert_eq!(sources[0].rel, label::SOURCE);
+    assert_eq!(sources[0].extra_fields["label:assets"], json!(["visual"]));
+    assert!(sources[1].extra_fields.get("label:assets").is_none());
+}
+
+#[test]
+fn test_update_label_overviews() {
+    let dir = label_dir("overviews");
+    let mut item = get_item();
+    item.update_label_overviews(&dir, "labels").unwrap();
+
+    let overviews = item.label().unwrap().overviews.unwrap();
+    assert_eq!(overviews.len(), 2);
+    let score = &overviews[0];
+    assert_eq!(score.property_key, Some(String::from("score")));
+    assert_eq!(score.statistics.as_ref().unwrap().len(), 1);
+    assert_eq!(
+        score.counts,
+        Some(vec![
+            Count {
+                name: String::from("1"),
+                count: 2
+            },
+            Count {
+                name: String::from("2"),
+                count: 1
+            },
+        ])
+    );
+    assert_eq!(
+        overviews[1].counts,
+        Some(vec![
+            Count {
+                name: String::from("building"),
+                count: 2
+            },
+            Count {
+                name: String::from("road"),
+                count: 1
+            },
+            Count {
+                name: String::from("water"),
+                count: 0
+            },
+        ])
+    );
+}
+
+#[test]
+fn test_update_label_overviews_errors() {
+    let dir = label_dir("errors");
+    let mut item = get_item();
+    assert!(item.update_label_overviews(&dir, "missing").is_err());
+
+    item.assets.get_mut("labels").unwrap().href = String::from("missing.geojson");
+    assert!(item.update_label_overviews(&dir, "labels").is_err());
+
+    let mut item = get_item();
+    item.set_label(&Label {
+        properties: None,
+        classes: None,
+        overviews: None,
+        ..item.label().unwrap()
+    })
+    .unwrap();
+    assert!(item.update_label_overviews(&dir, "labels").is_err());
+}
@@ -0,0 +1,254 @@
+use rustac_core::extensions::datacube::Datacube;
+use rustac_core::extensions::file::File;
+use rustac_core::extensions::label::Label;
+use rustac_core::extensions::Extensions;
+use rustac_core::migrate::{migrate, LATEST_VERSION};
+use rustac_core::{Catalog, Collection, Item};
+use semver::Version;
+use serde_json::{json, Value};
+
+fn version(version: &str) -> Version {
+    Version::parse(version).unwrap()
+}
+
+fn old_item() -> Value {
+    json!({
+        "stac_version": "0.7.0",
+        "stac_extensions": ["eo"],
+        "type": "Feature",
+        "id": "LC08_L1TP_107018_20181001",
+        "bbox": [148.13933, 59.51584, 152.52233, 60.63594],
+        "geometry": {
+            "type": "Polygon",
+            "coordinates": [[
+                [152.52233, 60.63594], [148.13933, 60.63594], [148.13933, 59.51584],
+                [152.52233, 59.51584], [152.52233, 60.63594]
+            ]]
+        },
+        "properties": {
+            "datetime": "2018-10-01T01:08:32.033Z",
+            "collection": "landsat-8-l1",
+            "eo:gsd": 15,
+            "eo:platform": "landsat-8",
+            "eo:instrument": "OLI_TIRS",
+            "eo:sun_azimuth": 168.7,
+            "eo:epsg": 32656,
+            "eo:bands": [
+                {"name": "B1", "common_name": "coastal", "gsd": 30, "accuracy": null},
+                {"name": "B2", "common_name": "blue", "gsd": 30}
+            ]
+        },
+        "links": [
+            {"rel": "collection", "href": "../collection.json"}
+        ],
+        "assets": {
+            "B1": {
+                "href": "B1.TIF",
+                "type": "image/vnd.stac.geotiff; cloud-optimized=true",
+                "eo:bands": [0]
+            },
+            "B2": {"href": "B2.TIF", "type": "image/vnd.stac.geotiff", "eo:bands": [1]}
+        }
+    })
+}
+
+#[test]
+fn test_migrate_item() {
+    let migration = migrate(&old_item(), &version(LATEST_VERSION)).unwrap();
+    let value = &migration.value;
+
+    assert_eq!(value["stac_version"], "1.0.0");
+    assert_eq!(value["collection"], "landsat-8-l1");
+    assert_eq!(value["properties"]["gsd"], 15);
+    assert_eq!(value["properties"]["platform"], "landsat-8");
+    assert_eq!(value["properties"]["instruments"], json!(["OLI_TIRS"]));
+    assert_eq!(value["properties"]["view:sun_azimuth"], 168.7);
+    assert_eq!(value["properties"]["proj:epsg"], 32656);
+    assert!(value["properties"].get("eo:gsd").is_none());
+    assert_eq!(
+        value["properties"]["eo:bands"][0],
+        json!({"name": "B1", "common_name": "coastal"})
+    );
+    assert_eq!(
+        value["assets"]["B2"]["eo:bands"],
+        json!([{"name": "B2", "common_name": "blue"}])
+    );
+    assert_eq!(
+        value["assets"]["B1"]["type"],
+        "image/tiff; application=geotiff; profile=cloud-optimized"
+    );
+    assert_eq!(
+        value["stac_extensions"],
+        json!([
+            "https://stac-extensions.github.io/eo/v1.0.0/schema.json",
+            "https://stac-extensions.github.io/view/v1.0.0/schema.json",
+            "https://stac-extensions.github.io/projection/v1.0.0/schema.json"
+        ])
+    );
+
+    let pointers: Vec<&str> = migration
+        .changes
+        .iter()
+        .map(|change| change.pointer.as_str())
+        .collect();
+    assert!(pointers.contains(&"/properties/proj:epsg"));
+    assert!(migration.changes.iter().any(|change| {
+        change.pointer == "/properties/instruments"
+            && change.description == "wrapped the instrument in an array"
+    }));
+    assert!(pointers.contains(&"/assets/B1/eo:bands/0/gsd"));
+    assert_eq!(pointers.last(), Some(&"/stac_version"));
+
+    let item: Item = serde_json::from_value(migration.value).unwrap();
+    assert_eq!(item.collection, Some(String::from("landsat-8-l1")));
+}
+
+#[test]
+fn test_migrate_to_intermediate_version() {
+    let migration = migrate(&old_item(), &version("0.9.0")).unwrap();
+
+    assert_eq!(migration.value["stac_version"], "0.9.0");
+    assert_eq!(
+        migration.value["stac_extensions"],
+        json!(["eo", "view", "projection"])
+    );
+    assert!(migration
+        .changes
+        .iter()
+        .all(|change| change.version <= version("0.9.0")));
+}
+
+#[test]
+fn test_migrate_collection() {
+    let old = json!({
+        "stac_version": "0.7.0",
+        "stac_extensions": ["commons", "proj", "projection"],
+        "id": "landsat-8-l1",
+        "description": "Landsat 8 imagery",
+        "license": "PDDL-1.0",
+        "extent": {
+            "spatial": [-180.0, -90.0, 180.0, 90.0],
+            "temporal": ["2013-06-01T00:00:00Z", null]
+        },
+        "properties": {"eo:platform": "landsat-8"},
+        "summaries": {"eo:cloud_cover": {"min": 0, "max": 100}},
+        "links": []
+    });
+
+    let migration = migrate(&old, &version("1.0.0")).unwrap();
+    let value = &migration.value;
+
+    assert_eq!(value["type"], "Collection");
+    assert!(value.get("properties").is_none());
+    assert_eq!(value["summaries"]["platform"], json!(["landsat-8"]));
+    assert_eq!(
+        value["summaries"]["eo:cloud_cover"],
+        json!({"minimum": 0, "maximum": 100})
+    );
+    assert_eq!(
+        value["stac_extensions"],
+        json!(["https://stac-extensions.github.io/projection/v1.0.0/schema.json"])
+    );
+
+    let collection: Collection = serde_json::from_value(migration.value).unwrap();
+    assert_eq!(
+        collection.extent.spatial.bbox,
+        vec![vec![-180.0, -90.0, 180.0, 90.0]]
+    );
+    assert_eq!(
+        collection.extent.temporal.interval,
+        vec![vec![Some(String::from("2013-06-01T00:00:00Z")), None]]
+    );
+}
+
+#[test]
+fn test_migrate_catalog() {
+    let old = json!({
+        "stac_version": "1.0.0-beta.2",
+        "id": "catalog",
+        "description": "A catalog",
+        "links": []
+    });
+    let migration = migrate(&old, &version("1.0.0")).unwrap();
+
+    let catalog: Catalog = serde_json::from_value(migration.value).unwrap();
+    assert_eq!(catalog.r#type, "Catalog");
+    assert_eq!(migration.changes.len(), 2);
+    assert_eq!(
+        migration.changes[0].to_string(),
+        "[1.0.0-rc.1] /type: set to Catalog"
+    );
+}
+
+#[test]
+fn test_collection_without_link() {
+    let mut old = old_item();
+    old["links"] = json!([]);
+    let migration = migrate(&old, &version("1.0.0")).unwrap();
+    assert!(migration.value.get("collection").is_none());
+}
+
+#[test]
+fn test_migrate_errors() {
+    let item = old_item();
+    assert!(migrate(&item, &version("0.6.0")).is_err());
+    assert!(migrate(&item, &version("1.1.0")).is_err());
+
+    let mut unversioned = item;
+    unversioned.as_object_mut().unwrap().remove("stac_version");
+    assert!(migrate(&unversioned, &version("1.0.0")).is_err());
+
+    assert!(migrate(&json!({"type": "FeatureCollection"}), &version("1.0.0")).is_err());
+}
+
+#[test]
+fn test_extension_uris_match_typed_extensions() {
+    let mut old = old_item();
+    old["stac_version"] = json!("1.0.0-beta.2");
+    old["stac_extensions"] = json!(["file", "label", "datacube"]);
+    let migration = migrate(&old, &version("1.0.0")).unwrap();
+    let mut item: Item = serde_json::from_value(migration.value).unwrap();
+    assert!(item.has_extension::<File>());
+    assert!(item.has_extension::<Label>());
+    assert!(item.has_extension::<Datacube>());
+
+    item.set_asset_file(
+        "B1",
+        &File {
+            size: Some(1024),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    assert_eq!(item.stac_extensions.as_ref().unwrap().len(), 3);
+}
+
+#[test]
+fn test_extension_uri_pointers() {
+    let mut old = old_item();
+    old["stac_version"] = json!("1.0.0-beta.2");
+    old["stac_extensions"] = json!(["proj", "projection", "eo"]);
+    let migration = migrate(&old, &version("1.0.0")).unwrap();
+    assert_eq!(
+        migration.value["stac_extensions"],
+        json!([
+            "https://stac-extensions.github.io/projection/v1.0.0/schema.json",
+            "https://stac-extensions.github.io/eo/v1.0.0/schema.json"
+        ])
+    );
+    let changes: Vec<String> = migration
+        .changes
+        .iter()
+        .filter(|change| change.pointer.starts_with("/stac_extensions"))
+        .map(|change| format!("{}: {}", change.pointer, change.description))
+        .collect();
+    assert_eq!(
+        changes,
+        vec![
+            "/stac_extensions/0: replaced proj with its schema URI",
+            "/stac_extensions: removed projection because \
+             https://stac-extensions.github.io/projection/v1.0.0/schema.json is already listed",
+            "/stac_extensions/1: replaced eo with its schema URI",
+        ]
+    );
+}
@@ -0,0 +1,133 @@
+mod helpers;
+use rustac_core::extensions::pointcloud::{self, Pointcloud, Schema, SchemaType, Statistics};
+use rustac_core::extensions::processing::{self, Processing};
+use rustac_core::Item;
+use serde_json::json;
+use std::collections::HashMap;
+
+fn get_item() -> Item {
+    let mut item = helpers::item(
+        "autzen-full",
+        json!({
+            "datetime": "2020-01-01T00:00:00Z",
+            "pc:count": 10653336,
+            "pc:type": "lidar",
+            "pc:encoding": "LASzip",
+            "pc:density": 0.0,
+            "pc:schemas": [
+                {"name": "X", "size": 8, "type": "floating"},
+                {"name": "Y", "size": 8, "type": "floating"},
+                {"name": "Z", "size": 8, "type": "floating"},
+                {"name": "Intensity", "size": 2, "type": "unsigned"},
+                {"name": "Classification", "size": 1, "type": "unsigned"}
+            ],
+            "pc:statistics": [
+                {
+                    "name": "Z",
+                    "position": 2,
+                    "average": 426.1,
+                    "count": 10653336,
+                    "maximum": 615.26,
+                    "minimum": 406.14,
+                    "stddev": 22.1,
+                    "variance": 488.4
+                }
+            ]
+        }),
+    );
+    item["stac_extensions"] = json!([pointcloud::SCHEMA_URI]);
+    serde_json::from_value(item).unwrap()
+}
+
+#[test]
+fn test_get_pointcloud() {
+    let item = get_item();
+    let fields = item.properties.pointcloud().unwrap();
+    assert_eq!(fields, item.pointcloud().unwrap());
+    assert_eq!(fields.count, Some(10_653_336));
+    assert_eq!(fields.encoding, Some(String::from("LASzip")));
+    assert_eq!(fields.point_size(), Some(27));
+    assert_eq!(
+        fields.schemas.as_ref().unwrap()[4],
+        Schema {
+            name: String::from("Classification"),
+            size: 1,
+            r#type: SchemaType::Unsigned,
+        }
+    );
+    let statistics = &fields.statistics.unwrap()[0];
+    assert_eq!(statistics.position, Some(2));
+    assert_eq!(statistics.maximum, Some(615.26));
+}
+
+#[test]
+fn test_invalid_pointcloud() {
+    let mut item = get_item();
+    let fields = item.pointcloud().unwrap();
+    let negative = Pointcloud {
+        density: Some(-1.0),
+        ..fields.clone()
+    };
+    assert!(item.properties.set_pointcloud(&negative).is_err());
+
+    let mut duplicate = fields.clone();
+    duplicate.schemas.as_mut().unwrap()[1].name = String::from("X");
+    assert!(item.properties.set_pointcloud(&duplicate).is_err());
+
+    let mut unknown = fields;
+    unknown.statistics = Some(vec![Statistics {
+        name: String::from("GpsTime"),
+        ..Default::default()
+    }]);
+    assert!(item.set_pointcloud(&unknown).is_err());
+    assert!(serde_json::from_value::<Schema>(json!({
+        "name": "X",
+        "size": 8,
+        "type": "complex"
+    }))
+    .is_err());
+}
+
+#[test]
+fn test_set_processing() {
+    let mut item = get_item();
+    assert_eq!(item.processing().unwrap(), Processing::default());
+
+    let mut software = HashMap::new();
+    software.insert(String::from("pdal"), String::from("2.4.3"));
+    let fields = Processing {
+        level: Some(String::from("L1")),
+        lineage: Some(String::from("Merged from flight lines")),
+        facility: None,
+        software: Some(software),
+    };
+    item.properties.set_processing(&fields).unwrap();
+    assert_eq!(item.properties.processing().unwrap(), fields);
+    assert_eq!(
+        item.properties.extra_fields["processing:software"],
+        json!({"pdal": "2.4.3"})
+    );
+    assert!(!item
+        .stac_extensions
+        .as_ref()
+        .unwrap()
+        .contains(&String::from(processing::SCHEMA_URI)));
+
+    item.set_processing(&fields).unwrap();
+    assert!(item
+        .stac_extensions
+        .as_ref()
+        .unwrap()
+        .contains(&String::from(processing::SCHEMA_URI)));
+
+    item.set_processing(&Processing::default()).unwrap();
+    assert!(item
+        .properties
+        .extra_fields
+        .get("processing:level")
+        .is_none());
+    assert_eq!(
+        item.stac_extensions,
+        Some(vec![String::from(pointcloud::SCHEMA_URI)])
+    );
+}
@@ -0,0 +1,159 @@
+mod helpers;
+
+use helpers::get_test_example;
+use rustac_core::extensions::proj::{self, Proj, Transform};
+use rustac_core::Item;
+use serde_json::Value;
+
+fn get_item() -> Item {
+    let data = get_test_example("core/extensions-collection/proj-example/proj-example.json");
+    serde_json::from_str(data.as_str()).unwrap()
+}
+
+#[test]
+fn test_get_proj() {
+    let item = get_item();
+    let fields = item.proj().unwrap();
+
+    assert_eq!(fields.epsg, Some(Some(32614)));
+    assert!(fields
+        .wkt2
+        .unwrap()
+        .starts_with("PROJCS[\"WGS 84 / UTM zone 14N\""));
+    assert_eq!(fields.projjson.unwrap()["type"], "ProjectedCRS");
+    assert_eq!(
+        fields.bbox,
+        Some(vec![169200.0, 3712800.0, 403200.0, 3951000.0])
+    );
+    assert_eq!(fields.shape, Some(vec![8391, 8311]));
+    assert!((fields.centroid.unwrap().lon + 101.344).abs() < 0.001);
+    assert!(fields.geometry.is_some());
+
+    let asset = item.assets["B8"].proj().unwrap();
+    assert_eq!(asset.shape, Some(vec![16781, 16621]));
+    assert!(asset.epsg.is_none());
+}
+
+#[test]
+fn test_transform() {
+    let transform = get_item().proj().unwrap().transform().unwrap().unwrap();
+    assert_eq!(
+        transform,
+        Transform {
+            a: 30.0,
+            b: 0.0,
+            c: 224985.0,
+            d: 0.0,
+            e: -30.0,
+            f: 6790215.0
+        }
+    );
+    assert_eq!(
+        transform.to_gdal(),
+        [224985.0, 30.0, 0.0, 6790215.0, 0.0, -30.0]
+    );
+    assert_eq!(Transform::from_gdal(transform.to_gdal()), transform);
+    assert_eq!(transform.to_world(1.0, 2.0), (225015.0, 6790155.0));
+    assert_eq!(transform.to_pixel(225015.0, 6790155.0).unwrap(), (1.0, 2.0));
+
+    // A rotated grid round-trips through the inverse
+    let rotated = Transform {
+        a: 10.0,
+        b: 5.0,
+        c: 1000.0,
+        d: -5.0,
+        e: -10.0,
+        f: 2000.0,
+    };
+    let (x, y) = rotated.to_world(3.0, 7.0);
+    let (col, row) = rotated.to_pixel(x, y).unwrap();
+    assert!((col - 3.0).abs() < 1e-9 && (row - 7.0).abs() < 1e-9);
+
+    assert!(Transform::from_slice(&[1.0, 0.0, 0.0, 0.0, 1.0]).is_err());
+    assert!(Transform::from_slice(&[1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 1.0]).is_err());
+    assert!(Transform::from_slice(&[1.0, 2.0, 0.0, 2.0, 4.0, 0.0])
+        .unwrap()
+        .inverse()
+        .is_err());
+}
+
+#[test]
+fn test_native_footprint() {
+    let fields = Proj {
+        shape: Some(vec![100, 200]),
+        transform: Some(vec![10.0, 0.0, 5000.0, 0.0, -10.0, 8000.0]),
+        ..Default::default()
+    };
+    assert_eq!(
+        fields.native_bbox().unwrap(),
+        Some(vec![5000.0, 7000.0, 7000.0, 8000.0])
+    );
+    let footprint = fields.native_footprint().unwrap().unwrap();
+    assert_eq!(
+        footprint.value,
+        geojson::Value::Polygon(vec![vec![
+            vec![5000.0, 8000.0],
+            vec![7000.0, 8000.0],
+            vec![7000.0, 7000.0],
+            vec![5000.0, 7000.0],
+            vec![5000.0, 8000.0],
+        ]])
+    );
+
+    assert!(Proj::default().native_bbox().unwrap().is_none());
+    let invalid = Proj {
+        shape: Some(vec![100]),
+        ..fields
+    };
+    assert!(invalid.native_footprint().is_err());
+}
+
+#[test]
+fn test_set_proj() {
+    let mut item = get_item();
+    item.stac_extensions = Some(Vec::new());
+
+    let mut fields = Proj {
+        epsg: Some(Some(32615)),
+        ..Default::default()
+    };
+    fields.set_transform(&Transform::from_gdal([0.0, 1.0, 0.0, 0.0, 0.0, -1.0]));
+    item.set_asset_proj("B8", &fields).unwrap();
+    assert_eq!(item.assets["B8"].proj().unwrap(), fields);
+    assert_eq!(
+        item.stac_extensions,
+        Some(vec![String::from(proj::SCHEMA_URI)])
+    );
+
+    item.set_proj(&Proj::default()).unwrap();
+    assert!(item.proj().unwrap().epsg.is_none());
+    assert_eq!(
+        item.stac_extensions,
+        Some(vec![String::from(proj::SCHEMA_URI)])
+    );
+}
+
+#[test]
+fn test_null_epsg() {
+    let mut item = get_item();
+    item.properties.extra_fields["proj:epsg"] = Value::Null;
+    let mut fields = item.proj().unwrap();
+    assert_eq!(fields.epsg, Some(None));
+
+    fields.shape = Some(vec![10, 20]);
+    item.set_proj(&fields).unwrap();
+    assert_eq!(item.properties.extra_fields["proj:epsg"], Value::Null);
+    assert_eq!(item.proj().unwrap(), fields);
+
+    let value = serde_json::to_value(&item).unwrap();
+    let read: Item = serde_json::from_value(value).unwrap();
+    assert_eq!(read.proj().unwrap().epsg, Some(None));
+
+    item.set_proj(&Proj {
+        epsg: None,
+        ..fields
+    })
+    .unwrap();
+    assert!(item.properties.extra_fields.get("proj:epsg").is_none());
+    assert!(item.proj().unwrap().epsg.is_none());
+}
@@ -0,0 +1,136 @@
+mod helpers;
+use rustac_core::extensions::raster::{self, Band, DataType, Histogram, NoData, Raster, Sampling};
+use rustac_core::Item;
+use serde_json::json;
+
+fn get_item() -> Item {
+    let mut item = helpers::item(
+        "LC08_L2SP_047027_20201204",
+        json!({"datetime": "2020-12-04T19:02:11Z"}),
+    );
+    item["stac_extensions"] = json!([raster::SCHEMA_URI]);
+    item["assets"] = json!({
+        "SR_B4": {
+            "href": "LC08_L2SP_047027_20201204_SR_B4.TIF",
+            "type": "image/tiff; application=geotiff; profile=cloud-optimized",
+            "raster:bands": [{
+                "data_type": "uint16",
+                "nodata": 0,
+                "sampling": "area",
+                "spatial_resolution": 30,
+                "scale": 0.0000275,
+                "offset": -0.2,
+                "unit": "reflectance",
+                "statistics": {"minimum": 7273, "maximum": 43636, "mean": 9500, "stddev": 1200},
+                "histogram": {"count": 4, "min": 0, "max": 40000, "buckets": [1, 20, 30, 4]}
+            }]
+        },
+        "ST_B10": {
+            "href": "LC08_L2SP_047027_20201204_ST_B10.TIF",
+            "raster:bands": [{"data_type": "float32", "nodata": "nan", "bits_per_sample": 32}]
+        }
+    });
+    serde_json::from_value(item).unwrap()
+}
+
+#[test]
+fn test_get_raster() {
+    let item = get_item();
+
+    let bands = item.assets["SR_B4"].raster().unwrap().bands.unwrap();
+    let band = &bands[0];
+    assert_eq!(band.data_type, Some(DataType::UInt16));
+    assert_eq!(band.sampling, Some(Sampling::Area));
+    assert_eq!(band.nodata, Some(NoData::Value(0.0)));
+    assert_eq!(band.spatial_resolution, Some(30.0));
+    assert_eq!(band.unit, Some(String::from("reflectance")));
+    assert_eq!(band.statistics.as_ref().unwrap().maximum, Some(43636.0));
+    assert_eq!(band.histogram.as_ref().unwrap().buckets, vec![1, 20, 30, 4]);
+
+    let thermal = &item.assets["ST_B10"].raster().unwrap().bands.unwrap()[0];
+    assert_eq!(thermal.nodata, Some(NoData::NaN));
+    assert!(thermal.is_nodata(f64::NAN));
+    assert!(!thermal.is_nodata(0.0));
+    assert_eq!(thermal.data_type.unwrap().bits(), thermal.bits_per_sample);
+}
+
+#[test]
+fn test_scale() {
+    let item = get_item();
+    let band = &item.assets["SR_B4"].raster().unwrap().bands.unwrap()[0];
+
+    let value = band.apply_scale(10000.0);
+    assert!((value - 0.075).abs() < 1e-9);
+    assert!((band.remove_scale(value) - 10000.0).abs() < 1e-6);
+
+    let statistics = band.scaled_statistics().unwrap();
+    assert!((statistics.minimum.unwrap() - (7273.0 * 0.0000275 - 0.2)).abs() < 1e-9);
+    assert!((statistics.stddev.unwrap() - 1200.0 * 0.0000275).abs() < 1e-9);
+
+    let unscaled = Band::default();
+    assert!((unscaled.apply_scale(42.0) - 42.0).abs() < f64::EPSILON);
+    assert!(unscaled.scaled_statistics().is_none());
+}
+
+#[test]
+fn test_nodata_serialization() {
+    for (value, nodata) in [
+        (json!(-9999.0), NoData::Value(-9999.0)),
+        (json!("nan"), NoData::NaN),
+        (json!("inf"), NoData::Inf),
+        (json!("-inf"), NoData::NegInf),
+    ] {
+        assert_eq!(
+            serde_json::from_value::<NoData>(value.clone()).unwrap(),
+            nodata
+        );
+        assert_eq!(serde_json::to_value(nodata).unwrap(), value);
+    }
+    assert!(serde_json::from_value::<NoData>(json!("missing")).is_err());
+}
+
+#[test]
+fn test_data_type() {
+    assert_eq!(
+        serde_json::to_value(DataType::CFloat64).unwrap(),
+        json!("cfloat64")
+    );
+    assert_eq!(DataType::CFloat64.bits(), Some(128));
+    assert!(DataType::CFloat32.is_complex() && DataType::CFloat32.is_float());
+    assert!(!DataType::Int8.is_float());
+    assert!(DataType::Other.bits().is_none());
+}
+
+#[test]
+fn test_histogram() {
+    let histogram = Histogram {
+        count: 4,
+        min: 0.0,
+        max: 40000.0,
+        buckets: vec![1, 20, 30, 4],
+    };
+    assert_eq!(histogram.bucket_range(1), Some((10000.0, 20000.0)));
+    assert!(histogram.bucket_range(4).is_none());
+}
+
+#[test]
+fn test_set_raster() {
+    let mut item = get_item();
+    let fields = Raster {
+        bands: Some(vec![Band {
+            data_type: Some(DataType::Int16),
+            nodata: Some(NoData::Value(-32768.0)),
+            ..Default::default()
+        }]),
+    };
+    item.set_asset_raster("ST_B10", &fields).unwrap();
+    assert_eq!(item.assets["ST_B10"].raster().unwrap(), fields);
+    assert_eq!(
+        item.assets["ST_B10"].extra_fields["raster:bands"],
+        json!([{"data_type": "int16", "nodata": -32768.0}])
+    );
+
+    item.set_asset_raster("SR_B4", &Raster::default()).unwrap();
+    item.set_asset_raster("ST_B10", &Raster::default()).unwrap();
+    assert_eq!(item.stac_extensions, Some(Vec::new()));
+}
@@ -0,0 +1,157 @@
+mod helpers;
+use chrono::DateTime;
+use rustac_core::extensions::sar::{self, FrequencyBand, ObservationDirection, Polarization, Sar};
+use rustac_core::extensions::sat::{self, OrbitState, Sat};
+use rustac_core::Item;
+use serde_json::json;
+
+fn get_item() -> Item {
+    let mut item = helpers::item(
+        "S1B_IW_GRDH_1SDV_20210101T172405_20210101T172430_024963_02F864_8C17",
+        json!({
+            "datetime": "2021-01-01T17:24:17Z",
+            "platform": "sentinel-1b",
+            "constellation": "sentinel-1",
+            "sar:instrument_mode": "IW",
+            "sar:frequency_band": "C",
+            "sar:center_frequency": 5.405,
+            "sar:polarizations": ["VV", "VH"],
+            "sar:product_type": "GRD",
+            "sar:resolution_range": 20,
+            "sar:resolution_azimuth": 22,
+            "sar:pixel_spacing_range": 10,
+            "sar:pixel_spacing_azimuth": 10,
+            "sar:looks_range": 5,
+            "sar:looks_azimuth": 1,
+            "sar:looks_equivalent_number": 4.4,
+            "sar:observation_direction": "right",
+            "sat:platform_international_designator": "2016-025A",
+            "sat:orbit_state": "ascending",
+            "sat:absolute_orbit": 24963,
+            "sat:relative_orbit": 35,
+            "sat:anx_datetime": "2021-01-01T17:07:08.913Z"
+        }),
+    );
+    item["stac_extensions"] = json!([sar::SCHEMA_URI, sat::SCHEMA_URI]);
+    item["assets"] = json!({
+        "vv": {"href": "measurement/iw-vv.tiff", "sar:polarizations": ["VV"]},
+        "vh": {"href": "measurement/iw-vh.tiff", "sar:polarizations": ["VH"]}
+    });
+    serde_json::from_value(item).unwrap()
+}
+
+#[test]
+fn test_get_sar() {
+    let item = get_item();
+    let fields = item.sar().unwrap();
+
+    assert_eq!(fields.instrument_mode, Some(String::from("IW")));
+    assert_eq!(fields.frequency_band, Some(FrequencyBand::C));
+    assert_eq!(
+        fields.polarizations,
+        Some(vec![Polarization::VV, Polarization::VH])
+    );
+    assert_eq!(fields.looks_range, Some(5));
+    assert_eq!(
+        fields.observation_direction,
+        Some(ObservationDirection::Right)
+    );
+    let (low, high) = fields.frequency_band.unwrap().frequency_range();
+    assert!(low <= fields.center_frequency.unwrap() && fields.center_frequency.unwrap() <= high);
+
+    assert_eq!(
+        item.assets["vh"].sar().unwrap().polarizations,
+        Some(vec![Polarization::VH])
+    );
+}
+
+#[test]
+fn test_get_sat() {
+    let item = get_item();
+    let fields = item.sat().unwrap();
+
+    assert_eq!(fields.orbit_state, Some(OrbitState::Ascending));
+    assert_eq!(fields.absolute_orbit, Some(24963));
+    assert_eq!(fields.relative_orbit, Some(35));
+    assert_eq!(
+        fields.anx_datetime,
+        Some(DateTime::parse_from_rfc3339("2021-01-01T17:07:08.913Z").unwrap())
+    );
+}
+
+#[test]
+fn test_enumerated_values() {
+    let mut item = get_item();
+    item.properties.extra_fields["sar:polarizations"] = json!(["VV", "XX"]);
+    assert!(item.sar().is_err());
+
+    item.properties.extra_fields["sat:orbit_state"] = json!("sideways");
+    assert!(item.sat().is_err());
+}
+
+#[test]
+fn test_validate_sar() {
+    let mut item = get_item();
+    let mut fields = item.sar().unwrap();
+
+    fields.polarizations = Some(vec![Polarization::HH, Polarization::HH]);
+    assert!(item.set_sar(&fields).is_err());
+
+    fields.polarizations = Some(vec![Polarization::HH, Polarization::HV]);
+    fields.looks_azimuth = Some(0);
+    assert!(item.set_sar(&fields).is_err());
+
+    fields.looks_azimuth = Some(2);
+    item.set_sar(&fields).unwrap();
+    assert_eq!(
+        item.properties.extra_fields["sar:polarizations"],
+        json!(["HH", "HV"])
+    );
+}
+
+#[test]
+fn test_validate_sat() {
+    let mut item = get_item();
+    let mut fields = item.sat().unwrap();
+
+    fields.relative_orbit = Some(0);
+    assert!(item.set_sat(&fields).is_err());
+
+    fields.relative_orbit = Some(12);
+    for invalid in [
+        "2016025A",
+        "16-025A",
+        "2016-25A",
+        "2016-025",
+        "2016-025ABCD",
+        "2016-025a",
+    ] {
+        fields.platform_international_designator = Some(String::from(invalid));
+        assert!(fields.validate().is_err(), "{}", invalid);
+    }
+    fields.platform_international_designator = Some(String::from("1998-067BQ"));
+    item.set_sat(&fields).unwrap();
+    assert_eq!(
+        item.properties.extra_fields["sat:relative_orbit"],
+        json!(12)
+    );
+}
+
+#[test]
+fn test_remove_extensions() {
+    let mut item = get_item();
+    item.set_sat(&Sat::default()).unwrap();
+    assert_eq!(
+        item.stac_extensions,
+        Some(vec![String::from(sar::SCHEMA_URI)])
+    );
+
+    item.set_sar(&Sar::default()).unwrap();
+    item.set_asset_sar("vv", &Sar::default()).unwrap();
+    assert_eq!(
+        item.stac_extensions,
+        Some(vec![String::from(sar::SCHEMA_URI)])
+    );
+    item.set_asset_sar("vh", &Sar::default()).unwrap();
+    assert_eq!(item.stac_extensions, Some(Vec::new()));
+}
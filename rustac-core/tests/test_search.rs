@@ -0,0 +1,354 @@
+mod helpers;
+use geojson::{Geometry, Value as GeometryValue};
+use rustac_core::search::{self, Fields, Search, SortBy};
+use rustac_core::Item;
+use serde_json::{json, Map, Value};
+use std::fs;
+use std::path::PathBuf;
+
+fn item_json(id: &str, collection: &str, x: f64, datetime: &str, cloud_cover: f64) -> Value {
+    let mut item = helpers::item(
+        id,
+        json!({
+            "datetime": datetime,
+            "platform": format!("{collection}-a"),
+            "eo:cloud_cover": cloud_cover
+        }),
+    );
+    item["collection"] = json!(collection);
+    item["bbox"] = json!([x, 0.0, x + 1.0, 1.0]);
+    item["geometry"] = json!({
+        "type": "Polygon",
+        "coordinates": [[[x, 0.0], [x + 1.0, 0.0], [x + 1.0, 1.0], [x, 1.0], [x, 0.0]]]
+    });
+    item["assets"] = json!({"data": {"href": "./data.tif"}});
+    item
+}
+
+fn get_items() -> Vec<Item> {
+    vec![
+        item_json("a", "sentinel", 0.0, "2021-01-01T00:00:00Z", 50.0),
+        item_json("b", "sentinel", 2.0, "2021-02-01T00:00:00Z", 10.0),
+        item_json("c", "landsat", 4.0, "2021-03-01T00:00:00Z", 30.0),
+        item_json("d", "landsat", 6.0, "2021-04-01T00:00:00Z", 10.0),
+    ]
+    .into_iter()
+    .map(|value| serde_json::from_value(value).unwrap())
+    .collect()
+}
+
+fn ids(search: &Search) -> Vec<String> {
+    search
+        .search(get_items())
+        .unwrap()
+        .into_iter()
+        .map(|item| item.id)
+        .collect()
+}
+
+#[test]
+fn test_spatial_temporal() {
+    assert_eq!(ids(&Search::new()), vec!["a", "b", "c", "d"]);
+    assert_eq!(
+        ids(&Search::new().bbox(vec![1.5, 0.5, 4.0, 0.6])),
+        vec!["b", "c"]
+    );
+    let line = Geometry::new(GeometryValue::LineString(vec![
+        vec![0.5, 2.0],
+        vec![0.5, 0.5],
+        vec![6.5, 0.5],
+    ]));
+    assert_eq!(
+        ids(&Search::new().intersects(line)),
+        vec!["a", "b", "c", "d"]
+    );
+    assert_eq!(
+        ids(&Search::new().datetime("2021-02-01T00:00:00Z/2021-03-01T00:00:00Z")),
+        vec!["b", "c"]
+    );
+    assert_eq!(
+        ids(&Search::new().datetime("../2021-01-15T00:00:00Z")),
+        vec!["a"]
+    );
+    assert_eq!(
+        ids(&Search::new().datetime("2021-04-01T00:00:00Z")),
+        vec!["d"]
+    );
+    assert!(Search::new().datetime("../..").to_cql2().is_err());
+}
+
+#[test]
+fn test_ids_collections_query_filter() {
+    assert_eq!(ids(&Search::new().ids(vec!["d", "a", "x"])), vec!["a", "d"]);
+    assert_eq!(
+        ids(&Search::new().collections(vec!["landsat"])),
+        vec!["c", "d"]
+    );
+    let query: Map<String, Value> = serde_json::from_value(json!({
+        "eo:cloud_cover": {"lte": 30, "gt": 5},
+        "platform": {"startsWith": "sentinel"}
+    }))
+    .unwrap();
+    assert_eq!(ids(&Search::new().query(query)), vec!["b"]);
+    let query: Map<String, Value> =
+        serde_json::from_value(json!({"platform": {"in": ["landsat-a"]}})).unwrap();
+    assert_eq!(ids(&Search::new().query(query)), vec!["c", "d"]);
+    let query: Map<String, Value> =
+        serde_json::from_value(json!({"platform": {"near": 1}})).unwrap();
+    assert!(Search::new().query(query).to_cql2().is_err());
+
+    let search = Search::new()
+        .collections(vec!["sentinel", "landsat"])
+        .filter("\"eo:cloud_cover\" < 40 AND id <> 'b'".parse().unwrap());
+    assert_eq!(ids(&search), vec!["c", "d"]);
+    assert_eq!(
+        search.to_cql2().unwrap().to_text(),
+        "collection IN ('sentinel', 'landsat') AND (eo:cloud_cover < 40 AND id <> 'b')"
+    );
+}
+
+#[test]
+fn test_sortby_limit() {
+    let search = Search::new().sortby(vec![SortBy::asc("eo:cloud_cover"), SortBy::desc("id")]);
+    assert_eq!(ids(&search), vec!["d", "b", "c", "a"]);
+    let search = search.limit(2);
+    assert_eq!(ids(&search), vec!["d", "b"]);
+    let search = Search::new().sortby(vec![SortBy::desc("properties.datetime")]);
+    assert_eq!(ids(&search), vec!["d", "c", "b", "a"]);
+    let search = Search::new().sortby(vec![SortBy::asc("missing"), SortBy::asc("collection")]);
+    assert_eq!(ids(&search), vec!["c", "d", "a", "b"]);
+}
+
+#[test]
+fn test_fields() {
+    let item = &get_items()[0];
+    let search = Search::new().fields(Fields {
+        include: vec![
+            String::from("id"),
+            String::from("properties.eo:cloud_cover"),
+        ],
+        exclude: Vec::new(),
+    });
+    assert_eq!(
+        search.project(item).unwrap(),
+        json!({"id": "a", "properties": {"eo:cloud_cover": 50.0}})
+    );
+    let search = Search::new().fields(Fields {
+        include: Vec::new(),
+        exclude: vec![String::from("assets"), String::from("properties.platform")],
+    });
+    let projected = search.project(item).unwrap();
+    assert!(projected.get("assets").is_none());
+    assert!(projected["properties"].get("platform").is_none());
+    assert_eq!(projected["properties"]["eo:cloud_cover"], 50.0);
+    assert_eq!(projected["bbox"], json!([0.0, 0.0, 1.0, 1.0]));
+}
+
+#[test]
+fn test_query_parameters() {
+    let search = Search::new()
+        .filter("id = 'a'".parse().unwrap())
+        .sortby(vec![SortBy::desc("datetime"), SortBy::asc("id")])
+        .fields(Fields {
+            include: vec![String::from("id")],
+            exclude: vec![String::from("links")],
+        });
+    assert_eq!(
+        search.to_query(),
+        vec![
+            (String::from("filter"), String::from("id = 'a'")),
+            (String::from("filter-lang"), String::from("cql2-text")),
+            (String::from("sortby"), String::from("-datetime,+id")),
+            (String::from("fields"), String::from("id,-links")),
+        ]
+    );
+    let value = serde_json::to_value(&search).unwrap();
+    assert_eq!(
+        value,
+        json!({
+            "filter": {"op": "=", "args": [{"property": "id"}, "a"]},
+            "sortby": [{"field": "datetime", "direction": "desc"}, {"field": "id", "direction": "asc"}],
+            "fields": {"include": ["id"], "exclude": ["links"]}
+        })
+    );
+    assert_eq!(serde_json::from_value::<Search>(value).unwrap(), search);
+    assert_eq!(Search::from_query(&search.to_query()).unwrap(), search);
+
+    let line = Geometry::new(GeometryValue::LineString(vec![
+        vec![0.0, 0.0],
+        vec![1.0, 1.0],
+    ]));
+    let search = Search::new()
+        .bbox(vec![-1.5, 0.0, 2.0, 3.0])
+        .intersects(line)
+        .datetime("../2021-01-01T00:00:00Z")
+        .ids(vec!["a", "b"])
+        .collections(vec!["sentinel"])
+        .limit(5)
+        .query(serde_json::from_value(json!({"eo:cloud_cover": {"lt": 10}})).unwrap());
+    assert_eq!(Search::from_query(&search.to_query()).unwrap(), search);
+
+    let query = |pairs: &[(&str, &str)]| -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(name, value)| (String::from(*name), String::from(*value)))
+            .collect()
+    };
+    let decoded = Search::from_query(&query(&[
+        (
+            "filter",
+            r#"{"op": "=", "args": [{"property": "id"}, "a"]}"#,
+        ),
+        ("filter-lang", "cql2-json"),
+        ("sortby", " id,-datetime"),
+        ("token", "2"),
+    ]))
+    .unwrap();
+    assert_eq!(decoded.filter.unwrap().to_text(), "id = 'a'");
+    assert_eq!(
+        decoded.sortby.unwrap(),
+        vec![SortBy::asc("id"), SortBy::desc("datetime")]
+    );
+    assert!(Search::from_query(&query(&[("bbox", "0,0,a,1")])).is_err());
+    assert!(
+        Search::from_query(&query(&[("filter", "id ="), ("filter-lang", "cql2-text")])).is_err()
+    );
+    assert!(Search::from_query(&query(&[("filter", "true"), ("filter-lang", "sql")])).is_err());
+}
+
+#[test]
+fn test_read_items() {
+    let dir = std::env::temp_dir().join(format!("rustac-search-{}", std::process::id()));
+    let write = |path: PathBuf, value: Value| {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, value.to_string()).unwrap();
+    };
+    let catalog = |id: &str, links: Value| {
+        json!({
+            "stac_version": "1.0.0",
+            "type": "Catalog",
+            "id": id,
+            "description": id,
+            "links": links
+        })
+    };
+    write(
+        dir.join("catalog.json"),
+        catalog(
+            "root",
+            json!([
+                {"rel": "self", "href": "./catalog.json"},
+                {"rel": "child", "href": "./sentinel/catalog.json"},
+                {"rel": "child", "href": "./landsat/items.json"},
+                {"rel": "item", "href": "./sentinel/a/a.json"}
+            ]),
+        ),
+    );
+    write(
+        dir.join("sentinel/catalog.json"),
+        catalog(
+            "sentinel",
+            json!([
+                {"rel": "parent", "href": "../catalog.json"},
+                {"rel": "item", "href": "./a/a.json"},
+                {"rel": "item", "href": "./b/b.json"}
+            ]),
+        ),
+    );
+    let items = get_items();
+    for item in &items[..2] {
+        write(
+            dir.join(format!("sentinel/{}/{}.json", item.id, item.id)),
+            serde_json::to_value(item).unwrap(),
+        );
+    }
+    write(
+        dir.join("landsat/items.json"),
+        json!({"type": "FeatureCollection", "features": [&items[2], &items[3]]}),
+    );
+
+    let read = search::read_items(dir.join("catalog.json")).unwrap();
+    let read_ids: Vec<_> = read.iter().map(|item| item.id.as_str()).collect();
+    assert_eq!(read_ids, vec!["a", "b", "c", "d"]);
+    let matched = Search::new()
+        .bbox(vec![0.0, 0.0, 2.5, 1.0])
+        .search(read)
+        .unwrap();
+    assert_eq!(matched.len(), 2);
+
+    write(
+        dir.join("remote.json"),
+        catalog(
+            "remote",
+            json!([{"rel": "child", "href": "https://example.com/catalog.json"}]),
+        ),
+    );
+    assert!(search::read_items(dir.join("remote.json")).is_err());
+    assert!(search::read_items(dir.join("missing.json")).is_err());
+}
+
+#[test]
+fn test_read_catalog() {
+    let dir = std::env::temp_dir().join(format!("rustac-catalog-{}", std::process::id()));
+    let write = |path: PathBuf, value: Value| {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, value.to_string()).unwrap();
+    };
+    write(
+        dir.join("catalog.json"),
+        json!({
+            "stac_version": "1.0.0",
+            "type": "Catalog",
+            "id": "root",
+            "description": "root",
+            "links": [
+                {"rel": "child", "href": "./sentinel/collection.json"},
+                {"rel": "item", "href": "./c.json"}
+            ]
+        }),
+    );
+    write(
+        dir.join("sentinel/collection.json"),
+        json!({
+            "stac_version": "1.0.0",
+            "type": "Collection",
+            "id": "sentinel",
+            "description": "sentinel",
+            "license": "proprietary",
+            "extent": {
+                "spatial": {"bbox": [[0.0, 0.0, 3.0, 1.0]]},
+                "temporal": {"interval": [["2021-01-01T00:00:00Z", null]]}
+            },
+            "links": [
+                {"rel": "root", "href": "../catalog.json"},
+                {"rel": "item", "href": "./a.json"},
+                {"rel": "item", "href": "./b.json"}
+            ]
+        }),
+    );
+    let items = get_items();
+    for (path, item) in [
+        ("sentinel/a.json", &items[0]),
+        ("sentinel/b.json", &items[1]),
+        ("c.json", &items[2]),
+    ] {
+        write(dir.join(path), serde_json::to_value(item).unwrap());
+    }
+
+    let found = search::read_catalog(dir.join("catalog.json")).unwrap();
+    let collection_ids: Vec<_> = found.collections.iter().map(|c| c.id.as_str()).collect();
+    assert_eq!(collection_ids, vec!["sentinel"]);
+    let item_collections: Vec<_> = found
+        .items
+        .iter()
+        .map(|(item, collection)| (item.id.as_str(), collection.as_deref()))
+        .collect();
+    assert_eq!(
+        item_collections,
+        vec![
+            ("a", Some("sentinel")),
+            ("b", Some("sentinel")),
+            ("c", None)
+        ]
+    );
+}
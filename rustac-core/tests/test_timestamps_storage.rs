@@ -0,0 +1,131 @@
+mod helpers;
+use chrono::{DateTime, FixedOffset};
+use rustac_core::extensions::storage::{self, Storage};
+use rustac_core::extensions::timestamps::{self, Timestamps};
+use rustac_core::Item;
+use serde_json::json;
+
+fn datetime(s: &str) -> DateTime<FixedOffset> {
+    DateTime::parse_from_rfc3339(s).unwrap()
+}
+
+fn get_item() -> Item {
+    let mut item = helpers::item(
+        "an-item",
+        json!({
+            "datetime": "2021-01-01T00:00:00Z",
+            "published": "2021-01-02T00:00:00Z",
+            "expires": "2022-01-01T00:00:00+02:00",
+            "storage:platform": "AWS",
+            "storage:region": "us-west-2",
+            "storage:tier": "Standard"
+        }),
+    );
+    item["stac_extensions"] = json!([timestamps::SCHEMA_URI, storage::SCHEMA_URI]);
+    item["assets"] = json!({
+        "data": {"href": "s3://bucket/data.tif"},
+        "archive": {
+            "href": "s3://archive/data.zip",
+            "storage:tier": "Glacier",
+            "storage:requester_pays": true,
+            "expires": "2021-06-01T00:00:00Z"
+        },
+        "thumbnail": {"href": "s3://bucket/thumbnail.png", "storage:tier": "standard"}
+    });
+    serde_json::from_value(item).unwrap()
+}
+
+#[test]
+fn test_get_timestamps() {
+    let item = get_item();
+    let fields = item.timestamps().unwrap();
+    assert_eq!(fields.published, Some(datetime("2021-01-02T00:00:00Z")));
+    assert_eq!(fields.expires, Some(datetime("2022-01-01T00:00:00+02:00")));
+    assert!(fields.unpublished.is_none());
+    assert_eq!(
+        item.assets["archive"].timestamps().unwrap().expires,
+        Some(datetime("2021-06-01T00:00:00Z"))
+    );
+}
+
+#[test]
+fn test_is_expired() {
+    let item = get_item();
+    let at = datetime("2021-12-31T23:00:00Z");
+    assert!(item.is_expired(&at).unwrap());
+    assert!(!item.is_expired(&datetime("2021-12-31T21:59:59Z")).unwrap());
+    assert!(item.assets["archive"].is_expired(&at).unwrap());
+    assert!(!item.assets["data"].is_expired(&at).unwrap());
+
+    let fields = Timestamps {
+        unpublished: Some(datetime("2021-03-01T00:00:00Z")),
+        ..item.timestamps().unwrap()
+    };
+    assert!(!fields.is_published(&datetime("2021-01-01T00:00:00Z")));
+    assert!(fields.is_published(&datetime("2021-02-01T00:00:00Z")));
+    assert!(!fields.is_published(&datetime("2021-03-01T00:00:00Z")));
+}
+
+#[test]
+fn test_set_timestamps() {
+    let mut item = get_item();
+    item.set_timestamps(&Timestamps::default()).unwrap();
+    assert!(item.properties.extra_fields.get("published").is_none());
+    assert!(item.properties.common.datetime.is_some());
+    // The asset still uses the extension
+    assert!(item
+        .stac_extensions
+        .as_ref()
+        .unwrap()
+        .contains(&String::from(timestamps::SCHEMA_URI)));
+
+    item.set_asset_timestamps("archive", &Timestamps::default())
+        .unwrap();
+    assert_eq!(
+        item.stac_extensions,
+        Some(vec![String::from(storage::SCHEMA_URI)])
+    );
+    assert!(item
+        .set_asset_timestamps("missing", &Timestamps::default())
+        .is_err());
+}
+
+fn tier_keys(item: &Item, tier: &str) -> Vec<String> {
+    item.assets_in_tier(tier)
+        .unwrap()
+        .into_iter()
+        .map(|(key, _)| String::from(key))
+        .collect()
+}
+
+#[test]
+fn test_storage() {
+    let mut item = get_item();
+    let fields = item.storage().unwrap();
+    assert_eq!(fields.platform, Some(String::from("AWS")));
+    assert_eq!(fields.region, Some(String::from("us-west-2")));
+    assert_eq!(
+        item.assets["archive"].storage().unwrap().requester_pays,
+        Some(true)
+    );
+
+    assert_eq!(tier_keys(&item, "Standard"), vec!["data", "thumbnail"]);
+    assert_eq!(tier_keys(&item, "glacier"), vec!["archive"]);
+
+    item.set_storage(&Storage {
+        tier: None,
+        ..fields
+    })
+    .unwrap();
+    assert_eq!(tier_keys(&item, "Standard"), vec!["thumbnail"]);
+
+    item.set_asset_storage(
+        "data",
+        &Storage {
+            tier: Some(String::from("Glacier")),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    assert_eq!(tier_keys(&item, "Glacier"), vec!["archive", "data"]);
+}
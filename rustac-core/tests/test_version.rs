@@ -0,0 +1,192 @@
+mod helpers;
+
+use helpers::get_test_example;
+use rustac_core::extensions::version::{self, Relation, Version};
+use rustac_core::extensions::Extensions;
+use rustac_core::{Collection, Item, Link};
+use serde_json::json;
+
+fn get_item() -> Item {
+    let mut item = helpers::item(
+        "scene-v1",
+        json!({"datetime": "2021-01-01T00:00:00Z", "version": "1"}),
+    );
+    item["stac_extensions"] = json!([version::SCHEMA_URI]);
+    item["links"] = json!([
+        {"rel": "self", "href": "./scene-v1.json"},
+        {"rel": "predecessor-version", "href": "./scene-v0.json"}
+    ]);
+    serde_json::from_value(item).unwrap()
+}
+
+fn get_collection() -> Collection {
+    let data = get_test_example("core/collection.json");
+    serde_json::from_str(data.as_str()).unwrap()
+}
+
+#[test]
+fn test_get_set_version() {
+    let mut item = get_item();
+    assert!(item.has_extension::<Version>());
+    assert_eq!(
+        item.version().unwrap(),
+        Version {
+            version: Some(String::from("1")),
+            deprecated: None,
+        }
+    );
+
+    item.set_version(&Version::default()).unwrap();
+    assert!(item.properties.extra_fields.get("version").is_none());
+    // The predecessor-version link still uses the extension
+    assert!(item.has_extension::<Version>());
+    item.set_version_link(Relation::Predecessor, None);
+    assert!(!item.has_extension::<Version>());
+    assert!(item.properties.common.datetime.is_some());
+
+    let mut collection = get_collection();
+    let fields = Version {
+        version: Some(String::from("2.1")),
+        deprecated: Some(false),
+    };
+    collection.set_version(&fields).unwrap();
+    assert_eq!(collection.extra_fields["version"], json!("2.1"));
+    assert_eq!(collection.version().unwrap(), fields);
+    assert!(collection
+        .stac_extensions
+        .as_ref()
+        .unwrap()
+        .contains(&String::from(version::SCHEMA_URI)));
+}
+
+#[test]
+fn test_version_links() {
+    let mut item = get_item();
+    assert_eq!(
+        item.version_link(Relation::Predecessor).unwrap().href,
+        "./scene-v0.json"
+    );
+    assert!(item.version_link(Relation::Latest).is_none());
+
+    item.links
+        .push(Link::new("./old-latest.json", Relation::Latest.rel()));
+    item.set_version_link(Relation::Latest, Some("./scene-v3.json"));
+    let latest: Vec<_> = item
+        .links
+        .iter()
+        .filter(|link| link.rel == "latest-version")
+        .collect();
+    assert_eq!(latest.len(), 1);
+    assert_eq!(latest[0].href, "./scene-v3.json");
+
+    item.set_version_link(Relation::Latest, None);
+    assert!(item.version_link(Relation::Latest).is_none());
+}
+
+#[test]
+fn test_new_version() {
+    let mut item = get_item();
+    let new = item
+        .new_version("scene-v2", "2", "./scene-v1.json", "./scene-v2.json")
+        .unwrap();
+
+    assert_eq!(new.id, "scene-v2");
+    assert_eq!(
+        new.version().unwrap(),
+        Version {
+            version: Some(String::from("2")),
+            deprecated: None,
+        }
+    );
+    assert_eq!(
+        new.links
+            .iter()
+            .find(|link| link.rel == "self")
+            .unwrap()
+            .href,
+        "./scene-v2.json"
+    );
+    assert_eq!(
+        new.version_link(Relation::Predecessor).unwrap().href,
+        "./scene-v1.json"
+    );
+    assert!(new.version_link(Relation::Successor).is_none());
+    assert!(new.version_link(Relation::Latest).is_none());
+
+    assert_eq!(item.id, "scene-v1");
+    assert_eq!(
+        item.version().unwrap(),
+        Version {
+            version: Some(String::from("1")),
+            deprecated: None,
+        }
+    );
+    assert_eq!(
+        item.version_link(Relation::Successor).unwrap().href,
+        "./scene-v2.json"
+    );
+    assert_eq!(
+        item.version_link(Relation::Latest).unwrap().href,
+        "./scene-v2.json"
+    );
+    assert_eq!(
+        item.version_link(Relation::Predecessor).unwrap().href,
+        "./scene-v0.json"
+    );
+}
+
+#[test]
+fn test_new_collection_version() {
+    let mut collection = get_collection();
+    let id = collection.id.clone();
+    let new = collection
+        .new_version("next", "2", "./collection.json", "../next/collection.json")
+        .unwrap();
+    assert_eq!(new.id, "next");
+    assert_eq!(new.version().unwrap().version, Some(String::from("2")));
+    assert_eq!(
+        new.version_link(Relation::Predecessor).unwrap().href,
+        "./collection.json"
+    );
+    assert_eq!(collection.id, id);
+    assert!(collection.version().unwrap().deprecated.is_none());
+    assert!(collection
+        .stac_extensions
+        .as_ref()
+        .unwrap()
+        .contains(&String::from(version::SCHEMA_URI)));
+    assert_eq!(
+        collection.version_link(Relation::Latest).unwrap().href,
+        "../next/collection.json"
+    );
+}
+
+#[test]
+fn test_version_links_declare_extension() {
+    let mut item = get_item();
+    item.stac_extensions = None;
+    item.properties
+        .extra_fields
+        .as_object_mut()
+        .unwrap()
+        .remove("version");
+    item.links.clear();
+    assert!(!item.has_extension::<Version>());
+
+    item.set_version_link(Relation::Latest, Some("./scene-v3.json"));
+    assert!(item.has_extension::<Version>());
+    item.set_version_link(Relation::Latest, None);
+    assert!(!item.has_extension::<Version>());
+
+    let mut predecessor: Item =
+        serde_json::from_value(serde_json::to_value(&item).unwrap()).unwrap();
+    version::link_versions(
+        &mut predecessor,
+        "./scene-v1.json",
+        &mut item,
+        "./scene-v2.json",
+    );
+    assert!(predecessor.has_extension::<Version>());
+    assert!(item.has_extension::<Version>());
+    assert!(predecessor.version().unwrap().deprecated.is_none());
+}
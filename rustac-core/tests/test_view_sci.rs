@@ -0,0 +1,214 @@
+mod helpers;
+
+use helpers::get_test_example;
+use rustac_core::extensions::sci::{self, Publication, Sci};
+use rustac_core::extensions::view::{self, View};
+use rustac_core::{Item, Link};
+
+fn get_item() -> Item {
+    let data = get_test_example("core/extended-item.json");
+    serde_json::from_str(data.as_str()).unwrap()
+}
+
+fn cite_as(item: &Item) -> Vec<&str> {
+    item.links
+        .iter()
+        .filter(|link| link.rel == sci::CITE_AS)
+        .map(|link| link.href.as_str())
+        .collect()
+}
+
+#[test]
+fn test_get_view() {
+    let fields = get_item().view().unwrap();
+    assert_eq!(fields.off_nadir, Some(3.8));
+    assert_eq!(fields.sun_azimuth, Some(135.7));
+    assert_eq!(fields.sun_elevation, Some(54.9));
+    assert!(fields.azimuth.is_none());
+}
+
+#[test]
+fn test_set_view() {
+    let mut item = get_item();
+    let fields = View {
+        incidence_angle: Some(12.5),
+        azimuth: Some(0.0),
+        ..Default::default()
+    };
+    item.set_view(&fields).unwrap();
+
+    assert_eq!(item.view().unwrap(), fields);
+    assert!(!item
+        .properties
+        .extra_fields
+        .as_object()
+        .unwrap()
+        .contains_key("view:off_nadir"));
+
+    item.set_view(&View::default()).unwrap();
+    assert!(!item
+        .stac_extensions
+        .as_ref()
+        .unwrap()
+        .iter()
+        .any(|uri| uri == view::SCHEMA_URI));
+}
+
+#[test]
+fn test_view_ranges() {
+    let mut item = get_item();
+    for fields in [
+        View {
+            off_nadir: Some(90.5),
+            ..Default::default()
+        },
+        View {
+            incidence_angle: Some(-1.0),
+            ..Default::default()
+        },
+        View {
+            sun_azimuth: Some(360.1),
+            ..Default::default()
+        },
+        View {
+            sun_elevation: Some(-90.1),
+            ..Default::default()
+        },
+    ] {
+        assert!(item.set_view(&fields).is_err());
+    }
+    assert_eq!(item.view().unwrap().off_nadir, Some(3.8));
+
+    let fields = View {
+        sun_elevation: Some(-90.0),
+        azimuth: Some(360.0),
+        ..Default::default()
+    };
+    assert!(fields.validate().is_ok());
+}
+
+#[test]
+fn test_parse_doi() {
+    for value in [
+        "10.5061/dryad.s2v81.2/27.2",
+        "doi:10.5061/dryad.s2v81.2/27.2",
+        "DOI:10.5061/dryad.s2v81.2/27.2",
+        "https://doi.org/10.5061/dryad.s2v81.2/27.2",
+        "http://dx.doi.org/10.5061/dryad.s2v81.2/27.2",
+    ] {
+        assert_eq!(sci::parse_doi(value).unwrap(), "10.5061/dryad.s2v81.2/27.2");
+    }
+    for value in [
+        "",
+        "10.5061",
+        "10./dryad",
+        "11.5061/dryad",
+        "10.50a1/dryad",
+        "10.5061/",
+    ] {
+        assert!(sci::parse_doi(value).is_err(), "{}", value);
+    }
+}
+
+#[test]
+fn test_set_sci() {
+    let mut item = get_item();
+    assert_eq!(
+        item.sci().unwrap().doi,
+        Some(String::from("10.5061/dryad.s2v81.2/27.2"))
+    );
+    assert!(cite_as(&item).is_empty());
+
+    let fields = Sci {
+        doi: Some(String::from("https://doi.org/10.1000/182")),
+        citation: Some(String::from("Doe, J. (2021). Example data.")),
+        publications: Some(vec![Publication {
+            doi: Some(String::from("doi:10.1000/183")),
+            citation: None,
+        }]),
+    };
+    item.set_sci(&fields).unwrap();
+
+    let stored = item.sci().unwrap();
+    assert_eq!(stored.doi, Some(String::from("10.1000/182")));
+    assert_eq!(
+        stored.publications.unwrap()[0].doi,
+        Some(String::from("10.1000/183"))
+    );
+    assert_eq!(cite_as(&item), vec!["https://doi.org/10.1000/182"]);
+
+    // Replacing the DOI replaces the generated link instead of adding another one
+    item.set_sci(&Sci {
+        doi: Some(String::from("10.1000/184")),
+        ..Default::default()
+    })
+    .unwrap();
+    assert_eq!(cite_as(&item), vec!["https://doi.org/10.1000/184"]);
+    assert!(item.sci().unwrap().citation.is_none());
+
+    item.set_sci(&Sci::default()).unwrap();
+    assert!(cite_as(&item).is_empty());
+    assert!(!item
+        .stac_extensions
+        .as_ref()
+        .unwrap()
+        .iter()
+        .any(|uri| uri == sci::SCHEMA_URI));
+}
+
+#[test]
+fn test_set_sci_invalid_doi() {
+    let mut item = get_item();
+    let fields = Sci {
+        doi: Some(String::from("not-a-doi")),
+        ..Default::default()
+    };
+    assert!(item.set_sci(&fields).is_err());
+    assert_eq!(
+        item.sci().unwrap().doi,
+        Some(String::from("10.5061/dryad.s2v81.2/27.2"))
+    );
+}
+
+#[test]
+fn test_set_sci_keeps_other_cite_as_links() {
+    let mut item = get_item();
+    item.set_sci(&Sci {
+        doi: Some(String::from("10.1000/182")),
+        ..Default::default()
+    })
+    .unwrap();
+    item.links = vec![
+        Link::new("https://doi.org/10.1000/999", sci::CITE_AS),
+        Link::new("http://dx.doi.org/10.1000/182", sci::CITE_AS),
+        Link::new("https://example.com/citation", sci::CITE_AS),
+    ];
+
+    item.set_sci(&Sci {
+        doi: Some(String::from("10.1000/184")),
+        ..Default::default()
+    })
+    .unwrap();
+    assert_eq!(
+        cite_as(&item),
+        vec![
+            "https://doi.org/10.1000/999",
+            "https://example.com/citation",
+            "https://doi.org/10.1000/184"
+        ]
+    );
+
+    // A DOI that already has a cite-as link does not get another one
+    item.set_sci(&Sci {
+        doi: Some(String::from("10.1000/999")),
+        ..Default::default()
+    })
+    .unwrap();
+    assert_eq!(
+        cite_as(&item),
+        vec![
+            "https://doi.org/10.1000/999",
+            "https://example.com/citation"
+        ]
+    );
+}
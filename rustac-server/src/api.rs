@@ -0,0 +1,980 @@
+//! The endpoints of a STAC API, backed by a [`Backend`].
+use hyper::body::HttpBody;
+use hyper::header::{
+    HeaderMap, HeaderValue, CONTENT_LENGTH, CONTENT_TYPE, ETAG, IF_MATCH, LOCATION,
+};
+use hyper::http::request::Parts;
+use hyper::{Body, Method, Request, Response, StatusCode};
+use rustac_core::search::Search;
+use rustac_core::{Collection, Item};
+use rustac_validate::{ValidationTarget, Validator};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use url::Url;
+
+use crate::backend::{check_etag, etag, Backend, ItemPage};
+use crate::error::{STACError, STACResult};
+
+/// The version of STAC reported by the landing page.
+const STAC_VERSION: &str = "1.0.0";
+
+/// The conformance classes implemented by every [`Api`].
+pub const CONFORMANCE_CLASSES: &[&str] = &[
+    "https://api.stacspec.org/v1.0.0/core",
+    "https://api.stacspec.org/v1.0.0/collections",
+    "https://api.stacspec.org/v1.0.0/ogcapi-features",
+    "https://api.stacspec.org/v1.0.0/item-search",
+    "https://api.stacspec.org/v1.0.0/item-search#query",
+    "https://api.stacspec.org/v1.0.0/item-search#filter",
+    "https://api.stacspec.org/v1.0.0/item-search#sort",
+    "https://api.stacspec.org/v1.0.0/item-search#fields",
+    "http://www.opengis.net/spec/ogcapi-features-1/1.0/conf/core",
+    "http://www.opengis.net/spec/ogcapi-features-1/1.0/conf/geojson",
+    "http://www.opengis.net/spec/ogcapi-features-3/1.0/conf/filter",
+    "http://www.opengis.net/spec/cql2/1.0/conf/cql2-text",
+    "http://www.opengis.net/spec/cql2/1.0/conf/cql2-json",
+    "http://www.opengis.net/spec/cql2/1.0/conf/basic-cql2",
+];
+
+/// The conformance classes of the Transactions extension, implemented by an [`Api`] whose backend
+/// supports [transactions](Backend::transactions).
+pub const TRANSACTION_CONFORMANCE_CLASSES: &[&str] = &[
+    "https://api.stacspec.org/v1.0.0/ogcapi-features/extensions/transaction",
+    "https://api.stacspec.org/v1.0.0/collections/extensions/transaction",
+    "http://www.opengis.net/spec/ogcapi-features-4/1.0/conf/create-replace-delete",
+    "http://www.opengis.net/spec/ogcapi-features-4/1.0/conf/update",
+];
+
+/// The media type of JSON documents other than GeoJSON.
+const JSON: &str = "application/json";
+
+/// The media type of Items and pages of Items.
+const GEOJSON: &str = "application/geo+json";
+
+/// Links that the API replaces with links to its own endpoints.
+const STRUCTURAL_RELS: &[&str] = &[
+    "self",
+    "root",
+    "parent",
+    "child",
+    "item",
+    "items",
+    "collection",
+];
+
+/// A STAC API serving the Collections and Items of a [`Backend`].
+///
+/// Links in responses are absolute URLs relative to the root URL of the API, which should be the
+/// URL that clients use to reach it.
+///
+/// If the backend supports [transactions](Backend::transactions), the API also serves the
+/// [Transactions](https://github.com/stac-api-extensions/transaction) extension: Collections and
+/// Items can be created with `POST`, replaced with `PUT`, updated with a JSON merge patch using
+/// `PATCH` and removed with `DELETE`, and many Items can be added at once with a `POST` to
+/// `/collections/{collectionId}/bulk_items`. Objects are checked with the [`Validator`] of the API
+/// before they are written. Collections and Items are returned with an `ETag` header, which can be
+/// sent back in an `If-Match` header so that a write fails if the object has changed since.
+pub struct Api<B: Backend> {
+    backend: B,
+    validator: Validator,
+    root: Url,
+    id: String,
+    title: Option<String>,
+    description: String,
+    default_limit: u64,
+    max_limit: u64,
+    max_body_size: usize,
+}
+
+impl<B: Backend> Api<B> {
+    /// Creates an API serving `backend` at the given root URL.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::Other`] if `root` is not a valid URL that can have a path.
+    pub fn new(backend: B, root: &str) -> STACResult<Api<B>> {
+        let mut root = Url::parse(root)
+            .map_err(|err| STACError::Other(format!("Invalid root {root}: {err}")))?;
+        if root.cannot_be_a_base() {
+            return Err(STACError::Other(format!(
+                "{root} cannot be the root of an API"
+            )));
+        }
+        if !root.path().ends_with('/') {
+            root.set_path(&format!("{}/", root.path()));
+        }
+        Ok(Api {
+            backend,
+            validator: Validator::new(),
+            root,
+            id: String::from("rustac"),
+            title: None,
+            description: String::from("A STAC API served by rustac"),
+            default_limit: 10,
+            max_limit: 10_000,
+            max_body_size: 10 * 1024 * 1024,
+        })
+    }
+
+    /// Sets the id of the landing page.
+    #[must_use]
+    pub fn id<S: Into<String>>(mut self, id: S) -> Api<B> {
+        self.id = id.into();
+        self
+    }
+
+    /// Sets the title of the landing page.
+    #[must_use]
+    pub fn title<S: Into<String>>(mut self, title: S) -> Api<B> {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Sets the description of the landing page.
+    #[must_use]
+    pub fn description<S: Into<String>>(mut self, description: S) -> Api<B> {
+        self.description = description.into();
+        self
+    }
+
+    /// Sets the number of Items on a page when a request does not have a `limit`. Defaults to 10.
+    #[must_use]
+    pub fn default_limit(mut self, limit: u64) -> Api<B> {
+        self.default_limit = limit;
+        self
+    }
+
+    /// Sets the largest number of Items on a page. Larger limits are reduced to this number.
+    /// Defaults to 10,000.
+    #[must_use]
+    pub fn max_limit(mut self, limit: u64) -> Api<B> {
+        self.max_limit = limit;
+        self
+    }
+
+    /// Sets the largest request body, in bytes. Requests with larger bodies are refused with
+    /// `413 Payload Too Large` before they are read completely. Defaults to 10 MiB.
+    #[must_use]
+    pub fn max_body_size(mut self, bytes: usize) -> Api<B> {
+        self.max_body_size = bytes;
+        self
+    }
+
+    /// Sets the validator that checks Collections and Items before they are written. Defaults to
+    /// [`Validator::new`], which fetches schemas over the network.
+    #[must_use]
+    pub fn validator(mut self, validator: Validator) -> Api<B> {
+        self.validator = validator;
+        self
+    }
+
+    /// The backend of the API.
+    #[must_use]
+    pub fn backend(&self) -> &B {
+        &self.backend
+    }
+
+    /// The root URL of the API, always ending in `/`.
+    #[must_use]
+    pub fn root(&self) -> &Url {
+        &self.root
+    }
+
+    /// Responds to a request. Errors are returned as JSON with a `code` and `description`, and a
+    /// matching status code.
+    pub async fn handle(&self, request: Request<Body>) -> Response<Body> {
+        let (parts, body) = request.into_parts();
+        let result = match self.read_body(&parts.headers, body).await {
+            Ok(body) => self.respond(&parts, &body).await,
+            Err(err) => Err(err),
+        };
+        result.unwrap_or_else(|err| error_response(&err))
+    }
+
+    /// Reads a request body, failing as soon as it is known to be larger than the maximum size.
+    async fn read_body(&self, headers: &HeaderMap, mut body: Body) -> STACResult<Vec<u8>> {
+        let too_large = || {
+            STACError::PayloadTooLarge(format!(
+                "Request bodies cannot be larger than {} bytes",
+                self.max_body_size
+            ))
+        };
+        let length = headers
+            .get(CONTENT_LENGTH)
+            .and_then(|length| length.to_str().ok())
+            .and_then(|length| length.parse::<u64>().ok());
+        if length.is_some_and(|length| length > self.max_body_size as u64) {
+            return Err(too_large());
+        }
+        let mut bytes = Vec::new();
+        while let Some(chunk) = body.data().await {
+            let chunk = chunk?;
+            if bytes.len() + chunk.len() > self.max_body_size {
+                return Err(too_large());
+            }
+            bytes.extend_from_slice(&chunk);
+        }
+        Ok(bytes)
+    }
+
+    async fn respond(&self, parts: &Parts, body: &[u8]) -> STACResult<Response<Body>> {
+        let path = parts.uri.path();
+        let relative = path
+            .strip_prefix(self.root.path().trim_end_matches('/'))
+            .filter(|relative| relative.is_empty() || relative.starts_with('/'))
+            .ok_or_else(|| STACError::NotFound(format!("{path} is not part of this API")))?;
+        let segments = relative
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .map(|segment| {
+                percent_encoding::percent_decode_str(segment)
+                    .decode_utf8()
+                    .map(String::from)
+                    .map_err(|err| STACError::BadRequest(format!("Invalid path {path}: {err}")))
+            })
+            .collect::<STACResult<Vec<_>>>()?;
+        let segments: Vec<&str> = segments.iter().map(String::as_str).collect();
+        let query: Vec<(String, String)> = parts
+            .uri
+            .query()
+            .map(|query| {
+                url::form_urlencoded::parse(query.as_bytes())
+                    .into_owned()
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let (content_type, value) = match (&parts.method, segments.as_slice()) {
+            (&Method::GET, []) => (JSON, self.landing_page()?),
+            (&Method::GET, ["conformance"]) => (JSON, self.conformance()),
+            (&Method::GET, ["collections"]) => (JSON, self.collections()?),
+            (&Method::GET, ["collections", id]) => {
+                let collection = self.get_collection(id)?;
+                let value = self.collection_json(&collection)?;
+                return tagged(json_response(StatusCode::OK, JSON, &value), &collection);
+            }
+            (&Method::GET, ["collections", id, "items"]) => (GEOJSON, self.items(id, &query)?),
+            (&Method::GET, ["collections", collection_id, "items", item_id]) => {
+                let item = self.get_item(collection_id, item_id)?;
+                let value = self.item_json(&item)?;
+                return tagged(json_response(StatusCode::OK, GEOJSON, &value), &item);
+            }
+            (&Method::GET, ["search"]) => (GEOJSON, self.search_get(&query)?),
+            (&Method::POST, ["search"]) => (GEOJSON, self.search_post(parse_body(body)?)?),
+            (_, segments) if self.backend.transactions() => {
+                return self.transaction(parts, segments, body).await;
+            }
+            (method, _) => return Err(not_an_endpoint(method, path)),
+        };
+        Ok(json_response(StatusCode::OK, content_type, &value))
+    }
+
+    /// Responds to a request of the Transactions extension.
+    async fn transaction(
+        &self,
+        parts: &Parts,
+        segments: &[&str],
+        body: &[u8],
+    ) -> STACResult<Response<Body>> {
+        let if_match = if_match(&parts.headers)?;
+        let if_match = if_match.as_deref();
+        match (&parts.method, segments) {
+            (&Method::POST, ["collections"]) => {
+                let collection: Collection = from_body(parse_body(body)?, "Collection")?;
+                self.validate_collection(&collection).await?;
+                self.backend.create_collection(&collection)?;
+                let value = self.collection_json(&collection)?;
+                let mut response = json_response(StatusCode::CREATED, JSON, &value);
+                located(&mut response, &self.url(&["collections", &collection.id]))?;
+                tagged(response, &collection)
+            }
+            (&Method::PUT, ["collections", id]) => {
+                let collection: Collection = from_body(parse_body(body)?, "Collection")?;
+                if collection.id != *id {
+                    return Err(STACError::BadRequest(format!(
+                        "The id of Collection {} does not match the path",
+                        collection.id
+                    )));
+                }
+                self.replace_collection(&collection, if_match).await
+            }
+            (&Method::PATCH, ["collections", id]) => {
+                let current = self.get_collection(id)?;
+                check_etag(&current, if_match)?;
+                let mut value = serde_json::to_value(&current)?;
+                merge_patch(&mut value, parse_body(body)?);
+                let collection: Collection = from_body(value, "Collection")?;
+                if collection.id != *id {
+                    return Err(STACError::BadRequest(String::from(
+                        "The id of a Collection cannot be changed",
+                    )));
+                }
+                self.replace_collection(&collection, Some(&[etag(&current)?]))
+                    .await
+            }
+            (&Method::DELETE, ["collections", id]) => {
+                self.backend.delete_collection(id, if_match)?;
+                Ok(empty_response(StatusCode::NO_CONTENT))
+            }
+            (&Method::PUT, ["collections", collection_id, "items", item_id]) => {
+                let item = item_in(parse_body(body)?, collection_id)?;
+                if item.id != *item_id {
+                    return Err(STACError::BadRequest(format!(
+                        "The id of Item {} does not match the path",
+                        item.id
+                    )));
+                }
+                self.replace_item(&item, if_match).await
+            }
+            (&Method::PATCH, ["collections", collection_id, "items", item_id]) => {
+                let current = self.get_item(collection_id, item_id)?;
+                check_etag(&current, if_match)?;
+                let mut value = serde_json::to_value(&current)?;
+                merge_patch(&mut value, parse_body(body)?);
+                let item = item_in(value, collection_id)?;
+                if item.id != *item_id {
+                    return Err(STACError::BadRequest(String::from(
+                        "The id of an Item cannot be changed",
+                    )));
+                }
+                self.replace_item(&item, Some(&[etag(&current)?])).await
+            }
+            (&Method::DELETE, ["collections", collection_id, "items", item_id]) => {
+                self.backend.delete_item(collection_id, item_id, if_match)?;
+                Ok(empty_response(StatusCode::NO_CONTENT))
+            }
+            (&Method::POST, ["collections", collection_id, "items"]) => {
+                self.post_items(collection_id, parse_body(body)?).await
+            }
+            (&Method::POST, ["collections", collection_id, "bulk_items"]) => {
+                self.bulk_items(collection_id, parse_body(body)?).await
+            }
+            (method, _) => Err(not_an_endpoint(method, parts.uri.path())),
+        }
+    }
+
+    /// Adds an Item, or the features of a FeatureCollection, to a Collection.
+    async fn post_items(&self, collection_id: &str, mut body: Value) -> STACResult<Response<Body>> {
+        if body["type"] != "FeatureCollection" {
+            let item = item_in(body, collection_id)?;
+            self.validate_item(&item).await?;
+            self.backend
+                .create_items(std::slice::from_ref(&item), false)?;
+            let value = self.item_json(&item)?;
+            let mut response = json_response(StatusCode::CREATED, GEOJSON, &value);
+            located(
+                &mut response,
+                &self.url(&["collections", collection_id, "items", &item.id]),
+            )?;
+            return tagged(response, &item);
+        }
+        let Value::Array(features) = body["features"].take() else {
+            return Err(STACError::BadRequest(String::from(
+                "A FeatureCollection must have an array of features",
+            )));
+        };
+        let items = features
+            .into_iter()
+            .map(|feature| item_in(feature, collection_id))
+            .collect::<STACResult<Vec<_>>>()?;
+        self.create_items(collection_id, &items, false, StatusCode::CREATED)
+            .await
+    }
+
+    /// Adds Items to a Collection from an object of Items by id, replacing existing Items if the
+    /// `method` is `upsert` rather than the default `insert`.
+    async fn bulk_items(&self, collection_id: &str, body: Value) -> STACResult<Response<Body>> {
+        let Value::Object(mut body) = body else {
+            return Err(STACError::BadRequest(String::from(
+                "The body of a bulk insert must be a JSON object",
+            )));
+        };
+        let upsert = match body.get("method").and_then(Value::as_str) {
+            Some("insert") | None => false,
+            Some("upsert") => true,
+            Some(method) => {
+                return Err(STACError::BadRequest(format!(
+                    "Invalid bulk insert method {method}"
+                )))
+            }
+        };
+        let Some(Value::Object(items)) = body.remove("items") else {
+            return Err(STACError::BadRequest(String::from(
+                "The items of a bulk insert must be an object of Items by id",
+            )));
+        };
+        let items = items
+            .into_iter()
+            .map(|(id, item)| {
+                let item = item_in(item, collection_id)?;
+                if item.id == id {
+                    Ok(item)
+                } else {
+                    Err(STACError::BadRequest(format!(
+                        "Item {} is listed under the id {id}",
+                        item.id
+                    )))
+                }
+            })
+            .collect::<STACResult<Vec<_>>>()?;
+        self.create_items(collection_id, &items, upsert, StatusCode::OK)
+            .await
+    }
+
+    /// Validates and replaces a Collection, responding with the new Collection.
+    async fn replace_collection(
+        &self,
+        collection: &Collection,
+        if_match: Option<&[String]>,
+    ) -> STACResult<Response<Body>> {
+        self.validate_collection(collection).await?;
+        self.backend.update_collection(collection, if_match)?;
+        let value = self.collection_json(collection)?;
+        tagged(json_response(StatusCode::OK, JSON, &value), collection)
+    }
+
+    /// Validates and replaces an Item, responding with the new Item.
+    async fn replace_item(
+        &self,
+        item: &Item,
+        if_match: Option<&[String]>,
+    ) -> STACResult<Response<Body>> {
+        self.validate_item(item).await?;
+        self.backend.update_item(item, if_match)?;
+        let value = self.item_json(item)?;
+        tagged(json_response(StatusCode::OK, GEOJSON, &value), item)
+    }
+
+    /// Validates and adds Items to a Collection, responding with a message.
+    async fn create_items(
+        &self,
+        collection_id: &str,
+        items: &[Item],
+        upsert: bool,
+        status: StatusCode,
+    ) -> STACResult<Response<Body>> {
+        for item in items {
+            self.validate_item(item).await?;
+        }
+        self.backend.create_items(items, upsert)?;
+        let message = format!("Added {} Items to Collection {collection_id}", items.len());
+        Ok(json_response(status, JSON, &json!({ "message": message })))
+    }
+
+    /// Checks a Collection with the validator of the API, as it would be served with links.
+    async fn validate_collection(&self, collection: &Collection) -> STACResult<()> {
+        let served: Collection = serde_json::from_value(self.collection_json(collection)?)?;
+        self.validate(&served, &format!("Collection {}", collection.id))
+            .await
+    }
+
+    /// Checks an Item with the validator of the API, as it would be served with links.
+    async fn validate_item(&self, item: &Item) -> STACResult<()> {
+        let served: Item = serde_json::from_value(self.item_json(item)?)?;
+        self.validate(&served, &format!("Item {}", item.id)).await
+    }
+
+    /// Checks an object with the validator of the API.
+    async fn validate<T>(&self, object: &T, name: &str) -> STACResult<()>
+    where
+        for<'a> &'a T: Into<ValidationTarget<'a>>,
+    {
+        let report = self.validator.validate_async(object).await?;
+        if report.has_errors() {
+            let issues: Vec<String> = report.issues.iter().map(ToString::to_string).collect();
+            return Err(STACError::BadRequest(format!(
+                "{name} is not valid: {}",
+                issues.join("; ")
+            )));
+        }
+        Ok(())
+    }
+
+    /// The landing page, with links to the other endpoints and to each Collection.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the backend cannot list its Collections.
+    pub fn landing_page(&self) -> STACResult<Value> {
+        let mut links = vec![
+            link("self", self.url(&[]), JSON),
+            link("root", self.url(&[]), JSON),
+            link("conformance", self.url(&["conformance"]), JSON),
+            link("data", self.url(&["collections"]), JSON),
+        ];
+        for method in ["GET", "POST"] {
+            let mut search = link("search", self.url(&["search"]), GEOJSON);
+            search["method"] = json!(method);
+            links.push(search);
+        }
+        for collection in self.backend.collections()? {
+            let mut child = link("child", self.url(&["collections", &collection.id]), JSON);
+            if let Some(title) = collection.title {
+                child["title"] = json!(title);
+            }
+            links.push(child);
+        }
+        let mut landing_page = json!({
+            "type": "Catalog",
+            "stac_version": STAC_VERSION,
+            "id": self.id,
+            "description": self.description,
+            "conformsTo": self.conformance_classes(),
+            "links": links,
+        });
+        if let Some(title) = &self.title {
+            landing_page["title"] = json!(title);
+        }
+        Ok(landing_page)
+    }
+
+    /// The conformance classes of the API: [`CONFORMANCE_CLASSES`], and
+    /// [`TRANSACTION_CONFORMANCE_CLASSES`] if the backend supports transactions.
+    #[must_use]
+    pub fn conformance(&self) -> Value {
+        json!({ "conformsTo": self.conformance_classes() })
+    }
+
+    /// All Collections, with links to the API.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the backend cannot list its Collections.
+    pub fn collections(&self) -> STACResult<Value> {
+        let collections = self
+            .backend
+            .collections()?
+            .iter()
+            .map(|collection| self.collection_json(collection))
+            .collect::<STACResult<Vec<_>>>()?;
+        Ok(json!({
+            "collections": collections,
+            "links": [
+                link("self", self.url(&["collections"]), JSON),
+                link("root", self.url(&[]), JSON),
+                link("parent", self.url(&[]), JSON),
+            ],
+        }))
+    }
+
+    /// A Collection, with links to the API.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::NotFound`] if there is no such Collection, or an error if the backend
+    /// cannot read it.
+    pub fn collection(&self, id: &str) -> STACResult<Value> {
+        self.collection_json(&self.get_collection(id)?)
+    }
+
+    /// A page of the Items in a Collection. `query` holds the query parameters of the request,
+    /// which may include `limit`, `bbox`, `datetime` and the `token` of a page, along with the
+    /// other parameters of an Item Search except for `collections`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::NotFound`] if there is no such Collection, [`STACError::BadRequest`]
+    /// if a parameter is invalid, or an error if the backend cannot run the search.
+    pub fn items(&self, collection_id: &str, query: &[(String, String)]) -> STACResult<Value> {
+        if self.backend.collection(collection_id)?.is_none() {
+            return Err(STACError::NotFound(format!(
+                "Collection {collection_id} does not exist"
+            )));
+        }
+        let mut search = parse_query(query)?;
+        search.collections = Some(vec![String::from(collection_id)]);
+        let url = self.url(&["collections", collection_id, "items"]);
+        let mut page = self.page(&search, token(query))?;
+        page["links"] = json!([
+            link("self", with_query(url.clone(), query), GEOJSON),
+            link("root", self.url(&[]), JSON),
+            link("parent", self.url(&["collections", collection_id]), JSON),
+            link(
+                "collection",
+                self.url(&["collections", collection_id]),
+                JSON
+            ),
+        ]);
+        Self::push_next_get(&mut page, url, query);
+        Ok(page)
+    }
+
+    /// An Item, with links to the API.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::NotFound`] if there is no such Item, or an error if the backend cannot
+    /// read it.
+    pub fn item(&self, collection_id: &str, item_id: &str) -> STACResult<Value> {
+        self.item_json(&self.get_item(collection_id, item_id)?)
+    }
+
+    /// A page of the Items matched by a `GET` Item Search with these query parameters, which may
+    /// include the `token` of a page.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::BadRequest`] if a parameter is invalid, or an error if the backend
+    /// cannot run the search.
+    pub fn search_get(&self, query: &[(String, String)]) -> STACResult<Value> {
+        let search = parse_query(query)?;
+        let url = self.url(&["search"]);
+        let mut page = self.page(&search, token(query))?;
+        page["links"] = json!([
+            link("self", with_query(url.clone(), query), GEOJSON),
+            link("root", self.url(&[]), JSON),
+        ]);
+        Self::push_next_get(&mut page, url, query);
+        Ok(page)
+    }
+
+    /// A page of the Items matched by a `POST` Item Search with this body, which may include the
+    /// `token` of a page. Links to the next page are `POST` links that merge the token into the
+    /// previous body.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::BadRequest`] if the body is not a valid search, or an error if the
+    /// backend cannot run the search.
+    pub fn search_post(&self, body: Value) -> STACResult<Value> {
+        let Value::Object(mut body) = body else {
+            return Err(STACError::BadRequest(String::from(
+                "The body of a search must be a JSON object",
+            )));
+        };
+        let token = match body.remove("token") {
+            Some(Value::String(token)) => Some(token),
+            Some(Value::Null) | None => None,
+            Some(token) => {
+                return Err(STACError::BadRequest(format!("Invalid token {token}")));
+            }
+        };
+        let search: Search = serde_json::from_value(Value::Object(body))
+            .map_err(|err| STACError::BadRequest(format!("Invalid search: {err}")))?;
+        let mut page = self.page(&search, token.as_deref())?;
+        let next = page["next"].take();
+        page["links"] = json!([
+            link("self", self.url(&["search"]), GEOJSON),
+            link("root", self.url(&[]), JSON),
+        ]);
+        if let Value::String(next) = next {
+            let mut link = link("next", self.url(&["search"]), GEOJSON);
+            link["method"] = json!("POST");
+            link["body"] = json!({ "token": next });
+            link["merge"] = json!(true);
+            push_link(&mut page, link);
+        }
+        remove_next(&mut page);
+        Ok(page)
+    }
+
+    /// Runs a search and returns a FeatureCollection of Items with a `next` field holding the
+    /// token of the next page, if any, to be replaced by a link.
+    fn page(&self, search: &Search, token: Option<&str>) -> STACResult<Value> {
+        let limit = match search.limit {
+            Some(0) => {
+                return Err(STACError::BadRequest(String::from(
+                    "limit must be at least 1",
+                )))
+            }
+            Some(limit) => limit.min(self.max_limit),
+            None => self.default_limit,
+        };
+        let search = Search {
+            limit: Some(limit),
+            ..search.clone()
+        };
+        let ItemPage {
+            items,
+            number_matched,
+            next,
+        } = self.backend.search(&search, token)?;
+        let features = items
+            .iter()
+            .map(|item| {
+                let value = self.item_json(item)?;
+                Ok(match &search.fields {
+                    Some(fields) => fields.apply(value),
+                    None => value,
+                })
+            })
+            .collect::<STACResult<Vec<_>>>()?;
+        let mut page = json!({
+            "type": "FeatureCollection",
+            "numberReturned": features.len(),
+            "features": features,
+            "next": next,
+        });
+        if let Some(number_matched) = number_matched {
+            page["numberMatched"] = json!(number_matched);
+        }
+        Ok(page)
+    }
+
+    /// Replaces the `next` token of a page with a `GET` link to `url` with the same query
+    /// parameters and the new token.
+    fn push_next_get(page: &mut Value, url: Url, query: &[(String, String)]) {
+        if let Value::String(next) = page["next"].take() {
+            let mut query: Vec<(String, String)> = query
+                .iter()
+                .filter(|(name, _)| name != "token")
+                .cloned()
+                .collect();
+            query.push((String::from("token"), next));
+            push_link(page, link("next", with_query(url, &query), GEOJSON));
+        }
+        remove_next(page);
+    }
+
+    fn conformance_classes(&self) -> Vec<&'static str> {
+        let mut classes = CONFORMANCE_CLASSES.to_vec();
+        if self.backend.transactions() {
+            classes.extend(TRANSACTION_CONFORMANCE_CLASSES);
+        }
+        classes
+    }
+
+    fn get_collection(&self, id: &str) -> STACResult<Collection> {
+        self.backend
+            .collection(id)?
+            .ok_or_else(|| STACError::NotFound(format!("Collection {id} does not exist")))
+    }
+
+    fn get_item(&self, collection_id: &str, item_id: &str) -> STACResult<Item> {
+        self.backend.item(collection_id, item_id)?.ok_or_else(|| {
+            STACError::NotFound(format!(
+                "Item {item_id} does not exist in Collection {collection_id}"
+            ))
+        })
+    }
+
+    fn collection_json(&self, collection: &Collection) -> STACResult<Value> {
+        let mut value = serde_json::to_value(collection)?;
+        let links = vec![
+            link("self", self.url(&["collections", &collection.id]), JSON),
+            link("root", self.url(&[]), JSON),
+            link("parent", self.url(&[]), JSON),
+            link(
+                "items",
+                self.url(&["collections", &collection.id, "items"]),
+                GEOJSON,
+            ),
+        ];
+        replace_links(&mut value, links);
+        Ok(value)
+    }
+
+    fn item_json(&self, item: &Item) -> STACResult<Value> {
+        let mut value = serde_json::to_value(item)?;
+        let mut links = vec![link("root", self.url(&[]), JSON)];
+        if let Some(collection) = &item.collection {
+            links.extend([
+                link(
+                    "self",
+                    self.url(&["collections", collection, "items", &item.id]),
+                    GEOJSON,
+                ),
+                link("parent", self.url(&["collections", collection]), JSON),
+                link("collection", self.url(&["collections", collection]), JSON),
+            ]);
+        }
+        replace_links(&mut value, links);
+        Ok(value)
+    }
+
+    /// The URL of an endpoint, with each segment percent-encoded.
+    fn url(&self, segments: &[&str]) -> Url {
+        let mut url = self.root.clone();
+        if let Ok(mut path) = url.path_segments_mut() {
+            path.pop_if_empty().extend(segments);
+        }
+        url
+    }
+}
+
+fn parse_query(query: &[(String, String)]) -> STACResult<Search> {
+    Search::from_query(query).map_err(|err| STACError::BadRequest(err.to_string()))
+}
+
+fn parse_body(body: &[u8]) -> STACResult<Value> {
+    if body.is_empty() {
+        return Ok(Value::Object(Map::new()));
+    }
+    serde_json::from_slice(body)
+        .map_err(|err| STACError::BadRequest(format!("Invalid JSON body: {err}")))
+}
+
+fn from_body<T: DeserializeOwned>(value: Value, kind: &str) -> STACResult<T> {
+    serde_json::from_value(value)
+        .map_err(|err| STACError::BadRequest(format!("Invalid {kind}: {err}")))
+}
+
+/// Reads an Item written to a Collection, which is its `collection` unless it names another one.
+fn item_in(mut value: Value, collection_id: &str) -> STACResult<Item> {
+    if let Some(object) = value.as_object_mut() {
+        object
+            .entry("collection")
+            .or_insert_with(|| Value::String(String::from(collection_id)));
+    }
+    let item: Item = from_body(value, "Item")?;
+    if item.collection.as_deref() != Some(collection_id) {
+        return Err(STACError::BadRequest(format!(
+            "Item {} is not in Collection {collection_id}",
+            item.id
+        )));
+    }
+    Ok(item)
+}
+
+/// Applies a JSON merge patch, as in [RFC 7396](https://www.rfc-editor.org/rfc/rfc7396).
+fn merge_patch(target: &mut Value, patch: Value) {
+    let Value::Object(patch) = patch else {
+        *target = patch;
+        return;
+    };
+    if !target.is_object() {
+        *target = Value::Object(Map::new());
+    }
+    if let Value::Object(target) = target {
+        for (key, value) in patch {
+            if value.is_null() {
+                target.remove(&key);
+            } else {
+                merge_patch(target.entry(key).or_insert(Value::Null), value);
+            }
+        }
+    }
+}
+
+/// The entity tags of an `If-Match` header, without quotes, or `*`. Weak tags are left out, since
+/// `If-Match` uses the strong comparison of [RFC 9110](https://www.rfc-editor.org/rfc/rfc9110#section-13.1.1)
+/// and they can never match.
+fn if_match(headers: &HeaderMap) -> STACResult<Option<Vec<String>>> {
+    let Some(header) = headers.get(IF_MATCH) else {
+        return Ok(None);
+    };
+    let invalid = || STACError::BadRequest(format!("Invalid If-Match header {header:?}"));
+    let value = header.to_str().map_err(|_| invalid())?.trim();
+    if value.is_empty() {
+        return Err(invalid());
+    }
+    if value == "*" {
+        return Ok(Some(vec![String::from(value)]));
+    }
+    let mut tags = Vec::new();
+    for tag in value
+        .split(',')
+        .map(str::trim)
+        .filter(|tag| !tag.is_empty())
+    {
+        let (weak, tag) = match tag.strip_prefix("W/") {
+            Some(tag) => (true, tag),
+            None => (false, tag),
+        };
+        let tag = tag
+            .strip_prefix('"')
+            .and_then(|tag| tag.strip_suffix('"'))
+            .filter(|tag| !tag.contains('"'))
+            .ok_or_else(invalid)?;
+        if !weak {
+            tags.push(String::from(tag));
+        }
+    }
+    Ok(Some(tags))
+}
+
+fn not_an_endpoint(method: &Method, path: &str) -> STACError {
+    STACError::NotFound(format!("{method} {path} is not an endpoint of this API"))
+}
+
+fn token(query: &[(String, String)]) -> Option<&str> {
+    query
+        .iter()
+        .find(|(name, _)| name == "token")
+        .map(|(_, token)| token.as_str())
+}
+
+fn with_query(mut url: Url, query: &[(String, String)]) -> Url {
+    if !query.is_empty() {
+        url.query_pairs_mut().extend_pairs(query);
+    }
+    url
+}
+
+fn link(rel: &str, href: Url, media_type: &str) -> Value {
+    json!({ "rel": rel, "href": String::from(href), "type": media_type })
+}
+
+fn push_link(page: &mut Value, link: Value) {
+    if let Some(links) = page["links"].as_array_mut() {
+        links.push(link);
+    }
+}
+
+fn remove_next(page: &mut Value) {
+    if let Some(page) = page.as_object_mut() {
+        page.remove("next");
+    }
+}
+
+/// Replaces the structural links of a Collection or Item, keeping links to other resources.
+fn replace_links(value: &mut Value, links: Vec<Value>) {
+    let mut kept: Vec<Value> = match value["links"].take() {
+        Value::Array(existing) => existing
+            .into_iter()
+            .filter(|link| {
+                link["rel"]
+                    .as_str()
+                    .is_none_or(|rel| !STRUCTURAL_RELS.contains(&rel))
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+    kept.extend(links);
+    value["links"] = Value::Array(kept);
+}
+
+fn json_response(status: StatusCode, content_type: &'static str, value: &Value) -> Response<Body> {
+    let mut response = Response::new(Body::from(value.to_string()));
+    *response.status_mut() = status;
+    response
+        .headers_mut()
+        .insert(CONTENT_TYPE, HeaderValue::from_static(content_type));
+    response
+}
+
+fn empty_response(status: StatusCode) -> Response<Body> {
+    let mut response = Response::new(Body::empty());
+    *response.status_mut() = status;
+    response
+}
+
+/// Adds the [`etag`] of an object to a response.
+fn tagged<T: Serialize>(mut response: Response<Body>, object: &T) -> STACResult<Response<Body>> {
+    let tag = HeaderValue::from_str(&format!("\"{}\"", etag(object)?))
+        .map_err(|err| STACError::Other(err.to_string()))?;
+    response.headers_mut().insert(ETAG, tag);
+    Ok(response)
+}
+
+fn located(response: &mut Response<Body>, url: &Url) -> STACResult<()> {
+    let location =
+        HeaderValue::from_str(url.as_str()).map_err(|err| STACError::Other(err.to_string()))?;
+    response.headers_mut().insert(LOCATION, location);
+    Ok(())
+}
+
+/// Describes an error as JSON, as in the STAC API specification.
+fn error_response(err: &STACError) -> Response<Body> {
+    let status = err.status();
+    let code = status
+        .canonical_reason()
+        .unwrap_or("Error")
+        .replace(' ', "");
+    json_response(
+        status,
+        JSON,
+        &json!({ "code": code, "description": err.to_string() }),
+    )
+}
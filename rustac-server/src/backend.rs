@@ -0,0 +1,187 @@
+//! Storage for the Collections and Items served by an API.
+use rustac_core::extensions::file::{HashFunction, Multihash};
+use rustac_core::search::Search;
+use rustac_core::{Collection, Item};
+use serde::Serialize;
+
+use crate::error::{STACError, STACResult};
+
+pub use memory::MemoryBackend;
+
+mod memory;
+
+/// A page of Items matched by a search.
+#[derive(Debug, Default)]
+pub struct ItemPage {
+    /// The Items on this page.
+    pub items: Vec<Item>,
+
+    /// The number of Items matched by the search across all pages, if known.
+    pub number_matched: Option<u64>,
+
+    /// A token for fetching the next page, or `None` if this is the last page.
+    pub next: Option<String>,
+}
+
+/// Stores the Collections and Items served by an [`Api`](crate::Api).
+///
+/// Backends only need to look up Collections and Items and run searches; the API adds links,
+/// applies the Fields extension and handles paging. Pages are identified by opaque tokens that the
+/// backend hands out in [`ItemPage::next`], so backends are free to page by offset, by cursor or by
+/// any other means.
+///
+/// Backends that can be written to also implement the methods of the
+/// [Transactions](https://github.com/stac-api-extensions/transaction) extension and return `true`
+/// from [`Backend::transactions`]. The API validates objects before they are written, and the
+/// backend checks the [`etag`]s of the objects being replaced or deleted, so that concurrent
+/// writes cannot silently overwrite each other.
+pub trait Backend: Send + Sync + 'static {
+    /// Returns all Collections.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the Collections cannot be read.
+    fn collections(&self) -> STACResult<Vec<Collection>>;
+
+    /// Returns the Collection with this id, or `None` if there is no such Collection.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the Collection cannot be read.
+    fn collection(&self, id: &str) -> STACResult<Option<Collection>>;
+
+    /// Returns the Item with this id in a Collection, or `None` if there is no such Item.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the Item cannot be read.
+    fn item(&self, collection_id: &str, item_id: &str) -> STACResult<Option<Item>>;
+
+    /// Returns a page of the Items matched by a search, sorted by its `sortby`. The page holds at
+    /// most `search.limit` Items, and starts after the page that returned `token`, or at the first
+    /// match if `token` is `None`. `search.fields` does not need to be applied.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::BadRequest`](crate::error::STACError::BadRequest) if the token is not
+    /// valid, or another error if the search is invalid or cannot be run.
+    fn search(&self, search: &Search, token: Option<&str>) -> STACResult<ItemPage>;
+
+    /// Returns `true` if the backend implements the write methods below, so the API should serve
+    /// the Transactions extension. Defaults to `false`.
+    fn transactions(&self) -> bool {
+        false
+    }
+
+    /// Adds a new Collection.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::Conflict`] if a Collection with the same id exists, or
+    /// [`STACError::NotImplemented`] by default.
+    fn create_collection(&self, _collection: &Collection) -> STACResult<()> {
+        Err(not_implemented())
+    }
+
+    /// Replaces a Collection, keeping its Items. If `if_match` is set, it must contain `*` or
+    /// the [`etag`] of the current Collection.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::NotFound`] if there is no such Collection,
+    /// [`STACError::PreconditionFailed`] if `if_match` does not match, or
+    /// [`STACError::NotImplemented`] by default.
+    fn update_collection(
+        &self,
+        _collection: &Collection,
+        _if_match: Option<&[String]>,
+    ) -> STACResult<()> {
+        Err(not_implemented())
+    }
+
+    /// Deletes a Collection and all of its Items. If `if_match` is set, it must contain `*` or
+    /// the [`etag`] of the Collection.
+    ///
+    /// # Errors
+    ///
+    /// See [`Backend::update_collection`].
+    fn delete_collection(&self, _id: &str, _if_match: Option<&[String]>) -> STACResult<()> {
+        Err(not_implemented())
+    }
+
+    /// Adds Items to the Collections named by their `collection` fields, either all of them or
+    /// none. Existing Items with the same ids are replaced if `upsert` is `true`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::BadRequest`] if an Item does not have a `collection`,
+    /// [`STACError::NotFound`] if its Collection does not exist, [`STACError::Conflict`] if an Item
+    /// exists and `upsert` is `false`, or [`STACError::NotImplemented`] by default.
+    fn create_items(&self, _items: &[Item], _upsert: bool) -> STACResult<()> {
+        Err(not_implemented())
+    }
+
+    /// Replaces an Item in the Collection named by its `collection` field. If `if_match` is set, it
+    /// must contain `*` or the [`etag`] of the current Item.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::NotFound`] if there is no such Item,
+    /// [`STACError::PreconditionFailed`] if `if_match` does not match, or
+    /// [`STACError::NotImplemented`] by default.
+    fn update_item(&self, _item: &Item, _if_match: Option<&[String]>) -> STACResult<()> {
+        Err(not_implemented())
+    }
+
+    /// Deletes an Item from a Collection. If `if_match` is set, it must contain `*` or the [`etag`]
+    /// of the Item.
+    ///
+    /// # Errors
+    ///
+    /// See [`Backend::update_item`].
+    fn delete_item(
+        &self,
+        _collection_id: &str,
+        _item_id: &str,
+        _if_match: Option<&[String]>,
+    ) -> STACResult<()> {
+        Err(not_implemented())
+    }
+}
+
+/// The entity tag of a Collection or Item: the SHA-256 multihash of its JSON, as a hex string.
+/// Objects that serialize to the same JSON have the same tag.
+///
+/// # Errors
+///
+/// Returns [`STACError::JSONParse`] if the object cannot be serialized.
+pub fn etag<T: Serialize>(object: &T) -> STACResult<String> {
+    let json = serde_json::to_value(object)?.to_string();
+    Ok(Multihash::digest(HashFunction::Sha2_256, json.as_bytes()).to_string())
+}
+
+/// Checks the tags of an `If-Match` header against the current version of an object. It matches if
+/// there is no header, or if one of the tags is `*` or the [`etag`] of `current`. An empty list of
+/// tags, such as one that only had weak tags, never matches.
+///
+/// # Errors
+///
+/// Returns [`STACError::PreconditionFailed`] if none of the tags match `current`.
+pub fn check_etag<T: Serialize>(current: &T, if_match: Option<&[String]>) -> STACResult<()> {
+    let Some(tags) = if_match else {
+        return Ok(());
+    };
+    let current = etag(current)?;
+    if tags.iter().any(|tag| tag == "*" || *tag == current) {
+        Ok(())
+    } else {
+        Err(STACError::PreconditionFailed(format!(
+            "{} does not match the current version",
+            tags.join(", ")
+        )))
+    }
+}
+
+fn not_implemented() -> STACError {
+    STACError::NotImplemented(String::from("This backend does not support transactions"))
+}
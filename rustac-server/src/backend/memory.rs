@@ -0,0 +1,277 @@
+//! A backend that keeps all Collections and Items in memory.
+use rustac_core::search::{self, Search};
+use rustac_core::{Collection, Item};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::collections::{BTreeMap, HashSet};
+use std::convert::TryFrom;
+use std::path::Path;
+use std::sync::{PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+use super::{check_etag, Backend, ItemPage};
+use crate::error::{STACError, STACResult};
+
+/// A [`Backend`] that keeps Collections and Items in memory as JSON, e.g. to serve a static catalog
+/// read from disk with [`MemoryBackend::from_catalog`]. It supports transactions, which are lost
+/// when the backend is dropped.
+///
+/// Collections and Items are returned in order of their ids, and search tokens are the number of
+/// matched Items on previous pages.
+#[derive(Debug, Default)]
+pub struct MemoryBackend {
+    data: RwLock<Data>,
+}
+
+#[derive(Debug, Default)]
+struct Data {
+    collections: BTreeMap<String, Value>,
+    items: BTreeMap<String, BTreeMap<String, Value>>,
+}
+
+impl MemoryBackend {
+    /// Creates a backend without any Collections or Items.
+    #[must_use]
+    pub fn new() -> MemoryBackend {
+        MemoryBackend::default()
+    }
+
+    /// Reads the Collections and Items of a static catalog on disk. `path` may be a Catalog or a
+    /// Collection, and `child` and `item` links with local hrefs are followed recursively, as in
+    /// [`read_catalog`](rustac_core::search::read_catalog). Items without a `collection` field are
+    /// assigned to the Collection they were found in.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::Core`] with the errors of
+    /// [`read_catalog`](rustac_core::search::read_catalog) if the catalog cannot be read, or the
+    /// errors of [`MemoryBackend::add_item`] if an Item is not in a Collection.
+    pub fn from_catalog<P: AsRef<Path>>(path: P) -> STACResult<MemoryBackend> {
+        let found = search::read_catalog(path)?;
+        let mut backend = MemoryBackend::new();
+        for collection in &found.collections {
+            backend.add_collection(collection)?;
+        }
+        for (mut item, collection) in found.items {
+            if item.collection.is_none() {
+                item.collection = collection;
+            }
+            backend.add_item(&item)?;
+        }
+        Ok(backend)
+    }
+
+    /// Adds a Collection, replacing any Collection with the same id but keeping its Items.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::JSONParse`] if the Collection cannot be serialized.
+    pub fn add_collection(&mut self, collection: &Collection) -> STACResult<()> {
+        let data = self.data.get_mut().unwrap_or_else(PoisonError::into_inner);
+        data.insert_collection(collection)
+    }
+
+    /// Adds an Item to the Collection named by its `collection` field, replacing any Item with the
+    /// same id in that Collection.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::BadRequest`] if the Item does not have a `collection`,
+    /// [`STACError::NotFound`] if its Collection has not been added, or [`STACError::JSONParse`]
+    /// if the Item cannot be serialized.
+    pub fn add_item(&mut self, item: &Item) -> STACResult<()> {
+        let data = self.data.get_mut().unwrap_or_else(PoisonError::into_inner);
+        let value = serde_json::to_value(item)?;
+        data.items_of(item)?.insert(item.id.clone(), value);
+        Ok(())
+    }
+
+    fn read(&self) -> RwLockReadGuard<'_, Data> {
+        self.data.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, Data> {
+        self.data.write().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl Data {
+    fn insert_collection(&mut self, collection: &Collection) -> STACResult<()> {
+        let value = serde_json::to_value(collection)?;
+        self.collections.insert(collection.id.clone(), value);
+        self.items.entry(collection.id.clone()).or_default();
+        Ok(())
+    }
+
+    /// The Items of the Collection that an Item belongs to.
+    fn items_of(&mut self, item: &Item) -> STACResult<&mut BTreeMap<String, Value>> {
+        let collection = item.collection.as_deref().ok_or_else(|| {
+            STACError::BadRequest(format!("Item {} is not in a Collection", item.id))
+        })?;
+        self.items.get_mut(collection).ok_or_else(|| {
+            STACError::NotFound(format!(
+                "Collection {collection} of Item {} does not exist",
+                item.id
+            ))
+        })
+    }
+
+    fn existing_collection(&self, id: &str) -> STACResult<&Value> {
+        self.collections
+            .get(id)
+            .ok_or_else(|| STACError::NotFound(format!("Collection {id} does not exist")))
+    }
+
+    fn existing_item(&mut self, collection_id: &str, item_id: &str) -> STACResult<&mut Value> {
+        self.items
+            .get_mut(collection_id)
+            .and_then(|items| items.get_mut(item_id))
+            .ok_or_else(|| {
+                STACError::NotFound(format!(
+                    "Item {item_id} does not exist in Collection {collection_id}"
+                ))
+            })
+    }
+}
+
+impl Backend for MemoryBackend {
+    fn collections(&self) -> STACResult<Vec<Collection>> {
+        self.read().collections.values().map(from_json).collect()
+    }
+
+    fn collection(&self, id: &str) -> STACResult<Option<Collection>> {
+        self.read().collections.get(id).map(from_json).transpose()
+    }
+
+    fn item(&self, collection_id: &str, item_id: &str) -> STACResult<Option<Item>> {
+        self.read()
+            .items
+            .get(collection_id)
+            .and_then(|items| items.get(item_id))
+            .map(from_json)
+            .transpose()
+    }
+
+    fn search(&self, search: &Search, token: Option<&str>) -> STACResult<ItemPage> {
+        let offset = match token {
+            Some(token) => token
+                .parse::<usize>()
+                .map_err(|_| STACError::BadRequest(format!("Invalid token {token}")))?,
+            None => 0,
+        };
+        let limit = search
+            .limit
+            .and_then(|limit| usize::try_from(limit).ok())
+            .unwrap_or(usize::MAX);
+        let candidates = self
+            .read()
+            .items
+            .iter()
+            .filter(|(collection, _)| {
+                search
+                    .collections
+                    .as_ref()
+                    .is_none_or(|collections| collections.contains(collection))
+            })
+            .flat_map(|(_, items)| items.values())
+            .map(from_json)
+            .collect::<STACResult<Vec<Item>>>()?;
+        let unlimited = Search {
+            limit: None,
+            ..search.clone()
+        };
+        let matched = unlimited.search(candidates)?;
+        let number_matched = matched.len();
+        let end = offset.saturating_add(limit);
+        Ok(ItemPage {
+            items: matched.into_iter().skip(offset).take(limit).collect(),
+            number_matched: u64::try_from(number_matched).ok(),
+            next: (end < number_matched).then(|| end.to_string()),
+        })
+    }
+
+    fn transactions(&self) -> bool {
+        true
+    }
+
+    fn create_collection(&self, collection: &Collection) -> STACResult<()> {
+        let mut data = self.write();
+        if data.collections.contains_key(&collection.id) {
+            return Err(STACError::Conflict(format!(
+                "Collection {} already exists",
+                collection.id
+            )));
+        }
+        data.insert_collection(collection)
+    }
+
+    fn update_collection(
+        &self,
+        collection: &Collection,
+        if_match: Option<&[String]>,
+    ) -> STACResult<()> {
+        let mut data = self.write();
+        check_etag(data.existing_collection(&collection.id)?, if_match)?;
+        data.insert_collection(collection)
+    }
+
+    fn delete_collection(&self, id: &str, if_match: Option<&[String]>) -> STACResult<()> {
+        let mut data = self.write();
+        check_etag(data.existing_collection(id)?, if_match)?;
+        data.collections.remove(id);
+        data.items.remove(id);
+        Ok(())
+    }
+
+    fn create_items(&self, items: &[Item], upsert: bool) -> STACResult<()> {
+        let values = items
+            .iter()
+            .map(|item| serde_json::to_value(item).map_err(STACError::from))
+            .collect::<STACResult<Vec<_>>>()?;
+        let mut data = self.write();
+        let mut ids = HashSet::new();
+        for item in items {
+            let exists = data.items_of(item)?.contains_key(&item.id);
+            let duplicate = !ids.insert((item.collection.as_deref(), item.id.as_str()));
+            if !upsert && (exists || duplicate) {
+                return Err(STACError::Conflict(format!(
+                    "Item {} already exists",
+                    item.id
+                )));
+            }
+        }
+        for (item, value) in items.iter().zip(values) {
+            data.items_of(item)?.insert(item.id.clone(), value);
+        }
+        Ok(())
+    }
+
+    fn update_item(&self, item: &Item, if_match: Option<&[String]>) -> STACResult<()> {
+        let value = serde_json::to_value(item)?;
+        let mut data = self.write();
+        let collection = item.collection.as_deref().ok_or_else(|| {
+            STACError::BadRequest(format!("Item {} is not in a Collection", item.id))
+        })?;
+        let current = data.existing_item(collection, &item.id)?;
+        check_etag(current, if_match)?;
+        *current = value;
+        Ok(())
+    }
+
+    fn delete_item(
+        &self,
+        collection_id: &str,
+        item_id: &str,
+        if_match: Option<&[String]>,
+    ) -> STACResult<()> {
+        let mut data = self.write();
+        check_etag(data.existing_item(collection_id, item_id)?, if_match)?;
+        if let Some(items) = data.items.get_mut(collection_id) {
+            items.remove(item_id);
+        }
+        Ok(())
+    }
+}
+
+fn from_json<T: DeserializeOwned>(value: &Value) -> STACResult<T> {
+    Ok(serde_json::from_value(value.clone())?)
+}
@@ -0,0 +1,112 @@
+//! Possible errors when serving a STAC API.
+use hyper::StatusCode;
+use std::error;
+use std::fmt;
+use std::result;
+
+/// Alias for [`result::Result`] that uses a [`STACError`]
+pub type STACResult<T> = result::Result<T, STACError>;
+
+/// All errors that may be encountered when serving a STAC API in this package
+#[derive(Debug)]
+pub enum STACError {
+    /// Errors resulting from failed serialization/deserialization of types using the [`serde_json`]
+    /// package
+    JSONParse(serde_json::Error),
+
+    /// Errors from [`rustac_core`], such as invalid searches or unreadable catalogs
+    Core(rustac_core::error::STACError),
+
+    /// Errors resulting from serving HTTP requests with the [`hyper`] package
+    HTTP(hyper::Error),
+
+    /// Requests for a Collection or Item that does not exist, with a description of what is missing
+    NotFound(String),
+
+    /// Requests that are not valid, e.g. with a malformed parameter or body
+    BadRequest(String),
+
+    /// Requests whose body is larger than the API accepts
+    PayloadTooLarge(String),
+
+    /// Requests to create a Collection or Item that already exists
+    Conflict(String),
+
+    /// Requests whose `If-Match` header does not match the current version of an object
+    PreconditionFailed(String),
+
+    /// Requests for functionality that the backend does not support, such as transactions
+    NotImplemented(String),
+
+    /// Errors from [`rustac_validate`] when objects cannot be validated, e.g. because a schema
+    /// cannot be fetched
+    Validate(rustac_validate::error::STACError),
+
+    /// Other errors not covered by the variants above.
+    Other(String),
+}
+
+impl STACError {
+    /// The HTTP status code of a response describing this error. Invalid searches and CQL2
+    /// expressions reported by [`rustac_core`] are bad requests, and other unexpected errors are
+    /// internal server errors.
+    #[must_use]
+    pub fn status(&self) -> StatusCode {
+        match self {
+            STACError::NotFound(_) => StatusCode::NOT_FOUND,
+            STACError::PayloadTooLarge(_) => StatusCode::PAYLOAD_TOO_LARGE,
+            STACError::Conflict(_) => StatusCode::CONFLICT,
+            STACError::PreconditionFailed(_) => StatusCode::PRECONDITION_FAILED,
+            STACError::NotImplemented(_) => StatusCode::NOT_IMPLEMENTED,
+            STACError::BadRequest(_)
+            | STACError::Core(
+                rustac_core::error::STACError::CQL2(_) | rustac_core::error::STACError::Other(_),
+            ) => StatusCode::BAD_REQUEST,
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
+
+impl error::Error for STACError {}
+
+impl fmt::Display for STACError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self {
+            STACError::JSONParse(source) => source.fmt(f),
+            STACError::Core(source) => source.fmt(f),
+            STACError::HTTP(source) => source.fmt(f),
+            STACError::Validate(source) => source.fmt(f),
+            STACError::NotFound(message)
+            | STACError::BadRequest(message)
+            | STACError::PayloadTooLarge(message)
+            | STACError::Conflict(message)
+            | STACError::PreconditionFailed(message)
+            | STACError::NotImplemented(message)
+            | STACError::Other(message) => write!(f, "{}", message.as_str()),
+        }
+    }
+}
+
+impl From<serde_json::Error> for STACError {
+    fn from(err: serde_json::Error) -> STACError {
+        STACError::JSONParse(err)
+    }
+}
+
+impl From<rustac_core::error::STACError> for STACError {
+    fn from(err: rustac_core::error::STACError) -> STACError {
+        STACError::Core(err)
+    }
+}
+
+impl From<hyper::Error> for STACError {
+    fn from(err: hyper::Error) -> STACError {
+        STACError::HTTP(err)
+    }
+}
+
+impl From<rustac_validate::error::STACError> for STACError {
+    fn from(err: rustac_validate::error::STACError) -> STACError {
+        STACError::Validate(err)
+    }
+}
@@ -0,0 +1,63 @@
+#![warn(missing_docs)]
+#![deny(clippy::all, clippy::pedantic)]
+#![allow(
+    clippy::module_name_repetitions,
+    clippy::doc_markdown,
+    clippy::upper_case_acronyms
+)]
+//! A [STAC API](https://github.com/radiantearth/stac-api-spec) server with a pluggable backend.
+//!
+//! [`Api`] implements the Core, Collections, Features and Item Search conformance classes on top
+//! of a [`Backend`], which stores the Collections and Items. Backends that can be written to also
+//! get the Transactions extension, with validation and `ETag`s. [`MemoryBackend`] keeps Collections
+//! and Items in memory, e.g. after reading a static catalog from disk, and [`serve`] serves an API
+//! over HTTP with [`hyper`]:
+//!
+//! ```no_run
+//! use rustac_server::{Api, MemoryBackend};
+//! use std::net::TcpListener;
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let backend = MemoryBackend::from_catalog("catalog/catalog.json").unwrap();
+//!     let api = Api::new(backend, "http://localhost:7822").unwrap();
+//!     let listener = TcpListener::bind("127.0.0.1:7822").unwrap();
+//!     rustac_server::serve(api, listener).await.unwrap();
+//! }
+//! ```
+use hyper::service::{make_service_fn, service_fn};
+use hyper::Server;
+use std::convert::Infallible;
+use std::net::TcpListener;
+use std::sync::Arc;
+
+pub use api::Api;
+pub use backend::{Backend, ItemPage, MemoryBackend};
+
+pub mod api;
+pub mod backend;
+pub mod error;
+
+/// Serves an API on a listener until the server fails. Must be called from within a [Tokio]
+/// runtime.
+///
+/// # Errors
+///
+/// Returns [`STACError::HTTP`](error::STACError::HTTP) if the listener cannot be used or the
+/// server fails.
+///
+/// [Tokio]: https://tokio.rs
+pub async fn serve<B: Backend>(api: Api<B>, listener: TcpListener) -> error::STACResult<()> {
+    let api = Arc::new(api);
+    let make_service = make_service_fn(move |_| {
+        let api = Arc::clone(&api);
+        async move {
+            Ok::<_, Infallible>(service_fn(move |request| {
+                let api = Arc::clone(&api);
+                async move { Ok::<_, Infallible>(api.handle(request).await) }
+            }))
+        }
+    });
+    Server::from_tcp(listener)?.serve(make_service).await?;
+    Ok(())
+}
@@ -0,0 +1,223 @@
+mod helpers;
+
+use helpers::{api, ids, link, scene, send, write_catalog};
+use hyper::header::CONTENT_LENGTH;
+use hyper::{Body, Request, StatusCode};
+use rustac_core::search::Search;
+use rustac_core::Item;
+use rustac_server::error::STACError;
+use rustac_server::{Backend, MemoryBackend};
+use serde_json::json;
+
+const ROOT: &str = "http://stac.test";
+
+/// The path and query of a link, to send to an API without a server.
+fn path(link: &serde_json::Value) -> &str {
+    link["href"].as_str().unwrap().strip_prefix(ROOT).unwrap()
+}
+
+#[test]
+fn test_memory_backend() {
+    let backend = MemoryBackend::from_catalog(write_catalog("backend")).unwrap();
+    let collections: Vec<String> = backend
+        .collections()
+        .unwrap()
+        .into_iter()
+        .map(|collection| collection.id)
+        .collect();
+    assert_eq!(collections, vec!["landsat", "sentinel"]);
+    let l1 = backend.item("landsat", "l1").unwrap().unwrap();
+    assert_eq!(l1.collection.as_deref(), Some("landsat"));
+    assert!(backend.item("sentinel", "l1").unwrap().is_none());
+    assert!(backend.collection("modis").unwrap().is_none());
+
+    let search = Search::new().limit(3);
+    let page = backend.search(&search, None).unwrap();
+    assert_eq!(page.items.len(), 3);
+    assert_eq!(page.number_matched, Some(7));
+    assert_eq!(page.next.as_deref(), Some("3"));
+    let page = backend.search(&search, Some("6")).unwrap();
+    assert_eq!(page.items[0].id, "s4");
+    assert!(page.next.is_none());
+    assert!(matches!(
+        backend.search(&search, Some("three")),
+        Err(STACError::BadRequest(_))
+    ));
+    let page = backend
+        .search(&Search::new().collections(vec!["landsat"]), None)
+        .unwrap();
+    assert_eq!(page.number_matched, Some(3));
+
+    let mut backend = MemoryBackend::new();
+    let orphan: Item = serde_json::from_value(scene("orphan", None, 0.0, 1, 0)).unwrap();
+    assert!(matches!(
+        backend.add_item(&orphan),
+        Err(STACError::BadRequest(_))
+    ));
+    let stray: Item = serde_json::from_value(scene("stray", Some("modis"), 0.0, 1, 0)).unwrap();
+    assert!(matches!(
+        backend.add_item(&stray),
+        Err(STACError::NotFound(_))
+    ));
+}
+
+#[test]
+fn test_landing_page_and_collections() {
+    let api = api("collections", ROOT);
+    let (status, landing_page) = send(&api, "GET", "/", None);
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(landing_page["type"], "Catalog");
+    assert_eq!(
+        link(&landing_page, "data").unwrap()["href"],
+        "http://stac.test/collections"
+    );
+    assert_eq!(link(&landing_page, "search").unwrap()["method"], "GET");
+    assert_eq!(
+        link(&landing_page, "child").unwrap()["href"],
+        "http://stac.test/collections/landsat"
+    );
+    let (_, conformance) = send(&api, "GET", "/conformance", None);
+    assert_eq!(conformance["conformsTo"], landing_page["conformsTo"]);
+    assert!(conformance["conformsTo"]
+        .as_array()
+        .unwrap()
+        .contains(&json!("https://api.stacspec.org/v1.0.0/item-search")));
+
+    let (_, collections) = send(&api, "GET", "/collections", None);
+    assert_eq!(collections["collections"].as_array().unwrap().len(), 2);
+    let (_, landsat) = send(&api, "GET", "/collections/landsat", None);
+    assert_eq!(landsat["title"], "LANDSAT");
+    assert_eq!(
+        link(&landsat, "items").unwrap()["href"],
+        "http://stac.test/collections/landsat/items"
+    );
+    assert!(link(&landsat, "item").is_none());
+    assert_eq!(landsat["links"].as_array().unwrap().len(), 4);
+
+    let (status, error) = send(&api, "GET", "/collections/modis", None);
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(error["code"], "NotFound");
+    assert_eq!(error["description"], "Collection modis does not exist");
+    let (status, _) = send(&api, "PUT", "/search", None);
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[test]
+fn test_items() {
+    let api = api("items", ROOT);
+    let (status, page) = send(&api, "GET", "/collections/landsat/items?limit=2", None);
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(page["type"], "FeatureCollection");
+    assert_eq!(ids(&page), vec!["l1", "l2"]);
+    assert_eq!(page["numberMatched"], 3);
+    assert_eq!(page["numberReturned"], 2);
+    let next = link(&page, "next").unwrap();
+    assert_eq!(path(next), "/collections/landsat/items?limit=2&token=2");
+    let (_, page) = send(&api, "GET", path(next), None);
+    assert_eq!(ids(&page), vec!["l3"]);
+    assert!(link(&page, "next").is_none());
+
+    let (_, page) = send(
+        &api,
+        "GET",
+        "/collections/sentinel/items?bbox=6.5,0,7.5,1&datetime=2021-06-01T00:00:00Z/..",
+        None,
+    );
+    assert_eq!(ids(&page), vec!["s3"]);
+
+    let (status, item) = send(&api, "GET", "/collections/landsat/items/l2", None);
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(item["collection"], "landsat");
+    assert_eq!(
+        link(&item, "self").unwrap()["href"],
+        "http://stac.test/collections/landsat/items/l2"
+    );
+    assert_eq!(
+        link(&item, "license").unwrap()["href"],
+        "https://example.com/license"
+    );
+    let (status, _) = send(&api, "GET", "/collections/sentinel/items/l2", None);
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _) = send(&api, "GET", "/collections/modis/items", None);
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[test]
+fn test_search() {
+    let api = api("search", ROOT);
+    let (_, page) = send(
+        &api,
+        "GET",
+        "/search?filter=%22eo%3Acloud_cover%22%20%3E%3D%2015&sortby=-eo:cloud_cover&fields=id,-links",
+        None,
+    );
+    assert_eq!(ids(&page), vec!["l3", "l2", "s4", "s3"]);
+    assert_eq!(page["features"][0], json!({"id": "l3"}));
+
+    let body = json!({"collections": ["sentinel"], "limit": 3, "sortby": [{"field": "id", "direction": "desc"}]});
+    let (_, page) = send(&api, "POST", "/search", Some(body.clone()));
+    assert_eq!(ids(&page), vec!["s4", "s3", "s2"]);
+    let next = link(&page, "next").unwrap();
+    assert_eq!(next["method"], "POST");
+    assert_eq!(next["merge"], true);
+    let mut body = body;
+    body["token"] = next["body"]["token"].clone();
+    let (_, page) = send(&api, "POST", "/search", Some(body));
+    assert_eq!(ids(&page), vec!["s1"]);
+    assert!(link(&page, "next").is_none());
+
+    for (method, uri, body) in [
+        ("GET", "/search?limit=0", None),
+        ("GET", "/search?bbox=0,0,1", None),
+        ("GET", "/search?token=x", None),
+        ("POST", "/search", Some(json!(["not", "a", "search"]))),
+        (
+            "POST",
+            "/search",
+            Some(json!({"filter": {"op": "nope", "args": []}})),
+        ),
+    ] {
+        let (status, error) = send(&api, method, uri, body);
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{}", uri);
+        assert_eq!(error["code"], "BadRequest");
+    }
+}
+
+#[test]
+fn test_max_body_size() {
+    let api = api("body-size", ROOT).max_body_size(64);
+    let (status, _) = send(&api, "POST", "/search", Some(json!({"limit": 1})));
+    assert_eq!(status, StatusCode::OK);
+    let body = json!({"ids": ["a".repeat(64)]});
+    let (status, error) = send(&api, "POST", "/search", Some(body));
+    assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+    assert_eq!(error["code"], "PayloadTooLarge");
+
+    let request = Request::builder()
+        .method("POST")
+        .uri("/search")
+        .header(CONTENT_LENGTH, "1000000")
+        .body(Body::from("{}"))
+        .unwrap();
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let response = runtime.block_on(api.handle(request));
+    assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+}
+
+#[test]
+fn test_root_with_path() {
+    let api = api("path", "http://stac.test/api/v1");
+    assert_eq!(api.root().as_str(), "http://stac.test/api/v1/");
+    let (status, page) = send(&api, "GET", "/api/v1/collections/landsat/items/l1", None);
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        link(&page, "collection").unwrap()["href"],
+        "http://stac.test/api/v1/collections/landsat"
+    );
+    let (status, _) = send(&api, "GET", "/api/v1", None);
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = send(&api, "GET", "/api/v1x/collections", None);
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _) = send(&api, "GET", "/collections", None);
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
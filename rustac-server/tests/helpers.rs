@@ -0,0 +1,180 @@
+use hyper::{Body, Request, StatusCode};
+use rustac_server::{Api, MemoryBackend};
+use serde_json::{json, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+fn write(path: &Path, value: &Value) {
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, value.to_string()).unwrap();
+}
+
+/// A minimal Item with the given id and properties.
+#[allow(dead_code)]
+pub(crate) fn item(id: &str, properties: Value) -> Value {
+    json!({
+        "stac_version": "1.0.0",
+        "type": "Feature",
+        "id": id,
+        "bbox": [0.0, 0.0, 1.0, 1.0],
+        "geometry": {"type": "Point", "coordinates": [0.0, 0.0]},
+        "properties": properties,
+        "links": [],
+        "assets": {}
+    })
+}
+
+/// An Item whose bbox starts at `x`, with a datetime in month `month` of 2021.
+#[allow(dead_code)]
+pub(crate) fn scene(
+    id: &str,
+    collection: Option<&str>,
+    x: f64,
+    month: u32,
+    cloud_cover: u32,
+) -> Value {
+    let mut scene = item(
+        id,
+        json!({
+            "datetime": format!("2021-{month:02}-01T00:00:00Z"),
+            "eo:cloud_cover": cloud_cover
+        }),
+    );
+    scene["bbox"] = json!([x, 0.0, x + 1.0, 1.0]);
+    scene["geometry"] = json!({"type": "Point", "coordinates": [x + 0.5, 0.5]});
+    scene["links"] = json!([{"rel": "license", "href": "https://example.com/license"}]);
+    if let Some(collection) = collection {
+        scene["collection"] = json!(collection);
+    }
+    scene
+}
+
+/// A minimal Collection with the given id and links.
+#[allow(dead_code)]
+pub(crate) fn collection(id: &str, links: Value) -> Value {
+    json!({
+        "stac_version": "1.0.0",
+        "type": "Collection",
+        "id": id,
+        "title": id.to_uppercase(),
+        "description": "A collection",
+        "license": "proprietary",
+        "extent": {
+            "spatial": {"bbox": [[0.0, 0.0, 10.0, 1.0]]},
+            "temporal": {"interval": [["2021-01-01T00:00:00Z", null]]}
+        },
+        "links": links
+    })
+}
+
+/// Writes a static catalog to a temporary directory and returns the path of its root. The
+/// `landsat` Collection has Items `l1` to `l3` without a `collection` field, and the `sentinel`
+/// Collection has Items `s1` to `s4` in a FeatureCollection.
+#[allow(dead_code)]
+pub(crate) fn write_catalog(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("rustac-server-{name}-{}", std::process::id()));
+    write(
+        &dir.join("catalog.json"),
+        &json!({
+            "stac_version": "1.0.0",
+            "type": "Catalog",
+            "id": "root",
+            "description": "A catalog",
+            "links": [
+                {"rel": "self", "href": "./catalog.json"},
+                {"rel": "child", "href": "./landsat/collection.json"},
+                {"rel": "child", "href": "./sentinel/collection.json"}
+            ]
+        }),
+    );
+    let landsat_links: Vec<Value> = (1..=3)
+        .map(|i| json!({"rel": "item", "href": format!("./l{i}/l{i}.json")}))
+        .chain([json!({"rel": "parent", "href": "../catalog.json"})])
+        .collect();
+    write(
+        &dir.join("landsat/collection.json"),
+        &collection("landsat", json!(landsat_links)),
+    );
+    for i in 1..=3 {
+        write(
+            &dir.join(format!("landsat/l{i}/l{i}.json")),
+            &scene(&format!("l{i}"), None, f64::from(i), i, 10 * i),
+        );
+    }
+    write(
+        &dir.join("sentinel/collection.json"),
+        &collection(
+            "sentinel",
+            json!([
+                {"rel": "item", "href": "./items.json"},
+                {"rel": "root", "href": "../catalog.json"}
+            ]),
+        ),
+    );
+    let features: Vec<Value> = (1..=4)
+        .map(|i| {
+            scene(
+                &format!("s{i}"),
+                Some("sentinel"),
+                f64::from(i) + 4.0,
+                i + 3,
+                5 * i,
+            )
+        })
+        .collect();
+    write(
+        &dir.join("sentinel/items.json"),
+        &json!({"type": "FeatureCollection", "features": features}),
+    );
+    dir.join("catalog.json")
+}
+
+/// An API serving the catalog written by [`write_catalog`].
+#[allow(dead_code)]
+pub(crate) fn api(name: &str, root: &str) -> Api<MemoryBackend> {
+    let backend = MemoryBackend::from_catalog(write_catalog(name)).unwrap();
+    Api::new(backend, root).unwrap()
+}
+
+/// Sends a request to an API without a server, returning the status and JSON body of the response.
+#[allow(dead_code)]
+pub(crate) fn send(
+    api: &Api<MemoryBackend>,
+    method: &str,
+    uri: &str,
+    body: Option<Value>,
+) -> (StatusCode, Value) {
+    let request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .body(body.map_or_else(Body::empty, |body| Body::from(body.to_string())))
+        .unwrap();
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    runtime.block_on(async {
+        let response = api.handle(request).await;
+        let status = response.status();
+        let bytes = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        (status, serde_json::from_slice(&bytes).unwrap())
+    })
+}
+
+/// Finds the first link with a rel in a document.
+#[allow(dead_code)]
+pub(crate) fn link<'a>(value: &'a Value, rel: &str) -> Option<&'a Value> {
+    value["links"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|link| link["rel"] == rel)
+}
+
+/// The ids of the features in a page of Items.
+#[allow(dead_code)]
+pub(crate) fn ids(page: &Value) -> Vec<&str> {
+    page["features"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|feature| feature["id"].as_str().unwrap())
+        .collect()
+}
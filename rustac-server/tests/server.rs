@@ -0,0 +1,57 @@
+mod helpers;
+
+use rustac_client::{Client, Search};
+use std::net::TcpListener;
+use std::thread;
+
+/// Serves the catalog written by [`helpers::write_catalog`] on a local port, returning its root.
+fn start(name: &str) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let root = format!("http://{}", listener.local_addr().unwrap());
+    let api = helpers::api(name, &root).title("Test API");
+    thread::spawn(move || {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        runtime
+            .block_on(rustac_server::serve(api, listener))
+            .unwrap();
+    });
+    root
+}
+
+fn ids<I: Iterator<Item = rustac_client::error::STACResult<rustac_core::Item>>>(
+    items: I,
+) -> Vec<String> {
+    items.map(|item| item.unwrap().id).collect()
+}
+
+#[test]
+fn test_client() {
+    let client = Client::new(&start("client")).unwrap();
+    let landing_page = client.landing_page().unwrap();
+    assert_eq!(landing_page.title.as_deref(), Some("Test API"));
+    assert!(client
+        .conformance()
+        .unwrap()
+        .conforms_to("https://api.stacspec.org/v1.0.0/item-search"));
+    assert_eq!(client.collections().unwrap().len(), 2);
+    assert_eq!(client.collection("sentinel").unwrap().id, "sentinel");
+    assert!(client.collection("modis").is_err());
+
+    let search = Search::new().limit(2);
+    assert_eq!(
+        ids(client.search_get(&search).unwrap()),
+        vec!["l1", "l2", "l3", "s1", "s2", "s3", "s4"]
+    );
+    let search = Search::new()
+        .collections(vec!["sentinel"])
+        .filter("\"eo:cloud_cover\" > 5".parse().unwrap())
+        .limit(1);
+    assert_eq!(
+        ids(client.search_post(&search).unwrap()),
+        vec!["s2", "s3", "s4"]
+    );
+    assert_eq!(
+        ids(client.search_get(&search).unwrap()),
+        vec!["s2", "s3", "s4"]
+    );
+}
@@ -0,0 +1,425 @@
+mod helpers;
+
+use helpers::{collection, link, scene, write_catalog};
+use hyper::header::{ETAG, IF_MATCH, LOCATION};
+use hyper::{Body, HeaderMap, Request, StatusCode};
+use rustac_core::search::Search;
+use rustac_core::{Collection, Item};
+use rustac_server::backend::{Backend, ItemPage};
+use rustac_server::error::STACResult;
+use rustac_server::{Api, MemoryBackend};
+use rustac_validate::cache::SchemaCache;
+use rustac_validate::retrieve::EmbeddedRetriever;
+use rustac_validate::Validator;
+use serde_json::{json, Value};
+
+const ROOT: &str = "http://stac.test";
+
+/// An API serving the catalog written by [`write_catalog`], validating with embedded schemas.
+fn api<B: Backend>(backend: B) -> Api<B> {
+    Api::new(backend, ROOT).unwrap().validator(
+        Validator::new()
+            .with_retriever(EmbeddedRetriever)
+            .with_cache(SchemaCache::new()),
+    )
+}
+
+fn memory_api(name: &str) -> Api<MemoryBackend> {
+    api(MemoryBackend::from_catalog(write_catalog(name)).unwrap())
+}
+
+/// Sends a request with an optional `If-Match` header, returning the status, headers and JSON body
+/// of the response, or `null` if the body is empty.
+fn send<B: Backend>(
+    api: &Api<B>,
+    method: &str,
+    uri: &str,
+    if_match: Option<&str>,
+    body: Option<Value>,
+) -> (StatusCode, HeaderMap, Value) {
+    let mut request = Request::builder().method(method).uri(uri);
+    if let Some(if_match) = if_match {
+        request = request.header(IF_MATCH, if_match);
+    }
+    let request = request
+        .body(body.map_or_else(Body::empty, |body| Body::from(body.to_string())))
+        .unwrap();
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    runtime.block_on(async {
+        let response = api.handle(request).await;
+        let status = response.status();
+        let headers = response.headers().clone();
+        let bytes = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        let value = if bytes.is_empty() {
+            Value::Null
+        } else {
+            serde_json::from_slice(&bytes).unwrap()
+        };
+        (status, headers, value)
+    })
+}
+
+fn etag(headers: &HeaderMap) -> &str {
+    headers[ETAG].to_str().unwrap()
+}
+
+/// A backend that cannot be written to.
+struct ReadOnly(MemoryBackend);
+
+impl Backend for ReadOnly {
+    fn collections(&self) -> STACResult<Vec<Collection>> {
+        self.0.collections()
+    }
+
+    fn collection(&self, id: &str) -> STACResult<Option<Collection>> {
+        self.0.collection(id)
+    }
+
+    fn item(&self, collection_id: &str, item_id: &str) -> STACResult<Option<Item>> {
+        self.0.item(collection_id, item_id)
+    }
+
+    fn search(&self, search: &Search, token: Option<&str>) -> STACResult<ItemPage> {
+        self.0.search(search, token)
+    }
+}
+
+#[test]
+fn test_conformance() {
+    let transaction =
+        json!("https://api.stacspec.org/v1.0.0/ogcapi-features/extensions/transaction");
+    let (_, _, conformance) = send(
+        &memory_api("conformance"),
+        "GET",
+        "/conformance",
+        None,
+        None,
+    );
+    assert!(conformance["conformsTo"]
+        .as_array()
+        .unwrap()
+        .contains(&transaction));
+
+    let read_only = api(ReadOnly(MemoryBackend::new()));
+    let (_, _, conformance) = send(&read_only, "GET", "/conformance", None, None);
+    assert!(!conformance["conformsTo"]
+        .as_array()
+        .unwrap()
+        .contains(&transaction));
+    let (status, _, _) = send(
+        &read_only,
+        "POST",
+        "/collections",
+        None,
+        Some(collection("modis", json!([]))),
+    );
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[test]
+fn test_items() {
+    let api = memory_api("transaction-items");
+    let (status, headers, created) = send(
+        &api,
+        "POST",
+        "/collections/landsat/items",
+        None,
+        Some(scene("l4", None, 4.0, 4, 40)),
+    );
+    assert_eq!(status, StatusCode::CREATED, "{}", created);
+    assert_eq!(created["collection"], "landsat");
+    assert_eq!(
+        headers[LOCATION],
+        "http://stac.test/collections/landsat/items/l4"
+    );
+    let created_tag = etag(&headers).to_string();
+
+    let (status, headers, _) = send(&api, "GET", "/collections/landsat/items/l4", None, None);
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(etag(&headers), created_tag);
+
+    for (body, status) in [
+        (scene("l4", None, 4.0, 4, 40), StatusCode::CONFLICT),
+        (
+            scene("l5", Some("sentinel"), 5.0, 5, 50),
+            StatusCode::BAD_REQUEST,
+        ),
+        (
+            json!({"type": "Feature", "id": "l5"}),
+            StatusCode::BAD_REQUEST,
+        ),
+    ] {
+        let (actual, _, error) = send(&api, "POST", "/collections/landsat/items", None, Some(body));
+        assert_eq!(actual, status, "{}", error);
+    }
+    let mut invalid = scene("l5", None, 5.0, 5, 50);
+    invalid["properties"]["datetime"] = Value::Null;
+    let (status, _, error) = send(
+        &api,
+        "POST",
+        "/collections/landsat/items",
+        None,
+        Some(invalid),
+    );
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(error["description"]
+        .as_str()
+        .unwrap()
+        .starts_with("Item l5 is not valid"));
+    let (status, _, _) = send(
+        &api,
+        "POST",
+        "/collections/modis/items",
+        None,
+        Some(scene("m1", None, 0.0, 1, 0)),
+    );
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let replacement = scene("l4", Some("landsat"), 4.0, 4, 45);
+    let (status, _, _) = send(
+        &api,
+        "PUT",
+        "/collections/landsat/items/l4",
+        Some("\"stale\""),
+        Some(replacement.clone()),
+    );
+    assert_eq!(status, StatusCode::PRECONDITION_FAILED);
+    let (status, headers, replaced) = send(
+        &api,
+        "PUT",
+        "/collections/landsat/items/l4",
+        Some(&created_tag),
+        Some(replacement.clone()),
+    );
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(replaced["properties"]["eo:cloud_cover"], 45);
+    assert_ne!(etag(&headers), created_tag);
+    let (status, _, _) = send(
+        &api,
+        "PUT",
+        "/collections/landsat/items/l5",
+        None,
+        Some(replacement),
+    );
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, headers, patched) = send(
+        &api,
+        "PATCH",
+        "/collections/landsat/items/l4",
+        Some("*"),
+        Some(json!({"properties": {"eo:cloud_cover": null, "platform": "landsat-8"}})),
+    );
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(patched["properties"]["platform"], "landsat-8");
+    assert!(patched["properties"].get("eo:cloud_cover").is_none());
+    let patched_tag = etag(&headers).to_string();
+    let (status, _, _) = send(
+        &api,
+        "PATCH",
+        "/collections/landsat/items/l4",
+        None,
+        Some(json!({"id": "l6"})),
+    );
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, _, _) = send(
+        &api,
+        "DELETE",
+        "/collections/landsat/items/l4",
+        Some(&created_tag),
+        None,
+    );
+    assert_eq!(status, StatusCode::PRECONDITION_FAILED);
+    let (status, _, _) = send(
+        &api,
+        "DELETE",
+        "/collections/landsat/items/l4",
+        Some(&format!("W/{patched_tag}")),
+        None,
+    );
+    assert_eq!(status, StatusCode::PRECONDITION_FAILED);
+    let (status, _, _) = send(
+        &api,
+        "DELETE",
+        "/collections/landsat/items/l4",
+        Some(&format!("{created_tag}, \"{patched_tag}")),
+        None,
+    );
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, _, body) = send(
+        &api,
+        "DELETE",
+        "/collections/landsat/items/l4",
+        Some(&format!("{created_tag}, W/{patched_tag},{patched_tag}")),
+        None,
+    );
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    assert!(body.is_null());
+    let (status, _, _) = send(&api, "GET", "/collections/landsat/items/l4", None, None);
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _, _) = send(&api, "DELETE", "/collections/landsat/items/l4", None, None);
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[test]
+fn test_feature_collection_and_bulk_items() {
+    let api = memory_api("bulk");
+    let features = json!({
+        "type": "FeatureCollection",
+        "features": [scene("l4", None, 4.0, 4, 40), scene("l5", None, 5.0, 5, 50)]
+    });
+    let (status, _, message) = send(
+        &api,
+        "POST",
+        "/collections/landsat/items",
+        None,
+        Some(features),
+    );
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(message["message"], "Added 2 Items to Collection landsat");
+
+    let bulk = |method: &str, ids: &[&str]| {
+        let items: serde_json::Map<String, Value> = ids
+            .iter()
+            .map(|id| (String::from(*id), scene(id, None, 6.0, 6, 60)))
+            .collect();
+        json!({"method": method, "items": items})
+    };
+    let (status, _, _) = send(
+        &api,
+        "POST",
+        "/collections/landsat/bulk_items",
+        None,
+        Some(bulk("insert", &["l5", "l6"])),
+    );
+    assert_eq!(status, StatusCode::CONFLICT);
+    let (status, _, _) = send(&api, "GET", "/collections/landsat/items/l6", None, None);
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _, message) = send(
+        &api,
+        "POST",
+        "/collections/landsat/bulk_items",
+        None,
+        Some(bulk("upsert", &["l5", "l6"])),
+    );
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(message["message"], "Added 2 Items to Collection landsat");
+    let (_, _, l5) = send(&api, "GET", "/collections/landsat/items/l5", None, None);
+    assert_eq!(l5["properties"]["eo:cloud_cover"], 60);
+
+    for body in [
+        bulk("replace", &["l7"]),
+        json!({"items": {"l7": scene("l8", None, 7.0, 7, 70)}}),
+        json!({"items": [scene("l7", None, 7.0, 7, 70)]}),
+    ] {
+        let (status, _, _) = send(
+            &api,
+            "POST",
+            "/collections/landsat/bulk_items",
+            None,
+            Some(body),
+        );
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+    let (_, _, page) = send(&api, "GET", "/collections/landsat/items", None, None);
+    assert_eq!(page["numberMatched"], 6);
+}
+
+#[test]
+fn test_collections() {
+    let api = memory_api("transaction-collections");
+    let (status, headers, created) = send(
+        &api,
+        "POST",
+        "/collections",
+        None,
+        Some(collection("modis", json!([]))),
+    );
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(headers[LOCATION], "http://stac.test/collections/modis");
+    assert_eq!(
+        link(&created, "items").unwrap()["href"],
+        "http://stac.test/collections/modis/items"
+    );
+    let (status, _, _) = send(
+        &api,
+        "POST",
+        "/collections",
+        None,
+        Some(collection("modis", json!([]))),
+    );
+    assert_eq!(status, StatusCode::CONFLICT);
+    let (_, _, landing_page) = send(&api, "GET", "/", None, None);
+    assert!(landing_page["links"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|link| link["href"] == "http://stac.test/collections/modis"));
+
+    let (_, headers, _) = send(&api, "GET", "/collections/landsat", None, None);
+    let tag = etag(&headers).to_string();
+    let mut replacement = collection("landsat", json!([]));
+    replacement["title"] = json!("Landsat");
+    let (status, _, _) = send(
+        &api,
+        "PUT",
+        "/collections/sentinel",
+        None,
+        Some(replacement.clone()),
+    );
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, _, replaced) = send(
+        &api,
+        "PUT",
+        "/collections/landsat",
+        Some(&tag),
+        Some(replacement.clone()),
+    );
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(replaced["title"], "Landsat");
+    let (status, _, _) = send(
+        &api,
+        "PUT",
+        "/collections/landsat",
+        Some(&tag),
+        Some(replacement),
+    );
+    assert_eq!(status, StatusCode::PRECONDITION_FAILED);
+    let (_, _, page) = send(&api, "GET", "/collections/landsat/items", None, None);
+    assert_eq!(page["numberMatched"], 3);
+
+    let (status, _, patched) = send(
+        &api,
+        "PATCH",
+        "/collections/landsat",
+        None,
+        Some(json!({"title": null, "keywords": ["landsat"]})),
+    );
+    assert_eq!(status, StatusCode::OK);
+    assert!(patched.get("title").is_none());
+    assert_eq!(patched["keywords"], json!(["landsat"]));
+    let (status, _, _) = send(
+        &api,
+        "PATCH",
+        "/collections/landsat",
+        None,
+        Some(json!({"license": 42})),
+    );
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, _, _) = send(
+        &api,
+        "PATCH",
+        "/collections/landsat",
+        Some("not quoted"),
+        Some(json!({})),
+    );
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, _, _) = send(&api, "DELETE", "/collections/landsat", None, None);
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let (status, _, _) = send(&api, "GET", "/collections/landsat/items/l1", None, None);
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (_, _, page) = send(&api, "GET", "/search", None, None);
+    assert_eq!(page["numberMatched"], 4);
+}
@@ -0,0 +1,88 @@
+//! In-memory cache of fetched JSON Schemas.
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock, RwLock};
+
+/// Thread-safe cache of JSON Schema documents keyed by their URI. Cloning a `SchemaCache` gives
+/// another handle to the same underlying cache.
+///
+/// The blocking and async validation functions both use [`SchemaCache::global`], so a schema only
+/// has to be fetched once per process regardless of which API fetched it. Schemas can also be
+/// added up front with [`SchemaCache::insert`] to avoid fetching them at all.
+#[derive(Debug, Clone, Default)]
+pub struct SchemaCache {
+    schemas: Arc<RwLock<HashMap<String, Arc<Value>>>>,
+}
+
+impl SchemaCache {
+    /// Creates an empty cache.
+    #[must_use]
+    pub fn new() -> SchemaCache {
+        SchemaCache::default()
+    }
+
+    /// Gets the cache shared by the validation functions in this crate.
+    pub fn global() -> &'static SchemaCache {
+        static GLOBAL: OnceLock<SchemaCache> = OnceLock::new();
+        GLOBAL.get_or_init(SchemaCache::new)
+    }
+
+    /// Gets the schema with the given URI, if it has been cached.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the lock protecting the cache has been poisoned.
+    #[must_use]
+    pub fn get(&self, uri: &str) -> Option<Arc<Value>> {
+        self.schemas.read().unwrap().get(uri).cloned()
+    }
+
+    /// Adds a schema to the cache, replacing any schema already cached under the same URI.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the lock protecting the cache has been poisoned.
+    pub fn insert<S: Into<String>>(&self, uri: S, schema: Value) -> Arc<Value> {
+        let schema = Arc::new(schema);
+        self.schemas
+            .write()
+            .unwrap()
+            .insert(uri.into(), Arc::clone(&schema));
+        schema
+    }
+
+    /// Returns `true` if a schema with the given URI has been cached.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the lock protecting the cache has been poisoned.
+    #[must_use]
+    pub fn contains(&self, uri: &str) -> bool {
+        self.schemas.read().unwrap().contains_key(uri)
+    }
+
+    /// Gets the number of cached schemas.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the lock protecting the cache has been poisoned.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.schemas.read().unwrap().len()
+    }
+
+    /// Returns `true` if no schemas have been cached.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Removes all schemas from the cache.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the lock protecting the cache has been poisoned.
+    pub fn clear(&self) {
+        self.schemas.write().unwrap().clear();
+    }
+}
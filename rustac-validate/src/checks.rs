@@ -0,0 +1,515 @@
+//! Semantic checks for rules that cannot be expressed using JSON Schema.
+//!
+//! Each rule has a stable ID (see [`Rule::id`]) and a default [`Severity`]. Rules can be enabled,
+//! disabled, or assigned a different severity on a [`Checker`], which then produces a [`Report`]
+//! for an object or a group of objects.
+//!
+//! ```
+//! use rustac_core::Item;
+//! use rustac_validate::checks::{Checker, Rule};
+//! use serde_json::json;
+//!
+//! let item: Item = serde_json::from_value(json!({
+//!     "stac_version": "1.0.0",
+//!     "type": "Feature",
+//!     "id": "an-item",
+//!     "bbox": [0.0, 0.0, 1.0, 1.0],
+//!     "geometry": {"type": "Point", "coordinates": [5.0, 5.0]},
+//!     "properties": {"datetime": "2021-01-01T00:00:00Z"},
+//!     "links": [],
+//!     "assets": {}
+//! })).unwrap();
+//!
+//! let report = Checker::new().check_item(&item);
+//! assert_eq!(report.for_rule("bbox-matches-geometry").count(), 1);
+//!
+//! let report = Checker::new().disable(Rule::BboxMatchesGeometry).check_item(&item);
+//! assert!(report.is_empty());
+//! ```
+use chrono::{DateTime, FixedOffset};
+use geojson::Geometry;
+use rustac_core::{Collection, Item, Provider};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::STACError;
+use crate::report::{Issue, Report, Severity};
+use crate::util::escape_pointer;
+
+/// The semantic rules known to the [`Checker`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Rule {
+    /// An Item's `bbox` must match the bounds of its `geometry`.
+    BboxMatchesGeometry,
+
+    /// `start_datetime` must not be after `end_datetime`.
+    DatetimeRangeOrder,
+
+    /// A `null` `datetime` requires both `start_datetime` and `end_datetime`.
+    NullDatetimeRequiresRange,
+
+    /// An Item with a `collection` field must have a `collection` link, and vice versa.
+    CollectionLink,
+
+    /// An Item must fall within the spatial and temporal extent of its Collection.
+    ItemWithinCollectionExtent,
+
+    /// Item IDs must be unique within a Collection.
+    UniqueItemIds,
+
+    /// A Provider must not list the same role more than once.
+    UniqueProviderRoles,
+
+    /// The assets of an Item must match the `item_assets` of its Collection.
+    ItemAssetsConformance,
+}
+
+impl Rule {
+    /// All rules, in the order they are run.
+    pub const ALL: [Rule; 8] = [
+        Rule::BboxMatchesGeometry,
+        Rule::DatetimeRangeOrder,
+        Rule::NullDatetimeRequiresRange,
+        Rule::CollectionLink,
+        Rule::ItemWithinCollectionExtent,
+        Rule::UniqueItemIds,
+        Rule::UniqueProviderRoles,
+        Rule::ItemAssetsConformance,
+    ];
+
+    /// Stable identifier for the rule, used in [`Issue::rule`] and when parsing rules from strings.
+    #[must_use]
+    pub fn id(self) -> &'static str {
+        match self {
+            Rule::BboxMatchesGeometry => "bbox-matches-geometry",
+            Rule::DatetimeRangeOrder => "datetime-range-order",
+            Rule::NullDatetimeRequiresRange => "null-datetime-requires-range",
+            Rule::CollectionLink => "collection-link",
+            Rule::ItemWithinCollectionExtent => "item-within-collection-extent",
+            Rule::UniqueItemIds => "unique-item-ids",
+            Rule::UniqueProviderRoles => "unique-provider-roles",
+            Rule::ItemAssetsConformance => "item-assets-conformance",
+        }
+    }
+
+    /// Severity used for issues from this rule unless overridden with [`Checker::with_severity`].
+    #[must_use]
+    pub fn default_severity(self) -> Severity {
+        match self {
+            Rule::UniqueProviderRoles | Rule::ItemAssetsConformance => Severity::Warning,
+            _ => Severity::Error,
+        }
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.id())
+    }
+}
+
+impl FromStr for Rule {
+    type Err = STACError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Rule::ALL
+            .iter()
+            .copied()
+            .find(|rule| rule.id() == s)
+            .ok_or_else(|| STACError::Other(format!("Unknown rule ID: {s}")))
+    }
+}
+
+/// Runs the enabled [`Rule`]s against STAC objects.
+#[derive(Debug, Clone)]
+pub struct Checker {
+    disabled: HashSet<Rule>,
+    severities: HashMap<Rule, Severity>,
+    tolerance: f64,
+}
+
+impl Default for Checker {
+    fn default() -> Self {
+        Checker {
+            disabled: HashSet::new(),
+            severities: HashMap::new(),
+            tolerance: 1e-7,
+        }
+    }
+}
+
+impl Checker {
+    /// Creates a checker with all rules enabled at their default severities.
+    #[must_use]
+    pub fn new() -> Checker {
+        Checker::default()
+    }
+
+    /// Disables the given rule.
+    #[must_use]
+    pub fn disable(mut self, rule: Rule) -> Checker {
+        self.disabled.insert(rule);
+        self
+    }
+
+    /// Re-enables a previously disabled rule.
+    #[must_use]
+    pub fn enable(mut self, rule: Rule) -> Checker {
+        self.disabled.remove(&rule);
+        self
+    }
+
+    /// Reports issues from the given rule with `severity` instead of its default severity.
+    #[must_use]
+    pub fn with_severity(mut self, rule: Rule, severity: Severity) -> Checker {
+        self.severities.insert(rule, severity);
+        self
+    }
+
+    /// Sets the tolerance used when comparing coordinates. Defaults to `1e-7`.
+    #[must_use]
+    pub fn with_tolerance(mut self, tolerance: f64) -> Checker {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Returns `true` if the rule will be run by this checker.
+    #[must_use]
+    pub fn is_enabled(&self, rule: Rule) -> bool {
+        !self.disabled.contains(&rule)
+    }
+
+    /// Gets the severity issues from this rule will be reported with.
+    #[must_use]
+    pub fn severity(&self, rule: Rule) -> Severity {
+        self.severities
+            .get(&rule)
+            .copied()
+            .unwrap_or_else(|| rule.default_severity())
+    }
+
+    /// Checks all rules that apply to a single Item.
+    #[must_use]
+    pub fn check_item(&self, item: &Item) -> Report {
+        let mut report = Report::new();
+        let mut emit = |rule: Rule, pointer: &str, message: String| {
+            self.emit(&mut report, rule, Some(&item.id), pointer, message);
+        };
+
+        if self.is_enabled(Rule::BboxMatchesGeometry) {
+            match geometry_bounds(&item.geometry) {
+                Some(bounds) if !self.bbox_matches(&item.bbox, &bounds) => emit(
+                    Rule::BboxMatchesGeometry,
+                    "/bbox",
+                    format!(
+                        "bbox {:?} does not match the bounds of the geometry {:?}",
+                        item.bbox, bounds
+                    ),
+                ),
+                _ => {}
+            }
+        }
+
+        let common = &item.properties.common;
+        if self.is_enabled(Rule::DatetimeRangeOrder) {
+            if let (Some(start), Some(end)) = (common.start_datetime, common.end_datetime) {
+                if start > end {
+                    emit(
+                        Rule::DatetimeRangeOrder,
+                        "/properties/start_datetime",
+                        format!(
+                            "start_datetime {} is after end_datetime {}",
+                            start.to_rfc3339(),
+                            end.to_rfc3339()
+                        ),
+                    );
+                }
+            }
+        }
+
+        if self.is_enabled(Rule::NullDatetimeRequiresRange)
+            && common.datetime.is_none()
+            && (common.start_datetime.is_none() || common.end_datetime.is_none())
+        {
+            emit(
+                Rule::NullDatetimeRequiresRange,
+                "/properties/datetime",
+                String::from(
+                    "datetime is null but start_datetime and end_datetime are not both set",
+                ),
+            );
+        }
+
+        if self.is_enabled(Rule::CollectionLink) {
+            let has_link = item.links.iter().any(|link| link.rel == "collection");
+            match (&item.collection, has_link) {
+                (Some(collection), false) => emit(
+                    Rule::CollectionLink,
+                    "/collection",
+                    format!(
+                        "collection is set to \"{collection}\" but there is no link with rel \"collection\""
+                    ),
+                ),
+                (None, true) => emit(
+                    Rule::CollectionLink,
+                    "/links",
+                    String::from("there is a link with rel \"collection\" but the collection field is not set"),
+                ),
+                _ => {}
+            }
+        }
+
+        if let Some(provider) = &common.provider {
+            self.check_provider(&mut report, &item.id, provider, "/properties/provider");
+        }
+        for (key, asset) in &item.assets {
+            if let Some(provider) = &asset.common.provider {
+                let pointer = format!("/assets/{}/provider", escape_pointer(key));
+                self.check_provider(&mut report, &item.id, provider, &pointer);
+            }
+        }
+
+        report
+    }
+
+    /// Checks all rules that apply to a single Collection.
+    #[must_use]
+    pub fn check_collection(&self, collection: &Collection) -> Report {
+        let mut report = Report::new();
+        if let Some(providers) = &collection.providers {
+            for (index, provider) in providers.iter().enumerate() {
+                let pointer = format!("/providers/{index}");
+                self.check_provider(&mut report, &collection.id, provider, &pointer);
+            }
+        }
+        report
+    }
+
+    /// Checks the Collection, each of the Items, and the rules that relate Items to their
+    /// Collection.
+    #[must_use]
+    pub fn check_collection_items(&self, collection: &Collection, items: &[Item]) -> Report {
+        let mut report = self.check_collection(collection);
+
+        if self.is_enabled(Rule::UniqueItemIds) {
+            let mut seen = HashSet::new();
+            for item in items {
+                if !seen.insert(item.id.as_str()) {
+                    self.emit(
+                        &mut report,
+                        Rule::UniqueItemIds,
+                        Some(&collection.id),
+                        "",
+                        format!("Item ID \"{}\" is used more than once", item.id),
+                    );
+                }
+            }
+        }
+
+        // An invalid item_assets is reported by schema validation, so it is not checked here.
+        let item_assets = collection
+            .item_assets()
+            .ok()
+            .filter(|item_assets| item_assets.assets.is_some());
+        for item in items {
+            report.merge(self.check_item(item));
+            if self.is_enabled(Rule::ItemWithinCollectionExtent) {
+                self.check_extent(&mut report, collection, item);
+            }
+            if let Some(item_assets) = &item_assets {
+                if self.is_enabled(Rule::ItemAssetsConformance) {
+                    for nonconformance in item_assets.check_item(item) {
+                        self.emit(
+                            &mut report,
+                            Rule::ItemAssetsConformance,
+                            Some(&item.id),
+                            &format!("/assets/{}", escape_pointer(nonconformance.key())),
+                            nonconformance.to_string(),
+                        );
+                    }
+                }
+            }
+        }
+
+        report
+    }
+
+    fn check_provider(
+        &self,
+        report: &mut Report,
+        object_id: &str,
+        provider: &Provider,
+        pointer: &str,
+    ) {
+        if !self.is_enabled(Rule::UniqueProviderRoles) {
+            return;
+        }
+        if let Some(roles) = &provider.roles {
+            let mut seen = HashSet::new();
+            for role in roles {
+                if !seen.insert(role) {
+                    self.emit(
+                        report,
+                        Rule::UniqueProviderRoles,
+                        Some(object_id),
+                        &format!("{pointer}/roles"),
+                        format!(
+                            "role {:?} is listed more than once for provider \"{}\"",
+                            role, provider.name
+                        ),
+                    );
+                }
+            }
+        }
+    }
+
+    fn check_extent(&self, report: &mut Report, collection: &Collection, item: &Item) {
+        if let Some(extent) = collection.extent.spatial.bbox.first() {
+            if !self.bbox_contains(extent, &item.bbox) {
+                self.emit(
+                    report,
+                    Rule::ItemWithinCollectionExtent,
+                    Some(&item.id),
+                    "/bbox",
+                    format!(
+                        "bbox {:?} is outside the spatial extent {:?} of collection \"{}\"",
+                        item.bbox, extent, collection.id
+                    ),
+                );
+            }
+        }
+
+        let Some(interval) = collection.extent.temporal.interval.first() else {
+            return;
+        };
+        let bound = |index: usize| {
+            interval
+                .get(index)
+                .and_then(Option::as_ref)
+                .and_then(|value| DateTime::parse_from_rfc3339(value).ok())
+        };
+        let (extent_start, extent_end) = (bound(0), bound(1));
+
+        let common = &item.properties.common;
+        let item_start = common.start_datetime.or(common.datetime);
+        let item_end = common.end_datetime.or(common.datetime);
+        let outside = |value: Option<DateTime<FixedOffset>>| {
+            value.is_some_and(|value| {
+                extent_start.is_some_and(|start| value < start)
+                    || extent_end.is_some_and(|end| value > end)
+            })
+        };
+        if outside(item_start) || outside(item_end) {
+            self.emit(
+                report,
+                Rule::ItemWithinCollectionExtent,
+                Some(&item.id),
+                "/properties",
+                format!(
+                    "datetime is outside the temporal extent of collection \"{}\"",
+                    collection.id
+                ),
+            );
+        }
+    }
+
+    fn emit(
+        &self,
+        report: &mut Report,
+        rule: Rule,
+        object_id: Option<&str>,
+        pointer: &str,
+        message: String,
+    ) {
+        report.push(Issue {
+            rule: rule.id().into(),
+            severity: self.severity(rule),
+            message,
+            object_id: object_id.map(String::from),
+            pointer: Some(pointer.into()),
+            source: None,
+        });
+    }
+
+    fn bbox_matches(&self, bbox: &[f64], bounds: &[f64; 4]) -> bool {
+        match horizontal(bbox) {
+            Some(bbox) => bbox
+                .iter()
+                .zip(bounds.iter())
+                .all(|(a, b)| (a - b).abs() <= self.tolerance),
+            None => false,
+        }
+    }
+
+    fn bbox_contains(&self, outer: &[f64], inner: &[f64]) -> bool {
+        match (horizontal(outer), horizontal(inner)) {
+            (Some(outer), Some(inner)) => {
+                // Bounding boxes crossing the antimeridian have a west edge greater than the east edge and are not
+                // compared.
+                if outer[0] > outer[2] || inner[0] > inner[2] {
+                    return true;
+                }
+                inner[0] >= outer[0] - self.tolerance
+                    && inner[1] >= outer[1] - self.tolerance
+                    && inner[2] <= outer[2] + self.tolerance
+                    && inner[3] <= outer[3] + self.tolerance
+            }
+            _ => true,
+        }
+    }
+}
+
+/// Gets the `[west, south, east, north]` values from a 2D or 3D bbox.
+fn horizontal(bbox: &[f64]) -> Option<[f64; 4]> {
+    match *bbox {
+        [west, south, east, north] | [west, south, _, east, north, _] => {
+            Some([west, south, east, north])
+        }
+        _ => None,
+    }
+}
+
+/// Computes the `[west, south, east, north]` bounds of all positions in a geometry.
+fn geometry_bounds(geometry: &Geometry) -> Option<[f64; 4]> {
+    let mut bounds: Option<[f64; 4]> = None;
+    visit_positions(&geometry.value, &mut |position| {
+        if let [x, y, ..] = *position {
+            bounds = Some(match bounds {
+                Some([west, south, east, north]) => {
+                    [west.min(x), south.min(y), east.max(x), north.max(y)]
+                }
+                None => [x, y, x, y],
+            });
+        }
+    });
+    bounds
+}
+
+fn visit_positions(value: &geojson::Value, visit: &mut dyn FnMut(&[f64])) {
+    use geojson::Value;
+
+    match value {
+        Value::Point(position) => visit(position),
+        Value::MultiPoint(positions) | Value::LineString(positions) => {
+            for position in positions {
+                visit(position);
+            }
+        }
+        Value::MultiLineString(lines) | Value::Polygon(lines) => {
+            for position in lines.iter().flatten() {
+                visit(position);
+            }
+        }
+        Value::MultiPolygon(polygons) => {
+            for position in polygons.iter().flatten().flatten() {
+                visit(position);
+            }
+        }
+        Value::GeometryCollection(geometries) => {
+            for geometry in geometries {
+                visit_positions(&geometry.value, visit);
+            }
+        }
+    }
+}
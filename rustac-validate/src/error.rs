@@ -23,6 +23,9 @@ pub enum STACError {
     /// Errors resulting from failed JSON Schema compilation in the [`jsonschema`] package
     Compilation(jsonschema::CompilationError),
 
+    /// Errors resulting from reading or writing files
+    IO(std::io::Error),
+
     /// Other errors not covered by the variants above.
     Other(String),
 }
@@ -36,6 +39,7 @@ impl fmt::Display for STACError {
             STACError::SemVer(source) => source.fmt(f),
             STACError::HTTP(source) => source.fmt(f),
             STACError::Compilation(source) => source.fmt(f),
+            STACError::IO(source) => source.fmt(f),
             STACError::Other(message) => {
                 write!(f, "{}", message.as_str())
             }
@@ -74,3 +78,9 @@ impl From<jsonschema::CompilationError> for STACError {
         STACError::Compilation(err)
     }
 }
+
+impl From<std::io::Error> for STACError {
+    fn from(err: std::io::Error) -> STACError {
+        STACError::IO(err)
+    }
+}
@@ -13,9 +13,10 @@ use serde_json::Value;
 use std::convert::From;
 
 use error::STACResult;
-use util::{get_extension_path, get_schema_root, is_valid_for_schema_type, STACObject};
+use report::Report;
+use util::{escape_pointer, get_extension_path, get_schema_root, STACObject};
 
-// pub use validate::{is_valid, ValidationTarget};
+pub use validator::Validator;
 
 extern crate jsonschema;
 extern crate reqwest;
@@ -30,7 +31,7 @@ extern crate serde_json;
 /// # Arguments
 ///
 /// * `instance` - This can be any struct that can be converted into a [`ValidationTarget`]. This
-///    currently applies to the [`Item`], [`Collection`], and [`Catalog`] types.
+///   currently applies to the [`Item`], [`Collection`], and [`Catalog`] types.
 ///
 /// # Errors
 ///
@@ -49,16 +50,48 @@ pub fn is_valid<'a, T>(instance: &'a T) -> STACResult<bool>
 where
     &'a T: 'a + Into<ValidationTarget<'a>>,
 {
-    let target: ValidationTarget = instance.into();
-    let schema_uris = &target.schema_uris();
+    Validator::new().is_valid(instance)
+}
 
-    for schema_uri in schema_uris {
-        if !is_valid_for_schema_type(&target, schema_uri)? {
-            return Ok(false);
-        }
-    }
+/// Validates the given instance against the same schemas as [`is_valid`], collecting every
+/// validation error into a [`Report`]. Each error is reported as an [`Issue`] whose `rule` is the
+/// URI of the schema that failed and whose `pointer` is the location of the failing attribute. The
+/// report also records all schemas the object was validated against, so that passing schemas can be
+/// included in the [`output`] formats.
+///
+/// # Errors
+///
+/// This function may return any of the following errors:
+///
+/// * [`STACError::HTTP`] if a schema cannot be fetched.
+/// * [`STACError::JSONParse`] if there is a problem parsing a schema from the JSON string.
+/// * [`STACError::Compilation`] if a schema is not a valid JSON Schema.
+///
+/// [`Issue`]: crate::report::Issue
+/// [`STACError::HTTP`]: crate::error::STACError::HTTP
+/// [`STACError::JSONParse`]: crate::error::STACError::JSONParse
+/// [`STACError::Compilation`]: crate::error::STACError::Compilation
+pub fn validate<'a, T>(instance: &'a T) -> STACResult<Report>
+where
+    &'a T: 'a + Into<ValidationTarget<'a>>,
+{
+    Validator::new().validate(instance)
+}
 
-    Ok(true)
+/// Async version of [`validate`], available with the `async` feature. Schemas are fetched using
+/// the async [`reqwest`] client, so this can be called from within an async runtime like `tokio`
+/// where the blocking client would panic. Fetched schemas are stored in the same
+/// [`SchemaCache::global`](cache::SchemaCache::global) cache used by [`validate`] and [`is_valid`].
+///
+/// # Errors
+///
+/// See [`validate`].
+#[cfg(feature = "async")]
+pub async fn validate_async<'a, T>(instance: &'a T) -> STACResult<Report>
+where
+    &'a T: 'a + Into<ValidationTarget<'a>>,
+{
+    Validator::new().validate_async(instance).await
 }
 
 /// Represents a target for validating against a STAC spec. Implements [`From`] for the
@@ -74,6 +107,15 @@ impl<'a> ValidationTarget<'a> {
         serde_json::to_value(&self.object).unwrap()
     }
 
+    /// Gets the ID of the object associated with this target
+    fn id(&self) -> &'a str {
+        match self.object {
+            STACObject::Item(item) => &item.id,
+            STACObject::Collection(collection) => &collection.id,
+            STACObject::Catalog(catalog) => &catalog.id,
+        }
+    }
+
     /// Gets the STAC spec version associated with this target
     fn stac_version(&self) -> &'a Version {
         match self.object {
@@ -87,17 +129,12 @@ impl<'a> ValidationTarget<'a> {
     /// extension IDs for extensions implemented on the target.
     fn schema_uris(&self) -> Vec<String> {
         let mut schema_uris = vec![self.core_schema_uri()];
-        let stac_extensions = match self.object {
-            STACObject::Item(item) => &item.stac_extensions,
-            STACObject::Collection(collection) => &collection.stac_extensions,
-            STACObject::Catalog(catalog) => &catalog.stac_extensions,
-        };
-        if let Some(stac_extensions) = stac_extensions {
+        if let Some(stac_extensions) = self.stac_extensions() {
             for ext in stac_extensions {
-                if ext.starts_with("https://") {
+                if ext.starts_with("https://") || ext.starts_with("http://") {
                     // If the object uses a full conformance URI as the extension ID (usually after about v1.0.0-rc.1), then just use
                     // this as the schema URI...
-                    schema_uris.push(ext.as_str().into())
+                    schema_uris.push(ext.as_str().into());
                 } else if let Some(extension_uri) = get_extension_path(ext.as_str(), &self.object) {
                     // ...otherwise try to map a short extension ID to a schema URI. This may result in a None response if the
                     // extension ID isn't explicitly mapped in get_extension_path.
@@ -108,6 +145,41 @@ impl<'a> ValidationTarget<'a> {
         schema_uris
     }
 
+    /// Gets the `stac_extensions` of the object
+    fn stac_extensions(&self) -> Option<&'a Vec<String>> {
+        match self.object {
+            STACObject::Item(item) => item.stac_extensions.as_ref(),
+            STACObject::Collection(collection) => collection.stac_extensions.as_ref(),
+            STACObject::Catalog(catalog) => catalog.stac_extensions.as_ref(),
+        }
+    }
+
+    /// Gets every set of `extra_fields` that may hold extension fields, along with a JSON Pointer to
+    /// the object the fields belong to.
+    fn extension_fields(&self) -> Vec<(String, &'a Value)> {
+        let (mut fields, assets) = match self.object {
+            STACObject::Item(item) => (
+                vec![
+                    (String::new(), &item.extra_fields),
+                    (String::from("/properties"), &item.properties.extra_fields),
+                ],
+                Some(&item.assets),
+            ),
+            STACObject::Collection(collection) => (
+                vec![(String::new(), &collection.extra_fields)],
+                collection.assets.as_ref(),
+            ),
+            STACObject::Catalog(catalog) => (vec![(String::new(), &catalog.extra_fields)], None),
+        };
+        for (key, asset) in assets.into_iter().flatten() {
+            fields.push((
+                format!("/assets/{}", escape_pointer(key)),
+                &asset.extra_fields,
+            ));
+        }
+        fields
+    }
+
     /// Gets the schema URI for the core schema associated with this STAC type.
     fn core_schema_uri(&self) -> String {
         let schema_path = match self.object {
@@ -115,14 +187,14 @@ impl<'a> ValidationTarget<'a> {
             STACObject::Collection(_) => "collection-spec/json-schema/collection.json",
             STACObject::Catalog(_) => "catalog-spec/json-schema/catalog.json",
         };
-        format!("{}/{}", get_schema_root(&self.stac_version()), schema_path)
+        format!("{}/{}", get_schema_root(self.stac_version()), schema_path)
     }
 }
 
 impl<'a> From<&'a Item> for ValidationTarget<'a> {
     fn from(item: &'a Item) -> ValidationTarget<'a> {
         ValidationTarget {
-            object: STACObject::Item(&item),
+            object: STACObject::Item(item),
         }
     }
 }
@@ -130,7 +202,7 @@ impl<'a> From<&'a Item> for ValidationTarget<'a> {
 impl<'a> From<&'a Collection> for ValidationTarget<'a> {
     fn from(collection: &'a Collection) -> ValidationTarget<'a> {
         ValidationTarget {
-            object: STACObject::Collection(&collection),
+            object: STACObject::Collection(collection),
         }
     }
 }
@@ -138,13 +210,20 @@ impl<'a> From<&'a Collection> for ValidationTarget<'a> {
 impl<'a> From<&'a Catalog> for ValidationTarget<'a> {
     fn from(catalog: &'a Catalog) -> ValidationTarget<'a> {
         ValidationTarget {
-            object: STACObject::Catalog(&catalog),
+            object: STACObject::Catalog(catalog),
         }
     }
 }
 
+pub mod cache;
+pub mod checks;
 pub mod error;
+pub mod lint;
+pub mod output;
+pub mod report;
+pub mod retrieve;
 mod util;
+mod validator;
 
 #[cfg(test)]
 mod tests {
@@ -0,0 +1,393 @@
+//! Lints based on the [STAC best practices](https://github.com/radiantearth/stac-spec/blob/master/best-practices.md).
+//!
+//! Unlike the rules in [`checks`](crate::checks), objects that fail a lint are still valid STAC, so
+//! most lints are reported as warnings. Lints are reported using the same [`Report`] model as the
+//! semantic checks and can be configured in code or loaded from a JSON config file:
+//!
+//! ```json
+//! {
+//!   "rules": {
+//!     "self-link": "off",
+//!     "collection-title": "error"
+//!   }
+//! }
+//! ```
+//!
+//! Each entry maps a lint ID (see [`Lint::id`]) to `"off"` or the severity it should be reported with.
+use rustac_core::{Asset, Catalog, Collection, Item, Link};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::error::{STACError, STACResult};
+use crate::report::{Issue, Report, Severity};
+use crate::util::escape_pointer;
+
+/// Media types that can be displayed directly by web browsers.
+const BROWSER_MEDIA_TYPES: [&str; 4] = ["image/png", "image/jpeg", "image/gif", "image/webp"];
+
+/// The best practices known to the [`Linter`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Lint {
+    /// IDs should only contain lowercase letters, numbers, `-` and `_`.
+    IdFormat,
+
+    /// Published objects should have a link with rel `"self"`.
+    SelfLink,
+
+    /// Collections should have a `title`.
+    CollectionTitle,
+
+    /// Assets should have `roles`.
+    AssetRoles,
+
+    /// Thumbnails should use a media type that browsers can display.
+    ThumbnailMediaType,
+
+    /// A `license` of `"proprietary"` or `"various"` should be accompanied by a link with rel `"license"`.
+    LicenseLink,
+
+    /// Item files should be named after the Item ID.
+    ItemFileName,
+}
+
+impl Lint {
+    /// All lints, in the order they are run.
+    pub const ALL: [Lint; 7] = [
+        Lint::IdFormat,
+        Lint::SelfLink,
+        Lint::CollectionTitle,
+        Lint::AssetRoles,
+        Lint::ThumbnailMediaType,
+        Lint::LicenseLink,
+        Lint::ItemFileName,
+    ];
+
+    /// Stable identifier for the lint, used in [`Issue::rule`] and in config files.
+    #[must_use]
+    pub fn id(self) -> &'static str {
+        match self {
+            Lint::IdFormat => "id-format",
+            Lint::SelfLink => "self-link",
+            Lint::CollectionTitle => "collection-title",
+            Lint::AssetRoles => "asset-roles",
+            Lint::ThumbnailMediaType => "thumbnail-media-type",
+            Lint::LicenseLink => "license-link",
+            Lint::ItemFileName => "item-file-name",
+        }
+    }
+
+    /// Severity used for issues from this lint unless overridden with [`Linter::with_severity`].
+    #[must_use]
+    pub fn default_severity(self) -> Severity {
+        Severity::Warning
+    }
+}
+
+impl fmt::Display for Lint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.id())
+    }
+}
+
+impl FromStr for Lint {
+    type Err = STACError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Lint::ALL
+            .iter()
+            .copied()
+            .find(|lint| lint.id() == s)
+            .ok_or_else(|| STACError::Other(format!("Unknown lint ID: {s}")))
+    }
+}
+
+/// Setting for a single lint in a [`LintConfig`].
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum LintLevel {
+    /// The lint is disabled.
+    Off,
+
+    /// The lint is reported as [`Severity::Info`].
+    Info,
+
+    /// The lint is reported as [`Severity::Warning`].
+    Warning,
+
+    /// The lint is reported as [`Severity::Error`].
+    Error,
+}
+
+/// Configuration for a [`Linter`], usually loaded from a JSON file using [`LintConfig::from_file`].
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct LintConfig {
+    /// Map of lint IDs to the level they should be reported at. Lints that are not listed use
+    /// their default severity.
+    #[serde(default)]
+    pub rules: HashMap<String, LintLevel>,
+}
+
+impl LintConfig {
+    /// Reads the config from a JSON file.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::IO`] if the file cannot be read and [`STACError::JSONParse`] if it is
+    /// not a valid config.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> STACResult<LintConfig> {
+        let data = fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&data)?)
+    }
+}
+
+/// Runs the enabled [`Lint`]s against STAC objects.
+#[derive(Debug, Clone, Default)]
+pub struct Linter {
+    disabled: HashSet<Lint>,
+    severities: HashMap<Lint, Severity>,
+}
+
+impl Linter {
+    /// Creates a linter with all lints enabled at their default severities.
+    #[must_use]
+    pub fn new() -> Linter {
+        Linter::default()
+    }
+
+    /// Creates a linter from a [`LintConfig`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::Other`] if the config refers to an unknown lint ID.
+    pub fn from_config(config: &LintConfig) -> STACResult<Linter> {
+        let mut linter = Linter::new();
+        for (id, level) in &config.rules {
+            let lint: Lint = id.parse()?;
+            linter = match level {
+                LintLevel::Off => linter.disable(lint),
+                LintLevel::Info => linter.with_severity(lint, Severity::Info),
+                LintLevel::Warning => linter.with_severity(lint, Severity::Warning),
+                LintLevel::Error => linter.with_severity(lint, Severity::Error),
+            };
+        }
+        Ok(linter)
+    }
+
+    /// Disables the given lint.
+    #[must_use]
+    pub fn disable(mut self, lint: Lint) -> Linter {
+        self.disabled.insert(lint);
+        self
+    }
+
+    /// Re-enables a previously disabled lint.
+    #[must_use]
+    pub fn enable(mut self, lint: Lint) -> Linter {
+        self.disabled.remove(&lint);
+        self
+    }
+
+    /// Reports issues from the given lint with `severity` instead of its default severity.
+    #[must_use]
+    pub fn with_severity(mut self, lint: Lint, severity: Severity) -> Linter {
+        self.severities.insert(lint, severity);
+        self
+    }
+
+    /// Returns `true` if the lint will be run by this linter.
+    #[must_use]
+    pub fn is_enabled(&self, lint: Lint) -> bool {
+        !self.disabled.contains(&lint)
+    }
+
+    /// Gets the severity issues from this lint will be reported with.
+    #[must_use]
+    pub fn severity(&self, lint: Lint) -> Severity {
+        self.severities
+            .get(&lint)
+            .copied()
+            .unwrap_or_else(|| lint.default_severity())
+    }
+
+    /// Lints an Item. If `path` is given, the file name is checked against the Item ID.
+    #[must_use]
+    pub fn lint_item(&self, item: &Item, path: Option<&Path>) -> Report {
+        let mut report = Report::new();
+        self.lint_id(&mut report, &item.id);
+        self.lint_self_link(&mut report, &item.id, &item.links);
+        self.lint_assets(&mut report, &item.id, &item.assets);
+        if let Some(license) = &item.properties.common.license {
+            self.lint_license(
+                &mut report,
+                &item.id,
+                license,
+                "/properties/license",
+                &item.links,
+            );
+        }
+
+        if let Some(path) = path {
+            let expected = format!("{}.json", item.id);
+            let file_name = path.file_name().and_then(|name| name.to_str());
+            if self.is_enabled(Lint::ItemFileName) && file_name != Some(expected.as_str()) {
+                self.emit(
+                    &mut report,
+                    Lint::ItemFileName,
+                    &item.id,
+                    None,
+                    format!("file {} should be named {}", path.display(), expected),
+                );
+            }
+        }
+        report
+    }
+
+    /// Lints a Collection.
+    #[must_use]
+    pub fn lint_collection(&self, collection: &Collection) -> Report {
+        let mut report = Report::new();
+        self.lint_id(&mut report, &collection.id);
+        self.lint_self_link(&mut report, &collection.id, &collection.links);
+        if self.is_enabled(Lint::CollectionTitle) && collection.title.is_none() {
+            self.emit(
+                &mut report,
+                Lint::CollectionTitle,
+                &collection.id,
+                Some("/title"),
+                String::from("Collection should have a title"),
+            );
+        }
+        if let Some(assets) = &collection.assets {
+            self.lint_assets(&mut report, &collection.id, assets);
+        }
+        self.lint_license(
+            &mut report,
+            &collection.id,
+            &collection.license,
+            "/license",
+            &collection.links,
+        );
+        report
+    }
+
+    /// Lints a Catalog.
+    #[must_use]
+    pub fn lint_catalog(&self, catalog: &Catalog) -> Report {
+        let mut report = Report::new();
+        self.lint_id(&mut report, &catalog.id);
+        let links = catalog.links.as_deref().unwrap_or_default();
+        self.lint_self_link(&mut report, &catalog.id, links);
+        report
+    }
+
+    fn lint_id(&self, report: &mut Report, id: &str) {
+        let valid = id
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_');
+        if self.is_enabled(Lint::IdFormat) && !valid {
+            self.emit(
+                report,
+                Lint::IdFormat,
+                id,
+                Some("/id"),
+                format!("ID \"{id}\" should only contain lowercase letters, numbers, '-' and '_'"),
+            );
+        }
+    }
+
+    fn lint_self_link(&self, report: &mut Report, id: &str, links: &[Link]) {
+        if self.is_enabled(Lint::SelfLink) && !links.iter().any(|link| link.rel == "self") {
+            self.emit(
+                report,
+                Lint::SelfLink,
+                id,
+                Some("/links"),
+                String::from("there is no link with rel \"self\""),
+            );
+        }
+    }
+
+    fn lint_assets(&self, report: &mut Report, id: &str, assets: &HashMap<String, Asset>) {
+        let mut keys: Vec<&String> = assets.keys().collect();
+        keys.sort();
+        for key in keys {
+            let asset = &assets[key];
+            let pointer = format!("/assets/{}", escape_pointer(key));
+            let roles = asset.roles.as_deref().unwrap_or_default();
+
+            if self.is_enabled(Lint::AssetRoles) && roles.is_empty() {
+                self.emit(
+                    report,
+                    Lint::AssetRoles,
+                    id,
+                    Some(&format!("{pointer}/roles")),
+                    format!("asset \"{key}\" should have roles"),
+                );
+            }
+
+            let is_thumbnail = roles.iter().any(|role| role == "thumbnail");
+            let browser_friendly = asset
+                .r#type
+                .as_deref()
+                .is_some_and(|media_type| BROWSER_MEDIA_TYPES.contains(&media_type));
+            if self.is_enabled(Lint::ThumbnailMediaType) && is_thumbnail && !browser_friendly {
+                self.emit(
+                    report,
+                    Lint::ThumbnailMediaType,
+                    id,
+                    Some(&format!("{pointer}/type")),
+                    format!(
+                        "thumbnail \"{}\" should use one of the media types {}",
+                        key,
+                        BROWSER_MEDIA_TYPES.join(", ")
+                    ),
+                );
+            }
+        }
+    }
+
+    fn lint_license(
+        &self,
+        report: &mut Report,
+        id: &str,
+        license: &str,
+        pointer: &str,
+        links: &[Link],
+    ) {
+        let needs_link = license == "proprietary" || license == "various";
+        if self.is_enabled(Lint::LicenseLink)
+            && needs_link
+            && !links.iter().any(|link| link.rel == "license")
+        {
+            self.emit(
+                report,
+                Lint::LicenseLink,
+                id,
+                Some(pointer),
+                format!("license is \"{license}\" but there is no link with rel \"license\""),
+            );
+        }
+    }
+
+    fn emit(
+        &self,
+        report: &mut Report,
+        lint: Lint,
+        object_id: &str,
+        pointer: Option<&str>,
+        message: String,
+    ) {
+        report.push(Issue {
+            rule: lint.id().into(),
+            severity: self.severity(lint),
+            message,
+            object_id: Some(object_id.into()),
+            pointer: pointer.map(String::from),
+            source: None,
+        });
+    }
+}
@@ -0,0 +1,303 @@
+//! Serializers that write a [`Report`] in formats understood by CI systems.
+//!
+//! * [`Format::Json`] - the report itself, serialized as JSON.
+//! * [`Format::JUnit`] - [JUnit XML](https://llg.cubic.org/docs/junit/) with one `testsuite` per object
+//!   and one `testcase` per schema or rule the object was checked against.
+//! * [`Format::Sarif`] - [SARIF 2.1.0](https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html)
+//!   with the source file of each object as the artifact location and the JSON Pointer to the
+//!   failing attribute as a logical location.
+//!
+//! ```
+//! use rustac_validate::output::{write, Format};
+//! use rustac_validate::report::{Issue, Report, Severity};
+//!
+//! let mut report = Report::new();
+//! report.push(Issue {
+//!     rule: String::from("datetime-range-order"),
+//!     severity: Severity::Error,
+//!     message: String::from("start_datetime is after end_datetime"),
+//!     object_id: Some(String::from("an-item")),
+//!     pointer: Some(String::from("/properties/start_datetime")),
+//!     source: Some(String::from("items/an-item.json")),
+//! });
+//!
+//! let xml = write(&report, Format::JUnit).unwrap();
+//! assert!(xml.contains(r#"<testcase name="datetime-range-order" classname="an-item">"#));
+//! ```
+use serde_json::{json, Value};
+use std::fmt::Write;
+use std::str::FromStr;
+
+use crate::error::{STACError, STACResult};
+use crate::report::{Issue, Report, Severity};
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const TOOL_NAME: &str = "rustac-validate";
+const TOOL_URI: &str = "https://github.com/duckontheweb/rustac";
+
+/// Output formats supported by [`write`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// Structured JSON.
+    Json,
+
+    /// JUnit XML.
+    JUnit,
+
+    /// SARIF 2.1.0.
+    Sarif,
+}
+
+impl FromStr for Format {
+    type Err = STACError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "json" => Ok(Format::Json),
+            "junit" => Ok(Format::JUnit),
+            "sarif" => Ok(Format::Sarif),
+            _ => Err(STACError::Other(format!("Unknown output format: {s}"))),
+        }
+    }
+}
+
+/// Writes the report in the given format.
+///
+/// # Errors
+///
+/// Returns [`STACError::JSONParse`] if the report cannot be serialized to JSON.
+pub fn write(report: &Report, format: Format) -> STACResult<String> {
+    match format {
+        Format::Json => to_json(report),
+        Format::JUnit => Ok(to_junit(report)),
+        Format::Sarif => Ok(serde_json::to_string_pretty(&to_sarif(report))?),
+    }
+}
+
+/// Serializes the report as pretty-printed JSON.
+///
+/// # Errors
+///
+/// Returns [`STACError::JSONParse`] if the report cannot be serialized.
+pub fn to_json(report: &Report) -> STACResult<String> {
+    Ok(serde_json::to_string_pretty(report)?)
+}
+
+/// Writes the report as JUnit XML. Only issues with [`Severity::Error`] are reported as failures;
+/// other issues are included in the `system-out` of their test case.
+#[must_use]
+pub fn to_junit(report: &Report) -> String {
+    let suites = test_suites(report);
+    let tests: usize = suites.iter().map(|suite| suite.cases.len()).sum();
+    let failures: usize = suites.iter().map(TestSuite::failures).sum();
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let _ = writeln!(
+        xml,
+        "<testsuites name=\"{TOOL_NAME}\" tests=\"{tests}\" failures=\"{failures}\">"
+    );
+    for suite in &suites {
+        let _ = writeln!(
+            xml,
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\">",
+            escape_xml(&suite.name),
+            suite.cases.len(),
+            suite.failures()
+        );
+        for case in &suite.cases {
+            let _ = write!(
+                xml,
+                "    <testcase name=\"{}\" classname=\"{}\"",
+                escape_xml(&case.name),
+                escape_xml(&suite.name)
+            );
+            if case.issues.is_empty() {
+                xml.push_str("/>\n");
+                continue;
+            }
+            xml.push_str(">\n");
+
+            let (errors, others): (Vec<&Issue>, Vec<&Issue>) = case
+                .issues
+                .iter()
+                .partition(|issue| issue.severity == Severity::Error);
+            if let Some(first) = errors.first() {
+                let _ = writeln!(
+                    xml,
+                    "      <failure message=\"{}\" type=\"error\">{}</failure>",
+                    escape_xml(&first.message),
+                    escape_xml(&describe(&errors))
+                );
+            }
+            if !others.is_empty() {
+                let _ = writeln!(
+                    xml,
+                    "      <system-out>{}</system-out>",
+                    escape_xml(&describe(&others))
+                );
+            }
+            xml.push_str("    </testcase>\n");
+        }
+        xml.push_str("  </testsuite>\n");
+    }
+    xml.push_str("</testsuites>\n");
+    xml
+}
+
+/// Converts the report to a SARIF 2.1.0 log.
+#[must_use]
+pub fn to_sarif(report: &Report) -> Value {
+    let mut rules: Vec<&str> = Vec::new();
+    for issue in &report.issues {
+        if !rules.contains(&issue.rule.as_str()) {
+            rules.push(&issue.rule);
+        }
+    }
+
+    let results: Vec<Value> = report
+        .issues
+        .iter()
+        .map(|issue| {
+            let mut location = json!({});
+            if let Some(source) = &issue.source {
+                location["physicalLocation"] = json!({"artifactLocation": {"uri": source}});
+            }
+            if let Some(pointer) = &issue.pointer {
+                let mut logical = json!({"fullyQualifiedName": pointer, "kind": "member"});
+                if let Some(object_id) = &issue.object_id {
+                    logical["name"] = json!(object_id);
+                }
+                location["logicalLocations"] = json!([logical]);
+            }
+
+            let mut result = json!({
+                "ruleId": issue.rule,
+                "ruleIndex": rules.iter().position(|rule| *rule == issue.rule),
+                "level": match issue.severity {
+                    Severity::Error => "error",
+                    Severity::Warning => "warning",
+                    Severity::Info => "note",
+                },
+                "message": {"text": issue.message},
+            });
+            if location
+                .as_object()
+                .is_some_and(|location| !location.is_empty())
+            {
+                result["locations"] = json!([location]);
+            }
+            result
+        })
+        .collect();
+
+    json!({
+        "$schema": SARIF_SCHEMA,
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": TOOL_NAME,
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "informationUri": TOOL_URI,
+                    "rules": rules.iter().map(|rule| json!({"id": rule})).collect::<Vec<Value>>(),
+                }
+            },
+            "results": results,
+        }]
+    })
+}
+
+struct TestSuite<'a> {
+    name: String,
+    cases: Vec<TestCase<'a>>,
+}
+
+impl TestSuite<'_> {
+    fn failures(&self) -> usize {
+        self.cases
+            .iter()
+            .filter(|case| {
+                case.issues
+                    .iter()
+                    .any(|issue| issue.severity == Severity::Error)
+            })
+            .count()
+    }
+}
+
+struct TestCase<'a> {
+    name: String,
+    issues: Vec<&'a Issue>,
+}
+
+/// Groups the report into one suite per object and one case per schema or rule, keeping the order
+/// in which objects and rules first appear.
+fn test_suites(report: &Report) -> Vec<TestSuite<'_>> {
+    let mut suites: Vec<TestSuite> = Vec::new();
+    for object in &report.objects {
+        for schema in &object.schemas {
+            test_case(&mut suites, &object.id, schema);
+        }
+    }
+    for issue in &report.issues {
+        let object = issue.object_id.as_deref().unwrap_or_default();
+        test_case(&mut suites, object, &issue.rule)
+            .issues
+            .push(issue);
+    }
+    suites
+}
+
+/// Gets the test case for the given object and rule, adding it if it does not exist yet.
+fn test_case<'s, 'a>(
+    suites: &'s mut Vec<TestSuite<'a>>,
+    object: &str,
+    rule: &str,
+) -> &'s mut TestCase<'a> {
+    let suite_index = if let Some(index) = suites.iter().position(|suite| suite.name == object) {
+        index
+    } else {
+        suites.push(TestSuite {
+            name: object.into(),
+            cases: Vec::new(),
+        });
+        suites.len() - 1
+    };
+    let cases = &mut suites[suite_index].cases;
+    let case_index = if let Some(index) = cases.iter().position(|case| case.name == rule) {
+        index
+    } else {
+        cases.push(TestCase {
+            name: rule.into(),
+            issues: Vec::new(),
+        });
+        cases.len() - 1
+    };
+    &mut cases[case_index]
+}
+
+fn describe(issues: &[&Issue]) -> String {
+    issues
+        .iter()
+        .map(|issue| match &issue.pointer {
+            Some(pointer) if !pointer.is_empty() => format!("{pointer}: {}", issue.message),
+            _ => issue.message.clone(),
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+fn escape_xml(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -0,0 +1,155 @@
+//! Types describing the outcome of checking STAC objects.
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// How serious a reported [`Issue`] is. Variants are ordered from least to most severe.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// Informational message that does not indicate a problem.
+    Info,
+
+    /// Something that is likely to cause problems, but does not make the object invalid.
+    Warning,
+
+    /// The object is not valid.
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let label = match self {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        };
+        write!(f, "{label}")
+    }
+}
+
+/// A single problem found while checking a STAC object.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Issue {
+    /// Identifier of the rule that produced this issue.
+    pub rule: String,
+
+    /// How serious the issue is.
+    pub severity: Severity,
+
+    /// Human readable description of the problem.
+    pub message: String,
+
+    /// The `id` of the object the issue was found in, if it has one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub object_id: Option<String>,
+
+    /// [JSON Pointer](https://tools.ietf.org/html/rfc6901) to the offending attribute within the object.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pointer: Option<String>,
+
+    /// Location of the file the object was read from, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} [{}]", self.severity, self.rule)?;
+        if let Some(source) = &self.source {
+            write!(f, " {source}")?;
+        }
+        if let Some(object_id) = &self.object_id {
+            write!(f, " {object_id}")?;
+        }
+        if let Some(pointer) = &self.pointer {
+            write!(f, " at {pointer}")?;
+        }
+        write!(f, ": {}", self.message)
+    }
+}
+
+/// Summary of the JSON Schemas an object was validated against. Used to report schemas that
+/// passed, since those do not produce any [`Issue`]s.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ValidatedObject {
+    /// The `id` of the object.
+    pub id: String,
+
+    /// URIs of all schemas the object was validated against.
+    pub schemas: Vec<String>,
+
+    /// Location of the file the object was read from, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+}
+
+/// Collection of [`Issue`]s found while checking one or more STAC objects.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct Report {
+    /// All issues, in the order they were found.
+    pub issues: Vec<Issue>,
+
+    /// Objects that were validated against JSON Schemas, in the order they were validated.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub objects: Vec<ValidatedObject>,
+}
+
+impl Report {
+    /// Creates an empty report.
+    #[must_use]
+    pub fn new() -> Report {
+        Report::default()
+    }
+
+    /// Adds an issue to the report.
+    pub fn push(&mut self, issue: Issue) {
+        self.issues.push(issue);
+    }
+
+    /// Moves all issues and validated objects from `other` into this report.
+    pub fn merge(&mut self, other: Report) {
+        self.issues.extend(other.issues);
+        self.objects.extend(other.objects);
+    }
+
+    /// Sets the source file location on all issues and validated objects that do not already
+    /// have one. Useful after checking an object that was read from `source`.
+    pub fn set_source(&mut self, source: &str) {
+        let sources = self
+            .issues
+            .iter_mut()
+            .map(|issue| &mut issue.source)
+            .chain(self.objects.iter_mut().map(|object| &mut object.source));
+        for existing in sources {
+            if existing.is_none() {
+                *existing = Some(source.into());
+            }
+        }
+    }
+
+    /// Returns `true` if no issues were found.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// Returns `true` if any issue has a severity of [`Severity::Error`].
+    #[must_use]
+    pub fn has_errors(&self) -> bool {
+        self.issues
+            .iter()
+            .any(|issue| issue.severity == Severity::Error)
+    }
+
+    /// Gets all issues with exactly the given severity.
+    pub fn with_severity(&self, severity: Severity) -> impl Iterator<Item = &Issue> {
+        self.issues
+            .iter()
+            .filter(move |issue| issue.severity == severity)
+    }
+
+    /// Gets all issues produced by the rule with the given ID.
+    pub fn for_rule<'a>(&'a self, rule: &'a str) -> impl Iterator<Item = &'a Issue> {
+        self.issues.iter().filter(move |issue| issue.rule == rule)
+    }
+}
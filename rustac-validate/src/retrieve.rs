@@ -0,0 +1,297 @@
+//! Control where JSON Schemas are loaded from.
+//!
+//! Schemas are always identified by their canonical URI (e.g.
+//! `https://schemas.stacspec.org/v1.0.0/item-spec/json-schema/item.json`), which is also the URI used
+//! to resolve `$ref`s between schemas. A [`SchemaRetriever`] decides how the document for a URI is
+//! actually loaded:
+//!
+//! * [`HttpRetriever`] fetches the URI over HTTP. This is the default.
+//! * [`DirectoryRetriever`] reads from a local mirror of the schema sites, where each schema is
+//!   stored at `<root>/<host>/<path>`.
+//! * [`EmbeddedRetriever`] uses the core STAC schemas bundled into this crate.
+//! * [`Rewrite`] rewrites URIs using prefix rules before passing them to another retriever, for
+//!   instance to point at an internal mirror.
+//! * [`Chain`] tries several retrievers in order.
+//!
+//! ```
+//! use rustac_validate::retrieve::{Chain, EmbeddedRetriever, HttpRetriever, Rewrite};
+//! use rustac_validate::Validator;
+//!
+//! let retriever = Chain::new()
+//!     .with(EmbeddedRetriever)
+//!     .with(Rewrite::new(HttpRetriever).rule(
+//!         "https://stac-extensions.github.io/",
+//!         "https://mirror.example.com/stac-extensions/",
+//!     ));
+//! let validator = Validator::new().with_retriever(retriever);
+//! ```
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+use url::Url;
+
+#[cfg(feature = "async")]
+use std::{future::Future, pin::Pin};
+
+use crate::error::{STACError, STACResult};
+
+/// Boxed future returned by [`SchemaRetriever::retrieve_async`].
+#[cfg(feature = "async")]
+pub type RetrieveFuture<'a> = Pin<Box<dyn Future<Output = STACResult<Value>> + Send + 'a>>;
+
+/// Loads the JSON Schema document identified by a URI.
+pub trait SchemaRetriever: Send + Sync {
+    /// Loads the schema with the given canonical URI.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the schema cannot be found or is not valid JSON.
+    fn retrieve(&self, uri: &str) -> STACResult<Value>;
+
+    /// Async version of [`SchemaRetriever::retrieve`], available with the `async` feature. The
+    /// default implementation calls [`SchemaRetriever::retrieve`], which is fine for retrievers that
+    /// do not block on the network.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the schema cannot be found or is not valid JSON.
+    #[cfg(feature = "async")]
+    fn retrieve_async<'a>(&'a self, uri: &'a str) -> RetrieveFuture<'a> {
+        Box::pin(async move { self.retrieve(uri) })
+    }
+}
+
+/// Fetches schemas over HTTP using [`reqwest`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HttpRetriever;
+
+impl SchemaRetriever for HttpRetriever {
+    fn retrieve(&self, uri: &str) -> STACResult<Value> {
+        Ok(reqwest::blocking::get(uri)?.error_for_status()?.json()?)
+    }
+
+    #[cfg(feature = "async")]
+    fn retrieve_async<'a>(&'a self, uri: &'a str) -> RetrieveFuture<'a> {
+        Box::pin(async move { Ok(reqwest::get(uri).await?.error_for_status()?.json().await?) })
+    }
+}
+
+/// Reads schemas from a local mirror of the schema sites. The schema for
+/// `https://schemas.stacspec.org/v1.0.0/item-spec/json-schema/item.json` is read from
+/// `<root>/schemas.stacspec.org/v1.0.0/item-spec/json-schema/item.json`.
+#[derive(Debug, Clone)]
+pub struct DirectoryRetriever {
+    root: PathBuf,
+}
+
+impl DirectoryRetriever {
+    /// Creates a retriever for the mirror in the given directory.
+    pub fn new<P: AsRef<Path>>(root: P) -> DirectoryRetriever {
+        DirectoryRetriever {
+            root: root.as_ref().to_path_buf(),
+        }
+    }
+
+    /// Gets the path a schema URI maps to within the mirror.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`STACError::Other`] if the URI cannot be parsed or has no host.
+    pub fn path(&self, uri: &str) -> STACResult<PathBuf> {
+        let url = Url::parse(uri).map_err(|err| STACError::Other(format!("{uri}: {err}")))?;
+        let host = url
+            .host_str()
+            .ok_or_else(|| STACError::Other(format!("{uri}: URI has no host")))?;
+        let mut path = self.root.join(host);
+        path.extend(url.path_segments().into_iter().flatten());
+        Ok(path)
+    }
+}
+
+impl SchemaRetriever for DirectoryRetriever {
+    fn retrieve(&self, uri: &str) -> STACResult<Value> {
+        let data = fs::read_to_string(self.path(uri)?)?;
+        Ok(serde_json::from_str(&data)?)
+    }
+}
+
+/// Core STAC schemas bundled into the crate, along with the GeoJSON schemas they reference.
+const EMBEDDED_SCHEMAS: [(&str, &str); 10] = [
+    (
+        "https://schemas.stacspec.org/v1.0.0/item-spec/json-schema/item.json",
+        include_str!("../schemas/schemas.stacspec.org/v1.0.0/item-spec/json-schema/item.json"),
+    ),
+    (
+        "https://schemas.stacspec.org/v1.0.0/item-spec/json-schema/basics.json",
+        include_str!("../schemas/schemas.stacspec.org/v1.0.0/item-spec/json-schema/basics.json"),
+    ),
+    (
+        "https://schemas.stacspec.org/v1.0.0/item-spec/json-schema/datetime.json",
+        include_str!("../schemas/schemas.stacspec.org/v1.0.0/item-spec/json-schema/datetime.json"),
+    ),
+    (
+        "https://schemas.stacspec.org/v1.0.0/item-spec/json-schema/instrument.json",
+        include_str!(
+            "../schemas/schemas.stacspec.org/v1.0.0/item-spec/json-schema/instrument.json"
+        ),
+    ),
+    (
+        "https://schemas.stacspec.org/v1.0.0/item-spec/json-schema/licensing.json",
+        include_str!("../schemas/schemas.stacspec.org/v1.0.0/item-spec/json-schema/licensing.json"),
+    ),
+    (
+        "https://schemas.stacspec.org/v1.0.0/item-spec/json-schema/provider.json",
+        include_str!("../schemas/schemas.stacspec.org/v1.0.0/item-spec/json-schema/provider.json"),
+    ),
+    (
+        "https://schemas.stacspec.org/v1.0.0/collection-spec/json-schema/collection.json",
+        include_str!(
+            "../schemas/schemas.stacspec.org/v1.0.0/collection-spec/json-schema/collection.json"
+        ),
+    ),
+    (
+        "https://schemas.stacspec.org/v1.0.0/catalog-spec/json-schema/catalog.json",
+        include_str!(
+            "../schemas/schemas.stacspec.org/v1.0.0/catalog-spec/json-schema/catalog.json"
+        ),
+    ),
+    (
+        "https://geojson.org/schema/Feature.json",
+        include_str!("../schemas/geojson.org/schema/Feature.json"),
+    ),
+    (
+        "https://geojson.org/schema/Geometry.json",
+        include_str!("../schemas/geojson.org/schema/Geometry.json"),
+    ),
+];
+
+/// Uses the STAC v1.0.0 core schemas bundled into this crate, so Items, Collections and Catalogs
+/// can be validated without network access. Extension schemas are not bundled.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EmbeddedRetriever;
+
+impl EmbeddedRetriever {
+    /// Gets the URIs of all bundled schemas.
+    pub fn uris() -> impl Iterator<Item = &'static str> {
+        EMBEDDED_SCHEMAS.iter().map(|(uri, _)| *uri)
+    }
+}
+
+impl SchemaRetriever for EmbeddedRetriever {
+    fn retrieve(&self, uri: &str) -> STACResult<Value> {
+        let uri = uri.trim_end_matches('#');
+        let (_, data) = EMBEDDED_SCHEMAS
+            .iter()
+            .find(|(embedded, _)| *embedded == uri)
+            .ok_or_else(|| STACError::Other(format!("{uri}: schema is not embedded")))?;
+        Ok(serde_json::from_str(data)?)
+    }
+}
+
+/// Rewrites schema URIs before passing them to another retriever. Each rule replaces a URI prefix;
+/// the first matching rule is used and URIs that match no rule are passed through unchanged.
+///
+/// ```
+/// use rustac_validate::retrieve::{HttpRetriever, Rewrite};
+///
+/// let rewrite = Rewrite::new(HttpRetriever)
+///     .rule("https://schemas.stacspec.org/", "https://mirror.example.com/stac/");
+/// assert_eq!(
+///     rewrite.rewrite("https://schemas.stacspec.org/v1.0.0/item-spec/json-schema/item.json"),
+///     "https://mirror.example.com/stac/v1.0.0/item-spec/json-schema/item.json"
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct Rewrite<R> {
+    rules: Vec<(String, String)>,
+    inner: R,
+}
+
+impl<R: SchemaRetriever> Rewrite<R> {
+    /// Creates a rewriting retriever with no rules that passes URIs to `inner`.
+    pub fn new(inner: R) -> Rewrite<R> {
+        Rewrite {
+            rules: Vec::new(),
+            inner,
+        }
+    }
+
+    /// Adds a rule that replaces the prefix `from` with `to`.
+    #[must_use]
+    pub fn rule<F: Into<String>, T: Into<String>>(mut self, from: F, to: T) -> Rewrite<R> {
+        self.rules.push((from.into(), to.into()));
+        self
+    }
+
+    /// Applies the first matching rule to the URI.
+    #[must_use]
+    pub fn rewrite(&self, uri: &str) -> String {
+        self.rules
+            .iter()
+            .find_map(|(from, to)| {
+                uri.strip_prefix(from.as_str())
+                    .map(|rest| format!("{to}{rest}"))
+            })
+            .unwrap_or_else(|| uri.into())
+    }
+}
+
+impl<R: SchemaRetriever> SchemaRetriever for Rewrite<R> {
+    fn retrieve(&self, uri: &str) -> STACResult<Value> {
+        self.inner.retrieve(&self.rewrite(uri))
+    }
+
+    #[cfg(feature = "async")]
+    fn retrieve_async<'a>(&'a self, uri: &'a str) -> RetrieveFuture<'a> {
+        Box::pin(async move { self.inner.retrieve_async(&self.rewrite(uri)).await })
+    }
+}
+
+/// Tries each retriever in turn, returning the first schema that is found. If all retrievers fail,
+/// the error from the last one is returned.
+#[derive(Default)]
+pub struct Chain {
+    retrievers: Vec<Box<dyn SchemaRetriever>>,
+}
+
+impl Chain {
+    /// Creates an empty chain.
+    #[must_use]
+    pub fn new() -> Chain {
+        Chain::default()
+    }
+
+    /// Adds a retriever to the end of the chain.
+    #[must_use]
+    pub fn with<R: SchemaRetriever + 'static>(mut self, retriever: R) -> Chain {
+        self.retrievers.push(Box::new(retriever));
+        self
+    }
+}
+
+impl SchemaRetriever for Chain {
+    fn retrieve(&self, uri: &str) -> STACResult<Value> {
+        let mut result = Err(STACError::Other(format!("{uri}: no schema retrievers")));
+        for retriever in &self.retrievers {
+            result = retriever.retrieve(uri);
+            if result.is_ok() {
+                break;
+            }
+        }
+        result
+    }
+
+    #[cfg(feature = "async")]
+    fn retrieve_async<'a>(&'a self, uri: &'a str) -> RetrieveFuture<'a> {
+        Box::pin(async move {
+            let mut result = Err(STACError::Other(format!("{uri}: no schema retrievers")));
+            for retriever in &self.retrievers {
+                result = retriever.retrieve_async(uri).await;
+                if result.is_ok() {
+                    break;
+                }
+            }
+            result
+        })
+    }
+}
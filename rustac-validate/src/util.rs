@@ -1,28 +1,205 @@
-use crate::{error::STACResult, ValidationTarget};
-use reqwest::blocking::get;
+use crate::{
+    cache::SchemaCache,
+    error::STACResult,
+    report::{Issue, Severity},
+    retrieve::SchemaRetriever,
+    ValidationTarget,
+};
+use jsonschema::JSONSchema;
+pub(crate) use rustac_core::pointer::escape as escape_pointer;
 use rustac_core::{Catalog, Collection, Item};
 use semver::{Version, VersionReq};
 use serde::Serialize;
+use serde_json::Value;
+use std::borrow::Cow;
+use std::sync::Arc;
+use url::Url;
+
+/// Meta-schemas are bundled with `jsonschema`, so references to them are never fetched.
+const META_SCHEMA_ROOT: &str = "http://json-schema.org/";
 
 pub(crate) fn is_valid_for_schema_type(
     target: &ValidationTarget,
-    schema_uri: &str,
+    schemas: &SchemaSet,
 ) -> STACResult<bool> {
     let instance = &target.serialized_object();
-    let schema = get(schema_uri)?.json()?;
-    Ok(jsonschema::is_valid(&schema, instance))
+    Ok(schemas.compile()?.is_valid(instance))
+}
+
+pub(crate) fn validate_for_schema_type(
+    target: &ValidationTarget,
+    schemas: &SchemaSet,
+) -> STACResult<Vec<Issue>> {
+    let instance = &target.serialized_object();
+    let compiled = schemas.compile()?;
+
+    let issues = match compiled.validate(instance) {
+        Ok(()) => Vec::new(),
+        Err(errors) => errors
+            .map(|error| Issue {
+                rule: schemas.uri().into(),
+                severity: Severity::Error,
+                message: error.to_string(),
+                object_id: Some(target.id().into()),
+                pointer: Some(error.instance_path.to_string()),
+                source: None,
+            })
+            .collect(),
+    };
+    Ok(issues)
+}
+
+/// A schema along with every remote schema it references, directly or indirectly. Compiling the
+/// set registers all referenced schemas with `jsonschema` so that it never has to fetch them
+/// itself.
+pub(crate) struct SchemaSet {
+    root: Value,
+    documents: Vec<(String, Arc<Value>)>,
+}
+
+impl SchemaSet {
+    /// URI of the root schema.
+    pub(crate) fn uri(&self) -> &str {
+        &self.documents[0].0
+    }
+
+    fn compile(&self) -> STACResult<JSONSchema<'_>> {
+        let mut options = JSONSchema::options();
+        for (uri, document) in &self.documents[1..] {
+            options.with_document(uri.clone(), Value::clone(document));
+        }
+        Ok(options.compile(&self.root)?)
+    }
+}
+
+/// Tracks which schemas still need to be fetched while building a [`SchemaSet`].
+pub(crate) struct SchemaSetBuilder {
+    pending: Vec<String>,
+    documents: Vec<(String, Arc<Value>)>,
+}
+
+impl SchemaSetBuilder {
+    pub(crate) fn new(uri: &str) -> SchemaSetBuilder {
+        SchemaSetBuilder {
+            pending: vec![uri.into()],
+            documents: Vec::new(),
+        }
+    }
+
+    /// Gets the next URI that has not been added yet.
+    pub(crate) fn next_uri(&mut self) -> Option<String> {
+        while let Some(uri) = self.pending.pop() {
+            if !self.documents.iter().any(|(existing, _)| *existing == uri) {
+                return Some(uri);
+            }
+        }
+        None
+    }
+
+    /// Adds a fetched schema and queues any remote schemas it references.
+    pub(crate) fn add(&mut self, uri: String, schema: Arc<Value>) {
+        if let Ok(base) = Url::parse(&uri) {
+            collect_refs(&schema, &base, &mut self.pending);
+        }
+        self.documents.push((uri, schema));
+    }
+
+    pub(crate) fn build(self) -> SchemaSet {
+        // Relative references are resolved against the `$id` of the root schema, so use the URI
+        // the schema was fetched from if it does not have one.
+        let (uri, root) = &self.documents[0];
+        let mut root = Value::clone(root);
+        if let Some(root) = root.as_object_mut() {
+            root.entry("$id")
+                .or_insert_with(|| Value::String(uri.clone()));
+        }
+        SchemaSet {
+            root,
+            documents: self.documents,
+        }
+    }
+}
+
+/// Gets the schema at `uri` and all schemas it references, using cached schemas where possible.
+pub(crate) fn fetch_schemas(
+    cache: &SchemaCache,
+    retriever: &dyn SchemaRetriever,
+    uri: &str,
+) -> STACResult<SchemaSet> {
+    let mut builder = SchemaSetBuilder::new(uri);
+    while let Some(uri) = builder.next_uri() {
+        let schema = match cache.get(&uri) {
+            Some(schema) => schema,
+            None => cache.insert(uri.as_str(), retriever.retrieve(&uri)?),
+        };
+        builder.add(uri, schema);
+    }
+    Ok(builder.build())
+}
+
+/// Same as [`fetch_schemas`], but retrieves any schemas that are not cached asynchronously.
+#[cfg(feature = "async")]
+pub(crate) async fn fetch_schemas_async(
+    cache: &SchemaCache,
+    retriever: &dyn SchemaRetriever,
+    uri: &str,
+) -> STACResult<SchemaSet> {
+    let mut builder = SchemaSetBuilder::new(uri);
+    while let Some(uri) = builder.next_uri() {
+        let schema = if let Some(schema) = cache.get(&uri) {
+            schema
+        } else {
+            let schema = retriever.retrieve_async(&uri).await?;
+            cache.insert(uri.as_str(), schema)
+        };
+        builder.add(uri, schema);
+    }
+    Ok(builder.build())
+}
+
+/// Collects the absolute URIs (without fragments) of all remote schemas referenced using `$ref`.
+fn collect_refs(value: &Value, base: &Url, refs: &mut Vec<String>) {
+    match value {
+        Value::Object(map) => {
+            let base = match map
+                .get("$id")
+                .and_then(Value::as_str)
+                .and_then(|id| base.join(id).ok())
+            {
+                Some(id) => Cow::Owned(id),
+                None => Cow::Borrowed(base),
+            };
+            for (key, value) in map {
+                match (key.as_str(), value) {
+                    ("$ref", Value::String(reference)) => {
+                        if let Ok(mut url) = base.join(reference) {
+                            url.set_fragment(None);
+                            let remote = matches!(url.scheme(), "http" | "https");
+                            if remote && !url.as_str().starts_with(META_SCHEMA_ROOT) {
+                                refs.push(url.into());
+                            }
+                        }
+                    }
+                    _ => collect_refs(value, &base, refs),
+                }
+            }
+        }
+        Value::Array(values) => {
+            for value in values {
+                collect_refs(value, base, refs);
+            }
+        }
+        _ => {}
+    }
 }
 
 pub(crate) fn get_schema_root(stac_version: &Version) -> String {
     let at_least_v1 = VersionReq::parse(">=1.0.0-beta.1").unwrap();
 
-    if at_least_v1.matches(&stac_version) {
-        format!("https://schemas.stacspec.org/v{}", stac_version.to_string())
+    if at_least_v1.matches(stac_version) {
+        format!("https://schemas.stacspec.org/v{stac_version}")
     } else {
-        format!(
-            "https://raw.githubusercontent.com/radiantearth/stac-spec/v{}",
-            stac_version.to_string()
-        )
+        format!("https://raw.githubusercontent.com/radiantearth/stac-spec/v{stac_version}")
     }
 }
 
@@ -0,0 +1,237 @@
+//! Configurable JSON Schema validation.
+use rustac_core::extensions::Extension;
+use serde_json::Value;
+
+use crate::cache::SchemaCache;
+use crate::error::STACResult;
+use crate::report::{Issue, Report, Severity, ValidatedObject};
+use crate::retrieve::{HttpRetriever, SchemaRetriever};
+use crate::util::{fetch_schemas, is_valid_for_schema_type, validate_for_schema_type};
+use crate::ValidationTarget;
+
+/// Validates STAC objects against JSON Schemas loaded by a [`SchemaRetriever`].
+///
+/// [`Validator::new`] fetches schemas over HTTP and stores them in [`SchemaCache::global`], which
+/// is what the [`is_valid`](crate::is_valid) and [`validate`](crate::validate) functions use. Use
+/// [`Validator::with_retriever`] to load schemas from somewhere else.
+///
+/// Extensions registered with [`Validator::with_extension`] are also checked with
+/// [`Extension::validate`], which catches rules that the extension's JSON Schema cannot express.
+///
+/// ```
+/// use rustac_core::Item;
+/// use rustac_validate::cache::SchemaCache;
+/// use rustac_validate::retrieve::EmbeddedRetriever;
+/// use rustac_validate::Validator;
+/// use serde_json::json;
+///
+/// let item: Item = serde_json::from_value(json!({
+///     "stac_version": "1.0.0",
+///     "type": "Feature",
+///     "id": "an-item",
+///     "bbox": [0.0, 0.0, 1.0, 1.0],
+///     "geometry": {"type": "Point", "coordinates": [0.0, 0.0]},
+///     "properties": {"datetime": "2021-01-01T00:00:00Z"},
+///     "links": [],
+///     "assets": {}
+/// })).unwrap();
+///
+/// let validator = Validator::new()
+///     .with_retriever(EmbeddedRetriever)
+///     .with_cache(SchemaCache::new());
+/// assert!(validator.is_valid(&item).unwrap());
+/// ```
+pub struct Validator {
+    retriever: Box<dyn SchemaRetriever>,
+    cache: SchemaCache,
+    extensions: Vec<ExtensionCheck>,
+}
+
+/// An extension registered with [`Validator::with_extension`].
+struct ExtensionCheck {
+    schema_uri: &'static str,
+    is_field: fn(&str) -> bool,
+    check: fn(&Value) -> rustac_core::error::STACResult<()>,
+}
+
+/// Deserializes the fields of `E` and checks them with [`Extension::validate`].
+fn check_extension<E: Extension>(fields: &Value) -> rustac_core::error::STACResult<()> {
+    E::from_fields(fields)?.validate()
+}
+
+impl Default for Validator {
+    fn default() -> Self {
+        Validator {
+            retriever: Box::new(HttpRetriever),
+            cache: SchemaCache::global().clone(),
+            extensions: Vec::new(),
+        }
+    }
+}
+
+impl Validator {
+    /// Creates a validator that fetches schemas over HTTP and uses the global schema cache.
+    #[must_use]
+    pub fn new() -> Validator {
+        Validator::default()
+    }
+
+    /// Loads schemas that are not already cached using the given retriever.
+    #[must_use]
+    pub fn with_retriever<R: SchemaRetriever + 'static>(mut self, retriever: R) -> Validator {
+        self.retriever = Box::new(retriever);
+        self
+    }
+
+    /// Stores loaded schemas in the given cache instead of the global cache.
+    #[must_use]
+    pub fn with_cache(mut self, cache: SchemaCache) -> Validator {
+        self.cache = cache;
+        self
+    }
+
+    /// Checks the fields of `E` with [`Extension::validate`] on objects that list
+    /// [`Extension::SCHEMA_URI`] in `stac_extensions`. Fields are checked wherever they are set: on
+    /// the object itself, in Item properties and on Assets. Each failure is reported as an [`Issue`]
+    /// whose `rule` is the extension's schema URI.
+    ///
+    /// ```
+    /// use rustac_core::extensions::view::View;
+    /// use rustac_validate::Validator;
+    ///
+    /// let validator = Validator::new().with_extension::<View>();
+    /// ```
+    #[must_use]
+    pub fn with_extension<E: Extension>(mut self) -> Validator {
+        self.extensions.push(ExtensionCheck {
+            schema_uri: E::SCHEMA_URI,
+            is_field: E::is_field,
+            check: check_extension::<E>,
+        });
+        self
+    }
+
+    /// Gets the cache used by this validator.
+    #[must_use]
+    pub fn cache(&self) -> &SchemaCache {
+        &self.cache
+    }
+
+    /// Checks if the instance is valid for all of its schemas. See [`is_valid`](crate::is_valid).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a schema cannot be retrieved or compiled.
+    pub fn is_valid<'a, T>(&self, instance: &'a T) -> STACResult<bool>
+    where
+        &'a T: 'a + Into<ValidationTarget<'a>>,
+    {
+        let target: ValidationTarget = instance.into();
+        for schema_uri in &target.schema_uris() {
+            let schemas = fetch_schemas(&self.cache, self.retriever.as_ref(), schema_uri)?;
+            if !is_valid_for_schema_type(&target, &schemas)? {
+                return Ok(false);
+            }
+        }
+        Ok(self.check_extensions(&target).is_empty())
+    }
+
+    /// Validates the instance against all of its schemas. See [`validate`](crate::validate).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a schema cannot be retrieved or compiled.
+    pub fn validate<'a, T>(&self, instance: &'a T) -> STACResult<Report>
+    where
+        &'a T: 'a + Into<ValidationTarget<'a>>,
+    {
+        let target: ValidationTarget = instance.into();
+        let schema_uris = target.schema_uris();
+
+        let mut report = Report::new();
+        for schema_uri in &schema_uris {
+            let schemas = fetch_schemas(&self.cache, self.retriever.as_ref(), schema_uri)?;
+            for issue in validate_for_schema_type(&target, &schemas)? {
+                report.push(issue);
+            }
+        }
+        for issue in self.check_extensions(&target) {
+            report.push(issue);
+        }
+        report.objects.push(ValidatedObject {
+            id: target.id().into(),
+            schemas: schema_uris,
+            source: None,
+        });
+
+        Ok(report)
+    }
+
+    /// Async version of [`Validator::validate`], available with the `async` feature. Schemas are
+    /// loaded using [`SchemaRetriever::retrieve_async`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a schema cannot be retrieved or compiled.
+    #[cfg(feature = "async")]
+    pub async fn validate_async<'a, T>(&self, instance: &'a T) -> STACResult<Report>
+    where
+        &'a T: 'a + Into<ValidationTarget<'a>>,
+    {
+        let target: ValidationTarget = instance.into();
+        let schema_uris = target.schema_uris();
+
+        let mut report = Report::new();
+        for schema_uri in &schema_uris {
+            let schemas =
+                crate::util::fetch_schemas_async(&self.cache, self.retriever.as_ref(), schema_uri)
+                    .await?;
+            for issue in validate_for_schema_type(&target, &schemas)? {
+                report.push(issue);
+            }
+        }
+        for issue in self.check_extensions(&target) {
+            report.push(issue);
+        }
+        report.objects.push(ValidatedObject {
+            id: target.id().into(),
+            schemas: schema_uris,
+            source: None,
+        });
+
+        Ok(report)
+    }
+
+    /// Runs the checks of all registered extensions that the target lists in `stac_extensions`.
+    fn check_extensions(&self, target: &ValidationTarget) -> Vec<Issue> {
+        let stac_extensions = target.stac_extensions();
+        let fields = target.extension_fields();
+        let mut issues = Vec::new();
+        for extension in &self.extensions {
+            if !stac_extensions
+                .is_some_and(|uris| uris.iter().any(|uri| uri == extension.schema_uri))
+            {
+                continue;
+            }
+            for (pointer, fields) in &fields {
+                let in_use = fields
+                    .as_object()
+                    .is_some_and(|fields| fields.keys().any(|key| (extension.is_field)(key)));
+                if !in_use {
+                    continue;
+                }
+                if let Err(err) = (extension.check)(fields) {
+                    issues.push(Issue {
+                        rule: extension.schema_uri.into(),
+                        severity: Severity::Error,
+                        message: err.to_string(),
+                        object_id: Some(target.id().into()),
+                        pointer: Some(pointer.clone()),
+                        source: None,
+                    });
+                }
+            }
+        }
+        issues
+    }
+}
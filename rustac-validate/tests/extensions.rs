@@ -0,0 +1,69 @@
+mod helpers;
+use rustac_core::extensions::view::{self, View};
+use rustac_core::Item;
+use rustac_validate::cache::SchemaCache;
+use rustac_validate::error::STACResult;
+use rustac_validate::retrieve::{Chain, EmbeddedRetriever, SchemaRetriever};
+use rustac_validate::Validator;
+use serde_json::{json, Value};
+
+/// Serves a schema that accepts anything, so that extension schemas do not have to be fetched.
+struct AnySchema;
+
+impl SchemaRetriever for AnySchema {
+    fn retrieve(&self, _uri: &str) -> STACResult<Value> {
+        Ok(json!({}))
+    }
+}
+
+fn item() -> Item {
+    let mut item = helpers::item(
+        "an-item",
+        json!({
+            "datetime": "2021-01-01T00:00:00Z",
+            "view:off_nadir": 95.0
+        }),
+    );
+    item["stac_extensions"] = json!([view::SCHEMA_URI]);
+    item["assets"] = json!({
+        "a/b": {"href": "./image.tif", "view:sun_elevation": -91.0},
+        "thumbnail": {"href": "./thumbnail.png", "view:sun_elevation": 45.0}
+    });
+    serde_json::from_value(item).unwrap()
+}
+
+fn validator() -> Validator {
+    Validator::new()
+        .with_retriever(Chain::new().with(EmbeddedRetriever).with(AnySchema))
+        .with_cache(SchemaCache::new())
+}
+
+#[test]
+fn test_unregistered_extension_is_not_checked() {
+    let validator = validator();
+    assert!(validator.is_valid(&item()).unwrap());
+    assert!(validator.validate(&item()).unwrap().is_empty());
+}
+
+#[test]
+fn test_registered_extension() {
+    let validator = validator().with_extension::<View>();
+    assert!(!validator.is_valid(&item()).unwrap());
+
+    let report = validator.validate(&item()).unwrap();
+    let mut pointers: Vec<_> = report
+        .for_rule(view::SCHEMA_URI)
+        .map(|issue| issue.pointer.clone().unwrap())
+        .collect();
+    pointers.sort();
+    assert_eq!(pointers, vec!["/assets/a~1b", "/properties"]);
+    assert!(report.issues[0].message.starts_with("view:"));
+}
+
+#[test]
+fn test_extension_not_listed() {
+    let mut item = item();
+    item.stac_extensions = Some(vec![]);
+    let validator = validator().with_extension::<View>();
+    assert!(validator.is_valid(&item).unwrap());
+}
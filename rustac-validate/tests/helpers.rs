@@ -1,7 +1,71 @@
+use serde_json::{json, Value};
+use std::collections::HashMap;
 use std::fs;
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::thread;
 
 #[allow(dead_code)]
 pub(crate) fn get_example(repo: &str, filename: &str) -> String {
     let path = format!("./tests/stac-examples/{}/{}", repo, filename);
     fs::read_to_string(&path).unwrap_or_else(|_| panic!("Could not open {}", &path.as_str()))
 }
+
+/// A minimal Item with the given id and properties. It is returned as JSON so that tests can set
+/// other fields, e.g. `stac_extensions` or `assets`, before deserializing it.
+#[allow(dead_code)]
+pub(crate) fn item(id: &str, properties: Value) -> Value {
+    json!({
+        "stac_version": "1.0.0",
+        "type": "Feature",
+        "id": id,
+        "bbox": [0.0, 0.0, 1.0, 1.0],
+        "geometry": {"type": "Point", "coordinates": [0.0, 0.0]},
+        "properties": properties,
+        "links": [],
+        "assets": {}
+    })
+}
+
+/// Serves each document at its path on a local HTTP server and returns the root URL of the server.
+/// The server runs until the test process exits.
+#[allow(dead_code)]
+pub(crate) fn serve(documents: HashMap<&str, Value>) -> String {
+    let documents: HashMap<String, String> = documents
+        .into_iter()
+        .map(|(path, document)| (path.to_string(), document.to_string()))
+        .collect();
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let root = format!("http://{}", listener.local_addr().unwrap());
+
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut request_line = String::new();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            reader.read_line(&mut request_line).unwrap();
+            loop {
+                let mut header = String::new();
+                reader.read_line(&mut header).unwrap();
+                if header.trim().is_empty() {
+                    break;
+                }
+            }
+
+            let path = request_line.split_whitespace().nth(1).unwrap_or_default();
+            let response = match documents.get(path) {
+                Some(body) => format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                ),
+                None => String::from(
+                    "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                ),
+            };
+            stream.write_all(response.as_bytes()).unwrap();
+        }
+    });
+
+    root
+}
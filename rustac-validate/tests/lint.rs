@@ -0,0 +1,104 @@
+mod helpers;
+use rustac_core::{Catalog, Collection, Item};
+use rustac_validate::lint::{Lint, LintConfig, Linter};
+use rustac_validate::report::Severity;
+use serde_json::json;
+use std::path::Path;
+
+fn item() -> Item {
+    let mut item = helpers::item(
+        "My Item",
+        json!({"datetime": "2021-01-01T00:00:00Z", "license": "proprietary"}),
+    );
+    item["assets"] = json!({
+        "data": {"href": "./data.tif"},
+        "thumbnail": {"href": "./thumb.tif", "type": "image/tiff", "roles": ["thumbnail"]}
+    });
+    serde_json::from_value(item).unwrap()
+}
+
+fn collection() -> Collection {
+    serde_json::from_value(json!({
+        "stac_version": "1.0.0",
+        "type": "Collection",
+        "id": "a-collection",
+        "description": "A collection",
+        "license": "various",
+        "extent": {
+            "spatial": {"bbox": [[0.0, 0.0, 1.0, 1.0]]},
+            "temporal": {"interval": [[null, null]]}
+        },
+        "links": [{"rel": "self", "href": "https://example.com/collection.json"}]
+    }))
+    .unwrap()
+}
+
+#[test]
+fn test_lint_item() {
+    let report = Linter::new().lint_item(&item(), Some(Path::new("items/my-item.json")));
+
+    let rules: Vec<&str> = report
+        .issues
+        .iter()
+        .map(|issue| issue.rule.as_str())
+        .collect();
+    assert_eq!(
+        rules,
+        vec![
+            "id-format",
+            "self-link",
+            "asset-roles",
+            "thumbnail-media-type",
+            "license-link",
+            "item-file-name"
+        ]
+    );
+    assert!(report
+        .issues
+        .iter()
+        .all(|issue| issue.severity == Severity::Warning));
+    assert_eq!(
+        report.issues[3].pointer,
+        Some(String::from("/assets/thumbnail/type"))
+    );
+}
+
+#[test]
+fn test_lint_collection_and_catalog() {
+    let report = Linter::new().lint_collection(&collection());
+    let rules: Vec<&str> = report
+        .issues
+        .iter()
+        .map(|issue| issue.rule.as_str())
+        .collect();
+    assert_eq!(rules, vec!["collection-title", "license-link"]);
+
+    let catalog: Catalog = serde_json::from_value(json!({
+        "stac_version": "1.0.0",
+        "type": "Catalog",
+        "id": "catalog",
+        "description": "A catalog"
+    }))
+    .unwrap();
+    let report = Linter::new().lint_catalog(&catalog);
+    assert_eq!(report.for_rule("self-link").count(), 1);
+}
+
+#[test]
+fn test_lint_config_file() {
+    let config = LintConfig::from_file("./tests/data/lint-config.json").unwrap();
+    let linter = Linter::from_config(&config).unwrap();
+
+    assert!(!linter.is_enabled(Lint::SelfLink));
+    assert_eq!(linter.severity(Lint::CollectionTitle), Severity::Error);
+
+    let report = linter.lint_collection(&collection());
+    assert!(report.has_errors());
+
+    let mut config = LintConfig::default();
+    config.rules.insert(
+        String::from("not-a-lint"),
+        serde_json::from_value(json!("off")).unwrap(),
+    );
+    assert!(Linter::from_config(&config).is_err());
+}
@@ -0,0 +1,93 @@
+use rustac_validate::output::{to_json, to_junit, to_sarif, write, Format};
+use rustac_validate::report::{Issue, Report, Severity, ValidatedObject};
+
+const ITEM_SCHEMA: &str = "https://schemas.stacspec.org/v1.0.0/item-spec/json-schema/item.json";
+const EO_SCHEMA: &str = "https://stac-extensions.github.io/eo/v1.0.0/schema.json";
+
+fn report() -> Report {
+    let mut report = Report::new();
+    report.objects.push(ValidatedObject {
+        id: String::from("item-1"),
+        schemas: vec![String::from(ITEM_SCHEMA), String::from(EO_SCHEMA)],
+        source: None,
+    });
+    report.push(Issue {
+        rule: String::from(EO_SCHEMA),
+        severity: Severity::Error,
+        message: String::from("\"red\" is not of type \"array\""),
+        object_id: Some(String::from("item-1")),
+        pointer: Some(String::from("/properties/eo:bands")),
+        source: None,
+    });
+    report.push(Issue {
+        rule: String::from("self-link"),
+        severity: Severity::Warning,
+        message: String::from("there is no link with rel \"self\""),
+        object_id: Some(String::from("item-1")),
+        pointer: Some(String::from("/links")),
+        source: None,
+    });
+    report.set_source("items/item-1.json");
+    report
+}
+
+#[test]
+fn test_json() {
+    let report = report();
+    let json = to_json(&report).unwrap();
+    let parsed: Report = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed, report);
+    assert_eq!(
+        parsed.objects[0].source,
+        Some(String::from("items/item-1.json"))
+    );
+}
+
+#[test]
+fn test_junit() {
+    let xml = to_junit(&report());
+
+    assert!(xml.contains(r#"<testsuites name="rustac-validate" tests="3" failures="1">"#));
+    assert!(xml.contains(r#"<testsuite name="item-1" tests="3" failures="1">"#));
+    assert!(xml.contains(&format!(
+        r#"<testcase name="{ITEM_SCHEMA}" classname="item-1"/>"#
+    )));
+    assert!(xml.contains(
+        r#"<failure message="&quot;red&quot; is not of type &quot;array&quot;" type="error">"#
+    ));
+    assert!(
+        xml.contains("<system-out>/links: there is no link with rel &quot;self&quot;</system-out>")
+    );
+}
+
+#[test]
+fn test_sarif() {
+    let sarif = to_sarif(&report());
+
+    assert_eq!(sarif["version"], "2.1.0");
+    let run = &sarif["runs"][0];
+    assert_eq!(run["tool"]["driver"]["name"], "rustac-validate");
+    assert_eq!(run["tool"]["driver"]["rules"][1]["id"], "self-link");
+
+    let result = &run["results"][0];
+    assert_eq!(result["ruleId"], EO_SCHEMA);
+    assert_eq!(result["level"], "error");
+    let location = &result["locations"][0];
+    assert_eq!(
+        location["physicalLocation"]["artifactLocation"]["uri"],
+        "items/item-1.json"
+    );
+    assert_eq!(
+        location["logicalLocations"][0]["fullyQualifiedName"],
+        "/properties/eo:bands"
+    );
+    assert_eq!(run["results"][1]["level"], "warning");
+}
+
+#[test]
+fn test_write() {
+    let format: Format = "SARIF".parse().unwrap();
+    let output = write(&report(), format).unwrap();
+    assert!(output.contains("\"$schema\""));
+    assert!("xml".parse::<Format>().is_err());
+}
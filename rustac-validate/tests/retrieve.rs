@@ -0,0 +1,136 @@
+mod helpers;
+use helpers::serve;
+use rustac_core::{Collection, Item};
+use rustac_validate::cache::SchemaCache;
+use rustac_validate::retrieve::{
+    Chain, DirectoryRetriever, EmbeddedRetriever, HttpRetriever, Rewrite, SchemaRetriever,
+};
+use rustac_validate::Validator;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+const ITEM_SCHEMA: &str = "https://schemas.stacspec.org/v1.0.0/item-spec/json-schema/item.json";
+
+fn item() -> Item {
+    let mut item = helpers::item(
+        "20201211_223832_CS2",
+        json!({"datetime": "2020-12-11T22:38:32.125000Z"}),
+    );
+    item["assets"] = json!({
+        "visual": {
+            "href": "https://storage.googleapis.com/open-cogs/stac-examples/20201211_223832_CS2.tif",
+            "type": "image/tiff; application=geotiff; profile=cloud-optimized",
+            "roles": ["visual"]
+        }
+    });
+    serde_json::from_value(item).unwrap()
+}
+
+fn validator<R: SchemaRetriever + 'static>(retriever: R) -> Validator {
+    Validator::new()
+        .with_retriever(retriever)
+        .with_cache(SchemaCache::new())
+}
+
+#[test]
+fn test_embedded() {
+    let validator = validator(EmbeddedRetriever);
+    let report = validator.validate(&item()).unwrap();
+    assert!(report.is_empty(), "{:?}", report.issues);
+    assert!(validator
+        .cache()
+        .contains("https://geojson.org/schema/Feature.json"));
+
+    let collection: Collection = serde_json::from_value(json!({
+        "stac_version": "1.0.0",
+        "type": "Collection",
+        "id": "a-collection",
+        "description": "A collection",
+        "license": "CC-BY-4.0",
+        "extent": {
+            "spatial": {"bbox": [[0.0, 0.0, 1.0, 1.0]]},
+            "temporal": {"interval": [["2021-01-01T00:00:00Z", null]]}
+        },
+        "links": []
+    }))
+    .unwrap();
+    assert!(validator.is_valid(&collection).unwrap());
+
+    let mut invalid = item();
+    invalid.properties.common.gsd = Some(-1.0);
+    let report = validator.validate(&invalid).unwrap();
+    assert_eq!(report.issues.len(), 1);
+    assert_eq!(report.issues[0].rule, ITEM_SCHEMA);
+    assert_eq!(
+        report.issues[0].pointer,
+        Some(String::from("/properties/gsd"))
+    );
+}
+
+#[test]
+fn test_directory() {
+    let retriever = DirectoryRetriever::new("./schemas");
+    assert_eq!(
+        retriever.path(ITEM_SCHEMA).unwrap(),
+        Path::new("./schemas/schemas.stacspec.org/v1.0.0/item-spec/json-schema/item.json")
+    );
+    assert!(retriever
+        .retrieve("https://example.com/missing.json")
+        .is_err());
+
+    let report = validator(retriever).validate(&item()).unwrap();
+    assert!(report.is_empty(), "{:?}", report.issues);
+}
+
+#[test]
+fn test_rewrite_to_mirror() {
+    // Serve the STAC schemas from a local "internal mirror"
+    let root = Path::new("./schemas/schemas.stacspec.org/v1.0.0/item-spec/json-schema");
+    let mut documents: HashMap<&str, Value> = HashMap::new();
+    for name in [
+        "item",
+        "basics",
+        "datetime",
+        "instrument",
+        "licensing",
+        "provider",
+    ] {
+        let path = format!("/mirror/v1.0.0/item-spec/json-schema/{}.json", name);
+        let data = fs::read_to_string(root.join(format!("{}.json", name))).unwrap();
+        documents.insert(
+            Box::leak(path.into_boxed_str()),
+            serde_json::from_str(&data).unwrap(),
+        );
+    }
+    let mirror = format!("{}/mirror/", serve(documents));
+
+    let rewrite =
+        Rewrite::new(HttpRetriever).rule("https://schemas.stacspec.org/", mirror.as_str());
+    assert_eq!(
+        rewrite.rewrite(ITEM_SCHEMA),
+        format!("{}v1.0.0/item-spec/json-schema/item.json", mirror)
+    );
+    assert_eq!(
+        rewrite.rewrite("https://geojson.org/schema/Feature.json"),
+        "https://geojson.org/schema/Feature.json"
+    );
+
+    // GeoJSON schemas are not on the mirror, so fall back to the embedded copies
+    let retriever = Chain::new().with(rewrite).with(EmbeddedRetriever);
+    let validator = validator(retriever);
+    let report = validator.validate(&item()).unwrap();
+    assert!(report.is_empty(), "{:?}", report.issues);
+
+    // Schemas are cached under their canonical URIs, not the rewritten ones
+    assert!(validator.cache().contains(ITEM_SCHEMA));
+}
+
+#[test]
+fn test_chain_error() {
+    let error = Chain::new()
+        .with(EmbeddedRetriever)
+        .retrieve("https://example.com/schema.json");
+    assert!(error.is_err());
+}
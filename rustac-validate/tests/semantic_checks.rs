@@ -0,0 +1,153 @@
+mod helpers;
+use rustac_core::{Collection, Item};
+use rustac_validate::checks::{Checker, Rule};
+use rustac_validate::report::Severity;
+use serde_json::{json, Value};
+
+fn item(id: &str) -> Value {
+    let mut item = helpers::item(id, json!({"datetime": "2020-12-11T22:38:32Z"}));
+    item["bbox"] = json!([172.9, 1.3, 173.0, 1.4]);
+    item["geometry"] = json!({
+        "type": "Polygon",
+        "coordinates": [[[172.9, 1.3], [173.0, 1.3], [173.0, 1.4], [172.9, 1.4], [172.9, 1.3]]]
+    });
+    item["collection"] = json!("simple-collection");
+    item["links"] = json!([{"rel": "collection", "href": "./collection.json"}]);
+    item
+}
+
+fn collection() -> Value {
+    json!({
+        "stac_version": "1.0.0",
+        "type": "Collection",
+        "id": "simple-collection",
+        "description": "A simple collection",
+        "license": "CC-BY-4.0",
+        "providers": [{"name": "Remote Data, Inc", "roles": ["producer", "licensor", "producer"]}],
+        "extent": {
+            "spatial": {"bbox": [[172.9, 1.3, 173.1, 1.5]]},
+            "temporal": {"interval": [["2020-12-11T00:00:00Z", "2020-12-31T00:00:00Z"]]}
+        },
+        "links": []
+    })
+}
+
+#[test]
+fn test_valid_item_has_no_issues() {
+    let item: Item = serde_json::from_value(item("valid")).unwrap();
+    let report = Checker::new().check_item(&item);
+    assert!(report.is_empty(), "{:?}", report.issues);
+}
+
+#[test]
+fn test_item_rules() {
+    let mut value = item("invalid");
+    value["bbox"] = json!([0.0, 0.0, 1.0, 1.0]);
+    value["properties"] = json!({
+        "datetime": null,
+        "start_datetime": "2021-01-02T00:00:00Z",
+        "end_datetime": "2021-01-01T00:00:00Z"
+    });
+    value["links"] = json!([]);
+    let item: Item = serde_json::from_value(value).unwrap();
+
+    let report = Checker::new().check_item(&item);
+
+    let rules: Vec<&str> = report
+        .issues
+        .iter()
+        .map(|issue| issue.rule.as_str())
+        .collect();
+    assert_eq!(
+        rules,
+        vec![
+            "bbox-matches-geometry",
+            "datetime-range-order",
+            "collection-link"
+        ]
+    );
+    assert_eq!(report.issues[0].pointer, Some(String::from("/bbox")));
+    assert_eq!(report.issues[0].object_id, Some(String::from("invalid")));
+    assert!(report.has_errors());
+}
+
+#[test]
+fn test_null_datetime_requires_range() {
+    let mut value = item("no-datetime");
+    value["properties"] = json!({"datetime": null, "start_datetime": "2021-01-01T00:00:00Z"});
+    let item: Item = serde_json::from_value(value).unwrap();
+
+    let report = Checker::new().check_item(&item);
+    assert_eq!(report.for_rule("null-datetime-requires-range").count(), 1);
+}
+
+#[test]
+fn test_collection_items() {
+    let collection: Collection = serde_json::from_value(collection()).unwrap();
+    let mut outside = item("outside");
+    outside["geometry"] = json!({"type": "Point", "coordinates": [10.0, 10.0]});
+    outside["bbox"] = json!([10.0, 10.0, 10.0, 10.0]);
+    outside["properties"]["datetime"] = json!("2021-06-01T00:00:00Z");
+    let items: Vec<Item> = vec![item("a"), item("a"), outside]
+        .into_iter()
+        .map(|value| serde_json::from_value(value).unwrap())
+        .collect();
+
+    let report = Checker::new().check_collection_items(&collection, &items);
+
+    assert_eq!(report.for_rule("unique-item-ids").count(), 1);
+    assert_eq!(report.for_rule("item-within-collection-extent").count(), 2);
+
+    let roles: Vec<_> = report.for_rule("unique-provider-roles").collect();
+    assert_eq!(roles.len(), 1);
+    assert_eq!(roles[0].severity, Severity::Warning);
+    assert_eq!(roles[0].pointer, Some(String::from("/providers/0/roles")));
+}
+
+#[test]
+fn test_configure_rules() {
+    let collection: Collection = serde_json::from_value(collection()).unwrap();
+
+    let checker = Checker::new()
+        .disable("unique-provider-roles".parse().unwrap())
+        .with_severity(Rule::UniqueItemIds, Severity::Info);
+    assert!(!checker.is_enabled(Rule::UniqueProviderRoles));
+    assert_eq!(checker.severity(Rule::UniqueItemIds), Severity::Info);
+    assert!(checker.check_collection(&collection).is_empty());
+
+    assert!("not-a-rule".parse::<Rule>().is_err());
+}
+
+#[test]
+fn test_item_assets_conformance() {
+    let mut collection = collection();
+    collection["item_assets"] = json!({
+        "data": {"type": "image/tiff", "roles": ["data"]},
+        "thumbnail": {"type": "image/png", "roles": ["thumbnail"]}
+    });
+    let collection: Collection = serde_json::from_value(collection).unwrap();
+    let mut value = item("a");
+    value["assets"] = json!({
+        "data": {"href": "./data.tif", "type": "image/jpeg", "roles": ["data"]},
+        "metadata": {"href": "./metadata.xml"}
+    });
+    let items = vec![serde_json::from_value(value).unwrap()];
+
+    let report = Checker::new().check_collection_items(&collection, &items);
+    let pointers: Vec<_> = report
+        .for_rule("item-assets-conformance")
+        .map(|issue| issue.pointer.clone().unwrap())
+        .collect();
+    assert_eq!(
+        pointers,
+        vec!["/assets/data", "/assets/metadata", "/assets/thumbnail"]
+    );
+    assert!(report
+        .for_rule("item-assets-conformance")
+        .all(|issue| issue.severity == Severity::Warning));
+
+    let report = Checker::new()
+        .disable(Rule::ItemAssetsConformance)
+        .check_collection_items(&collection, &items);
+    assert_eq!(report.for_rule("item-assets-conformance").count(), 0);
+}
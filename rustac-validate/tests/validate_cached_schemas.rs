@@ -0,0 +1,89 @@
+mod helpers;
+use helpers::serve;
+use rustac_core::Item;
+use rustac_validate::cache::SchemaCache;
+use rustac_validate::validate;
+use serde_json::json;
+use std::collections::HashMap;
+
+const ITEM_SCHEMA: &str = "https://schemas.stacspec.org/v1.0.0/item-spec/json-schema/item.json";
+
+/// Caches a minimal core Item schema so that tests do not need to reach schemas.stacspec.org and
+/// serves an extension schema, which references a second schema, from a local server.
+fn setup() -> String {
+    SchemaCache::global().insert(ITEM_SCHEMA, json!({"type": "object", "required": ["id"]}));
+
+    let root = serve(HashMap::from([
+        (
+            "/test/v1.0.0/schema.json",
+            json!({
+                "$schema": "http://json-schema.org/draft-07/schema#",
+                "type": "object",
+                "properties": {"properties": {"$ref": "definitions.json#/definitions/fields"}}
+            }),
+        ),
+        (
+            "/test/v1.0.0/definitions.json",
+            json!({
+                "definitions": {
+                    "fields": {
+                        "type": "object",
+                        "properties": {"test:count": {"type": "integer"}}
+                    }
+                }
+            }),
+        ),
+    ]));
+    format!("{}/test/v1.0.0/schema.json", root)
+}
+
+fn item(extension: &str) -> Item {
+    let mut item = helpers::item(
+        "an-item",
+        json!({"datetime": "2021-01-01T00:00:00Z", "test:count": "three"}),
+    );
+    item["stac_extensions"] = json!([extension]);
+    serde_json::from_value(item).unwrap()
+}
+
+#[test]
+fn test_validate_with_referenced_schemas() {
+    let extension = setup();
+
+    let report = validate(&item(&extension)).unwrap();
+
+    assert_eq!(report.issues.len(), 1);
+    assert_eq!(report.issues[0].rule, extension);
+    assert_eq!(
+        report.issues[0].pointer,
+        Some(String::from("/properties/test:count"))
+    );
+    assert_eq!(
+        report.objects[0].schemas,
+        vec![ITEM_SCHEMA, extension.as_str()]
+    );
+
+    let definitions = extension.replace("schema.json", "definitions.json");
+    assert!(SchemaCache::global().contains(&definitions));
+}
+
+#[cfg(feature = "async")]
+#[tokio::test]
+async fn test_validate_async() {
+    let extension = setup();
+    let item = item(&extension);
+
+    let report = rustac_validate::validate_async(&item).await.unwrap();
+    assert_eq!(report.issues.len(), 1);
+    assert_eq!(
+        report.issues[0].pointer,
+        Some(String::from("/properties/test:count"))
+    );
+    assert!(SchemaCache::global().contains(&extension));
+
+    // The blocking API reuses the schemas fetched by the async API
+    let blocking = tokio::task::spawn_blocking(move || validate(&item).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(blocking, report);
+}